@@ -4,5 +4,7 @@
 pub mod gbd;
 #[cfg(feature = "gfx")]
 pub mod gfx;
+#[cfg(feature = "prof")]
+pub mod prof;
 #[cfg(feature = "trace")]
 pub mod trace;
@@ -0,0 +1,95 @@
+//! Execution profiling.
+
+use std::cmp::Reverse;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use anyhow::{Context, Result};
+use log::{info, warn};
+use rugby::GameBoy;
+use rugby::arch::Shared;
+use rugby::core::dmg::dbg::prof::{Profile, cdl};
+use rugby::core::dmg::dbg::sym::Symbols;
+
+use crate::exe::run::cli::Debugger;
+
+/// Number of entries included in reports.
+const TOP: usize = 10;
+
+/// Enables the emulator's profiler, if requested.
+pub fn init(args: &Debugger, emu: &GameBoy) -> Option<Shared<Profile>> {
+    // Allow none if disabled
+    if args.prof.is_none() && args.cover.is_none() {
+        return None;
+    }
+    // Profiling is DMG-only
+    let prof = match emu {
+        GameBoy::Dmg0(dmg) => dmg.inner().soc.prof.clone(),
+        GameBoy::DmgA(dmg) | GameBoy::DmgB(dmg) | GameBoy::DmgC(dmg) => {
+            dmg.inner().soc.prof.clone()
+        }
+        _ => {
+            warn!("profiling requires a DMG model; skipping");
+            return None;
+        }
+    };
+    prof.borrow_mut().enable();
+    Some(prof)
+}
+
+/// Reports and exports profiling results.
+pub fn dump(args: &Debugger, prof: &Profile, emu: &GameBoy, syms: &Symbols) -> Result<()> {
+    // Report cycles by bank
+    let total = prof.cycles().values().sum::<u64>().max(1);
+    #[expect(clippy::cast_precision_loss)]
+    let pct = |count: u64| 100. * count as f64 / total as f64;
+    for (bank, count) in prof.banks() {
+        info!("profile: bank {bank:02x}: {pct:>5.1}%", pct = pct(count));
+    }
+    // Report hottest functions (or instructions, without symbols)
+    let mut hot = if syms.is_empty() {
+        prof.cycles()
+            .iter()
+            .map(|(sym, &count)| (format!("${:04x}", sym.addr), count))
+            .collect::<Vec<_>>()
+    } else {
+        prof.functions(syms)
+            .into_iter()
+            .map(|(func, count)| (func.to_string(), count))
+            .collect()
+    };
+    hot.sort_by_key(|&(_, count)| Reverse(count));
+    for (name, count) in hot.into_iter().take(TOP) {
+        info!("profile: {pct:>5.1}%: {name}", pct = pct(count));
+    }
+
+    // Export folded stacks
+    if let Some(path) = &args.prof {
+        let file =
+            File::create(path).with_context(|| format!("failed to open: `{}`", path.display()))?;
+        let mut out = BufWriter::new(file);
+        prof.folded(syms, &mut out)
+            .and_then(|()| out.flush())
+            .context("failed to write profile")?;
+        info!("wrote profile: `{}`", path.display());
+    }
+    // Export coverage
+    if let Some(path) = &args.cover {
+        // Pad to the size of the ROM
+        let mut data = prof.coverage().to_vec();
+        let size = emu.cart().map_or(0, |cart| cart.header().memory.romsz);
+        if data.len() < size {
+            data.resize(size, 0);
+        }
+        std::fs::write(path, &data).context("failed to write coverage")?;
+        // Report coverage
+        let code = data.iter().filter(|&&flag| flag & cdl::CODE != 0).count();
+        let used = data.iter().filter(|&&flag| flag != 0).count();
+        info!(
+            "wrote coverage: `{}` (code: {code} B, total: {used} B)",
+            path.display()
+        );
+    }
+
+    Ok(())
+}
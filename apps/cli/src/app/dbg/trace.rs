@@ -8,6 +8,7 @@ use std::path::Path;
 use anyhow::{Context, bail};
 use either::Either;
 use rugby::GameBoy;
+use rugby::core::dmg::dbg::sym::Symbols;
 use rugby::core::{Revision, dmg};
 
 use self::cmp::Tracer as Tracecmp;
@@ -15,7 +16,9 @@ use self::log::Tracer as Tracelog;
 use crate::exe::run::cli::trace::{Format, Trace};
 
 /// Builds a tracing instance.
-pub fn init(Trace { fmt, log, cmp }: &Trace) -> anyhow::Result<Tracer> {
+///
+/// When logging, entries at labelled addresses are preceded by their label.
+pub fn init(Trace { fmt, log, cmp }: &Trace, syms: Symbols) -> anyhow::Result<Tracer> {
     Ok(match (log, cmp) {
         (None, Some(cmp)) => {
             Tracer::Cmp(Tracecmp {
@@ -45,6 +48,7 @@ pub fn init(Trace { fmt, log, cmp }: &Trace) -> anyhow::Result<Tracer> {
                     // Use `stdout` for missing path or as alias of "-"
                     _ => Either::Right(std::io::stdout()),
                 })),
+                syms,
             })
         }
         _ => bail!("unspecified mode for tracer"),
@@ -66,7 +70,7 @@ impl Tracer {
         match emu {
             GameBoy::Dmg0(dmg) => self.emit_inner(dmg),
             GameBoy::DmgA(dmg) | GameBoy::DmgB(dmg) | GameBoy::DmgC(dmg) => self.emit_inner(dmg),
-            _ => unreachable!(),
+            _ => Err(Error::Unsupported),
         }
     }
 
@@ -95,6 +99,9 @@ pub enum Error {
     /// Source log finished.
     #[error("tracelog comparison has finished")]
     Finished,
+    /// Unsupported hardware model.
+    #[error("tracing is unsupported for this model")]
+    Unsupported,
 }
 
 /// Trace comparisons.
@@ -189,7 +196,10 @@ mod cmp {
 mod log {
     use std::io::{BufWriter, Write};
 
+    use rugby::arch::reg::Port;
     use rugby::core::Revision;
+    use rugby::core::chip::cpu;
+    use rugby::core::dmg::dbg::sym::Symbols;
     use rugby::core::dmg::{self, GameBoy};
 
     use super::Format;
@@ -202,6 +212,8 @@ mod log {
         pub(super) fmt: Format,
         /// Tracelog output file.
         pub(super) log: BufWriter<Box<dyn Write>>,
+        /// Debug symbols.
+        pub(super) syms: Symbols,
     }
 
     impl Tracer {
@@ -212,6 +224,16 @@ mod log {
                 Format::Binjgb => dmg::dbg::trace::binjgb,
                 Format::Doctor => dmg::dbg::trace::doctor,
             }(emu);
+            // Write label to logfile
+            if !self.syms.is_empty() {
+                // Entries are emitted after the opcode has been fetched
+                let pc: u16 = emu.inner().soc.cpu.load(cpu::Select16::PC);
+                let insn = pc.wrapping_sub(1);
+                let bank = emu.cart().map_or(1, |cart| cart.bank());
+                if let Some(label) = self.syms.label(bank, insn) {
+                    writeln!(self.log, "{label}:")?;
+                }
+            }
             // Write to logfile
             writeln!(self.log, "{entry}").map_err(Into::into)
         }
@@ -0,0 +1,182 @@
+//! Sound rip player.
+
+use std::io::{self, BufRead};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use anyhow::{Context as _, Result, ensure};
+use log::{debug, info, warn};
+use rugby::api::audio::Audio;
+use rugby::arch::Block;
+use rugby::core::cart::gbs::Gbs;
+use rugby::core::dmg::{self, GameBoy};
+
+use super::run::emu::sync::Clocking;
+use super::run::{aux, install, watch};
+use crate::app;
+use crate::app::init;
+use crate::exe::gbs::Cli;
+
+/// Track control.
+#[derive(Debug)]
+enum Control {
+    /// Play the next track.
+    Next,
+    /// Play the previous track.
+    Prev,
+    /// Play the specified track (zero-indexed).
+    Goto(u8),
+}
+
+/// Run player.
+pub fn main(args: &Cli) -> Result<()> {
+    // Load rip
+    let gbs = {
+        // Read file data
+        let data =
+            init::util::load_until(&args.gbs, 0x0080_0000).context("unable to load GBS rip")?;
+        // Parse rip header
+        Gbs::new(&data).context("failed to construct GBS rip")?
+    };
+    info!("loaded rip: {gbs}");
+
+    // Install handlers
+    install();
+
+    // Spin up application threads
+    thread::scope(|s| {
+        // Run playback thread
+        let aux = s.spawn(watch(|| aux::play(args.cfg.data.audio.rate)));
+        // Run player on this thread
+        let emu = watch(|| self::play(args, &gbs))();
+
+        // Join thread handles, collecting any errors
+        let res = [aux.join().expect("playback thread panicked"), emu];
+
+        // Log exit reason
+        debug!(
+            "exit reason: {}",
+            app::reason().expect("missing exit reason")
+        );
+
+        // Propagate errors
+        res.into_iter().collect()
+    })
+}
+
+/// Plays the rip until the application exits.
+fn play(args: &Cli, gbs: &Gbs) -> Result<()> {
+    // Instantiate emulator
+    let mut emu = GameBoy::new();
+    emu.set_rate(args.cfg.data.audio.rate);
+    emu.set_mixer(args.cfg.data.audio.mix.unwrap_or_default().into());
+    // Load starting track
+    let mut track = args.track.map_or(gbs.first, |track| track - 1);
+    ensure!(
+        track < gbs.songs,
+        "invalid track: {} (rip has {} tracks)",
+        track + 1,
+        gbs.songs
+    );
+    debug!(
+        "play routine driven by: {}",
+        if gbs.timer() { "timer" } else { "vblank" }
+    );
+    self::load(&mut emu, gbs, track)?;
+    // Listen for track controls
+    let ctrl = self::input();
+
+    // Player loop
+    //
+    // Clocks the emulator at its nominal frequency, such that samples are
+    // synthesized as quickly as they are played.
+    let mut clock = Clocking::new(dmg::CLOCK);
+    let mut audio = Vec::new();
+    let mut total = 0u64;
+    while app::running() {
+        // Synchronize thread
+        if clock.sync() {
+            continue;
+        }
+
+        // Cycle emulator
+        emu.cycle();
+
+        // Perform lower-frequency actions
+        if total.is_multiple_of(u64::from(dmg::CLOCK / 64)) {
+            // Sample audio
+            emu.pull(&mut audio);
+            app::data::audio::push(&audio);
+            audio.clear();
+
+            // Switch tracks
+            if let Ok(ctrl) = ctrl.try_recv() {
+                let next = match ctrl {
+                    Control::Next => (track + 1) % gbs.songs,
+                    Control::Prev => track.checked_sub(1).unwrap_or(gbs.songs - 1),
+                    Control::Goto(next) => next,
+                };
+                if next < gbs.songs {
+                    track = next;
+                    self::load(&mut emu, gbs, track)?;
+                } else {
+                    warn!("invalid track: {}", next + 1);
+                }
+            }
+        }
+
+        // Count clocked cycle
+        clock.tick();
+        total += 1;
+    }
+
+    Ok(())
+}
+
+/// Loads a track into the emulator.
+///
+/// The track's cartridge replaces any previous one, after which the emulator
+/// is reset to start playback.
+fn load(emu: &mut GameBoy, gbs: &Gbs, track: u8) -> Result<()> {
+    let cart = gbs
+        .cartridge(track)
+        .with_context(|| format!("failed to build track: {}", track + 1))?;
+    emu.eject();
+    emu.insert(cart);
+    emu.reset();
+    info!("playing track: {}/{}", track + 1, gbs.songs);
+
+    Ok(())
+}
+
+/// Listens for track controls on standard input.
+///
+/// Accepts a track number, or "n" and "p" for the next and previous tracks.
+fn input() -> Receiver<Control> {
+    let (tx, rx) = mpsc::channel();
+    // Reading from standard input blocks indefinitely, so the thread is left
+    // detached rather than joined at exit.
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            let ctrl = match line.trim() {
+                "" => continue,
+                "n" | "next" => Control::Next,
+                "p" | "prev" => Control::Prev,
+                word => {
+                    let Ok(track @ 1..) = word.parse::<u8>() else {
+                        warn!("unknown control: `{word}`");
+                        continue;
+                    };
+                    Control::Goto(track - 1)
+                }
+            };
+            if tx.send(ctrl).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
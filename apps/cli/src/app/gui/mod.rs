@@ -2,13 +2,13 @@
 
 use anyhow::Result;
 use minifb::Key;
-use rugby::api::input::Event;
-use rugby::api::video::Frame;
-use rugby::core::chip::ppu;
+use rugby::api::input::{Event, State};
 use rugby::core::dmg::soc::joy::Button;
 use rugby::pal::Palette;
 
 use self::win::{Main, Window};
+use crate::app;
+use crate::app::data::video::Frame;
 #[cfg(feature = "gfx")]
 use crate::app::dbg::gfx::Gfx;
 use crate::exe::run::Cli;
@@ -47,9 +47,13 @@ impl Frontend {
 impl Frontend {
     #[rustfmt::skip]
     pub fn events(&mut self) -> Vec<Event<Button>> {
-        self.lcd
-            // Fetch keys
-            .keys()
+        // Fetch keys
+        let keys = self.lcd.keys();
+        // Handle hotkeys
+        if keys.iter().any(|key| key.input == Key::F9 && key.state == State::Dn) {
+            app::data::record::toggle();
+        }
+        keys
             .into_iter()
             // Perform key mapping
             .filter_map(|Event { input, state }| match input {
@@ -65,13 +69,9 @@ impl Frontend {
             }).collect()
     }
 
-    pub fn draw(&mut self, frame: &Frame<ppu::Color>) {
+    pub fn draw(&mut self, frame: &Frame) {
         // Translate pixels
-        let frame = frame
-            .iter()
-            .map(|&pix| self.pal[pix as usize])
-            .map(u32::from)
-            .collect::<Vec<_>>();
+        let frame = frame.render(&self.pal);
         // Redraw main window
         self.lcd.redraw(&frame).unwrap();
     }
@@ -4,9 +4,11 @@ use self::ctrl::{Exit, exit, reason, running};
 
 #[cfg(feature = "debug")]
 mod dbg;
+mod gbs;
 mod gui;
 mod run;
 
+pub use self::gbs::main as gbs;
 pub use self::run::main as run;
 
 /// Application assembly.
@@ -104,17 +106,17 @@ pub mod data {
         static STREAM: OnceLock<Mutex<Stream>> = OnceLock::new();
 
         /// Initializes the audio system.
-        pub fn init(ifrq: u32, ofrq: u32) {
-            debug!("audio sample rate: (input: {ifrq}, output: {ofrq})");
-            STREAM.get_or_init(|| Mutex::new(Stream::new(ifrq, ofrq)));
+        pub fn init(ofrq: u32) {
+            debug!("audio sample rate: {ofrq}");
+            STREAM.get_or_init(|| Mutex::new(Stream::new(ofrq)));
         }
 
-        /// Push a sample to the audio system.
+        /// Push samples to the audio system.
         ///
         /// This function blocks if the mutex is held by another thread.
-        pub fn push(sample: Sample) {
+        pub fn push(samples: &[Sample]) {
             if let Some(stream) = STREAM.get() {
-                stream.lock().push(sample);
+                stream.lock().push(samples);
             }
         }
 
@@ -202,14 +204,80 @@ pub mod data {
         }
     }
 
+    /// Recording state.
+    pub mod record {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        /// Recording toggle request.
+        static TOGGLE: AtomicBool = AtomicBool::new(false);
+
+        /// Requests video recording be toggled.
+        ///
+        /// This function will never block.
+        pub fn toggle() {
+            TOGGLE.store(true, Ordering::Relaxed);
+        }
+
+        /// Takes any pending toggle request.
+        ///
+        /// This function will never block.
+        pub fn take() -> bool {
+            TOGGLE.swap(false, Ordering::Relaxed)
+        }
+    }
+
     /// Video state.
     pub mod video {
         use parking_lot::Mutex;
-        use rugby::core::chip::ppu::Frame;
+        use rugby::core::api::video;
+        use rugby::core::chip::ppu::{self, Rgb555};
+        use rugby::pal::Palette;
 
         /// Video framebuffer.
         static FRAME: Mutex<Option<Frame>> = Mutex::new(None);
 
+        /// Owned video framebuffer.
+        #[derive(Debug)]
+        pub enum Frame {
+            /// Monochrome shades.
+            Dmg(ppu::Frame),
+            /// RGB555 colors.
+            Cgb(video::Frame<Rgb555>),
+        }
+
+        impl Frame {
+            /// Renders the frame as RGB pixels.
+            ///
+            /// Monochrome shades are recolored using the provided palette.
+            pub fn render(&self, pal: &Palette) -> Vec<u32> {
+                match self {
+                    Frame::Dmg(frame) => frame
+                        .iter()
+                        .map(|&pix| pal[pix as usize])
+                        .map(u32::from)
+                        .collect(),
+                    Frame::Cgb(frame) => frame
+                        .iter()
+                        .map(|pix| {
+                            // Expand each 5-bit channel to 8 bits
+                            let [r, g, b] = [pix.red(), pix.green(), pix.blue()]
+                                .map(|ch| u32::from(ch << 3 | ch >> 2));
+                            r << 16 | g << 8 | b
+                        })
+                        .collect(),
+                }
+            }
+        }
+
+        impl From<rugby::emu::Frame<'_>> for Frame {
+            fn from(frame: rugby::emu::Frame<'_>) -> Self {
+                match frame {
+                    rugby::emu::Frame::Dmg(frame) => Self::Dmg(frame.into()),
+                    rugby::emu::Frame::Cgb(frame) => Self::Cgb(frame.into()),
+                }
+            }
+        }
+
         /// Write the most recent frame for drawing.
         ///
         /// This function blocks if the mutex is held by another thread.
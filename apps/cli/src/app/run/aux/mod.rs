@@ -6,7 +6,6 @@ use std::time::Duration;
 use anyhow::{Result, anyhow};
 use log::debug;
 use rugby::api::audio::Sample;
-use rugby::core::dmg;
 
 use crate::app;
 use crate::exe::run::Cli;
@@ -29,18 +28,16 @@ pub fn main(args: &Cli) -> Result<()> {
         return Ok(());
     }
 
-    // Define sample rates
-    let ifrq = args
-        .cli
-        .spd
-        .clone()
-        .unwrap_or_default()
-        .freq()
-        .unwrap_or(dmg::CLOCK);
-    let ofrq = args.cfg.data.audio.rate;
+    // Play audio
+    self::play(args.cfg.data.audio.rate)
+}
 
+/// Plays audio at the provided sample rate.
+///
+/// Samples pushed to the audio system are played until the application exits.
+pub fn play(ofrq: u32) -> Result<()> {
     // Initialize audio system
-    app::data::audio::init(ifrq, ofrq);
+    app::data::audio::init(ofrq);
 
     // Define output device parameters
     let params = tinyaudio::OutputDeviceParameters {
@@ -1,17 +1,35 @@
 //! Emulator assembly.
 
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result, bail, ensure};
 use log::{debug, info, warn};
+use rugby::api::camera::{ImageSource, Pattern, Still};
+use rugby::api::cheat::{Cheat, Code};
+use rugby::cfg::types::When;
 use rugby::cfg::types::model::dmg::Rev;
 use rugby::cfg::types::model::{self};
 use rugby::core::cart::Cartridge;
+use rugby::core::cart::head::parts::Board;
+use rugby::core::cgb;
 use rugby::core::dmg::boot::Boot;
+#[cfg(feature = "debug")]
+use rugby::core::dmg::dbg::sym::Symbols;
 use rugby::core::dmg::{self, rev};
+#[cfg(feature = "gbd")]
+use rugby::gbd::Debugger;
+#[cfg(feature = "gbd")]
+use rugby::gbd::prompt::Script;
+#[cfg(feature = "gbd")]
+use rugby::gbd::rsp::Remote;
+use rugby::link::Link;
 use rugby::{GameBoy, cfg};
 
 use super::save;
+#[cfg(feature = "gbd")]
+use crate::app::dbg::gbd::Console;
 use crate::app::init;
 use crate::dir;
 use crate::exe::run::{self, Cli};
@@ -33,22 +51,30 @@ pub fn emu(args: &Cli) -> Result<GameBoy> {
 
     // Instantiate emulator
     let rev = match &args.cli.model {
-        Some(model::Model::Dmg(dmg)) => dmg.rev.unwrap_or_default(),
-        None => cfg.model.dmg.rev.unwrap_or_default(),
+        Some(model::Model::Dmg(dmg)) => Some(dmg.rev.unwrap_or_default()),
+        Some(model::Model::Cgb) => None,
+        None => Some(cfg.model.dmg.rev.unwrap_or_default()),
     };
     let mut emu = match rev {
-        Rev::Zero => rugby::GameBoy::Dmg0(
+        Some(Rev::Zero) => rugby::GameBoy::Dmg0(
             boot.map_or_else(dmg::GameBoy::<rev::Zero>::new, dmg::GameBoy::with),
         ),
-        Rev::A => {
+        Some(Rev::A) => {
             rugby::GameBoy::DmgA(boot.map_or_else(dmg::GameBoy::<rev::A>::new, dmg::GameBoy::with))
         }
-        Rev::B => {
+        Some(Rev::B) => {
             rugby::GameBoy::DmgB(boot.map_or_else(dmg::GameBoy::<rev::B>::new, dmg::GameBoy::with))
         }
-        Rev::C => {
+        Some(Rev::C) => {
             rugby::GameBoy::DmgC(boot.map_or_else(dmg::GameBoy::<rev::C>::new, dmg::GameBoy::with))
         }
+        None => {
+            // Boot ROMs are DMG-only
+            if boot.is_some() {
+                warn!("boot ROM is unsupported on the CGB; skipping");
+            }
+            rugby::GameBoy::Cgb(cgb::GameBoyColor::new())
+        }
     };
     // Insert cartridge
     if let Some(cart) = cart {
@@ -61,6 +87,10 @@ pub fn emu(args: &Cli) -> Result<GameBoy> {
         );
         warn!("missing cartridge");
     }
+    // Activate cheats
+    for code in self::cheat(&args.cfg.data.cheat).context("invalid cheat")? {
+        emu.add_cheat(code);
+    }
 
     // Return emulator
     Ok(emu)
@@ -91,6 +121,34 @@ pub fn boot(args: &cfg::Boot, cli: &run::cli::Boot) -> Result<Option<Boot>> {
     Ok(Some(boot))
 }
 
+/// Builds cheat code instances.
+pub fn cheat(args: &cfg::Cheat) -> Result<Vec<Code>> {
+    args.codes
+        .iter()
+        .map(|code| {
+            code.parse()
+                .with_context(|| format!("could not parse: `{code}`"))
+                .inspect(|_| info!("activated cheat: {code}"))
+        })
+        .collect()
+}
+
+/// Builds a link cable instance.
+pub fn link(args: &cfg::Cable) -> Result<Option<Link>> {
+    // Allow none if unconfigured
+    let (Some(host), Some(peer)) = (args.host, args.peer) else {
+        return Ok(None);
+    };
+
+    // Open link socket
+    let link = Link::open(host, peer)
+        .with_context(|| format!("unable to open link: `{host}` -> `{peer}`"))?;
+    info!("connected link cable: {host} -> {peer}");
+
+    // Return success
+    Ok(Some(link))
+}
+
 /// Builds a cartridge instance.
 pub fn cart(rom: Option<&PathBuf>, args: &cfg::Cart) -> Result<Option<Cartridge>> {
     // Allow none if forced
@@ -108,7 +166,7 @@ pub fn cart(rom: Option<&PathBuf>, args: &cfg::Cart) -> Result<Option<Cartridge>
     let rom = init::util::load_until(path, 0x0080_0000).context("unable to load ROM image")?;
 
     // Initialize cartridge
-    let cart = if args.force {
+    let mut cart = if args.force {
         // If both force and check are supplied, default to force
         if args.check {
             warn!("use of `--force` overrides `--check`");
@@ -125,6 +183,143 @@ pub fn cart(rom: Option<&PathBuf>, args: &cfg::Cart) -> Result<Option<Cartridge>
     .context("unable to construct cartridge")?;
     info!("loaded cart ROM");
 
+    // Override multicart wiring
+    match args.multicart.unwrap_or_default() {
+        When::Never => cart.set_multicart(false),
+        When::Auto => (),
+        When::Always => cart.set_multicart(true),
+    }
+    if cart.multicart() {
+        info!("using MBC1 multicart wiring");
+    }
+
+    // Connect camera image source
+    if let Board::Camera = cart.header().board {
+        cart.set_camera(self::camera(args.camera.as_deref()).context("invalid camera image")?);
+    }
+
     // Return success
     Ok(Some(cart))
 }
+
+/// Loads debug symbols.
+///
+/// Without an explicit path, looks for a symbol file alongside the ROM.
+#[cfg(feature = "debug")]
+pub fn symbols(rom: Option<&PathBuf>, path: Option<&PathBuf>) -> Result<Symbols> {
+    // Find the symbol file
+    let Some(path) = path.cloned().or_else(|| {
+        rom.into_iter()
+            .flat_map(|rom| ["sym", "noi"].map(|ext| rom.with_extension(ext)))
+            .find(|path| path.is_file())
+    }) else {
+        debug!("no symbol file found");
+        return Ok(Symbols::new());
+    };
+
+    // Parse symbol file
+    let syms: Symbols = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read: `{}`", path.display()))?
+        .parse()
+        .with_context(|| format!("failed to parse: `{}`", path.display()))?;
+    info!("loaded {} symbols: `{}`", syms.len(), path.display());
+
+    Ok(syms)
+}
+
+/// Builds a debugger instance.
+#[cfg(feature = "gbd")]
+pub fn gbd(args: &Cli, emu: &GameBoy, syms: Symbols) -> Result<Option<Debugger>> {
+    let dbg = &args.dbg;
+    // Allow none if disabled
+    if !dbg.debug && dbg.script.is_none() && dbg.gdb.is_none() {
+        return Ok(None);
+    }
+    // Debugging is DMG-only
+    if !matches!(emu, GameBoy::DmgC(_)) {
+        // Scripts and remotes cannot be served without the debugger
+        ensure!(
+            dbg.script.is_none(),
+            "debugger script requires the DMG (rev. C) model"
+        );
+        ensure!(
+            dbg.gdb.is_none(),
+            "remote debugger requires the DMG (rev. C) model"
+        );
+        warn!("debugger requires the DMG (rev. C) model; skipping");
+        return Ok(None);
+    }
+
+    // Instantiate debugger
+    let mut gbd = Debugger::new();
+    gbd.symbols(syms);
+    gbd.sample_rate(args.cfg.data.audio.rate);
+    if let Some(size) = dbg.history {
+        gbd.history(size.saturating_mul(0x10_0000));
+    }
+    if let Some(log) = crate::log::RELOAD.get() {
+        gbd.logger(log.clone());
+    }
+    // Attach prompt
+    if let Some(path) = &dbg.script {
+        // Read commands from script
+        let script =
+            Script::open(path).with_context(|| format!("failed to open: `{}`", path.display()))?;
+        gbd.prompt(script);
+        info!("running debugger script: `{}`", path.display());
+    } else {
+        // Read commands from console
+        gbd.prompt(Console::new()?);
+    }
+    // Attach remote debugger
+    if let Some(addr) = dbg.gdb {
+        info!("waiting for remote debugger: `{addr}`");
+        let rsp = Remote::listen(addr)
+            .with_context(|| format!("failed to serve remote debugger: `{addr}`"))?;
+        gbd.remote(rsp);
+    }
+    // Pause before the first cycle
+    gbd.enable();
+
+    // Return success
+    Ok(Some(gbd))
+}
+
+/// Builds a camera image source.
+pub fn camera(path: Option<&Path>) -> Result<Box<dyn ImageSource>> {
+    // Default to a test pattern
+    let Some(path) = path else {
+        info!("using camera test pattern");
+        return Ok(Box::new(Pattern::default()));
+    };
+
+    // Decode PNG file
+    let file = File::open(path).with_context(|| format!("failed to open: `{}`", path.display()))?;
+    let mut decoder = png::Decoder::new(BufReader::new(file));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().context("unable to decode image")?;
+    let mut buf = vec![0; reader.output_buffer_size().context("image is too large")?];
+    let frame = reader
+        .next_frame(&mut buf)
+        .context("unable to decode image")?;
+
+    // Convert to grayscale
+    let data = buf[..frame.buffer_size()]
+        .chunks_exact(frame.color_type.samples())
+        .map(|pix| match pix {
+            // Grayscale
+            &[lum] | &[lum, _] => lum,
+            // RGB, using Rec. 601 luma
+            &[r, g, b, ..] => {
+                let lum = 299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b);
+                u8::try_from(lum / 1000).unwrap_or(u8::MAX)
+            }
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    info!("loaded camera image: {width}x{height}");
+
+    // Return success
+    Ok(Box::new(Still::new(width, height, &data)))
+}
@@ -4,13 +4,23 @@ use std::thread;
 use std::time::{Duration, Instant};
 
 use anyhow::{Context as _, Result};
+#[cfg(feature = "gbd")]
+use anyhow::{anyhow, ensure};
 use log::{debug, info};
-use rugby::api::audio::Audio;
+#[cfg(feature = "gbd")]
+use rugby::GameBoy;
+use rugby::api::audio::{Audio, Sample};
 use rugby::api::input::Input;
+use rugby::api::rumble::Rumble;
 use rugby::api::video::Video;
 use rugby::arch::Block;
+#[cfg(feature = "gfx")]
+use rugby::core::cgb;
 use rugby::core::chip::ppu;
 use rugby::core::dmg;
+#[cfg(feature = "gbd")]
+use rugby::gbd::{self, Debugger};
+use rugby::link::Link;
 
 use crate::app;
 #[cfg(feature = "trace")]
@@ -20,10 +30,12 @@ use crate::exe::run::Cli;
 pub mod drop;
 pub mod init;
 pub mod perf;
+pub mod rec;
 pub mod save;
 pub mod sync;
 
 use self::perf::Profiler;
+use self::rec::Recorder;
 use self::sync::Clocking;
 
 /// Emulator context.
@@ -39,6 +51,12 @@ pub struct Context {
     pub start: Instant,
     /// Total counter.
     pub total: u64,
+    /// Rumble status.
+    pub rumble: bool,
+    /// Link cable.
+    pub link: Option<Link>,
+    /// Audio samples.
+    pub audio: Vec<Sample>,
 }
 
 impl Default for Context {
@@ -49,11 +67,15 @@ impl Default for Context {
             batch: Profiler::default(),
             start: Instant::now(),
             total: u64::default(),
+            rumble: bool::default(),
+            link: Option::default(),
+            audio: Vec::default(),
         }
     }
 }
 
 /// Emulator main.
+#[expect(clippy::too_many_lines)]
 pub fn main(args: &Cli) -> Result<()> {
     // Instantiate emulator
     let mut emu = init::emu(args)?;
@@ -61,15 +83,47 @@ pub fn main(args: &Cli) -> Result<()> {
     let mut ctx = Context::default();
     // Prepare clocking
     ctx.clock.frq = args.cli.spd.clone().unwrap_or_default().freq();
+    // Prepare audio
+    //
+    // The output rate is scaled inversely to the emulation speed, such that
+    // samples are synthesized as quickly as they are played.
+    if !args.cli.mute {
+        let rate = (u64::from(args.cfg.data.audio.rate) * u64::from(dmg::CLOCK))
+            .checked_div(ctx.clock.frq.unwrap_or(dmg::CLOCK).into())
+            .unwrap_or_default();
+        emu.set_rate(u32::try_from(rate).unwrap_or(u32::MAX));
+    }
+    emu.set_mixer(args.cfg.data.audio.mix.unwrap_or_default().into());
+    // Prepare recording
+    //
+    // Unlike playback, audio is recorded at the configured rate regardless of
+    // emulation speed.
+    let mut rec = Recorder::new(args, &mut emu).context("recording initialization failed")?;
+    // Connect link cable
+    ctx.link = init::link(&args.cfg.data.cable).context("link cable initialization failed")?;
+    // Load debug symbols
+    #[cfg(feature = "debug")]
+    #[cfg_attr(
+        not(any(feature = "gbd", feature = "prof", feature = "trace")),
+        allow(unused)
+    )]
+    let syms = init::symbols(args.cli.cart.rom.as_ref(), args.dbg.sym.as_ref())
+        .context("symbol file initialization failed")?;
     // Initialize tracing
     #[cfg(feature = "trace")]
     let mut trace = args
         .dbg
         .tracer
         .as_ref()
-        .map(app::dbg::trace::init)
+        .map(|args| app::dbg::trace::init(args, syms.clone()))
         .transpose()
         .context("tracelog initialization failed")?;
+    // Initialize profiler
+    #[cfg(feature = "prof")]
+    let prof = app::dbg::prof::init(&args.dbg, &emu);
+    // Initialize debugger
+    #[cfg(feature = "gbd")]
+    let mut gbd = init::gbd(args, &emu, syms.clone()).context("debugger initialization failed")?;
 
     // Emulator loop
     //
@@ -97,26 +151,39 @@ pub fn main(args: &Cli) -> Result<()> {
             continue;
         }
 
-        // Cycle emulator
+        // Synchronize link cable
         //
-        // Advances the emulator by a single virtual clock cycle.
-        emu.cycle();
+        // Stalls the emulator while a serial transfer awaits the linked peer.
+        if let Some(link) = ctx.link.as_mut()
+            && link.sync(&mut emu).context("link cable failure")?
+        {
+            continue;
+        }
 
-        // Sample audio
+        // Cycle debugger
         //
-        // Audio is sampled each cycle in order to ensure the audio system
-        // remain busy, otherwise, audible "pops" will sound.
-        if !args.cli.mute {
-            app::data::audio::push(emu.sample().mix());
+        // When enabled, the debugger is synchronized with the emulator before
+        // each cycle, prompting for commands whenever it is ready.
+        #[cfg(feature = "gbd")]
+        if let Some(gbd) = gbd.as_mut()
+            && self::debug(gbd, &mut emu, &mut ctx)?
+        {
+            app::exit(app::Exit::Debugger);
+            break;
         }
 
+        // Cycle emulator
+        //
+        // Advances the emulator by a single virtual clock cycle.
+        emu.cycle();
+
         // Sample video
         //
         // Video is sampled only once per vsync, then the emulator indicates it
         // has completed drawing the frame.
         if !args.cli.headless && emu.vsync() {
             // Render video frame
-            app::data::video::draw(emu.frame().into());
+            app::data::video::draw(emu.screen().into());
             // Render debug frame
             //
             // This contains a graphical representation of the contents of VRAM.
@@ -127,12 +194,33 @@ pub fn main(args: &Cli) -> Result<()> {
                     rugby::GameBoy::DmgA(dmg)
                     | rugby::GameBoy::DmgB(dmg)
                     | rugby::GameBoy::DmgC(dmg) => dmg::dbg::ppu(dmg),
+                    rugby::GameBoy::Cgb(cgb) => cgb::dbg::ppu(cgb),
                     _ => unreachable!(),
                 };
                 app::data::debug::gfx::draw(debug);
             }
         }
 
+        // Record video
+        //
+        // Frames are recorded once per vsync, independently of the frontend,
+        // such that recording works while headless.
+        if emu.vsync() {
+            rec.frame(&mut emu).context("video recording failed")?;
+        }
+
+        // Sample rumble
+        //
+        // The cartridge's rumble motor (if any) is sampled once per frame, and
+        // reported whenever it starts or stops.
+        if emu.vsync() {
+            let rumble = emu.rumble();
+            if ctx.rumble != (rumble > 0.) {
+                ctx.rumble = rumble > 0.;
+                info!("rumble: {pct:>3.0}%", pct = 100. * rumble);
+            }
+        }
+
         // Sample trace
         //
         // Processor tracing only occurs on the the first T-cycle of the CPU's
@@ -146,6 +234,7 @@ pub fn main(args: &Cli) -> Result<()> {
                 rugby::GameBoy::DmgA(dmg)
                 | rugby::GameBoy::DmgB(dmg)
                 | rugby::GameBoy::DmgC(dmg) => dmg.inner().soc.cpu.busy(),
+                rugby::GameBoy::Cgb(cgb) => cgb.inner().soc.cpu.busy(),
                 _ => unreachable!(),
             };
             if !busy {
@@ -173,6 +262,28 @@ pub fn main(args: &Cli) -> Result<()> {
                 emu.recv(keys);
             }
 
+            // Sample audio
+            //
+            // Band-limited audio is pulled from the emulator at the same rate,
+            // which is frequent enough to keep the audio system busy,
+            // otherwise, audible "pops" will sound.
+            if !args.cli.mute {
+                emu.pull(&mut ctx.audio);
+                app::data::audio::push(&ctx.audio);
+                ctx.audio.clear();
+            }
+
+            // Toggle recording
+            if app::data::record::take() {
+                rec.toggle();
+            }
+
+            // Record audio
+            //
+            // Captured audio is written at the same rate, as the emulator only
+            // buffers a fraction of a second.
+            rec.sample(&mut emu).context("audio recording failed")?;
+
             // Report performance
             //
             // Approximately once per second, we should generate a performance
@@ -199,12 +310,58 @@ pub fn main(args: &Cli) -> Result<()> {
     let mean = ctx.clock.perf().report();
     info!("{}", self::frequency(mean));
 
+    // Export profile
+    #[cfg(feature = "prof")]
+    if let Some(prof) = prof {
+        app::dbg::prof::dump(&args.dbg, &prof.borrow(), &emu, &syms)
+            .context("profile export failed")?;
+    }
+
+    // Finish recording
+    rec.finish(&mut emu).context("recording failed")?;
+
     // Destroy emulator
     drop::emu(emu, args).context("shutdown sequence failed")?;
 
+    // Report failed assertions
+    #[cfg(feature = "gbd")]
+    if let Some(fail) = gbd.map(|gbd| gbd.failures()) {
+        ensure!(fail == 0, "debugger assertions failed: {fail}");
+    }
+
     Ok(())
 }
 
+/// Cycles the debugger.
+///
+/// Returns whether the debugger requested to quit.
+#[cfg(feature = "gbd")]
+fn debug(gbd: &mut Debugger, emu: &mut GameBoy, ctx: &mut Context) -> Result<bool> {
+    // Debugging is DMG-only
+    let GameBoy::DmgC(emu) = emu else {
+        return Ok(false);
+    };
+    // Synchronize with the emulator
+    gbd.sync(emu);
+    if gbd.ready() {
+        // Prompt for commands
+        match gbd.run(emu) {
+            Err(gbd::Error::Quit) => return Ok(true),
+            res => res
+                .map_err(|err| anyhow!("{err}"))
+                .context("debugger failed")?,
+        }
+        // Re-synchronize clock after pausing
+        ctx.clock = Clocking {
+            frq: ctx.clock.frq,
+            ..Clocking::default()
+        };
+    }
+    gbd.cycle();
+
+    Ok(false)
+}
+
 /// Generates a benchmark report.
 fn benchmark(tick: u64, time: Duration) -> String {
     format!(
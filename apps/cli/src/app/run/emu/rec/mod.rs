@@ -0,0 +1,247 @@
+//! Gameplay recording.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result, ensure};
+use log::{error, info};
+use rugby::GameBoy;
+use rugby::api::audio::rec::Recording as Audio;
+use rugby::api::audio::{Audio as _, Capture};
+use rugby::pal::Palette;
+
+use crate::app::data::video::Frame;
+use crate::exe::run::Cli;
+
+pub mod video;
+
+/// Video recording, alongside its muxed audio.
+type Movie = (video::Recording, Option<Audio>);
+
+/// Gameplay recorder.
+///
+/// Manages audio and video recordings, sharing audio captured from the
+/// emulator between them.
+///
+/// Video recordings start and stop on frame boundaries, such that any muxed
+/// audio covers the same period.
+///
+/// Should the recorder be dropped before it is [finished](Self::finish), its
+/// recordings are still closed, such that they remain valid.
+#[derive(Debug)]
+pub struct Recorder {
+    /// Audio sample rate.
+    rate: u32,
+    /// Color palette.
+    pal: Palette,
+    /// Video path template.
+    ///
+    /// Recordings started by toggling are named after this path.
+    base: PathBuf,
+    /// Mux audio with video.
+    mux: bool,
+    /// Audio recording.
+    audio: Option<Audio>,
+    /// Video recording.
+    video: Option<Movie>,
+    /// Pending video recording.
+    start: Option<PathBuf>,
+    /// Pending video stop.
+    stop: bool,
+    /// Captured audio.
+    buf: Capture,
+}
+
+impl Recorder {
+    /// Constructs a new `Recorder`.
+    ///
+    /// Audio recording begins immediately, whereas video recording begins on
+    /// the next frame.
+    pub fn new(args: &Cli, emu: &mut GameBoy) -> Result<Self> {
+        let rate = args.cfg.data.audio.rate;
+        // Resolve video path template
+        let base = args.cli.rec.video.clone().unwrap_or_else(|| {
+            let stem = args
+                .cli
+                .cart
+                .rom
+                .as_deref()
+                .and_then(Path::file_stem)
+                .unwrap_or(OsStr::new(crate::NAME));
+            PathBuf::from(stem).with_extension("png")
+        });
+        if let Some(path) = &args.cli.rec.video {
+            video::Format::from_path(path)?;
+        }
+        // Reject clashing recordings
+        //
+        // Muxed audio is named after the video, and so must not overwrite the
+        // audio recording.
+        if args.cli.rec.mux
+            && let (Some(video), Some(audio)) = (&args.cli.rec.video, &args.cli.rec.audio)
+        {
+            ensure!(
+                video.with_extension("wav") != *audio,
+                "muxed audio would overwrite the audio recording: `{}`",
+                audio.display()
+            );
+        }
+        // Start audio recording
+        let audio = args
+            .cli
+            .rec
+            .audio
+            .as_ref()
+            .map(|path| Self::record(path, rate, args.cli.rec.stems))
+            .transpose()?;
+
+        let this = Self {
+            rate,
+            pal: args.cfg.data.video.pal.clone().unwrap_or_default().into(),
+            base,
+            mux: args.cli.rec.mux,
+            audio,
+            video: None,
+            start: args.cli.rec.video.clone(),
+            stop: false,
+            buf: Capture::default(),
+        };
+        if this.capturing() {
+            emu.set_capture(rate);
+        }
+
+        Ok(this)
+    }
+
+    /// Toggles video recording.
+    ///
+    /// Recordings started this way are written alongside the video path
+    /// template, suffixed by a timestamp.
+    pub fn toggle(&mut self) {
+        if self.video.is_some() {
+            self.stop = !self.stop;
+        } else if self.start.take().is_none() {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis();
+            let stem = self.base.file_stem().unwrap_or_default().to_string_lossy();
+            let ext = self.base.extension().unwrap_or_default().to_string_lossy();
+            self.start = Some(self.base.with_file_name(format!("{stem}-{time}.{ext}")));
+        }
+    }
+
+    /// Writes audio captured by the emulator.
+    pub fn sample(&mut self, emu: &mut GameBoy) -> Result<()> {
+        if !self.capturing() {
+            return Ok(());
+        }
+        emu.capture(&mut self.buf);
+        if let Some(rec) = self.audio.as_mut() {
+            rec.write(&self.buf).context("failed to write audio")?;
+        }
+        if let Some((_, Some(rec))) = self.video.as_mut() {
+            rec.write(&self.buf)
+                .context("failed to write muxed audio")?;
+        }
+        self.buf.clear();
+
+        Ok(())
+    }
+
+    /// Writes the frame drawn by the emulator.
+    ///
+    /// Must be called upon each vertical sync.
+    pub fn frame(&mut self, emu: &mut GameBoy) -> Result<()> {
+        let capturing = self.capturing();
+        // Stop pending recording
+        if std::mem::take(&mut self.stop) {
+            self.sample(emu)?;
+            if let Some(movie) = self.video.take() {
+                Self::close(movie)?;
+            }
+        }
+        // Start pending recording
+        if let Some(path) = self.start.take() {
+            self.sample(emu)?;
+            let mux = self
+                .mux
+                .then(|| Self::record(&path.with_extension("wav"), self.rate, false))
+                .transpose()?;
+            self.video = Some((video::Recording::new(&path)?, mux));
+        }
+        // Update audio capture
+        //
+        // Capture is only reconfigured when needed, as doing so discards any
+        // partially synthesized samples.
+        if self.capturing() != capturing {
+            emu.set_capture(if self.capturing() { self.rate } else { 0 });
+        }
+        // Write frame
+        if let Some((rec, _)) = self.video.as_mut() {
+            rec.write(&Frame::from(emu.screen()).render(&self.pal))?;
+        }
+
+        Ok(())
+    }
+
+    /// Finishes all recordings.
+    ///
+    /// Any remaining captured audio is written before the files are closed.
+    pub fn finish(mut self, emu: &mut GameBoy) -> Result<()> {
+        self.sample(emu)?;
+        if let Some(movie) = self.video.take() {
+            Self::close(movie)?;
+        }
+        if let Some(rec) = self.audio.take() {
+            Self::stop(rec)?;
+        }
+
+        Ok(())
+    }
+
+    /// Starts an audio recording.
+    fn record(path: &Path, rate: u32, stems: bool) -> Result<Audio> {
+        let rec = Audio::new(path, rate, stems)
+            .with_context(|| format!("failed to open: `{}`", path.display()))?;
+        info!("recording audio: `{}`", path.display());
+
+        Ok(rec)
+    }
+
+    /// Stops an audio recording, closing its files.
+    fn stop(rec: Audio) -> Result<()> {
+        for path in rec.finish().context("failed to finish audio")? {
+            info!("wrote recording: `{}`", path.display());
+        }
+
+        Ok(())
+    }
+
+    /// Checks if audio is being captured.
+    fn capturing(&self) -> bool {
+        self.audio.is_some() || matches!(self.video, Some((_, Some(_))))
+    }
+
+    /// Closes a video recording, alongside its muxed audio.
+    fn close((video, audio): Movie) -> Result<()> {
+        video.finish()?;
+        if let Some(rec) = audio {
+            Self::stop(rec)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        // Ensure unfinished recordings remain valid
+        let video = self.video.take().map(Self::close);
+        let audio = self.audio.take().map(Self::stop);
+        for err in [video, audio].into_iter().flatten().filter_map(Result::err) {
+            error!("could not finish recording: {err:#}");
+        }
+    }
+}
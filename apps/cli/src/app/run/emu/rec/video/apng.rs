@@ -0,0 +1,225 @@
+//! Animated PNG encoding.
+
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+
+use anyhow::{Context, Result};
+use log::error;
+use rugby::core::chip::ppu::{self, LCD};
+use rugby::core::dmg;
+
+/// Animation control chunk position.
+///
+/// As no ancillary chunks are configured, the encoder writes the animation
+/// control chunk directly following the signature and image header.
+const ACTL: u64 = 8 + 25;
+
+/// Maximum frames to hold.
+///
+/// Keeps the delay of a held frame, in milliseconds, within range.
+const HOLD: u32 = 3600;
+
+/// Animated PNG encoder.
+///
+/// As the format records the number of frames ahead of the image data, the
+/// count is patched into the header once [finished](Self::finish).
+///
+/// Consecutive identical frames are held, being written once with a longer
+/// delay.
+pub struct Apng {
+    /// Image encoder.
+    png: Option<png::Writer<BufWriter<File>>>,
+    /// Output file.
+    file: File,
+    /// Held frame.
+    held: Option<Vec<u8>>,
+    /// Held duration (in frames).
+    hold: u32,
+    /// Elapsed duration (in frames).
+    tick: u64,
+    /// Elapsed time (in milliseconds).
+    time: u64,
+    /// Written frames.
+    count: u32,
+}
+
+impl Apng {
+    /// Constructs a new `Apng`.
+    pub fn new(file: File) -> Result<Self> {
+        // Declare image properties
+        let out = BufWriter::new(file.try_clone()?);
+        let mut enc = png::Encoder::new(out, LCD.wd.into(), LCD.ht.into());
+        enc.set_color(png::ColorType::Rgb);
+        enc.set_depth(png::BitDepth::Eight);
+        enc.set_animated(u32::MAX, 0)?;
+        // Write header
+        let png = enc.write_header()?;
+
+        Ok(Self {
+            png: Some(png),
+            file,
+            held: None,
+            hold: 0,
+            tick: 0,
+            time: 0,
+            count: 0,
+        })
+    }
+
+    /// Writes a frame to the image.
+    pub fn write(&mut self, frame: &[u32]) -> Result<()> {
+        let data = frame
+            .iter()
+            .flat_map(|pix| {
+                let [_, r, g, b] = pix.to_be_bytes();
+                [r, g, b]
+            })
+            .collect::<Vec<_>>();
+        // Hold repeated frames
+        if self.held.as_ref() == Some(&data) && self.hold < HOLD {
+            self.hold += 1;
+            return Ok(());
+        }
+        // Write previous frame
+        self.flush()?;
+        self.held = Some(data);
+        self.hold = 1;
+
+        Ok(())
+    }
+
+    /// Finishes the image, completing its header.
+    pub fn finish(mut self) -> Result<()> {
+        self.close()
+    }
+
+    /// Writes the held frame, if any.
+    fn flush(&mut self) -> Result<()> {
+        let (Some(png), Some(data)) = (self.png.as_mut(), self.held.take()) else {
+            return Ok(());
+        };
+        // Compute frame delay
+        //
+        // Delays are rounded from the elapsed time, such that rounding errors
+        // do not accumulate across frames.
+        self.tick += u64::from(self.hold);
+        let time = (self.tick * u64::from(ppu::FRAME) * 1000 + u64::from(dmg::CLOCK) / 2)
+            / u64::from(dmg::CLOCK);
+        let delay = u16::try_from(time - self.time).unwrap_or(u16::MAX);
+        self.time = time;
+        // Write frame
+        png.set_frame_delay(delay, 1000)?;
+        png.write_image_data(&data)?;
+        self.count += 1;
+
+        Ok(())
+    }
+
+    /// Closes the image, completing its header.
+    fn close(&mut self) -> Result<()> {
+        // Ensure at least one frame, as the image is otherwise invalid
+        if self.count == 0 && self.held.is_none() && self.png.is_some() {
+            self.held = Some(vec![0; usize::from(LCD.wd) * usize::from(LCD.ht) * 3]);
+            self.hold = 1;
+        }
+        self.flush()?;
+        let Some(png) = self.png.take() else {
+            return Ok(());
+        };
+        png.finish()?;
+        // Record frame count
+        let mut chunk = b"acTL".to_vec();
+        chunk.extend(self.count.to_be_bytes());
+        chunk.extend(0_u32.to_be_bytes()); // loop forever
+        let crc = self::crc(&chunk);
+        self.file.seek(SeekFrom::Start(ACTL + 4))?;
+        self.file.write_all(&chunk)?;
+        self.file.write_all(&crc.to_be_bytes())?;
+        self.file.flush().context("failed to record frame count")
+    }
+}
+
+impl Debug for Apng {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Apng")
+            .field("file", &self.file)
+            .field("hold", &self.hold)
+            .field("tick", &self.tick)
+            .field("time", &self.time)
+            .field("count", &self.count)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Apng {
+    fn drop(&mut self) {
+        // Ensure unfinished recordings remain valid
+        if let Err(err) = self.close() {
+            error!("could not finish recording: {err}");
+        }
+    }
+}
+
+/// Computes a chunk's CRC.
+fn crc(data: &[u8]) -> u32 {
+    !data.iter().fold(u32::MAX, |crc, &byte| {
+        (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes an image, returning its frame count and frames.
+    fn decode(path: &std::path::Path) -> (u32, Vec<Vec<u8>>) {
+        let file = std::io::BufReader::new(File::open(path).unwrap());
+        let mut dec = png::Decoder::new(file).read_info().unwrap();
+        let count = dec.info().animation_control().unwrap().num_frames;
+        let mut frames = Vec::new();
+        for _ in 0..count {
+            let mut buf = vec![0; dec.output_buffer_size().unwrap()];
+            dec.next_frame(&mut buf).unwrap();
+            frames.push(buf);
+        }
+        (count, frames)
+    }
+
+    #[test]
+    fn crc_works() {
+        assert_eq!(self::crc(b"IEND"), 0xae42_6082);
+    }
+
+    #[test]
+    fn apng_works() {
+        let path = std::env::temp_dir().join("rugby-apng-works.png");
+        let mut apng = Apng::new(File::create(&path).unwrap()).unwrap();
+        let len = usize::from(LCD.wd) * usize::from(LCD.ht);
+        apng.write(&vec![0x00ff_ffff; len]).unwrap();
+        apng.write(&vec![0x00ff_ffff; len]).unwrap();
+        apng.write(&vec![0x0000_0000; len]).unwrap();
+        apng.finish().unwrap();
+        let (count, frames) = decode(&path);
+        std::fs::remove_file(path).unwrap();
+
+        // Repeated frames are held
+        assert_eq!(count, 2);
+        assert!(frames[0].iter().all(|&byte| byte == 0xff));
+        assert!(frames[1].iter().all(|&byte| byte == 0x00));
+    }
+
+    #[test]
+    fn apng_empty_works() {
+        let path = std::env::temp_dir().join("rugby-apng-empty.png");
+        drop(Apng::new(File::create(&path).unwrap()).unwrap());
+        let (count, frames) = decode(&path);
+        std::fs::remove_file(path).unwrap();
+
+        // Unwritten images hold a blank frame
+        assert_eq!(count, 1);
+        assert!(frames[0].iter().all(|&byte| byte == 0x00));
+    }
+}
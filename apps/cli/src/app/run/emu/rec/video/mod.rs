@@ -0,0 +1,102 @@
+//! Video recording.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow};
+use log::info;
+
+mod apng;
+mod y4m;
+
+use self::apng::Apng;
+use self::y4m::Y4m;
+
+/// Video format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Animated PNG.
+    Apng,
+    /// YUV4MPEG2 stream.
+    Y4m,
+}
+
+impl Format {
+    /// Selects a format by the path's extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("png" | "apng") => Ok(Self::Apng),
+            Some("y4m") => Ok(Self::Y4m),
+            _ => Err(anyhow!(
+                "unsupported video format: `{}` (expected \".png\" or \".y4m\")",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// Video encoder.
+#[derive(Debug)]
+enum Encoder {
+    /// Animated PNG.
+    Apng(Apng),
+    /// YUV4MPEG2 stream.
+    Y4m(Y4m),
+}
+
+/// Video recording.
+///
+/// Writes rendered frames to a file, encoded according to its extension.
+#[derive(Debug)]
+pub struct Recording {
+    /// Output path.
+    path: PathBuf,
+    /// Frame encoder.
+    enc: Encoder,
+}
+
+impl Recording {
+    /// Constructs a new `Recording`.
+    pub fn new(path: &Path) -> Result<Self> {
+        let fmt = Format::from_path(path)?;
+        let file =
+            File::create(path).with_context(|| format!("failed to open: `{}`", path.display()))?;
+        let enc = match fmt {
+            Format::Apng => Apng::new(file).map(Encoder::Apng),
+            Format::Y4m => Y4m::new(file).map(Encoder::Y4m),
+        }
+        .with_context(|| format!("failed to write: `{}`", path.display()))?;
+        info!("recording video: `{}`", path.display());
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            enc,
+        })
+    }
+
+    /// Writes a rendered frame.
+    pub fn write(&mut self, frame: &[u32]) -> Result<()> {
+        match &mut self.enc {
+            Encoder::Apng(enc) => enc.write(frame),
+            Encoder::Y4m(enc) => enc.write(frame),
+        }
+        .with_context(|| format!("failed to write: `{}`", self.path.display()))
+    }
+
+    /// Finishes the recording, closing its file.
+    pub fn finish(self) -> Result<()> {
+        match self.enc {
+            Encoder::Apng(enc) => enc.finish(),
+            Encoder::Y4m(enc) => enc.finish(),
+        }
+        .with_context(|| format!("failed to write: `{}`", self.path.display()))?;
+        info!("wrote recording: `{}`", self.path.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+//! YUV4MPEG2 encoding.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use anyhow::Result;
+use rugby::core::chip::ppu::{self, LCD};
+use rugby::core::dmg;
+
+/// YUV4MPEG2 encoder.
+///
+/// Frames are streamed uncompressed as full-resolution (4:4:4) planes, at the
+/// console's exact frame rate.
+#[derive(Debug)]
+pub struct Y4m {
+    /// Output stream.
+    out: BufWriter<File>,
+}
+
+impl Y4m {
+    /// Constructs a new `Y4m`.
+    pub fn new(file: File) -> Result<Self> {
+        let mut out = BufWriter::new(file);
+        writeln!(
+            out,
+            "YUV4MPEG2 W{wd} H{ht} F{num}:{den} Ip A1:1 C444",
+            wd = LCD.wd,
+            ht = LCD.ht,
+            num = dmg::CLOCK,
+            den = ppu::FRAME,
+        )?;
+
+        Ok(Self { out })
+    }
+
+    /// Writes a frame to the stream.
+    pub fn write(&mut self, frame: &[u32]) -> Result<()> {
+        let pixels = frame.iter().copied().map(self::yuv).collect::<Vec<_>>();
+        self.out.write_all(b"FRAME\n")?;
+        for plane in 0..3 {
+            let data = pixels.iter().map(|pix| pix[plane]).collect::<Vec<_>>();
+            self.out.write_all(&data)?;
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the stream.
+    pub fn finish(mut self) -> Result<()> {
+        self.out.flush().map_err(Into::into)
+    }
+}
+
+/// Converts a pixel to limited-range BT.601 YCbCr.
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn yuv(pix: u32) -> [u8; 3] {
+    let [_, r, g, b] = pix.to_be_bytes().map(i32::from);
+    let luma = ((66 * r + 129 * g + 25 * b + 128) >> 8) + 16;
+    let cb = ((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128;
+    let cr = ((112 * r - 94 * g - 18 * b + 128) >> 8) + 128;
+    [luma, cb, cr].map(|ch| ch as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yuv_works() {
+        assert_eq!(self::yuv(0x0000_0000), [16, 128, 128]);
+        assert_eq!(self::yuv(0x00ff_ffff), [235, 128, 128]);
+        assert_eq!(self::yuv(0x00ff_0000), [82, 90, 240]);
+    }
+
+    #[test]
+    fn y4m_works() {
+        let path = std::env::temp_dir().join("rugby-y4m-works.y4m");
+        let mut y4m = Y4m::new(File::create(&path).unwrap()).unwrap();
+        let len = usize::from(LCD.wd) * usize::from(LCD.ht);
+        y4m.write(&vec![0x00ff_ffff; len]).unwrap();
+        y4m.write(&vec![0x0000_0000; len]).unwrap();
+        y4m.finish().unwrap();
+        let data = std::fs::read(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let head = b"YUV4MPEG2 W160 H144 F4194304:70224 Ip A1:1 C444\n";
+        assert!(data.starts_with(head));
+        let body = &data[head.len()..];
+        let size = b"FRAME\n".len() + 3 * len;
+        assert_eq!(body.len(), 2 * size);
+        assert!(body.starts_with(b"FRAME\n"));
+        assert!(body[6..6 + len].iter().all(|&luma| luma == 235));
+        assert!(body[size..].starts_with(b"FRAME\n"));
+        assert!(
+            body[size + 6..size + 6 + len]
+                .iter()
+                .all(|&luma| luma == 16)
+        );
+    }
+}
@@ -22,7 +22,7 @@ pub fn load(rom: Option<&PathBuf>, args: &cfg::Cart, cart: &mut Cartridge) -> Re
         }
         When::Auto => {
             let board = &cart.header().board;
-            if board.has_ram() && board.has_battery() {
+            if (board.has_ram() || board.has_clock()) && board.has_battery() {
                 trace!("load RAM automatically enabled");
             } else {
                 trace!("load RAM automatically disabled");
@@ -33,7 +33,7 @@ pub fn load(rom: Option<&PathBuf>, args: &cfg::Cart, cart: &mut Cartridge) -> Re
             trace!("load RAM enabled by user");
         }
     }
-    if !cart.header().board.has_ram() {
+    if !(cart.header().board.has_ram() || cart.header().board.has_clock()) {
         error!("cannot load: cartridge does not support RAM");
         return Ok(());
     }
@@ -44,7 +44,7 @@ pub fn load(rom: Option<&PathBuf>, args: &cfg::Cart, cart: &mut Cartridge) -> Re
     // Open RAM file
     let mut file = File::open(&path)
         .with_context(|| format!("failed to open: `{}`", path.display()))?
-        .take(0x0002_0000); // cartridge ROM has a maximum of 128 KiB
+        .take(0x0002_0030); // cartridge RAM has a maximum of 128 KiB, plus clock data
     // Load into cartridge
     let nbytes = cart
         .flash(&mut file)
@@ -71,7 +71,7 @@ pub fn dump(rom: Option<&PathBuf>, args: &cfg::Cart, cart: &Cartridge) -> Result
         }
         When::Auto => {
             let board = &cart.header().board;
-            if board.has_ram() && board.has_battery() {
+            if (board.has_ram() || board.has_clock()) && board.has_battery() {
                 trace!("dump RAM automatically enabled");
             } else {
                 trace!("dump RAM automatically disabled");
@@ -82,7 +82,7 @@ pub fn dump(rom: Option<&PathBuf>, args: &cfg::Cart, cart: &Cartridge) -> Result
             trace!("dump RAM enabled by user");
         }
     }
-    if !cart.header().board.has_ram() {
+    if !(cart.header().board.has_ram() || cart.header().board.has_clock()) {
         error!("cannot dump: cartridge does not support RAM");
         return Ok(());
     }
@@ -28,7 +28,6 @@ impl Default for Clocking {
 
 impl Clocking {
     /// Constructs a new `Sync`
-    #[expect(unused)]
     pub fn new(delay: u32) -> Self {
         Self {
             frq: Some(delay),
@@ -94,7 +93,12 @@ impl Clocking {
     }
 
     /// Resets synchronization clock.
+    ///
+    /// The target frequency is retained.
     pub fn reset(&mut self) {
-        std::mem::take(self);
+        *self = Self {
+            frq: self.frq,
+            ..Self::default()
+        };
     }
 }
@@ -18,50 +18,9 @@ pub mod gui;
 ///
 /// In order to satisfy constraints of Cocoa-based systems, this function must
 /// be called from the main thread.
-#[expect(irrefutable_let_patterns)]
 pub fn main(args: &Cli) -> Result<()> {
-    // Health flag
-    //
-    // If the application is healthy, it should respond to the first
-    // termination request by lowering the flag.
-    let mut healthy = true;
-
-    // Replace default panic hook
-    //
-    // Ensure all threads exit on panic by modifying the panic hook to signal
-    // global application exit.
-    if let panic = std::panic::take_hook() {
-        std::panic::set_hook(Box::new(move |info| {
-            // Exit all threads
-            app::exit(Exit::Unknown);
-            // Run default hook
-            panic(info);
-        }));
-    }
-
-    // Install signal handler
-    ctrlc::try_set_handler(move || {
-        trace!("received signal");
-        // Attempt graceful exit (with cleanup) on first signal
-        if healthy {
-            debug!("attempting graceful exit");
-            if app::running() {
-                // Signal app exit
-                app::exit(Exit::Signal);
-            } else {
-                // Already exiting, no-op
-                debug!("exit already in progress");
-            }
-            // Lower health flag
-            healthy = false;
-        }
-        // Abort application (without cleanup) if hanging
-        else {
-            error!("process terminated");
-            process::abort();
-        }
-    })
-    .expect("unable to register signal handler"); // application error
+    // Install handlers
+    self::install();
 
     // Process exit flag
     if args.cli.exit {
@@ -108,6 +67,56 @@ pub fn main(args: &Cli) -> Result<()> {
     })
 }
 
+/// Installs process-wide handlers.
+///
+/// Replaces the panic hook and installs a signal handler, both of which signal
+/// all threads to exit.
+#[expect(irrefutable_let_patterns)]
+pub fn install() {
+    // Health flag
+    //
+    // If the application is healthy, it should respond to the first
+    // termination request by lowering the flag.
+    let mut healthy = true;
+
+    // Replace default panic hook
+    //
+    // Ensure all threads exit on panic by modifying the panic hook to signal
+    // global application exit.
+    if let panic = std::panic::take_hook() {
+        std::panic::set_hook(Box::new(move |info| {
+            // Exit all threads
+            app::exit(Exit::Unknown);
+            // Run default hook
+            panic(info);
+        }));
+    }
+
+    // Install signal handler
+    ctrlc::try_set_handler(move || {
+        trace!("received signal");
+        // Attempt graceful exit (with cleanup) on first signal
+        if healthy {
+            debug!("attempting graceful exit");
+            if app::running() {
+                // Signal app exit
+                app::exit(Exit::Signal);
+            } else {
+                // Already exiting, no-op
+                debug!("exit already in progress");
+            }
+            // Lower health flag
+            healthy = false;
+        }
+        // Abort application (without cleanup) if hanging
+        else {
+            error!("process terminated");
+            process::abort();
+        }
+    })
+    .expect("unable to register signal handler"); // application error
+}
+
 /// Monitors a thread for errors.
 pub fn watch<F>(main: F) -> impl FnOnce() -> Result<()>
 where
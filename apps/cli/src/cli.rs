@@ -50,10 +50,18 @@ pub enum Command {
     #[command(visible_alias = "c")]
     Chk(Box<exe::chk::Cli>),
 
+    /// Disassemble a cartridge ROM.
+    #[command(name = "dis")]
+    #[command(visible_alias = "d")]
+    Dis(Box<exe::dis::Cli>),
+
     /// Run a cartridge ROM in the emulator.
     #[command(visible_alias = "r")]
     Run(Box<exe::run::Cli>),
 
+    /// Play a GBS sound rip.
+    Gbs(Box<exe::gbs::Cli>),
+
     /// Generate application support files.
     Gen(Box<exe::r#gen::Cli>),
 
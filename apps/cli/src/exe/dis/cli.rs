@@ -0,0 +1,26 @@
+//! Command-line interface.
+
+use std::path::PathBuf;
+
+use super::NAME;
+
+/// Disassemble a cartridge ROM.
+#[derive(Debug)]
+#[derive(clap::Parser)]
+#[command(name = NAME)]
+#[command(arg_required_else_help = true)]
+#[group(id = "dis::Cli")]
+pub struct Cli {
+    /// Disassemble a single bank.
+    ///
+    /// When omitted, every bank in the ROM is disassembled in order.
+    #[arg(short, long)]
+    #[arg(value_name = "BANK")]
+    #[arg(help_heading = None)]
+    pub bank: Option<usize>,
+
+    /// Cartridge ROM image file.
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    #[arg(help_heading = None)]
+    pub rom: PathBuf,
+}
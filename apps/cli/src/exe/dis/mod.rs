@@ -0,0 +1,80 @@
+//! Disassemble provided ROM.
+
+use std::io::{BufWriter, Write};
+
+use anyhow::{Context, anyhow};
+use constcat::concat;
+use log::trace;
+use rugby::core::chip::cpu::insn::dis;
+
+use crate::app::init;
+use crate::err::Result;
+
+pub mod cli;
+
+pub use self::cli::Cli;
+
+/// Subcommand name.
+pub const NAME: &str = concat!(crate::NAME, "-dis");
+
+/// ROM bank size.
+const BANK: usize = 0x4000;
+
+/// [`Disassemble`](crate::cli::Command::Dis) entrypoint.
+#[expect(clippy::needless_pass_by_value)]
+pub fn main(args: Cli) -> Result<()> {
+    // Initialize logger
+    crate::log::init(None).context("logger initialization failed")?;
+    // Log arguments
+    trace!("{args:#?}");
+
+    // Read ROM data
+    //
+    // NOTE: Game Paks manufactured by Nintendo have a maximum 8 MiB ROM.
+    let rom = init::util::load_until(&args.rom, 0x0080_0000).context("unable to load ROM image")?;
+
+    // Select banks to disassemble
+    let nbanks = rom.len().div_ceil(BANK);
+    let banks = match args.bank {
+        Some(bank) if bank >= nbanks => {
+            return Err(anyhow!("bank {bank} out of range (found {nbanks} banks)").into());
+        }
+        Some(bank) => bank..bank + 1,
+        None => 0..nbanks,
+    };
+
+    // Disassemble each bank
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    for bank in banks {
+        // Map the bank to its address in memory
+        //
+        // Bank 0 is fixed at `$0000`, with all others switched in at `$4000`.
+        let (base, data): (u16, _) = match bank {
+            0 => (0x0000, &rom[..]),
+            _ => (0x4000, &rom[(bank - 1) * BANK..]),
+        };
+        let size = (rom.len() - bank * BANK).min(BANK);
+        let data = &data[..usize::from(base) + size];
+        let last = u16::try_from(size - 1).expect("bank size should fit in an address");
+        let range = base..=base + last;
+        // Print the listing
+        writeln!(out, "; bank ${bank:02x}").context("failed to write output")?;
+        for insn in dis::disassemble(data, range) {
+            let bytes = insn
+                .bytes()
+                .iter()
+                .map(|byte| format!("{byte:02X}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(
+                out,
+                "{bank:02x}:{addr:04x}: {bytes:<8} ; {insn}",
+                addr = insn.addr(),
+            )
+            .context("failed to write output")?;
+        }
+    }
+    out.flush().context("failed to write output")?;
+
+    Ok(())
+}
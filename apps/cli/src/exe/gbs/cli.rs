@@ -0,0 +1,37 @@
+//! Command-line interface.
+
+use std::path::PathBuf;
+
+use super::NAME;
+use crate::cli::Settings;
+
+/// Play a GBS sound rip.
+#[derive(Debug)]
+#[derive(clap::Parser)]
+#[command(name = NAME)]
+#[command(arg_required_else_help = true)]
+#[group(id = "gbs::Cli")]
+pub struct Cli {
+    /// Configuration options.
+    #[command(flatten)]
+    pub cfg: Settings,
+
+    /// GBS sound rip file.
+    ///
+    /// A cartridge will be constructed around the data in this file, which
+    /// calls its sound driver to play the selected track.
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    #[arg(help_heading = None)]
+    pub gbs: PathBuf,
+
+    /// Starting track.
+    ///
+    /// Selects the track to play first, numbered from 1. If unspecified, the
+    /// rip's default track is played. While playing, tracks can be switched by
+    /// entering a track number, or "n" and "p" for the next and previous
+    /// tracks.
+    #[arg(short = 't', long = "track", value_name = "N")]
+    #[arg(value_parser = clap::value_parser!(u8).range(1..))]
+    #[arg(help_heading = None)]
+    pub track: Option<u8>,
+}
@@ -0,0 +1,32 @@
+//! Play provided GBS rip.
+
+use anyhow::Context as _;
+use constcat::concat;
+use log::trace;
+use merge::Merge;
+
+use crate::err::Result;
+use crate::{app, cfg};
+
+pub mod cli;
+
+pub use self::cli::Cli;
+
+/// Subcommand name.
+pub const NAME: &str = concat!(crate::NAME, "-gbs");
+
+/// [`Gbs`](crate::cli::Command::Gbs) entrypoint.
+pub fn main(mut args: Cli) -> Result<()> {
+    // Load configuration
+    args.cfg.data.merge({
+        // Parse config from file
+        cfg::load(&args.cfg.path)?
+    });
+    // Initialize logger
+    crate::log::init(args.cfg.data.log.as_deref()).context("logger initialization failed")?;
+    // Log arguments
+    trace!("{args:#?}");
+
+    // Run application
+    app::gbs(&args).map_err(Into::into)
+}
@@ -61,9 +61,15 @@ pub enum Command {
     #[value(name = "check")]
     #[value(alias = "c")]
     Chk,
+    /// Disassemble ROM.
+    #[value(name = "dis")]
+    #[value(alias = "d")]
+    Dis,
     /// Play ROM in emulator.
     #[value(alias = "r")]
     Run,
+    /// Play GBS rip in emulator.
+    Gbs,
     /// Generate application support files.
     Gen,
     /// Display docs for a command.
@@ -111,7 +111,9 @@ pub mod man {
         let mut cmd = match cmd {
             None => crate::Cli::command(),
             Some(Subcommand::Chk) => crate::exe::chk::Cli::command(),
+            Some(Subcommand::Dis) => crate::exe::dis::Cli::command(),
             Some(Subcommand::Run) => crate::exe::run::Cli::command(),
+            Some(Subcommand::Gbs) => crate::exe::gbs::Cli::command(),
             Some(Subcommand::Gen) => crate::exe::r#gen::Cli::command(),
             Some(Subcommand::Man) => crate::exe::man::Cli::command(),
         }
@@ -29,7 +29,9 @@ pub fn main(args: Cli) -> Result<()> {
     let mut cmd = match args.cmd {
         None => crate::Cli::command(),
         Some(cli::Command::Chk) => crate::exe::chk::Cli::command(),
+        Some(cli::Command::Dis) => crate::exe::dis::Cli::command(),
         Some(cli::Command::Run) => crate::exe::run::Cli::command(),
+        Some(cli::Command::Gbs) => crate::exe::gbs::Cli::command(),
         Some(cli::Command::Gen) => crate::exe::r#gen::Cli::command(),
         Some(cli::Command::Man) => crate::exe::man::Cli::command(),
     }
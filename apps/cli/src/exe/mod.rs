@@ -1,6 +1,8 @@
 //! Application subcommands.
 
 pub mod chk;
+pub mod dis;
+pub mod gbs;
 pub mod r#gen;
 pub mod man;
 pub mod run;
@@ -1,5 +1,7 @@
 //! Command-line interface.
 
+#[cfg(feature = "gbd")]
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 use rugby::cfg::types::{model, speed};
@@ -40,6 +42,10 @@ pub struct Opt {
     #[command(flatten)]
     pub cart: Cart,
 
+    /// Recording options.
+    #[command(flatten)]
+    pub rec: Record,
+
     /// Hardware model.
     ///
     /// Selects the emulated hardware platform and optionally a silicon
@@ -111,6 +117,50 @@ pub struct Cart {
     pub rom: Option<PathBuf>,
 }
 
+/// Recording options.
+#[derive(Debug)]
+#[derive(clap::Args)]
+#[group(id = "run::Record")]
+pub struct Record {
+    /// Record audio to a file.
+    ///
+    /// Captures audio at the configured sample rate, writing it as a WAV file.
+    /// Recording is independent of playback, such that it may be used while
+    /// muted or running at a different speed.
+    #[arg(long = "record-audio", help_heading = "Audio")]
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    #[arg(value_name = "PATH")]
+    pub audio: Option<PathBuf>,
+
+    /// Record per-channel stems.
+    ///
+    /// Alongside the audio recording, writes each channel's contribution to the
+    /// mix as a separate WAV file, named by channel (e.g. "out.ch1.wav").
+    #[arg(long = "record-stems", help_heading = "Audio")]
+    #[arg(requires = "audio")]
+    pub stems: bool,
+
+    /// Record video to a file.
+    ///
+    /// Captures each frame, recolored with the active palette, from startup.
+    /// The format is selected by extension: ".png" for an animated PNG, or
+    /// ".y4m" for a raw YUV4MPEG2 stream. Recording works while headless, and
+    /// may also be toggled by pressing F9, writing to a new file named after
+    /// this one (or the ROM) suffixed with a timestamp.
+    #[arg(long = "record", help_heading = "Video")]
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    #[arg(value_name = "PATH")]
+    pub video: Option<PathBuf>,
+
+    /// Record audio alongside video.
+    ///
+    /// Writes the audio captured over the same period as each video recording
+    /// to a WAV file of the same name (e.g. "out.wav"), ready to be muxed by an
+    /// external tool.
+    #[arg(long = "record-mux", help_heading = "Video")]
+    pub mux: bool,
+}
+
 /// Debugging options.
 #[derive(Debug)]
 #[derive(clap::Args)]
@@ -125,6 +175,40 @@ pub struct Debugger {
     #[arg(visible_alias = "gbd")]
     pub debug: bool,
 
+    /// Debugger script.
+    ///
+    /// Runs the Game Boy Debugger (GBD) non-interactively, reading commands
+    /// from the specified file in place of the prompt. Once the script is
+    /// exhausted, the program exits, reporting failure if any assertions did
+    /// not hold.
+    #[cfg(feature = "gbd")]
+    #[arg(long = "debug-script")]
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    #[arg(value_name = "PATH")]
+    pub script: Option<PathBuf>,
+
+    /// Remote debugger address.
+    ///
+    /// Serves the Game Boy Debugger (GBD) to an external debugger which speaks
+    /// the GDB remote serial protocol, waiting for one to connect on the
+    /// specified address (e.g. "127.0.0.1:2159") before starting.
+    #[cfg(feature = "gbd")]
+    #[arg(long = "gdb")]
+    #[arg(value_name = "ADDR")]
+    pub gdb: Option<SocketAddr>,
+
+    /// Debugger history size.
+    ///
+    /// Enables reverse execution in the Game Boy Debugger (GBD), recording a
+    /// snapshot once per frame. Bounds how far execution may be rewound by the
+    /// size of recorded snapshots, in MiB (default: 32). Otherwise, no history
+    /// is recorded.
+    #[cfg(feature = "gbd")]
+    #[arg(long = "debug-history")]
+    #[arg(num_args(0..=1), default_missing_value = "32")]
+    #[arg(value_name = "MIB")]
+    pub history: Option<usize>,
+
     /// Enable VRAM windows.
     ///
     /// Enables debug windows for visually rendering contents of VRAM.
@@ -132,6 +216,40 @@ pub struct Debugger {
     #[arg(long = "debug-vram")]
     pub vram: bool,
 
+    /// Symbol file.
+    ///
+    /// Loads labels from an RGBDS (".sym") or GBDK (".noi") symbol file for
+    /// use while debugging. If unspecified, a symbol file with either
+    /// extension alongside the ROM is loaded automatically.
+    #[cfg(feature = "debug")]
+    #[arg(long = "sym")]
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    #[arg(value_name = "PATH")]
+    pub sym: Option<PathBuf>,
+
+    /// Profile output file.
+    ///
+    /// Profiles CPU time spent executing each instruction, writing the cycles
+    /// spent within each call stack as folded stacks upon exit. These can be
+    /// rendered using flamegraph tooling.
+    #[cfg(feature = "prof")]
+    #[arg(long = "profile")]
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    #[arg(value_name = "PATH")]
+    pub prof: Option<PathBuf>,
+
+    /// Coverage output file.
+    ///
+    /// Records how each byte of the cartridge ROM is accessed, writing a
+    /// code/data log (CDL) upon exit. Each ROM byte has a corresponding flags
+    /// byte, with bit 0 set if it was executed and bit 1 set if it was read as
+    /// data.
+    #[cfg(feature = "prof")]
+    #[arg(long = "coverage")]
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    #[arg(value_name = "PATH")]
+    pub cover: Option<PathBuf>,
+
     /// Introspective tracing.
     #[cfg(feature = "trace")]
     #[command(flatten)]
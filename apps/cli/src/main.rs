@@ -36,10 +36,18 @@ fn main() -> Exit {
             // rugby check
             exe::chk::main(*cli)
         }
+        Command::Dis(cli) => {
+            // rugby dis
+            exe::dis::main(*cli)
+        }
         Command::Run(cli) => {
             // rugby run
             exe::run::main(*cli)
         }
+        Command::Gbs(cli) => {
+            // rugby gbs
+            exe::gbs::main(*cli)
+        }
         Command::Gen(cli) => {
             // rugby gen
             exe::r#gen::main(*cli)
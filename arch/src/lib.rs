@@ -13,6 +13,7 @@ mod clk;
 pub mod dev;
 pub mod mem;
 pub mod reg;
+pub mod state;
 
 use std::cell::RefCell;
 use std::ops::{Deref, DerefMut};
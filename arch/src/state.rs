@@ -0,0 +1,544 @@
+//! State snapshots.
+//!
+//! Blocks implementing [`State`] can be saved into, and later restored from, a
+//! compact binary encoding. Values are encoded little-endian, without padding,
+//! in declaration order.
+//!
+//! # Sections
+//!
+//! To keep snapshots self-describing, related values are grouped into tagged
+//! [sections](Writer::section). Each section records a 4-byte [tag](Tag)
+//! followed by the length of its payload, allowing readers to validate, or
+//! [skip](Reader::skip), sections they do not expect.
+//!
+//! # Loading
+//!
+//! States are always restored in place. This preserves any connections
+//! between blocks (such as [shared](Shared) registers), which are not part of
+//! the encoding.
+
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display};
+
+use crate::Shared;
+use crate::mem::{Memory, Ram};
+
+/// Serializable block state.
+pub trait State {
+    /// Saves the state into the writer.
+    fn save(&self, out: &mut Writer);
+
+    /// Restores the state from the reader.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the encoded state could not be decoded.
+    fn restore(&mut self, inp: &mut Reader) -> Result<()>;
+}
+
+/// Section tag.
+///
+/// Four ASCII characters identifying a section, in the style of a [FourCC].
+///
+/// [fourcc]: https://en.wikipedia.org/wiki/FourCC
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Tag(pub [u8; 4]);
+
+impl Debug for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.0.escape_ascii())
+    }
+}
+
+impl From<&[u8; 4]> for Tag {
+    fn from(value: &[u8; 4]) -> Self {
+        Self(*value)
+    }
+}
+
+/// State encoder.
+#[derive(Debug, Default)]
+pub struct Writer(Vec<u8>);
+
+impl Writer {
+    /// Constructs a new, empty `Writer`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends raw bytes.
+    pub fn put(&mut self, data: &[u8]) {
+        self.0.extend_from_slice(data);
+    }
+
+    /// Appends a value's state.
+    pub fn save(&mut self, value: &(impl State + ?Sized)) -> &mut Self {
+        value.save(self);
+        self
+    }
+
+    /// Appends a length-prefixed sequence of values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sequence has more than [`u32::MAX`] items.
+    pub fn seq<'a, T: State + 'a>(
+        &mut self,
+        items: impl IntoIterator<Item = &'a T, IntoIter: ExactSizeIterator>,
+    ) -> &mut Self {
+        let items = items.into_iter();
+        len(items.len()).save(self);
+        items.for_each(|item| item.save(self));
+        self
+    }
+
+    /// Appends a tagged section.
+    ///
+    /// The section's payload is produced by the provided closure, and is
+    /// prefixed by its tag and length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the section's payload exceeds 4 GiB.
+    pub fn section(&mut self, tag: impl Into<Tag>, body: impl FnOnce(&mut Self)) -> &mut Self {
+        // Write the header
+        self.put(&tag.into().0);
+        let mark = self.0.len();
+        self.put(&[0; 4]);
+        // Write the payload
+        body(self);
+        // Patch the length
+        let size = u32::try_from(self.0.len() - mark - 4).expect("section too large");
+        self.0[mark..mark + 4].copy_from_slice(&size.to_le_bytes());
+        self
+    }
+
+    /// Gets the number of bytes written.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if no bytes have been written.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Consumes the writer, returning the encoded bytes.
+    #[must_use]
+    pub fn finish(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// State decoder.
+#[derive(Debug)]
+pub struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    /// Constructs a new `Reader` over the encoded bytes.
+    #[must_use]
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self(buf)
+    }
+
+    /// Consumes raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// Errors if fewer than `len` bytes remain.
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(Error::Truncated);
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    /// Consumes a fixed number of raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// Errors if fewer than `N` bytes remain.
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        self.take(N).map(|data| {
+            let mut buf = [0; N];
+            buf.copy_from_slice(data);
+            buf
+        })
+    }
+
+    /// Restores a value's state.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the value could not be decoded.
+    pub fn load(&mut self, value: &mut (impl State + ?Sized)) -> Result<&mut Self> {
+        value.restore(self)?;
+        Ok(self)
+    }
+
+    /// Consumes a length-prefixed sequence of values.
+    ///
+    /// Each item is constructed by `make`, then restored in place.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any item could not be decoded.
+    pub fn seq<T: State>(&mut self, mut make: impl FnMut() -> T) -> Result<Vec<T>> {
+        let size = u32::from_le_bytes(self.array()?);
+        let size = usize::try_from(size).map_err(|_| Error::Truncated)?;
+        // Bound preallocation by the remaining input
+        let mut items = Vec::with_capacity(size.min(self.len()));
+        for _ in 0..size {
+            let mut item = make();
+            item.restore(self)?;
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    /// Consumes a tagged section.
+    ///
+    /// The section's payload is provided to the closure, which must consume it
+    /// entirely.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the next section has a different tag, is truncated, is not
+    /// consumed entirely, or if the closure returns an error.
+    pub fn section(
+        &mut self,
+        tag: impl Into<Tag>,
+        body: impl FnOnce(&mut Reader<'a>) -> Result<()>,
+    ) -> Result<&mut Self> {
+        let expected = tag.into();
+        let (found, mut inner) = self.next()?;
+        if found != expected {
+            return Err(Error::Section { found, expected });
+        }
+        body(&mut inner)?;
+        inner.finish()?;
+        Ok(self)
+    }
+
+    /// Skips over the next section, returning its tag.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the section is truncated.
+    pub fn skip(&mut self) -> Result<Tag> {
+        self.next().map(|(tag, _)| tag)
+    }
+
+    /// Splits off the next section.
+    fn next(&mut self) -> Result<(Tag, Reader<'a>)> {
+        let tag = Tag(self.array()?);
+        let len = u32::from_le_bytes(self.array()?);
+        let len = usize::try_from(len).map_err(|_| Error::Truncated)?;
+        Ok((tag, Reader(self.take(len)?)))
+    }
+
+    /// Gets the number of bytes remaining.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if no bytes remain.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Finishes reading, ensuring all bytes were consumed.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any bytes remain.
+    pub fn finish(self) -> Result<()> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Trailing(self.0.len()))
+        }
+    }
+}
+
+macro_rules! number {
+    ($($ty:ty),* $(,)?) => {$(
+        impl State for $ty {
+            fn save(&self, out: &mut Writer) {
+                out.put(&self.to_le_bytes());
+            }
+
+            fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+                *self = Self::from_le_bytes(inp.array()?);
+                Ok(())
+            }
+        }
+    )*};
+}
+
+number!(u8, u16, u32, u64, u128, i8, i16, i32, f32);
+
+impl State for bool {
+    fn save(&self, out: &mut Writer) {
+        u8::from(*self).save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+        *self = match u8::from_le_bytes(inp.array()?) {
+            0 => false,
+            1 => true,
+            _ => return Err(Error::Invalid("bool")),
+        };
+        Ok(())
+    }
+}
+
+impl<T: State, const N: usize> State for [T; N] {
+    fn save(&self, out: &mut Writer) {
+        for item in self {
+            item.save(out);
+        }
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+        self.iter_mut().try_for_each(|item| item.restore(inp))
+    }
+}
+
+impl<T: State> State for [T] {
+    fn save(&self, out: &mut Writer) {
+        len(self.len()).save(out);
+        for item in self {
+            item.save(out);
+        }
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+        // Sizes are fixed, so must match exactly
+        let size = u32::from_le_bytes(inp.array()?);
+        let size = usize::try_from(size).map_err(|_| Error::Truncated)?;
+        if size != self.len() {
+            return Err(Error::Length {
+                found: size,
+                expected: self.len(),
+            });
+        }
+        self.iter_mut().try_for_each(|item| item.restore(inp))
+    }
+}
+
+impl<T: State + ?Sized> State for Box<T> {
+    fn save(&self, out: &mut Writer) {
+        self.as_ref().save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+        self.as_mut().restore(inp)
+    }
+}
+
+impl<T: State + Default> State for Vec<T> {
+    fn save(&self, out: &mut Writer) {
+        out.seq(self);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+        *self = inp.seq(T::default)?;
+        Ok(())
+    }
+}
+
+impl<T: State + Default> State for VecDeque<T> {
+    fn save(&self, out: &mut Writer) {
+        out.seq(self);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+        *self = inp.seq(T::default)?.into();
+        Ok(())
+    }
+}
+
+impl<T: State + Default> State for Option<T> {
+    fn save(&self, out: &mut Writer) {
+        self.is_some().save(out);
+        if let Some(item) = self {
+            item.save(out);
+        }
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+        let mut some = false;
+        some.restore(inp)?;
+        *self = if some {
+            let mut item = T::default();
+            item.restore(inp)?;
+            Some(item)
+        } else {
+            None
+        };
+        Ok(())
+    }
+}
+
+impl<M: Memory + State> State for Ram<M> {
+    fn save(&self, out: &mut Writer) {
+        self.inner().save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+        self.inner_mut().restore(inp)
+    }
+}
+
+impl<T: State + ?Sized> State for Shared<T> {
+    fn save(&self, out: &mut Writer) {
+        self.borrow().save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> Result<()> {
+        self.borrow_mut().restore(inp)
+    }
+}
+
+/// Encodes a collection's length.
+fn len(size: usize) -> u32 {
+    u32::try_from(size).expect("collection too large")
+}
+
+/// A convenient type alias for [`Result`](std::result::Result).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error caused by loading a [state](State).
+#[derive(Debug)]
+#[derive(thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Unexpected end of input.
+    #[error("unexpected end of input")]
+    Truncated,
+    /// Unconsumed trailing bytes.
+    #[error("unexpected trailing bytes (found: {0})")]
+    Trailing(usize),
+    /// Mismatched section.
+    #[error("unexpected section {found} (expected: {expected})")]
+    Section {
+        /// Found tag.
+        found: Tag,
+        /// Expected tag.
+        expected: Tag,
+    },
+    /// Mismatched length.
+    #[error("unexpected length {found} (expected: {expected})")]
+    Length {
+        /// Found length.
+        found: usize,
+        /// Expected length.
+        expected: usize,
+    },
+    /// Invalid encoded value.
+    #[error("invalid value for `{0}`")]
+    Invalid(&'static str),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_round_trip_works() {
+        let mut out = Writer::new();
+        out.save(&0x12_u8).save(&0x3456_u16).save(&-1.5_f32);
+        let buf = out.finish();
+        assert_eq!(buf.len(), 7);
+
+        let mut inp = Reader::new(&buf);
+        let (mut a, mut b, mut c) = (0_u8, 0_u16, 0_f32);
+        inp.load(&mut a)
+            .unwrap()
+            .load(&mut b)
+            .unwrap()
+            .load(&mut c)
+            .unwrap();
+        inp.finish().unwrap();
+        assert_eq!((a, b, c), (0x12, 0x3456, -1.5));
+    }
+
+    #[test]
+    fn section_round_trip_works() {
+        let mut out = Writer::new();
+        out.section(b"ABCD", |out| {
+            out.save(&[1_u8, 2, 3]);
+        })
+        .section(b"EFGH", |out| {
+            out.save(&vec![4_u16, 5]);
+        });
+        let buf = out.finish();
+
+        let mut inp = Reader::new(&buf);
+        let mut arr = [0_u8; 3];
+        let mut vec = Vec::<u16>::new();
+        inp.section(b"ABCD", |inp| inp.load(&mut arr).map(drop))
+            .unwrap()
+            .section(b"EFGH", |inp| inp.load(&mut vec).map(drop))
+            .unwrap();
+        inp.finish().unwrap();
+        assert_eq!(arr, [1, 2, 3]);
+        assert_eq!(vec, [4, 5]);
+    }
+
+    #[test]
+    fn section_mismatch_fails() {
+        let mut out = Writer::new();
+        out.section(b"ABCD", |out| {
+            out.save(&true);
+        });
+        let buf = out.finish();
+
+        // Wrong tag
+        let mut inp = Reader::new(&buf);
+        assert!(matches!(
+            inp.section(b"WXYZ", |_| Ok(())),
+            Err(Error::Section { .. })
+        ));
+        // Unconsumed payload
+        let mut inp = Reader::new(&buf);
+        assert!(matches!(
+            inp.section(b"ABCD", |_| Ok(())),
+            Err(Error::Trailing(1))
+        ));
+        // Skipped payload
+        let mut inp = Reader::new(&buf);
+        assert_eq!(inp.skip().unwrap(), Tag(*b"ABCD"));
+        assert!(inp.is_empty());
+    }
+
+    #[test]
+    fn slice_length_mismatch_fails() {
+        let mut out = Writer::new();
+        out.save(&vec![0_u8; 4].into_boxed_slice());
+        let buf = out.finish();
+
+        let mut mem = vec![0_u8; 8].into_boxed_slice();
+        assert!(matches!(
+            Reader::new(&buf).load(&mut mem),
+            Err(Error::Length {
+                found: 4,
+                expected: 8
+            })
+        ));
+    }
+}
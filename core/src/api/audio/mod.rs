@@ -0,0 +1,330 @@
+//! Audio API.
+
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use rugby_arch::state::{self, Reader, State, Writer};
+
+pub mod rec;
+pub mod wav;
+
+/// Audio interface.
+pub trait Audio {
+    /// Samples audio output signals.
+    ///
+    /// Returns a rich audio sample with channel metadata.
+    fn sample(&self) -> Chiptune;
+
+    /// Configures the sample rate of band-limited output.
+    ///
+    /// Band-limited output is disabled until configured with a non-zero rate.
+    /// By default, band-limited output is unsupported, and this does nothing.
+    fn set_rate(&mut self, rate: u32) {
+        let _ = rate;
+    }
+
+    /// Pulls band-limited output samples.
+    ///
+    /// Appends to `out` each sample synthesized since the previous pull, at
+    /// the [configured](Self::set_rate) rate. By default, no samples are
+    /// appended.
+    fn pull(&mut self, out: &mut Vec<Sample>) {
+        let _ = out;
+    }
+
+    /// Selects the mixer used for band-limited output.
+    ///
+    /// By default, this does nothing.
+    fn set_mixer(&mut self, mix: Mixer) {
+        let _ = mix;
+    }
+
+    /// Configures the sample rate of audio capture.
+    ///
+    /// Capture records band-limited output independently of playback, such
+    /// that it may use a different rate. It is disabled until configured with
+    /// a non-zero rate. By default, capture is unsupported, and this does
+    /// nothing.
+    fn set_capture(&mut self, rate: u32) {
+        let _ = rate;
+    }
+
+    /// Pulls captured audio.
+    ///
+    /// Appends to `out` the output and each channel's contribution to it
+    /// synthesized since the previous pull, at the
+    /// [configured](Self::set_capture) rate. By default, no samples are
+    /// appended.
+    fn capture(&mut self, out: &mut Capture) {
+        let _ = out;
+    }
+}
+
+/// Captured audio.
+///
+/// Holds the mixed output alongside each channel's contribution to it, as
+/// [split](Chiptune::split) from the mix.
+#[derive(Clone, Debug, Default)]
+pub struct Capture {
+    /// Mixed output.
+    pub mix: Vec<Sample>,
+    /// Channel outputs.
+    ///
+    /// Indexed by channel, such that `chs[0]` holds channel 1.
+    pub chs: [Vec<Sample>; 4],
+}
+
+impl Capture {
+    /// Clears all captured samples.
+    pub fn clear(&mut self) {
+        self.mix.clear();
+        self.chs.iter_mut().for_each(Vec::clear);
+    }
+}
+
+/// Audio mixer mode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mixer {
+    /// Digital mixer.
+    ///
+    /// Channels are [mixed](Chiptune::mix) as ideal digital signals, without
+    /// any of the console's analog output stage.
+    #[default]
+    Digital,
+    /// Analog mixer.
+    ///
+    /// Models the console's analog output stage. Enabled DACs hold an offset
+    /// even while their channel is silent, such that switching them produces
+    /// an audible pop. The master volume never fully mutes. Finally, the output
+    /// is passed through a high-pass [capacitor](Capacitor), decaying any
+    /// offset.
+    ///
+    /// Cartridge audio (VIN) is out of scope, and is never mixed.
+    Analog,
+}
+
+/// Output high-pass capacitor.
+///
+/// Removes the DC offset from an output signal, as it slowly charges towards
+/// the signal's level. See more details [here][hpf].
+///
+/// [hpf]: https://gbdev.io/pandocs/Audio_details.html#mixer
+#[derive(Clone, Debug)]
+pub struct Capacitor {
+    /// Charge factor per cycle.
+    charge: f32,
+    /// Charge factor per sample.
+    factor: f32,
+    /// Charged level.
+    level: Sample,
+}
+
+impl Capacitor {
+    /// DMG-CPU 0 output capacitor.
+    ///
+    /// As no revision-specific measurements exist, all DMG revisions share a
+    /// single charge factor.
+    pub const DMG0: Self = Self::new(0.999_958);
+
+    /// DMG-CPU A output capacitor.
+    pub const DMGA: Self = Self::DMG0;
+
+    /// DMG-CPU B output capacitor.
+    pub const DMGB: Self = Self::DMGA;
+
+    /// DMG-CPU C output capacitor.
+    pub const DMGC: Self = Self::DMGB;
+
+    /// CGB output capacitor.
+    pub const CGB: Self = Self::new(0.998_943);
+
+    /// Constructs a new `Capacitor`.
+    ///
+    /// Its `charge` factor is the fraction of the offset retained each cycle
+    /// of a 4 MiHz clock.
+    #[must_use]
+    pub const fn new(charge: f32) -> Self {
+        Self {
+            charge,
+            factor: charge,
+            level: Sample { lt: 0., rt: 0. },
+        }
+    }
+
+    /// Configures the input and output sample rates.
+    ///
+    /// Adjusts the charge factor such that the capacitor may be sampled at the
+    /// output rate while decaying as though clocked at the input rate.
+    pub fn set_rate(&mut self, ifrq: u32, ofrq: u32) {
+        #[expect(clippy::cast_possible_truncation)]
+        let factor = f64::from(self.charge).powf(f64::from(ifrq) / f64::from(ofrq)) as f32;
+        self.factor = factor;
+    }
+
+    /// Filters a sample through the capacitor.
+    pub fn filter(&mut self, inp: Sample) -> Sample {
+        let out = inp - self.level;
+        self.level = inp - out * self.factor;
+        out
+    }
+}
+
+impl Default for Capacitor {
+    fn default() -> Self {
+        Self::DMGC
+    }
+}
+
+impl State for Capacitor {
+    // NOTE: Only the charged level is saved, as charge factors are fixed by the
+    //       model and output rate.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.level.lt).save(&self.level.rt);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.level.lt)?.load(&mut self.level.rt)?;
+        Ok(())
+    }
+}
+
+/// Rich audio sample.
+///
+/// Contains individual samples generated by each audio channel. In a real
+/// system, these are [mixed](Self::mix) together before being sent to the
+/// speaker.
+#[derive(Clone, Debug)]
+pub struct Chiptune {
+    /// Master volume.
+    ///
+    /// Generally used as a multiplier across all channels.
+    pub vol: Sample,
+    /// Channel 1 output.
+    pub ch1: Sample,
+    /// Channel 2 output.
+    pub ch2: Sample,
+    /// Channel 3 output.
+    pub ch3: Sample,
+    /// Channel 4 output.
+    pub ch4: Sample,
+}
+
+impl Chiptune {
+    /// Mixes all channels together to produce a single audio sample.
+    #[must_use]
+    pub fn mix(self) -> Sample {
+        [self.ch1, self.ch2, self.ch3, self.ch4]
+            .into_iter()
+            // mix channel samples
+            .sum::<Sample>()
+            // normalize output
+            .div(4.)
+            // apply master volume
+            .mul(self.vol)
+    }
+
+    /// Splits each channel's contribution to the [mix](Self::mix).
+    ///
+    /// The resulting samples are normalized and scaled by the master volume,
+    /// such that they sum to the mix.
+    #[must_use]
+    pub fn split(&self) -> [Sample; 4] {
+        [self.ch1, self.ch2, self.ch3, self.ch4].map(|ch| ch / 4. * self.vol)
+    }
+}
+
+/// Audio sample.
+///
+/// Represents a pair of stereo channel outputs. Samples are 32-bit pulse-code
+/// modulated floating point values that are linearly scaled between -1 and 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Sample {
+    /// Left channel.
+    pub lt: f32,
+    /// Right channel.
+    pub rt: f32,
+}
+
+impl From<(f32, f32)> for Sample {
+    fn from((lt, rt): (f32, f32)) -> Self {
+        Self { lt, rt }
+    }
+}
+
+impl Add<Sample> for Sample {
+    type Output = Self;
+
+    fn add(self, rhs: Sample) -> Self::Output {
+        Self::Output {
+            lt: self.lt + rhs.lt,
+            rt: self.rt + rhs.rt,
+        }
+    }
+}
+
+impl Sum for Sample {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), std::ops::Add::add)
+    }
+}
+
+impl Sub<Sample> for Sample {
+    type Output = Self;
+
+    fn sub(self, rhs: Sample) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for Sample {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::Output {
+            lt: -self.lt,
+            rt: -self.rt,
+        }
+    }
+}
+
+impl Mul<Sample> for Sample {
+    type Output = Sample;
+
+    fn mul(self, rhs: Sample) -> Self::Output {
+        Self::Output {
+            lt: self.lt * rhs.lt,
+            rt: self.rt * rhs.rt,
+        }
+    }
+}
+
+impl Mul<f32> for Sample {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::Output {
+            lt: self.lt * rhs,
+            rt: self.rt * rhs,
+        }
+    }
+}
+
+impl Div<f32> for Sample {
+    type Output = Self;
+
+    #[expect(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: f32) -> Self::Output {
+        self * rhs.recip()
+    }
+}
+
+impl Mul<Sample> for f32 {
+    type Output = Sample;
+
+    fn mul(self, rhs: Sample) -> Self::Output {
+        Self::Output {
+            lt: self * rhs.lt,
+            rt: self * rhs.rt,
+        }
+    }
+}
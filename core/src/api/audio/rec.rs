@@ -0,0 +1,139 @@
+//! Audio recording.
+//!
+//! Writes [captured](Capture) audio to [WAV](super::wav) files.
+
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+use log::error;
+
+use super::Capture;
+use super::wav::Encoder;
+
+/// WAV file encoder.
+type Wav = Encoder<BufWriter<File>>;
+
+/// Audio recording.
+///
+/// Writes captured audio to a WAV file, alongside optional per-channel stems.
+///
+/// # Note
+///
+/// Should a recording be dropped before it is [finished](Self::finish), its
+/// files are still completed, such that they remain valid.
+#[derive(Debug)]
+pub struct Recording {
+    /// Output files.
+    ///
+    /// The mixed output is always first, followed by any channel stems.
+    wavs: Vec<(PathBuf, Wav)>,
+}
+
+impl Recording {
+    /// Constructs a new `Recording`.
+    ///
+    /// Stems are written alongside the output, with the channel inserted before
+    /// the extension (e.g. "out.ch1.wav").
+    ///
+    /// # Errors
+    ///
+    /// Errors if any of the files could not be created.
+    pub fn new(path: &Path, rate: u32, stems: bool) -> io::Result<Self> {
+        let mut paths = vec![path.to_path_buf()];
+        if stems {
+            paths.extend((1..=4).map(|ch| path.with_extension(format!("ch{ch}.wav"))));
+        }
+        let wavs = paths
+            .into_iter()
+            .map(|path| {
+                let file = File::create(&path)?;
+                let wav = Encoder::new(BufWriter::new(file), rate)?;
+                Ok((path, wav))
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { wavs })
+    }
+
+    /// Writes captured audio.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the audio could not be written.
+    pub fn write(&mut self, buf: &Capture) -> io::Result<()> {
+        let bufs = std::iter::once(&buf.mix).chain(&buf.chs);
+        for ((_, wav), buf) in self.wavs.iter_mut().zip(bufs) {
+            wav.write(buf)?;
+        }
+
+        Ok(())
+    }
+
+    /// Finishes the recording, closing its files.
+    ///
+    /// Returns the paths of the written files.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any of the files could not be completed.
+    pub fn finish(mut self) -> io::Result<Vec<PathBuf>> {
+        self.close()
+    }
+
+    /// Closes the output files, completing their headers.
+    fn close(&mut self) -> io::Result<Vec<PathBuf>> {
+        std::mem::take(&mut self.wavs)
+            .into_iter()
+            .map(|(path, wav)| wav.finish().map(|_| path))
+            .collect()
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        // Ensure unfinished recordings remain valid
+        if let Err(err) = self.close() {
+            error!("could not finish recording: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::audio::Sample;
+
+    #[test]
+    fn finish_works() {
+        let path = std::env::temp_dir().join("rugby-rec-finish.wav");
+        let mut rec = Recording::new(&path, 48_000, true).unwrap();
+        let mut buf = Capture::default();
+        buf.mix.push(Sample::from((0.5, -0.5)));
+        buf.chs.iter_mut().for_each(|ch| ch.push(Sample::default()));
+        rec.write(&buf).unwrap();
+        let paths = rec.finish().unwrap();
+
+        assert_eq!(paths.len(), 5);
+        for path in paths {
+            let data = std::fs::read(&path).unwrap();
+            std::fs::remove_file(path).unwrap();
+            assert_eq!(&data[40..44], &4_u32.to_le_bytes());
+        }
+    }
+
+    #[test]
+    fn drop_works() {
+        let path = std::env::temp_dir().join("rugby-rec-drop.wav");
+        let mut rec = Recording::new(&path, 48_000, false).unwrap();
+        let mut buf = Capture::default();
+        buf.mix.push(Sample::from((0.5, -0.5)));
+        rec.write(&buf).unwrap();
+        drop(rec);
+
+        let data = std::fs::read(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(&data[4..8], &40_u32.to_le_bytes());
+        assert_eq!(&data[40..44], &4_u32.to_le_bytes());
+    }
+}
@@ -0,0 +1,161 @@
+//! WAV encoding.
+//!
+//! Encodes audio [samples](Sample) as a stereo, 16-bit PCM stream within a
+//! RIFF container. See more details [here][wav].
+//!
+//! [wav]: http://soundfile.sapp.org/doc/WaveFormat/
+
+use std::io::{self, Seek, SeekFrom, Write};
+
+use super::Sample;
+
+/// Channel count.
+const CHANNELS: u16 = 2;
+
+/// Bits per sample.
+const DEPTH: u16 = 16;
+
+/// Header size (in bytes).
+const HEADER: u32 = 44;
+
+/// WAV encoder.
+///
+/// Samples are streamed to the output as they are written. As the header
+/// records the length of the stream, it is only completed once
+/// [finished](Self::finish).
+#[derive(Debug)]
+pub struct Encoder<W: Write + Seek> {
+    /// Output stream.
+    out: W,
+    /// Stream position of the header.
+    base: u64,
+    /// Encoded data size (in bytes).
+    len: u32,
+}
+
+impl<W: Write + Seek> Encoder<W> {
+    /// Constructs a new `Encoder`.
+    ///
+    /// Writes the header for a stream at the provided sample rate.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the header could not be written.
+    pub fn new(mut out: W, rate: u32) -> io::Result<Self> {
+        let base = out.stream_position()?;
+        let align = CHANNELS * DEPTH / 8;
+        // Write RIFF chunk
+        out.write_all(b"RIFF")?;
+        out.write_all(&(HEADER - 8).to_le_bytes())?;
+        out.write_all(b"WAVE")?;
+        // Write format chunk
+        out.write_all(b"fmt ")?;
+        out.write_all(&16_u32.to_le_bytes())?;
+        out.write_all(&1_u16.to_le_bytes())?; // PCM
+        out.write_all(&CHANNELS.to_le_bytes())?;
+        out.write_all(&rate.to_le_bytes())?;
+        out.write_all(&rate.saturating_mul(align.into()).to_le_bytes())?;
+        out.write_all(&align.to_le_bytes())?;
+        out.write_all(&DEPTH.to_le_bytes())?;
+        // Write data chunk
+        out.write_all(b"data")?;
+        out.write_all(&0_u32.to_le_bytes())?;
+
+        Ok(Self { out, base, len: 0 })
+    }
+
+    /// Writes samples to the stream.
+    ///
+    /// Samples are clamped to the unit range before being quantized.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the samples could not be written.
+    pub fn write(&mut self, samples: &[Sample]) -> io::Result<()> {
+        let data = samples
+            .iter()
+            // interleave channels
+            .flat_map(|sample| [sample.lt, sample.rt])
+            // quantize each channel
+            .flat_map(|level| self::quantize(level).to_le_bytes())
+            .collect::<Vec<_>>();
+        self.out.write_all(&data)?;
+        self.len = u32::try_from(data.len())
+            .ok()
+            .and_then(|size| self.len.checked_add(size))
+            .unwrap_or(u32::MAX);
+
+        Ok(())
+    }
+
+    /// Finishes the stream, completing its header.
+    ///
+    /// Returns the output stream.
+    ///
+    /// # Note
+    ///
+    /// The format cannot describe more than 4 GiB of data, beyond which the
+    /// recorded length saturates.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the header could not be written.
+    pub fn finish(mut self) -> io::Result<W> {
+        // Record RIFF chunk size
+        self.out.seek(SeekFrom::Start(self.base + 4))?;
+        let size = self.len.saturating_add(HEADER - 8);
+        self.out.write_all(&size.to_le_bytes())?;
+        // Record data chunk size
+        self.out.seek(SeekFrom::Start(self.base + 40))?;
+        self.out.write_all(&self.len.to_le_bytes())?;
+        // Return to the end of the stream
+        self.out.seek(SeekFrom::End(0))?;
+        self.out.flush()?;
+
+        Ok(self.out)
+    }
+}
+
+/// Quantizes a sample level as a signed 16-bit integer.
+#[expect(clippy::cast_possible_truncation)]
+fn quantize(level: f32) -> i16 {
+    (level.clamp(-1., 1.) * f32::from(i16::MAX)) as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn header_works() {
+        let enc = Encoder::new(Cursor::new(Vec::new()), 48_000).unwrap();
+        let out = enc.finish().unwrap().into_inner();
+
+        assert_eq!(out.len(), HEADER as usize);
+        assert_eq!(&out[0..4], b"RIFF");
+        assert_eq!(&out[4..8], &36_u32.to_le_bytes());
+        assert_eq!(&out[8..16], b"WAVEfmt ");
+        assert_eq!(&out[24..28], &48_000_u32.to_le_bytes());
+        assert_eq!(&out[28..32], &192_000_u32.to_le_bytes());
+        assert_eq!(&out[36..40], b"data");
+        assert_eq!(&out[40..44], &0_u32.to_le_bytes());
+    }
+
+    #[test]
+    fn write_works() {
+        let mut enc = Encoder::new(Cursor::new(Vec::new()), 48_000).unwrap();
+        enc.write(&[Sample::from((0.5, -0.5)), Sample::from((2., -2.))])
+            .unwrap();
+        let out = enc.finish().unwrap().into_inner();
+
+        assert_eq!(&out[4..8], &44_u32.to_le_bytes());
+        assert_eq!(&out[40..44], &8_u32.to_le_bytes());
+        let data = out[44..]
+            .chunks(2)
+            .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]))
+            .collect::<Vec<_>>();
+        assert_eq!(data, [16383, -16383, i16::MAX, -i16::MAX]);
+    }
+}
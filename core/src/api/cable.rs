@@ -15,4 +15,35 @@ pub trait Cable {
     /// Writing to the transmitter forwards data to the core.
     #[must_use]
     fn tx(&mut self) -> &mut dyn Write;
+
+    /// Gets the status of the serial port.
+    ///
+    /// By default, the port is always reported as [idle](Status::Idle).
+    #[must_use]
+    fn status(&self) -> Status {
+        Status::Idle
+    }
+
+    /// Ticks the serial port's external clock.
+    ///
+    /// Shifts a single bit when awaiting a transfer on the external clock, and
+    /// is otherwise ignored. Bits are shifted in from data written to the
+    /// [transmitter](Cable::tx). By default, this does nothing.
+    fn tick(&mut self) {}
+}
+
+/// Serial port status.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// No transfer is in progress.
+    Idle,
+    /// Transferring using the internal clock.
+    ///
+    /// The port drives the cable's clock, acting as the master.
+    Master,
+    /// Awaiting a transfer on the external clock.
+    ///
+    /// The port is clocked by the cable, acting as the slave. Holds the data
+    /// that will be transferred out.
+    Slave(u8),
 }
@@ -0,0 +1,100 @@
+//! Camera API.
+
+use std::fmt::Debug;
+
+/// Sensor width.
+pub const WIDTH: usize = 128;
+
+/// Sensor height.
+pub const HEIGHT: usize = 112;
+
+/// Camera interface.
+pub trait Camera {
+    /// Connects an image source to the camera's sensor.
+    ///
+    /// Cartridges without a camera ignore this.
+    fn set_source(&mut self, src: Box<dyn ImageSource>);
+}
+
+/// Camera image source.
+pub trait ImageSource: Debug {
+    /// Captures a frame of light reaching the sensor.
+    ///
+    /// The provided buffer holds [`WIDTH`] × [`HEIGHT`] pixels in row-major
+    /// order, each of which should be set to its luminance, from `0x00`
+    /// (black) to `0xff` (white).
+    fn capture(&mut self, img: &mut [u8]);
+}
+
+/// Still image source.
+///
+/// Repeatedly captures the same image.
+#[derive(Clone, Debug)]
+pub struct Still(Box<[u8]>);
+
+impl Still {
+    /// Constructs a new `Still` from grayscale image data.
+    ///
+    /// Images are resized to fit the sensor using nearest-neighbour sampling.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the image data is smaller than the specified dimensions.
+    #[must_use]
+    pub fn new(width: usize, height: usize, data: &[u8]) -> Self {
+        assert!(data.len() >= width * height, "image data is too small");
+        let img = (0..HEIGHT)
+            .flat_map(|row| (0..WIDTH).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let y = row * height / HEIGHT;
+                let x = col * width / WIDTH;
+                data[y * width + x]
+            })
+            .collect();
+        Self(img)
+    }
+}
+
+impl ImageSource for Still {
+    fn capture(&mut self, img: &mut [u8]) {
+        img.copy_from_slice(&self.0);
+    }
+}
+
+/// Test pattern source.
+///
+/// Procedurally generates a diagonal gradient which scrolls with each capture,
+/// providing moving input without any external image.
+#[derive(Clone, Debug, Default)]
+pub struct Pattern {
+    /// Scroll offset.
+    idx: u8,
+}
+
+impl ImageSource for Pattern {
+    #[expect(clippy::cast_possible_truncation)]
+    fn capture(&mut self, img: &mut [u8]) {
+        for (pix, idx) in img.iter_mut().zip(0..) {
+            let (row, col) = (idx / WIDTH, idx % WIDTH);
+            *pix = ((row + col) as u8).wrapping_add(self.idx);
+        }
+        self.idx = self.idx.wrapping_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn still_works() {
+        // Upscale a 2x2 checkerboard
+        let mut src = Still::new(2, 2, &[0x00, 0xff, 0xff, 0x00]);
+        let mut img = [0; WIDTH * HEIGHT];
+        src.capture(&mut img);
+        assert_eq!(img[0], 0x00);
+        assert_eq!(img[WIDTH - 1], 0xff);
+        assert_eq!(img[WIDTH * (HEIGHT - 1)], 0xff);
+        assert_eq!(img[WIDTH * HEIGHT - 1], 0x00);
+    }
+}
@@ -0,0 +1,304 @@
+//! Cheat API.
+//!
+//! Two kinds of cheat device are supported:
+//!
+//! - [Game Genie][`Genie`]: Patches bytes read from the cartridge ROM.
+//! - [GameShark][`Shark`]: Writes bytes into memory once per frame.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Cheat interface.
+///
+/// Cheats remain active across resets, as the physical devices sit between
+/// the console and its cartridge.
+pub trait Cheat {
+    /// Gets the active cheat codes.
+    #[must_use]
+    fn cheats(&self) -> Vec<Code>;
+
+    /// Activates a cheat code.
+    fn add_cheat(&mut self, code: Code);
+
+    /// Deactivates all cheat codes.
+    fn clear_cheats(&mut self);
+}
+
+/// Cheat code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Code {
+    /// Game Genie code.
+    Genie(Genie),
+    /// GameShark code.
+    Shark(Shark),
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Code::Genie(code) => code.fmt(f),
+            Code::Shark(code) => code.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Code {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // Determine the device by the code's layout
+        let s = s.trim();
+        if s.contains('-') || matches!(s.len(), 6 | 9) {
+            s.parse().map(Self::Genie)
+        } else {
+            s.parse().map(Self::Shark)
+        }
+    }
+}
+
+/// Game Genie code.
+///
+/// Written as `ABC-DEF` or `ABC-DEF-GHI`, where each letter is a hexadecimal
+/// digit. The optional third group encodes a compare byte, which restricts the
+/// patch to only apply when the original byte matches. (This is most commonly
+/// used to target a single bank of the switchable ROM area.)
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Genie {
+    /// Patched ROM address.
+    pub addr: u16,
+    /// Replacement byte.
+    pub data: u8,
+    /// Compare byte.
+    pub cmp: Option<u8>,
+    /// Unused digit.
+    ///
+    /// Retained such that codes are displayed as entered.
+    key: u8,
+}
+
+impl Genie {
+    /// Constructs a new `Genie` code.
+    #[must_use]
+    pub fn new(addr: u16, data: u8, cmp: Option<u8>) -> Self {
+        Self {
+            addr,
+            data,
+            cmp,
+            key: 0,
+        }
+    }
+
+    /// Patches a byte read from ROM, returning the replacement if the code
+    /// applies.
+    #[must_use]
+    pub fn patch(&self, addr: u16, data: u8) -> Option<u8> {
+        (addr == self.addr && self.cmp.is_none_or(|cmp| cmp == data)).then_some(self.data)
+    }
+}
+
+impl Display for Genie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let &Self {
+            addr,
+            data,
+            cmp,
+            key,
+        } = self;
+        // Scramble the address
+        let addr = (addr & 0x0fff) << 4 | (!addr >> 12 & 0xf);
+        write!(f, "{data:02X}{:X}-{:03X}", addr >> 12, addr & 0x0fff)?;
+        // Scramble the compare byte
+        if let Some(cmp) = cmp {
+            let cmp = (cmp ^ 0xba).rotate_left(2);
+            write!(f, "-{:X}{key:X}{:X}", cmp >> 4, cmp & 0xf)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Genie {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // Join groups of digits
+        let digits = if s.contains('-') {
+            s.split('-')
+                .enumerate()
+                .map(|(idx, part)| match (idx, part.len()) {
+                    (0..=2, 3) => Ok(part),
+                    _ => Err(Error::Format),
+                })
+                .collect::<Result<String>>()?
+        } else {
+            s.to_string()
+        };
+        if !matches!(digits.len(), 6 | 9) {
+            return Err(Error::Length(digits.len()));
+        }
+        let digits = self::digits(&digits)?;
+        // Decode each field
+        let data = digits[0] << 4 | digits[1];
+        let addr = u16::from(digits[5] ^ 0xf) << 12
+            | u16::from(digits[2]) << 8
+            | u16::from(digits[3]) << 4
+            | u16::from(digits[4]);
+        let (cmp, key) = match digits.get(6..9) {
+            Some(&[g, h, i]) => (Some((g << 4 | i).rotate_right(2) ^ 0xba), h),
+            _ => (None, 0),
+        };
+        // Reject addresses outside of ROM
+        if addr > 0x7fff {
+            return Err(Error::Address(addr));
+        }
+        Ok(Self {
+            addr,
+            data,
+            cmp,
+            key,
+        })
+    }
+}
+
+/// GameShark code.
+///
+/// Written as `TTVVLLHH`, where each pair is a hexadecimal byte. When active,
+/// the value `VV` is written to address `$HHLL` once per frame.
+///
+/// # Note
+///
+/// The type byte `TT` (usually `01`) selects the external RAM bank on original
+/// hardware. As it cannot be honoured without interfering with the cartridge's
+/// banking, writes are always made to the currently mapped bank.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Shark {
+    /// Code type.
+    pub kind: u8,
+    /// Written byte.
+    pub data: u8,
+    /// Written address.
+    pub addr: u16,
+}
+
+impl Display for Shark {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let &Self { kind, data, addr } = self;
+        let [lo, hi] = addr.to_le_bytes();
+        write!(f, "{kind:02X}{data:02X}{lo:02X}{hi:02X}")
+    }
+}
+
+impl FromStr for Shark {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != 8 {
+            return Err(Error::Length(s.len()));
+        }
+        let digits = self::digits(s)?;
+        let [kind, data, lo, hi] = [0, 2, 4, 6].map(|idx| digits[idx] << 4 | digits[idx + 1]);
+        Ok(Self {
+            kind,
+            data,
+            addr: u16::from_le_bytes([lo, hi]),
+        })
+    }
+}
+
+/// Decodes a string of hexadecimal digits.
+fn digits(s: &str) -> Result<Vec<u8>> {
+    s.chars()
+        .map(|ch| {
+            ch.to_digit(16)
+                .and_then(|dig| u8::try_from(dig).ok())
+                .ok_or(Error::Digit(ch))
+        })
+        .collect()
+}
+
+/// A convenient type alias for [`Result`](std::result::Result).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error caused by parsing a cheat [code](Code).
+#[derive(Debug)]
+#[derive(thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Address outside of ROM.
+    #[error("address out of range: ${0:04x}")]
+    Address(u16),
+    /// Invalid digit.
+    #[error("invalid digit: {0:?}")]
+    Digit(char),
+    /// Malformed code.
+    #[error("malformed code")]
+    Format,
+    /// Invalid number of digits.
+    #[error("invalid length: {0} digits")]
+    Length(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn genie_parse_works() {
+        // Without compare byte
+        let code: Genie = "00A-17B".parse().unwrap();
+        assert_eq!(code.addr, 0x4a17);
+        assert_eq!(code.data, 0x00);
+        assert_eq!(code.cmp, None);
+        // With compare byte
+        let code: Genie = "3E0-1DF-4C1".parse().unwrap();
+        assert_eq!(code.addr, 0x001d);
+        assert_eq!(code.data, 0x3e);
+        assert_eq!(code.cmp, Some(0x41_u8.rotate_right(2) ^ 0xba));
+    }
+
+    #[test]
+    fn genie_display_works() {
+        for code in ["00A-17B", "3E0-1DF-4C1", "FFF-FFF-FFF"] {
+            let genie: Genie = code.parse().unwrap();
+            assert_eq!(genie.to_string(), code);
+        }
+    }
+
+    #[test]
+    fn genie_invalid_fails() {
+        assert!(matches!("00A-17".parse::<Genie>(), Err(Error::Format)));
+        assert!(matches!("00A-17B-4C".parse::<Genie>(), Err(Error::Format)));
+        assert!(matches!("00A-17X".parse::<Genie>(), Err(Error::Digit('X'))));
+        assert!(matches!("00A-170".parse::<Genie>(), Err(Error::Address(_))));
+    }
+
+    #[test]
+    fn genie_patch_works() {
+        let code = Genie::new(0x4a17, 0x00, Some(0x3c));
+        assert_eq!(code.patch(0x4a17, 0x3c), Some(0x00));
+        assert_eq!(code.patch(0x4a17, 0x3d), None);
+        assert_eq!(code.patch(0x4a18, 0x3c), None);
+    }
+
+    #[test]
+    fn shark_parse_works() {
+        let code: Shark = "010238CD".parse().unwrap();
+        assert_eq!(
+            code,
+            Shark {
+                kind: 0x01,
+                data: 0x02,
+                addr: 0xcd38,
+            }
+        );
+        assert_eq!(code.to_string(), "010238CD");
+    }
+
+    #[test]
+    fn code_parse_works() {
+        assert!(matches!("00A-17B".parse(), Ok(Code::Genie(_))));
+        assert!(matches!("00A17B".parse(), Ok(Code::Genie(_))));
+        assert!(matches!("3E01DF4C1".parse(), Ok(Code::Genie(_))));
+        assert!(matches!("010238CD".parse(), Ok(Code::Shark(_))));
+        assert!(matches!("0102".parse::<Code>(), Err(Error::Length(4))));
+    }
+}
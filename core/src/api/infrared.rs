@@ -0,0 +1,11 @@
+//! Infrared API.
+
+/// Infrared interface.
+pub trait Infrared {
+    /// Checks if the infrared LED is emitting light.
+    #[must_use]
+    fn emit(&self) -> bool;
+
+    /// Signals whether infrared light is reaching the receiver.
+    fn detect(&mut self, light: bool);
+}
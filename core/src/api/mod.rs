@@ -2,5 +2,11 @@
 
 pub mod audio;
 pub mod cable;
+pub mod camera;
+pub mod cheat;
+pub mod infrared;
 pub mod input;
+pub mod motion;
+pub mod rumble;
+pub mod snapshot;
 pub mod video;
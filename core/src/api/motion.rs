@@ -0,0 +1,11 @@
+//! Motion API.
+
+/// Motion interface.
+pub trait Motion {
+    /// Tilts the console, as sensed by an accelerometer.
+    ///
+    /// Acceleration along each axis is specified in units of standard gravity,
+    /// where positive `x` and `y` tilt the console right and towards the
+    /// player, respectively. Cartridges without an accelerometer ignore this.
+    fn tilt(&mut self, x: f32, y: f32);
+}
@@ -0,0 +1,12 @@
+//! Rumble API.
+
+/// Rumble interface.
+pub trait Rumble {
+    /// Gets the rumble motor's strength over the most recent frame.
+    ///
+    /// As games modulate the motor's strength by rapidly toggling it, this is
+    /// measured as the fraction of the frame it was active, from `0.0` to
+    /// `1.0`. Cartridges without a motor always report `0.0`.
+    #[must_use]
+    fn rumble(&self) -> f32;
+}
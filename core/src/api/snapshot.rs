@@ -0,0 +1,61 @@
+//! Snapshot API.
+
+/// Snapshot interface.
+///
+/// Snapshots (also known as save states) capture the complete state of the
+/// emulated machine, such that it can later be restored exactly.
+///
+/// # Note
+///
+/// Snapshots do not include the contents of any read-only memories, such as
+/// the cartridge or boot ROMs. These must be identical when the snapshot is
+/// loaded.
+pub trait Snapshot {
+    /// Saves a snapshot of the current state.
+    #[must_use]
+    fn save_state(&self) -> Vec<u8>;
+
+    /// Loads a snapshot, restoring a previously saved state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot was not saved from a compatible
+    /// machine, or could not be decoded. When an error is returned, the
+    /// current state is left unchanged.
+    fn load_state(&mut self, buf: &[u8]) -> Result<()>;
+}
+
+/// A convenient type alias for [`Result`](std::result::Result).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error caused by loading a [snapshot](Snapshot).
+#[derive(Debug)]
+#[derive(thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Missing snapshot signature.
+    #[error("missing snapshot signature")]
+    Signature,
+    /// Unsupported snapshot version.
+    #[error("unsupported snapshot version: {found} (expected: {expected})")]
+    Version {
+        /// Found version.
+        found: u16,
+        /// Expected version.
+        expected: u16,
+    },
+    /// Mismatched hardware model.
+    #[error("mismatched model: {found} (expected: {expected})")]
+    Model {
+        /// Found model.
+        found: String,
+        /// Expected model.
+        expected: String,
+    },
+    /// Mismatched game cartridge.
+    #[error("mismatched cartridge")]
+    Cartridge,
+    /// Malformed snapshot.
+    #[error(transparent)]
+    Decode(#[from] rugby_arch::state::Error),
+}
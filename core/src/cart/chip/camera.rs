@@ -0,0 +1,536 @@
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::state::{self, Reader, State, Writer};
+use rugby_arch::{Block, Shared};
+
+use super::{Data, Device, Mbc};
+use crate::api::camera::{HEIGHT, ImageSource, WIDTH};
+
+/// [Game Boy Camera][camera] cartridge type.
+///
+/// Also known as the Pocket Camera, its MAC-GBD controller pairs MBC3-style
+/// banking with a Mitsubishi M64282FP image sensor. Captured images are
+/// processed by the controller, then written to RAM as tile data.
+///
+/// Light reaching the sensor is provided by a pluggable [`ImageSource`]. When
+/// none is connected, the sensor sees only darkness.
+///
+/// [camera]: https://gbdev.io/pandocs/Gameboy_Camera.html
+#[derive(Clone, Debug)]
+pub struct Camera {
+    reg: Shared<File>,
+    pub(super) rom: Shared<Rom>,
+    pub(super) ram: Shared<Ram>,
+    pub(super) cam: Shared<Sensor>,
+}
+
+impl Camera {
+    /// Constructs a new `Camera`.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let reg = Shared::new(File::default());
+        let cam = Shared::new(Sensor::default());
+        Self {
+            rom: Shared::new(Rom::new(reg.clone(), rom)),
+            ram: Shared::new(Ram::new(reg.clone(), cam.clone(), ram)),
+            reg,
+            cam,
+        }
+    }
+
+    /// Connects an image source to the sensor.
+    pub fn set_source(&mut self, src: Box<dyn ImageSource>) {
+        self.cam.borrow_mut().src = Some(src);
+    }
+}
+
+impl Block for Camera {
+    fn ready(&self) -> bool {
+        self.cam.borrow().busy > 0
+    }
+
+    fn cycle(&mut self) {
+        let mut cam = self.cam.borrow_mut();
+        if cam.busy == 0 {
+            return;
+        }
+        cam.busy -= 1;
+        if cam.busy == 0 {
+            // Write captured image to RAM
+            let img = cam.capture();
+            let mut ram = self.ram.borrow_mut();
+            if let Some(mem) = ram.mem.get_mut(Sensor::ADDR..Sensor::ADDR + img.len()) {
+                mem.copy_from_slice(&img);
+            }
+            debug!("finished capture");
+        }
+    }
+
+    fn reset(&mut self) {
+        self.reg.take();
+        self.cam.reset();
+    }
+}
+
+impl State for Camera {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg)
+            .save(&self.ram.borrow().mem)
+            .save(&self.cam);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.ram.borrow_mut().mem)?
+            .load(&mut self.cam)?;
+        Ok(())
+    }
+}
+
+impl Mbc for Camera {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn bank(&self) -> usize {
+        self.rom.borrow().adjust(0x4000) >> 14
+    }
+}
+
+/// Camera registers.
+///
+/// |     Address     | Size | Name | Description                  |
+/// |:---------------:|------|------|------------------------------|
+/// | `$0000..=$1FFF` | 1bit | ENA  | RAM Write Enable.            |
+/// | `$2000..=$3FFF` | 6bit | ROM  | ROM Bank Number.             |
+/// | `$4000..=$5FFF` | 5bit | RAM  | RAM Bank Number/Sensor.      |
+#[derive(Debug, Default)]
+struct File {
+    /// RAM Write Enable.
+    ena: bool,
+    /// ROM Bank Number.
+    rom: u8,
+    /// RAM Bank Number.
+    ///
+    /// Selects the sensor's registers when bit 4 is set.
+    ram: u8,
+}
+
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena).save(&self.rom).save(&self.ram);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena)?
+            .load(&mut self.rom)?
+            .load(&mut self.ram)?;
+        Ok(())
+    }
+}
+
+/// Camera ROM.
+#[derive(Debug)]
+pub(super) struct Rom {
+    reg: Shared<File>,
+    pub(super) mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(reg: Shared<File>, mem: Data) -> Self {
+        Self { reg, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: u16) -> usize {
+        let bank = usize::from(self.reg.borrow().rom);
+        let addr = usize::from(addr);
+        ((bank << 14) | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: u16) -> Result<u8> {
+        let index = match addr {
+            0x0000..=0x3fff => usize::from(addr),
+            0x4000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        trace!("Camera::write(${addr:04x}, {data:#04x})");
+        let mut reg = self.reg.borrow_mut();
+        match addr {
+            // RAM Write Enable
+            0x0000..=0x1fff => {
+                // reg.ena <- data[3:0] == 0xA
+                reg.ena = data & 0x0f == 0x0a;
+                debug!("RAM Write Enable: {}", reg.ena);
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // reg.rom[5:0] <- data[5:0]
+                reg.rom = data & 0x3f;
+                debug!("ROM Bank Number: {:#04x}", reg.rom);
+            }
+            // RAM Bank Number
+            0x4000..=0x5fff => {
+                // reg.ram[4:0] <- data[4:0]
+                reg.ram = data & 0x1f;
+                debug!("RAM Bank Number: {:#04x}", reg.ram);
+            }
+            // Unused
+            0x6000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// Camera RAM.
+///
+/// # Note
+///
+/// RAM may always be read, but only written once enabled. While a capture is
+/// in progress, RAM reads as `$00`.
+#[derive(Debug)]
+pub(super) struct Ram {
+    reg: Shared<File>,
+    cam: Shared<Sensor>,
+    pub(super) mem: Data,
+}
+
+impl Ram {
+    /// Constructs a new `Ram`.
+    fn new(reg: Shared<File>, cam: Shared<Sensor>, mem: Data) -> Self {
+        Self { reg, cam, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: u16) -> usize {
+        let bank = usize::from(self.reg.borrow().ram & 0x0f);
+        let addr = usize::from(addr);
+        ((bank << 13) | addr & 0x1fff) % self.mem.len().max(1)
+    }
+
+    /// Checks if the sensor's registers are selected.
+    fn sensor(&self) -> bool {
+        self.reg.borrow().ram & 0x10 != 0
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: u16) -> Result<u8> {
+        // Read from sensor when selected
+        if self.sensor() {
+            return self.cam.read(addr);
+        }
+        // Block reads during capture
+        if self.cam.borrow().busy > 0 {
+            return Ok(0x00);
+        }
+        // Perform adjusted read
+        let index = self.adjust(addr);
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        // Error when disabled
+        if !self.reg.borrow().ena {
+            return Err(Error::Disabled);
+        }
+        // Write to sensor when selected
+        if self.sensor() {
+            return self.cam.write(addr, data);
+        }
+        // Perform adjusted write
+        let index = self.adjust(addr);
+        *self.mem.get_mut(index).ok_or(Error::Range)? = data;
+        Ok(())
+    }
+}
+
+/// Camera image sensor.
+///
+/// Registers are mirrored every 128 bytes within `$A000..=$BFFF`:
+///
+/// |    Offset     | Description                                          |
+/// |:-------------:|------------------------------------------------------|
+/// |     `$00`     | Bit 0: Start capture (reads 1 while busy).           |
+/// |     `$01`     | Bits 7-5: Edge mode (N, VH). Bits 4-0: Gain.         |
+/// | `$02..=$03`   | Exposure time (big-endian).                          |
+/// |     `$04`     | Bit 7: Invert. Bits 6-4: Edge ratio. Bits 2-0: Vref. |
+/// |     `$05`     | Bits 7-6: Zero point. Bits 5-0: Offset.              |
+/// | `$06..=$35`   | Dither matrix thresholds (4×4×3).                    |
+///
+/// Only the first register may be read back; all others read as `$00`.
+///
+/// Each capture passes through the following pipeline:
+///
+/// 1. Exposure: The source's luminance is scaled by the gain and exposure.
+/// 2. Edge enhancement: Each pixel is sharpened against its horizontal and/or
+///    vertical neighbours, as selected by the VH bits.
+/// 3. Dithering: Each pixel is quantized to 2 bits by comparing against the
+///    three thresholds at its position in the 4×4 dither matrix.
+///
+/// # Note
+///
+/// The analog voltage registers (invert, Vref, zero point and offset) are
+/// accepted but not modelled.
+#[derive(Debug)]
+pub struct Sensor {
+    /// Image source.
+    src: Option<Box<dyn ImageSource>>,
+    /// Sensor registers.
+    reg: [u8; 0x36],
+    /// Remaining capture cycles.
+    busy: u32,
+}
+
+impl Sensor {
+    /// Address of captured image in RAM.
+    const ADDR: usize = 0x0100;
+
+    /// Offset of the dither matrix registers.
+    const DITHER: usize = 0x06;
+
+    /// Gets the capture duration, in cartridge cycles.
+    ///
+    /// The sensor is clocked at 1 MiHz, whereas the cartridge is cycled at
+    /// 32 kHz.
+    fn duration(&self) -> u32 {
+        let exp = u32::from(self.exposure());
+        let edge = if self.reg[1] & 0x80 == 0 { 512 } else { 0 };
+        (32446 + edge + 16 * exp).div_ceil(32)
+    }
+
+    /// Gets the exposure time.
+    fn exposure(&self) -> u16 {
+        u16::from_be_bytes([self.reg[2], self.reg[3]])
+    }
+
+    /// Gets the gain multiplier.
+    ///
+    /// Each step increases gain by 1.5 dB.
+    fn gain(&self) -> f32 {
+        10f32.powf(1.5 * f32::from(self.reg[1] & 0x1f) / 20.)
+    }
+
+    /// Gets the edge enhancement ratio.
+    fn ratio(&self) -> f32 {
+        [0.5, 0.75, 1., 1.25, 2., 3., 4., 5.][usize::from((self.reg[4] >> 4) & 0x07)]
+    }
+
+    /// Captures and processes an image, encoding it as tile data.
+    #[expect(clippy::cast_possible_truncation)]
+    #[expect(clippy::cast_sign_loss)]
+    fn capture(&mut self) -> Box<[u8]> {
+        // Capture light reaching the sensor
+        let mut img = vec![0; WIDTH * HEIGHT].into_boxed_slice();
+        if let Some(src) = self.src.as_mut() {
+            src.capture(&mut img);
+        }
+        // Apply exposure
+        let scale = self.gain() * f32::from(self.exposure()) / f32::from(0x1000u16);
+        let pix: Box<[f32]> = img.iter().map(|&lum| f32::from(lum) * scale).collect();
+        let get = |x: usize, y: usize| pix[y.min(HEIGHT - 1) * WIDTH + x.min(WIDTH - 1)];
+        // Apply edge enhancement
+        let (horz, vert) = match (self.reg[1] >> 5) & 0x03 {
+            0b00 => (false, false),
+            0b01 => (true, false),
+            0b10 => (false, true),
+            _ => (true, true),
+        };
+        let ratio = self.ratio();
+        let pix: Box<[f32]> = (0..HEIGHT)
+            .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let this = get(x, y);
+                let mut edge = 0.;
+                if horz {
+                    edge += 2. * this - get(x.saturating_sub(1), y) - get(x + 1, y);
+                }
+                if vert {
+                    edge += 2. * this - get(x, y.saturating_sub(1)) - get(x, y + 1);
+                }
+                this + ratio * edge
+            })
+            .collect();
+        // Apply dithering
+        let color = |x: usize, y: usize| -> u8 {
+            let val = pix[y * WIDTH + x].clamp(0., 255.) as u8;
+            let idx = Self::DITHER + 3 * ((y & 0x3) * 4 + (x & 0x3));
+            let thresh = &self.reg[idx..idx + 3];
+            thresh.iter().take_while(|&&lvl| val >= lvl).count() as u8 ^ 0x3
+        };
+        // Encode as tiles
+        let mut out = vec![0; WIDTH * HEIGHT / 4].into_boxed_slice();
+        for (idx, byte) in out.chunks_exact_mut(2).enumerate() {
+            let (tile, row) = (idx / 8, idx % 8);
+            let (x, y) = (8 * (tile % (WIDTH / 8)), 8 * (tile / (WIDTH / 8)) + row);
+            for col in 0..8 {
+                let px = color(x + col, y);
+                byte[0] |= (px & 0x1) << (7 - col);
+                byte[1] |= (px >> 1) << (7 - col);
+            }
+        }
+        out
+    }
+}
+
+impl Default for Sensor {
+    fn default() -> Self {
+        Self {
+            src: Option::default(),
+            reg: [0; 0x36],
+            busy: u32::default(),
+        }
+    }
+}
+
+impl Block for Sensor {
+    fn reset(&mut self) {
+        self.reg = [0; 0x36];
+        self.busy = 0;
+    }
+}
+
+impl Memory for Sensor {
+    fn read(&self, addr: u16) -> Result<u8> {
+        Ok(match addr & 0x7f {
+            0x00 => (self.reg[0] & 0x06) | u8::from(self.busy > 0),
+            _ => 0x00,
+        })
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        let idx = usize::from(addr & 0x7f);
+        match idx {
+            0x00 => {
+                self.reg[0] = data & 0x07;
+                // Start a new capture
+                if data & 0x01 != 0 && self.busy == 0 {
+                    self.busy = self.duration();
+                    debug!(
+                        "started capture: exposure: {:#06x}, cycles: {}",
+                        self.exposure(),
+                        self.busy,
+                    );
+                }
+            }
+            0x01..0x36 => self.reg[idx] = data,
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+impl State for Sensor {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.busy);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?.load(&mut self.busy)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::camera::Still;
+
+    fn setup(lum: u8) -> Camera {
+        let mut mbc = Camera::new(vec![0; 0x10_0000].into(), vec![0; 0x2_0000].into());
+        mbc.set_source(Box::new(Still::new(1, 1, &[lum])));
+        // Enable RAM
+        mbc.rom.write(0x0000, 0x0a).unwrap();
+        // Select sensor
+        mbc.rom.write(0x4000, 0x10).unwrap();
+        // Set unity exposure
+        mbc.ram.write(0x0002, 0x10).unwrap();
+        mbc.ram.write(0x0003, 0x00).unwrap();
+        // Set evenly spaced thresholds
+        for idx in 0..16 {
+            mbc.ram.write(0x0006 + 3 * idx, 0x40).unwrap();
+            mbc.ram.write(0x0007 + 3 * idx, 0x80).unwrap();
+            mbc.ram.write(0x0008 + 3 * idx, 0xc0).unwrap();
+        }
+        mbc
+    }
+
+    /// Decodes a pixel from captured tile data.
+    fn pixel(img: &[u8], x: usize, y: usize) -> u8 {
+        let idx = 16 * ((y / 8) * (WIDTH / 8) + x / 8) + 2 * (y % 8);
+        let bit = 7 - x % 8;
+        (img[idx] >> bit & 1) | (img[idx + 1] >> bit & 1) << 1
+    }
+
+    fn capture(mbc: &mut Camera) -> Vec<u8> {
+        mbc.ram.write(0x0000, 0x01).unwrap();
+        while mbc.ready() {
+            mbc.cycle();
+        }
+        // Select RAM
+        mbc.rom.write(0x4000, 0x00).unwrap();
+        (0x0100..0x0f00)
+            .map(|addr| mbc.ram.read(addr).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn timing_works() {
+        let mut mbc = setup(0x00);
+        mbc.ram.write(0x0000, 0x01).unwrap();
+        assert_eq!(mbc.ram.read(0x0000).unwrap() & 0x01, 0x01);
+        // Exposure of 0x1000 takes 32446 + 512 + 16 * 4096 cycles at 1 MiHz
+        let mut cycles = 0;
+        while mbc.ready() {
+            mbc.cycle();
+            cycles += 1;
+        }
+        assert_eq!(cycles, 3078);
+        assert_eq!(mbc.ram.read(0x0000).unwrap() & 0x01, 0x00);
+    }
+
+    #[test]
+    fn capture_works() {
+        // White light is captured as color 0
+        let mut mbc = setup(0xff);
+        assert!(capture(&mut mbc).iter().all(|&byte| byte == 0x00));
+        // Black light is captured as color 3
+        let mut mbc = setup(0x00);
+        assert!(capture(&mut mbc).iter().all(|&byte| byte == 0xff));
+        // Mid-gray light is captured as color 1 (between thresholds)
+        let mut mbc = setup(0x90);
+        let img = capture(&mut mbc);
+        assert!(img.chunks(2).all(|pair| pair == [0xff, 0x00]));
+    }
+
+    #[test]
+    fn edge_works() {
+        let mut mbc = setup(0x00);
+        mbc.set_source(Box::new(Still::new(2, 1, &[0x60, 0xa0])));
+        let plain = capture(&mut mbc);
+        assert_eq!(pixel(&plain, 63, 0), 2);
+        assert_eq!(pixel(&plain, 64, 0), 1);
+        // Enable horizontal edge enhancement
+        mbc.rom.write(0x4000, 0x10).unwrap();
+        mbc.ram.write(0x0001, 0x20).unwrap();
+        mbc.ram.write(0x0004, 0x20).unwrap();
+        let sharp = capture(&mut mbc);
+        // Contrast is raised only along the edge
+        assert_eq!(pixel(&sharp, 0, 0), 2);
+        assert_eq!(pixel(&sharp, 63, 0), 3);
+        assert_eq!(pixel(&sharp, 64, 0), 0);
+        assert_eq!(pixel(&sharp, 127, 0), 1);
+    }
+}
@@ -0,0 +1,398 @@
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
+use rugby_arch::{Block, Shared};
+
+use super::{Data, Device, Mbc};
+
+/// [HuC1][huc1] cartridge type.
+///
+/// Hudson Soft's controller, which pairs MBC1-style banking with an infrared
+/// transceiver.
+///
+/// [huc1]: https://gbdev.io/pandocs/HuC1.html
+#[derive(Clone, Debug)]
+pub struct HuC1 {
+    reg: File,
+    pub(super) rom: Shared<Rom>,
+    pub(super) ram: Shared<Ram>,
+    pub(super) ir: Shared<Ir>,
+}
+
+impl HuC1 {
+    /// Constructs a new `HuC1`.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let reg = File::default();
+        let ir = Shared::new(Ir::default());
+        Self {
+            rom: Shared::new(Rom::new(reg.clone(), rom)),
+            ram: Shared::new(Ram::new(reg.clone(), ir.clone(), ram)),
+            reg,
+            ir,
+        }
+    }
+}
+
+impl Block for HuC1 {
+    fn reset(&mut self) {
+        self.reg.reset();
+        self.ir.reset();
+    }
+}
+
+impl State for HuC1 {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg)
+            .save(&self.ram.borrow().mem)
+            .save(&self.ir);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.ram.borrow_mut().mem)?
+            .load(&mut self.ir)?;
+        Ok(())
+    }
+}
+
+impl Mbc for HuC1 {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn bank(&self) -> usize {
+        self.rom.borrow().adjust(0x4000) >> 14
+    }
+}
+
+/// HuC1 registers.
+///
+/// |     Address     | Size | Name | Description      |
+/// |:---------------:|------|------|------------------|
+/// | `$0000..=$1FFF` | 1bit | SEL  | RAM/IR Select.   |
+/// | `$2000..=$3FFF` | 6bit | ROM  | ROM Bank Number. |
+/// | `$4000..=$5FFF` | 2bit | RAM  | RAM Bank Number. |
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+struct File {
+    /// RAM/IR Select.
+    sel: Shared<Select>,
+    /// ROM Bank Number.
+    rom: Shared<RomBank>,
+    /// RAM Bank Number.
+    ram: Shared<RamBank>,
+}
+
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.sel.borrow().0)
+            .save(&self.rom.borrow().0)
+            .save(&self.ram.borrow().0);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.sel.borrow_mut().0)?
+            .load(&mut self.rom.borrow_mut().0)?
+            .load(&mut self.ram.borrow_mut().0)?;
+        Ok(())
+    }
+}
+
+impl Block for File {
+    fn reset(&mut self) {
+        self.sel.take();
+        self.rom.take();
+        self.ram.take();
+    }
+}
+
+/// RAM/IR Select.
+///
+/// Writing `$0E` maps the infrared transceiver in place of RAM.
+#[derive(Debug, Default)]
+struct Select(bool);
+
+impl Memory for Select {
+    fn read(&self, _: u16) -> Result<u8> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: u16, data: u8) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Select {
+    type Value = u8;
+
+    fn load(&self) -> Self::Value {
+        u8::from(self.0)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = value & 0x0f == 0x0e;
+        debug!("RAM/IR Select: {}", if self.0 { "IR" } else { "RAM" });
+    }
+}
+
+/// ROM Bank Number.
+#[derive(Debug, Default)]
+struct RomBank(u8);
+
+impl RomBank {
+    const MASK: u8 = 0x3f;
+}
+
+impl Memory for RomBank {
+    fn read(&self, _: u16) -> Result<u8> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: u16, data: u8) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RomBank {
+    type Value = u8;
+
+    fn load(&self) -> Self::Value {
+        self.0 & Self::MASK
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = Self::MASK & value;
+        debug!("ROM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// RAM Bank Number.
+#[derive(Debug, Default)]
+struct RamBank(u8);
+
+impl RamBank {
+    const MASK: u8 = 0x03;
+}
+
+impl Memory for RamBank {
+    fn read(&self, _: u16) -> Result<u8> {
+        Err(Error::Misuse)
+    }
+
+    fn write(&mut self, _: u16, data: u8) -> Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for RamBank {
+    type Value = u8;
+
+    fn load(&self) -> Self::Value {
+        self.0 & Self::MASK
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = Self::MASK & value;
+        debug!("RAM Bank Number: {:#04x}", self.0);
+    }
+}
+
+/// Infrared transceiver.
+///
+/// Used by both the HuC1 and [HuC3](super::HuC3) when mapped in place of RAM.
+///
+/// | Access | Description                                  |
+/// |--------|----------------------------------------------|
+/// | Read   | `$C1` if light is received, otherwise `$C0`. |
+/// | Write  | Bit 0 drives the LED.                        |
+#[derive(Debug, Default)]
+pub struct Ir {
+    /// LED enable.
+    led: bool,
+    /// Received light.
+    rx: bool,
+}
+
+impl Ir {
+    /// Checks if the LED is emitting light.
+    #[must_use]
+    pub fn emit(&self) -> bool {
+        self.led
+    }
+
+    /// Signals whether light is reaching the receiver.
+    pub fn detect(&mut self, light: bool) {
+        self.rx = light;
+    }
+}
+
+impl Block for Ir {
+    fn reset(&mut self) {
+        self.led = false;
+    }
+}
+
+impl Memory for Ir {
+    fn read(&self, _: u16) -> Result<u8> {
+        Ok(0xc0 | u8::from(self.rx))
+    }
+
+    fn write(&mut self, _: u16, data: u8) -> Result<()> {
+        let led = data & 0x01 != 0;
+        if self.led != led {
+            trace!("IR LED: {led}");
+        }
+        self.led = led;
+        Ok(())
+    }
+}
+
+impl State for Ir {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.led).save(&self.rx);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.led)?.load(&mut self.rx)?;
+        Ok(())
+    }
+}
+
+/// HuC1 ROM.
+#[derive(Debug)]
+pub(super) struct Rom {
+    reg: File,
+    pub(super) mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(reg: File, mem: Data) -> Self {
+        Self { reg, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: u16) -> usize {
+        let bank = usize::from(self.reg.rom.load());
+        let addr = usize::from(addr);
+        ((bank << 14) | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: u16) -> Result<u8> {
+        let index = match addr {
+            0x0000..=0x3fff => usize::from(addr),
+            0x4000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        trace!("HuC1::write(${addr:04x}, {data:#04x})");
+        match addr {
+            // RAM/IR Select
+            0x0000..=0x1fff => {
+                // reg.sel <- data[3:0] == 0xE
+                self.reg.sel.store(data);
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // reg.rom[5:0] <- data[5:0]
+                self.reg.rom.store(data);
+            }
+            // RAM Bank Number
+            0x4000..=0x5fff => {
+                // reg.ram[1:0] <- data[1:0]
+                self.reg.ram.store(data);
+            }
+            // Unused
+            0x6000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// HuC1 RAM.
+///
+/// # Note
+///
+/// RAM is always enabled, unless the infrared transceiver is selected in its
+/// place.
+#[derive(Debug)]
+pub(super) struct Ram {
+    reg: File,
+    ir: Shared<Ir>,
+    pub(super) mem: Data,
+}
+
+impl Ram {
+    /// Constructs a new `Ram`.
+    fn new(reg: File, ir: Shared<Ir>, mem: Data) -> Self {
+        Self { reg, ir, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: u16) -> usize {
+        let bank = usize::from(self.reg.ram.load());
+        let addr = usize::from(addr);
+        ((bank << 13) | addr & 0x1fff) % self.mem.len().max(1)
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: u16) -> Result<u8> {
+        // Read from IR when selected
+        if self.reg.sel.load() != 0 {
+            return self.ir.read(addr);
+        }
+        // Perform adjusted read
+        let index = self.adjust(addr);
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        // Write to IR when selected
+        if self.reg.sel.load() != 0 {
+            return self.ir.write(addr, data);
+        }
+        // Perform adjusted write
+        let index = self.adjust(addr);
+        *self.mem.get_mut(index).ok_or(Error::Range)? = data;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infrared_works() {
+        let mut mbc = HuC1::new(vec![0; 0x8000].into(), vec![0; 0x2000].into());
+        // Select IR
+        mbc.rom.write(0x0000, 0x0e).unwrap();
+        assert_eq!(mbc.ram.read(0x0000).unwrap(), 0xc0);
+        mbc.ir.borrow_mut().detect(true);
+        assert_eq!(mbc.ram.read(0x0000).unwrap(), 0xc1);
+        // Drive the LED
+        mbc.ram.write(0x0000, 0x01).unwrap();
+        assert!(mbc.ir.borrow().emit());
+        // Select RAM
+        mbc.rom.write(0x0000, 0x0a).unwrap();
+        mbc.ram.write(0x0000, 0x42).unwrap();
+        assert_eq!(mbc.ram.read(0x0000).unwrap(), 0x42);
+        assert!(mbc.ir.borrow().emit());
+    }
+}
@@ -0,0 +1,594 @@
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::{debug, trace, warn};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::state::{self, Reader, State, Writer};
+use rugby_arch::{Block, Shared};
+
+use super::{Data, Device, Ir, Mbc};
+
+/// [HuC3][huc3] cartridge type.
+///
+/// Hudson Soft's controller, which extends the [HuC1](super::HuC1) with a
+/// real-time clock, driven by a small microcontroller accessed through a
+/// command interface.
+///
+/// [huc3]: https://gbdev.io/pandocs/HuC3.html
+#[derive(Clone, Debug)]
+pub struct HuC3 {
+    reg: Shared<File>,
+    pub(super) rom: Shared<Rom>,
+    pub(super) ram: Shared<Ram>,
+    pub(super) ir: Shared<Ir>,
+    pub(super) rtc: Shared<Rtc>,
+}
+
+impl HuC3 {
+    /// Constructs a new `HuC3`.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let reg = Shared::new(File::default());
+        let ir = Shared::new(Ir::default());
+        let rtc = Shared::new(Rtc::default());
+        Self {
+            rom: Shared::new(Rom::new(reg.clone(), rom)),
+            ram: Shared::new(Ram::new(reg.clone(), ir.clone(), rtc.clone(), ram)),
+            reg,
+            ir,
+            rtc,
+        }
+    }
+}
+
+impl Block for HuC3 {
+    fn ready(&self) -> bool {
+        true
+    }
+
+    fn cycle(&mut self) {
+        self.rtc.cycle();
+    }
+
+    fn reset(&mut self) {
+        self.reg.take();
+        self.ir.reset();
+    }
+}
+
+impl State for HuC3 {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg)
+            .save(&self.ram.borrow().mem)
+            .save(&self.ir)
+            .save(&self.rtc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.ram.borrow_mut().mem)?
+            .load(&mut self.ir)?
+            .load(&mut self.rtc)?;
+        Ok(())
+    }
+}
+
+impl Mbc for HuC3 {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn bank(&self) -> usize {
+        self.rom.borrow().adjust(0x4000) >> 14
+    }
+}
+
+/// HuC3 registers.
+///
+/// |     Address     | Size | Name | Description      |
+/// |:---------------:|------|------|------------------|
+/// | `$0000..=$1FFF` | 4bit | SEL  | Mode Select.     |
+/// | `$2000..=$3FFF` | 7bit | ROM  | ROM Bank Number. |
+/// | `$4000..=$5FFF` | 2bit | RAM  | RAM Bank Number. |
+#[derive(Debug, Default)]
+struct File {
+    /// Mode Select.
+    sel: u8,
+    /// ROM Bank Number.
+    rom: u8,
+    /// RAM Bank Number.
+    ram: u8,
+}
+
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.sel).save(&self.rom).save(&self.ram);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.sel)?
+            .load(&mut self.rom)?
+            .load(&mut self.ram)?;
+        Ok(())
+    }
+}
+
+/// HuC3 ROM.
+#[derive(Debug)]
+pub(super) struct Rom {
+    reg: Shared<File>,
+    pub(super) mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(reg: Shared<File>, mem: Data) -> Self {
+        Self { reg, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: u16) -> usize {
+        let bank = usize::from(self.reg.borrow().rom);
+        let addr = usize::from(addr);
+        ((bank << 14) | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: u16) -> Result<u8> {
+        let index = match addr {
+            0x0000..=0x3fff => usize::from(addr),
+            0x4000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        trace!("HuC3::write(${addr:04x}, {data:#04x})");
+        let mut reg = self.reg.borrow_mut();
+        match addr {
+            // Mode Select
+            0x0000..=0x1fff => {
+                // reg.sel[3:0] <- data[3:0]
+                reg.sel = data & 0x0f;
+                debug!("Mode Select: {:#03x}", reg.sel);
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // reg.rom[6:0] <- data[6:0]
+                reg.rom = data & 0x7f;
+                debug!("ROM Bank Number: {:#04x}", reg.rom);
+            }
+            // RAM Bank Number
+            0x4000..=0x5fff => {
+                // reg.ram[1:0] <- data[1:0]
+                reg.ram = data & 0x03;
+                debug!("RAM Bank Number: {:#04x}", reg.ram);
+            }
+            // Unused
+            0x6000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// HuC3 RAM.
+///
+/// The selected mode determines the device mapped to `$A000..=$BFFF`:
+///
+/// | Mode | Device                     |
+/// |:----:|----------------------------|
+/// | `$0` | RAM (read-only)            |
+/// | `$A` | RAM                        |
+/// | `$B` | Clock command (write-only) |
+/// | `$C` | Clock response (read-only) |
+/// | `$D` | Clock semaphore            |
+/// | `$E` | [Infrared](Ir) transceiver |
+#[derive(Debug)]
+pub(super) struct Ram {
+    reg: Shared<File>,
+    ir: Shared<Ir>,
+    rtc: Shared<Rtc>,
+    pub(super) mem: Data,
+}
+
+impl Ram {
+    /// Constructs a new `Ram`.
+    fn new(reg: Shared<File>, ir: Shared<Ir>, rtc: Shared<Rtc>, mem: Data) -> Self {
+        Self { reg, ir, rtc, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: u16) -> usize {
+        let bank = usize::from(self.reg.borrow().ram);
+        let addr = usize::from(addr);
+        ((bank << 13) | addr & 0x1fff) % self.mem.len().max(1)
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: u16) -> Result<u8> {
+        match self.reg.borrow().sel {
+            // RAM
+            0x0 | 0xa => {
+                let index = self.adjust(addr);
+                self.mem.get(index).ok_or(Error::Range).copied()
+            }
+            // Clock response
+            0xc => Ok(self.rtc.borrow().response()),
+            // Clock semaphore (always ready)
+            0xd => Ok(0x01),
+            // Infrared
+            0xe => self.ir.read(addr),
+            _ => Err(Error::Disabled),
+        }
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        let sel = self.reg.borrow().sel;
+        match sel {
+            // RAM
+            0xa => {
+                let index = self.adjust(addr);
+                *self.mem.get_mut(index).ok_or(Error::Range)? = data;
+                Ok(())
+            }
+            // Clock command
+            0xb => {
+                self.rtc.borrow_mut().command(data);
+                Ok(())
+            }
+            // Clock semaphore
+            0xd => Ok(()),
+            // Infrared
+            0xe => self.ir.write(addr, data),
+            _ => Err(Error::Disabled),
+        }
+    }
+}
+
+/// HuC3 real-time clock.
+///
+/// The clock's microcontroller has 256 nibbles of memory, accessed through the
+/// following commands, each with a 4-bit argument:
+///
+/// | Command | Description                                  |
+/// |:-------:|----------------------------------------------|
+/// |   `$1`  | Read nibble into response, then increment.   |
+/// |   `$3`  | Write argument to nibble, then increment.    |
+/// |   `$4`  | Set address (lower).                         |
+/// |   `$5`  | Set address (upper).                         |
+/// |   `$6`  | Extended command.                            |
+///
+/// Extended commands either copy the current time to `$00..=$06` (`$0`), set
+/// the time from `$10..=$16` (`$1`), or report the clock's status (`$2`).
+/// Times are encoded as 12 bits of minutes past midnight, followed by 16 bits
+/// of days.
+#[derive(Debug)]
+pub struct Rtc {
+    /// Nibble memory.
+    mem: [u8; 0x100],
+    /// Nibble address.
+    addr: u8,
+    /// Last command.
+    cmd: u8,
+    /// Response nibble.
+    out: u8,
+    /// Minutes past midnight.
+    min: u16,
+    /// Day counter.
+    day: u16,
+    /// Sub-minute counter.
+    sub: u32,
+}
+
+impl Default for Rtc {
+    fn default() -> Self {
+        Self {
+            mem: [0; 0x100],
+            addr: u8::default(),
+            cmd: u8::default(),
+            out: u8::default(),
+            min: u16::default(),
+            day: u16::default(),
+            sub: u32::default(),
+        }
+    }
+}
+
+impl Rtc {
+    /// Oscillator frequency.
+    pub const FREQ: u32 = 0x8000;
+
+    /// Minutes per day.
+    const DAY: u16 = 1440;
+
+    /// Size of save file trailers.
+    const TRAILER: usize = 0x110;
+
+    /// Gets the response register.
+    fn response(&self) -> u8 {
+        0x80 | self.cmd << 4 | self.out
+    }
+
+    /// Reads a nibble of memory.
+    fn peek(&self, addr: u8) -> u8 {
+        self.mem[usize::from(addr)]
+    }
+
+    /// Writes a nibble of memory.
+    fn poke(&mut self, addr: u8, data: u8) {
+        self.mem[usize::from(addr)] = data & 0x0f;
+    }
+
+    /// Executes a command.
+    fn command(&mut self, data: u8) {
+        let (cmd, arg) = ((data >> 4) & 0x07, data & 0x0f);
+        self.cmd = cmd;
+        match cmd {
+            // Read
+            0x1 => {
+                self.out = self.peek(self.addr);
+                self.addr = self.addr.wrapping_add(1);
+            }
+            // Write
+            0x3 => {
+                self.poke(self.addr, arg);
+                self.addr = self.addr.wrapping_add(1);
+            }
+            // Set address (lower)
+            0x4 => self.addr = (self.addr & 0xf0) | arg,
+            // Set address (upper)
+            0x5 => self.addr = (self.addr & 0x0f) | arg << 4,
+            // Extended
+            0x6 => match arg {
+                // Latch time
+                0x0 => {
+                    let time = u32::from(self.min) | u32::from(self.day) << 12;
+                    for idx in 0..7 {
+                        #[expect(clippy::cast_possible_truncation)]
+                        self.poke(idx, (time >> (4 * idx)) as u8);
+                    }
+                }
+                // Set time
+                0x1 => {
+                    let time = (0x10..0x17)
+                        .rev()
+                        .fold(0, |time, addr| time << 4 | u32::from(self.peek(addr)));
+                    #[expect(clippy::cast_possible_truncation)]
+                    {
+                        self.min = (time & 0xfff) as u16 % Self::DAY;
+                        self.day = (time >> 12) as u16;
+                    }
+                    self.sub = 0;
+                    debug!(
+                        "RTC: day {}, {:02}:{:02}",
+                        self.day,
+                        self.min / 60,
+                        self.min % 60
+                    );
+                }
+                // Status
+                0x2 => self.out = 0x1,
+                _ => warn!("unknown HuC3 extended command: {arg:#03x}"),
+            },
+            _ => warn!("unknown HuC3 command: {data:#04x}"),
+        }
+    }
+
+    /// Advances the clock by several seconds.
+    fn advance(&mut self, secs: u64) {
+        let period = 60 * u64::from(Self::FREQ);
+        let ticks = u64::from(self.sub) + secs * u64::from(Self::FREQ);
+        let time = u64::from(self.min) + ticks / period;
+        #[expect(clippy::cast_possible_truncation)]
+        {
+            self.sub = (ticks % period) as u32;
+            self.min = (time % u64::from(Self::DAY)) as u16;
+            self.day = self.day.wrapping_add((time / u64::from(Self::DAY)) as u16);
+        }
+    }
+
+    /// Loads the clock from a save file trailer.
+    ///
+    /// # Note
+    ///
+    /// Stores the microcontroller's nibble memory, followed by the minute, day
+    /// and sub-minute counters, then a 64-bit UNIX timestamp. Time elapsed
+    /// since the timestamp is applied to the counters.
+    pub(super) fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        let mut data = Vec::with_capacity(Self::TRAILER);
+        buf.read_to_end(&mut data)?;
+        match data.len() {
+            0 => return Ok(0),
+            Self::TRAILER => (),
+            len => {
+                warn!(
+                    "ignoring unrecognized clock data: {}",
+                    bfmt::Size::from(len)
+                );
+                return Ok(len);
+            }
+        }
+        // Parse the trailer
+        let (mem, etc) = data.split_at(self.mem.len());
+        self.mem.copy_from_slice(mem);
+        self.mem.iter_mut().for_each(|nib| *nib &= 0x0f);
+        self.min = u16::from_le_bytes([etc[0], etc[1]]) % Self::DAY;
+        self.day = u16::from_le_bytes([etc[2], etc[3]]);
+        self.sub = u32::from_le_bytes([etc[4], etc[5], etc[6], etc[7]]) % (60 * Self::FREQ);
+        let time = etc[8..16]
+            .try_into()
+            .map(u64::from_le_bytes)
+            .map_err(io::Error::other)?;
+        // Catch up on elapsed time
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |dur| dur.as_secs());
+        self.advance(now.saturating_sub(time));
+        debug!(
+            "loaded clock: day {}, {:02}:{:02}",
+            self.day,
+            self.min / 60,
+            self.min % 60
+        );
+        Ok(data.len())
+    }
+
+    /// Dumps the clock as a save file trailer.
+    ///
+    /// See [`Rtc::flash`] for details on the format.
+    pub(super) fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        let mut data = Vec::with_capacity(Self::TRAILER);
+        data.extend_from_slice(&self.mem);
+        data.extend_from_slice(&self.min.to_le_bytes());
+        data.extend_from_slice(&self.day.to_le_bytes());
+        data.extend_from_slice(&self.sub.to_le_bytes());
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |dur| dur.as_secs());
+        data.extend_from_slice(&now.to_le_bytes());
+        buf.write_all(&data)?;
+        Ok(data.len())
+    }
+}
+
+impl Block for Rtc {
+    fn ready(&self) -> bool {
+        true
+    }
+
+    fn cycle(&mut self) {
+        self.sub += 1;
+        if self.sub < 60 * Self::FREQ {
+            return;
+        }
+        self.sub = 0;
+        // Advance by a minute
+        self.min += 1;
+        if self.min == Self::DAY {
+            self.min = 0;
+            self.day = self.day.wrapping_add(1);
+        }
+    }
+}
+
+impl State for Rtc {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.mem)
+            .save(&self.addr)
+            .save(&self.cmd)
+            .save(&self.out)
+            .save(&self.min)
+            .save(&self.day)
+            .save(&self.sub);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.mem)?
+            .load(&mut self.addr)?
+            .load(&mut self.cmd)?
+            .load(&mut self.out)?
+            .load(&mut self.min)?
+            .load(&mut self.day)?
+            .load(&mut self.sub)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> HuC3 {
+        HuC3::new(vec![0; 0x8000].into(), vec![0; 0x8000].into())
+    }
+
+    /// Issues a clock command.
+    fn command(mbc: &mut HuC3, data: u8) -> u8 {
+        mbc.rom.write(0x0000, 0x0b).unwrap();
+        mbc.ram.write(0x0000, data).unwrap();
+        mbc.rom.write(0x0000, 0x0c).unwrap();
+        mbc.ram.read(0x0000).unwrap()
+    }
+
+    #[test]
+    fn rtc_works() {
+        let mut mbc = setup();
+        // Set day 2, 23:59 (1439 minutes)
+        let time: u32 = 0x59f | 2 << 12;
+        command(&mut mbc, 0x40);
+        command(&mut mbc, 0x51);
+        for idx in 0..7 {
+            let data = u8::try_from((time >> (4 * idx)) & 0xf).unwrap();
+            command(&mut mbc, 0x30 | data);
+        }
+        command(&mut mbc, 0x61);
+        // Tick one minute
+        (0..60 * Rtc::FREQ).for_each(|_| mbc.cycle());
+        // Latch, then read back the time
+        command(&mut mbc, 0x60);
+        command(&mut mbc, 0x40);
+        command(&mut mbc, 0x50);
+        let time = (0..7).fold(0, |time, idx| {
+            time | u32::from(command(&mut mbc, 0x10) & 0xf) << (4 * idx)
+        });
+        assert_eq!(time, 3 << 12);
+    }
+
+    #[test]
+    fn rtc_trailer_works() {
+        let mut mbc = setup();
+        // Set day 2, 23:59 (1439 minutes)
+        let time: u32 = 0x59f | 2 << 12;
+        command(&mut mbc, 0x40);
+        command(&mut mbc, 0x51);
+        for idx in 0..7 {
+            let data = u8::try_from((time >> (4 * idx)) & 0xf).unwrap();
+            command(&mut mbc, 0x30 | data);
+        }
+        command(&mut mbc, 0x61);
+
+        // Dump the clock
+        let mut buf = Vec::new();
+        assert_eq!(mbc.rtc.borrow().dump(&mut buf).unwrap(), Rtc::TRAILER);
+
+        // Flash it onto a new clock
+        let mut other = Rtc::default();
+        assert_eq!(other.flash(&mut buf.as_slice()).unwrap(), Rtc::TRAILER);
+        assert_eq!(other.mem, mbc.rtc.borrow().mem);
+        assert_eq!((other.min, other.day), (1439, 2));
+    }
+
+    #[test]
+    fn rtc_advance_works() {
+        let mut rtc = Rtc::default();
+
+        // Advance across days
+        rtc.advance(3 * 86_400 + 3_723);
+        assert_eq!((rtc.day, rtc.min, rtc.sub), (3, 62, 3 * Rtc::FREQ));
+    }
+
+    #[test]
+    fn mode_works() {
+        let mut mbc = setup();
+        // Read-only RAM
+        mbc.rom.write(0x0000, 0x0a).unwrap();
+        mbc.ram.write(0x0000, 0x42).unwrap();
+        mbc.rom.write(0x0000, 0x00).unwrap();
+        assert!(mbc.ram.write(0x0000, 0x00).is_err());
+        assert_eq!(mbc.ram.read(0x0000).unwrap(), 0x42);
+        // Infrared
+        mbc.rom.write(0x0000, 0x0e).unwrap();
+        assert_eq!(mbc.ram.read(0x0000).unwrap(), 0xc0);
+        // Semaphore
+        mbc.rom.write(0x0000, 0x0d).unwrap();
+        assert_eq!(mbc.ram.read(0x0000).unwrap(), 0x01);
+    }
+}
@@ -1,12 +1,20 @@
 use log::{debug, trace};
 use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::{Data, Device, Mbc};
+use crate::cart::head::LOGO;
 
 /// [MBC1][mbc1] cartridge type.
 ///
+/// # Multicarts
+///
+/// Multi-game compilations use a variant of the MBC1 wiring (known as MBC1M),
+/// which disconnects bit 4 of the ROM Bank Number. Each game thus occupies its
+/// own 256 KiB "slot", selected by the RAM Bank Number.
+///
 /// [mbc1]: https://gbdev.io/pandocs/MBC1.html
 #[derive(Clone, Debug)]
 pub struct Mbc1 {
@@ -19,13 +27,55 @@ impl Mbc1 {
     /// Constructs a new `Mbc1`.
     #[must_use]
     pub fn new(rom: Data, ram: Data) -> Self {
+        Self::build(rom, ram, false)
+    }
+
+    /// Constructs a new `Mbc1` wired as a multicart.
+    #[must_use]
+    pub fn multicart(rom: Data, ram: Data) -> Self {
+        Self::build(rom, ram, true)
+    }
+
+    /// Constructs a new `Mbc1` from its parts.
+    fn build(rom: Data, ram: Data, multi: bool) -> Self {
         let reg = File::default();
         Self {
-            rom: Shared::new(Rom::new(reg.clone(), rom)),
+            rom: Shared::new(Rom::new(reg.clone(), multi, rom)),
             ram: Shared::new(Ram::new(reg.clone(), ram)),
             reg,
         }
     }
+
+    /// Detects whether a ROM is wired as a multicart.
+    ///
+    /// Multicarts are identified heuristically, by finding the Nintendo logo
+    /// repeated in the headers of several games, which each start at a 256 KiB
+    /// boundary of the ROM.
+    #[must_use]
+    pub fn detect(rom: &[u8]) -> bool {
+        // Multicarts hold at least two games
+        if rom.len() < 0x80000 {
+            return false;
+        }
+        // Count game headers
+        (0..rom.len())
+            .step_by(0x40000)
+            .filter(|base| rom.get(base + 0x104..base + 0x134) == Some(&LOGO[..]))
+            .count()
+            > 1
+    }
+
+    /// Checks if wired as a multicart.
+    #[must_use]
+    pub fn multi(&self) -> bool {
+        self.rom.borrow().multi
+    }
+
+    /// Overrides the multicart wiring.
+    pub fn set_multi(&mut self, multi: bool) {
+        debug!("Multicart: {multi}");
+        self.rom.borrow_mut().multi = multi;
+    }
 }
 
 impl Block for Mbc1 {
@@ -34,6 +84,18 @@ impl Block for Mbc1 {
     }
 }
 
+impl State for Mbc1 {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.ram.borrow().mem);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.ram.borrow_mut().mem)?;
+        Ok(())
+    }
+}
+
 impl Mbc for Mbc1 {
     fn rom(&self) -> Device {
         self.rom.clone().into()
@@ -42,6 +104,10 @@ impl Mbc for Mbc1 {
     fn ram(&self) -> Device {
         self.ram.clone().into()
     }
+
+    fn bank(&self) -> usize {
+        self.rom.borrow().adjust1(0x4000) >> 14
+    }
 }
 
 /// MBC1 registers.
@@ -65,6 +131,23 @@ struct File {
     sel: Shared<Select>,
 }
 
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena.borrow().0)
+            .save(&self.rom.borrow().0)
+            .save(&self.ram.borrow().0)
+            .save(&self.sel.borrow().0);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena.borrow_mut().0)?
+            .load(&mut self.rom.borrow_mut().0)?
+            .load(&mut self.ram.borrow_mut().0)?
+            .load(&mut self.sel.borrow_mut().0)?;
+        Ok(())
+    }
+}
+
 impl Block for File {
     fn reset(&mut self) {
         self.ena.take();
@@ -206,13 +289,21 @@ impl Register for Select {
 #[derive(Debug)]
 pub(super) struct Rom {
     reg: File,
+    multi: bool,
     pub(super) mem: Data,
 }
 
 impl Rom {
     /// Constructs a new `Rom`.
-    fn new(reg: File, mem: Data) -> Self {
-        Self { reg, mem }
+    fn new(reg: File, multi: bool, mem: Data) -> Self {
+        Self { reg, multi, mem }
+    }
+
+    /// Width of the lower ROM bank number bits.
+    ///
+    /// Multicarts leave bit 4 of the ROM Bank Number disconnected.
+    fn width(&self) -> u8 {
+        if self.multi { 4 } else { 5 }
     }
 
     /// Adjusts addresses by internal bank number.
@@ -224,7 +315,7 @@ impl Rom {
             } else {
                 usize::from(self.reg.ram.load())
             };
-            (hi << 5) | lo
+            (hi << self.width()) | lo
         };
         let addr = usize::from(addr);
         ((bank << 14) | addr & 0x3fff) % self.mem.len().max(0x8000)
@@ -236,9 +327,9 @@ impl Rom {
             let lo = match usize::from(self.reg.rom.load()) {
                 0 => 1,
                 x => x,
-            };
+            } & ((1 << self.width()) - 1);
             let hi = usize::from(self.reg.ram.load());
-            (hi << 5) | lo
+            (hi << self.width()) | lo
         };
         let addr = usize::from(addr);
         ((bank << 14) | addr & 0x3fff) % self.mem.len().max(0x8000)
@@ -347,3 +438,96 @@ impl Memory for Ram {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Constructs a 1 MiB ROM, tagging each bank with its number.
+    fn rom() -> Data {
+        let mut rom = vec![0; 0x100000].into_boxed_slice();
+        for (bank, data) in rom.chunks_mut(0x4000).enumerate() {
+            data[0] = u8::try_from(bank).unwrap();
+        }
+        rom
+    }
+
+    #[test]
+    fn banking_works() {
+        let mut mbc = Mbc1::new(rom(), Data::default());
+        // Select bank 0x31
+        mbc.rom.write(0x2000, 0x11).unwrap();
+        mbc.rom.write(0x4000, 0x01).unwrap();
+        assert_eq!(mbc.rom.read(0x4000).unwrap(), 0x31);
+        // Bank zero maps to bank one
+        mbc.rom.write(0x2000, 0x00).unwrap();
+        assert_eq!(mbc.rom.read(0x4000).unwrap(), 0x21);
+        // Advanced mode banks the lower region
+        mbc.rom.write(0x6000, 0x01).unwrap();
+        assert_eq!(mbc.rom.read(0x0000).unwrap(), 0x20);
+    }
+
+    #[test]
+    fn multicart_works() {
+        let mut mbc = Mbc1::multicart(rom(), Data::default());
+        // Select bank 0x13
+        mbc.rom.write(0x2000, 0x03).unwrap();
+        mbc.rom.write(0x4000, 0x01).unwrap();
+        assert_eq!(mbc.rom.read(0x4000).unwrap(), 0x13);
+        // Bit 4 is disconnected, but still prevents remapping
+        mbc.rom.write(0x2000, 0x10).unwrap();
+        assert_eq!(mbc.rom.read(0x4000).unwrap(), 0x10);
+        mbc.rom.write(0x2000, 0x00).unwrap();
+        assert_eq!(mbc.rom.read(0x4000).unwrap(), 0x11);
+        // Advanced mode banks the lower region
+        mbc.rom.write(0x4000, 0x03).unwrap();
+        mbc.rom.write(0x6000, 0x01).unwrap();
+        assert_eq!(mbc.rom.read(0x0000).unwrap(), 0x30);
+    }
+
+    #[test]
+    fn detect_works() {
+        let mut rom = rom();
+        // Add a header to the first game
+        rom[0x104..0x134].copy_from_slice(&LOGO);
+        assert!(!Mbc1::detect(&rom));
+        // Add a header to another game
+        rom[0x80104..0x80134].copy_from_slice(&LOGO);
+        assert!(Mbc1::detect(&rom));
+        // Small ROMs cannot be multicarts
+        assert!(!Mbc1::detect(&rom[..0x40000]));
+    }
+
+    #[test]
+    fn multicart_board_works() {
+        use crate::cart::chip::Chip;
+        use crate::cart::head::Header;
+
+        // Lay out four games, as in an 8 Mbit compilation
+        let mut rom = rom();
+        for game in rom.chunks_mut(0x40000) {
+            game[0x104..0x134].copy_from_slice(&LOGO);
+        }
+        // Declare an MBC1 board with 1 MiB of ROM
+        rom[0x147] = 0x01;
+        rom[0x148] = 0x05;
+        let head = Header::new(&rom).unwrap();
+        let Chip::Mbc1(mut mbc) = Chip::new(&head, &rom).unwrap() else {
+            panic!("expected an MBC1 chip");
+        };
+        // Detected as a multicart
+        assert!(mbc.multi());
+        // Each game is selected by the upper bank bits
+        mbc.rom.write(0x6000, 0x01).unwrap();
+        for game in 0..4 {
+            mbc.rom.write(0x4000, game).unwrap();
+            // Lower region maps to the game's first bank
+            mbc.rom.write(0x2000, 0x00).unwrap();
+            assert_eq!(mbc.rom.read(0x0000).unwrap(), game << 4);
+            assert_eq!(mbc.rom.read(0x4000).unwrap(), game << 4 | 0x01);
+            // Only 4 bits select the bank within a game
+            mbc.rom.write(0x2000, 0x1f).unwrap();
+            assert_eq!(mbc.rom.read(0x4000).unwrap(), game << 4 | 0x0f);
+        }
+    }
+}
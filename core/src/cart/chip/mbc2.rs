@@ -1,6 +1,7 @@
 use log::{debug, trace};
 use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::{Data, Device, Mbc};
@@ -34,6 +35,18 @@ impl Block for Mbc2 {
     }
 }
 
+impl State for Mbc2 {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.ram.borrow().mem);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.ram.borrow_mut().mem)?;
+        Ok(())
+    }
+}
+
 impl Mbc for Mbc2 {
     fn rom(&self) -> Device {
         self.rom.clone().into()
@@ -42,6 +55,10 @@ impl Mbc for Mbc2 {
     fn ram(&self) -> Device {
         self.ram.clone().into()
     }
+
+    fn bank(&self) -> usize {
+        self.rom.borrow().adjust(0x4000) >> 14
+    }
 }
 
 /// MBC2 registers.
@@ -63,6 +80,18 @@ struct File {
     rom: Shared<RomBank>,
 }
 
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena.borrow().0).save(&self.rom.borrow().0);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena.borrow_mut().0)?
+            .load(&mut self.rom.borrow_mut().0)?;
+        Ok(())
+    }
+}
+
 impl Block for File {
     fn reset(&mut self) {
         self.ena.take();
@@ -1,6 +1,10 @@
-use log::{debug, error, trace};
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use log::{debug, trace, warn};
 use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::{Data, Device, Mbc};
@@ -13,30 +17,73 @@ pub struct Mbc3 {
     reg: File,
     pub(super) rom: Shared<Rom>,
     pub(super) ram: Shared<Ram>,
-    #[expect(unused)]
-    rtc: Rtc,
+    pub(super) rtc: Option<Shared<Rtc>>,
 }
 
 impl Mbc3 {
     /// Constructs a new `Mbc3`.
     #[must_use]
     pub fn new(rom: Data, ram: Data) -> Self {
+        Self::build(rom, ram, None)
+    }
+
+    /// Constructs a new `Mbc3` with a real-time clock.
+    #[must_use]
+    pub fn with_rtc(rom: Data, ram: Data) -> Self {
+        Self::build(rom, ram, Some(Shared::new(Rtc::default())))
+    }
+
+    /// Constructs a new `Mbc3` from its parts.
+    fn build(rom: Data, ram: Data, rtc: Option<Shared<Rtc>>) -> Self {
         let reg = File::default();
         Self {
-            rom: Shared::new(Rom::new(reg.clone(), rom)),
-            ram: Shared::new(Ram::new(reg.clone(), ram)),
+            rom: Shared::new(Rom::new(reg.clone(), rtc.clone(), rom)),
+            ram: Shared::new(Ram::new(reg.clone(), rtc.clone(), ram)),
             reg,
-            rtc: Rtc,
+            rtc,
         }
     }
 }
 
 impl Block for Mbc3 {
+    fn ready(&self) -> bool {
+        self.rtc.as_ref().is_some_and(Block::ready)
+    }
+
+    fn cycle(&mut self) {
+        if let Some(rtc) = self.rtc.as_mut() {
+            rtc.cycle();
+        }
+    }
+
     fn reset(&mut self) {
         self.reg.reset();
     }
 }
 
+impl State for Mbc3 {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg)
+            .save(&self.ram.borrow().mem)
+            .save(&self.rtc.is_some());
+        if let Some(rtc) = &self.rtc {
+            out.save(rtc);
+        }
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut some = bool::default();
+        inp.load(&mut self.reg)?
+            .load(&mut self.ram.borrow_mut().mem)?
+            .load(&mut some)?;
+        match (self.rtc.as_mut(), some) {
+            (Some(rtc), true) => rtc.restore(inp),
+            (None, false) => Ok(()),
+            _ => Err(state::Error::Invalid("Rtc")),
+        }
+    }
+}
+
 impl Mbc for Mbc3 {
     fn rom(&self) -> Device {
         self.rom.clone().into()
@@ -45,6 +92,10 @@ impl Mbc for Mbc3 {
     fn ram(&self) -> Device {
         self.ram.clone().into()
     }
+
+    fn bank(&self) -> usize {
+        self.rom.borrow().adjust(0x4000) >> 14
+    }
 }
 
 /// MBC3 registers.
@@ -68,6 +119,23 @@ struct File {
     lcd: Shared<Latch>,
 }
 
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena.borrow().0)
+            .save(&self.rom.borrow().0)
+            .save(&self.ram.borrow().0)
+            .save(&self.lcd.borrow().0);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena.borrow_mut().0)?
+            .load(&mut self.rom.borrow_mut().0)?
+            .load(&mut self.ram.borrow_mut().0)?
+            .load(&mut self.lcd.borrow_mut().0)?;
+        Ok(())
+    }
+}
+
 impl Block for File {
     fn reset(&mut self) {
         self.ena.take();
@@ -212,13 +280,14 @@ impl Register for Latch {
 #[derive(Debug)]
 pub(super) struct Rom {
     reg: File,
+    rtc: Option<Shared<Rtc>>,
     pub(super) mem: Data,
 }
 
 impl Rom {
     /// Constructs a new `Rom`.
-    fn new(reg: File, mem: Data) -> Self {
-        Self { reg, mem }
+    fn new(reg: File, rtc: Option<Shared<Rtc>>, mem: Data) -> Self {
+        Self { reg, rtc, mem }
     }
 
     /// Adjusts addresses by internal bank number.
@@ -260,11 +329,18 @@ impl Memory for Rom {
                 // reg.rom[1:0] <- data[1:0]
                 self.reg.ram.store(data);
             }
-            // Banking Mode Select
+            // Latch Clock Data
             0x6000..=0x7fff => {
-                // reg.sel <- data[3:0] == 0xA
-                error!("unimplemented: Mbc3::write(${addr:04x}, {data:#04x})");
+                // reg.lcd <- data[0]
+                let prev = self.reg.lcd.load();
                 self.reg.lcd.store(data);
+                // Latch on rising edge
+                if prev == 0
+                    && self.reg.lcd.load() == 1
+                    && let Some(rtc) = &self.rtc
+                {
+                    rtc.borrow_mut().latch();
+                }
             }
             _ => return Err(Error::Range),
         }
@@ -273,16 +349,36 @@ impl Memory for Rom {
 }
 
 /// MBC3 RAM.
+///
+/// # Note
+///
+/// When a RAM bank number of `$08..=$0C` is selected, accesses are instead
+/// routed to the corresponding real-time clock register.
 #[derive(Debug)]
 pub(super) struct Ram {
     reg: File,
+    rtc: Option<Shared<Rtc>>,
     pub(super) mem: Data,
 }
 
 impl Ram {
     /// Constructs a new `Ram`.
-    fn new(reg: File, mem: Data) -> Self {
-        Self { reg, mem }
+    fn new(reg: File, rtc: Option<Shared<Rtc>>, mem: Data) -> Self {
+        Self { reg, rtc, mem }
+    }
+
+    /// Gets the selected real-time clock register, if any.
+    fn clock(&self) -> Option<Result<(&Shared<Rtc>, usize)>> {
+        match self.reg.ram.load() {
+            0x00..=0x07 => None,
+            bank @ 0x08..=0x0c => Some(
+                self.rtc
+                    .as_ref()
+                    .map(|rtc| (rtc, usize::from(bank - 0x08)))
+                    .ok_or(Error::Disabled),
+            ),
+            _ => Some(Err(Error::Disabled)),
+        }
     }
 
     /// Adjusts addresses by internal bank number.
@@ -299,9 +395,10 @@ impl Memory for Ram {
         if self.reg.ena.load() == 0 {
             return Err(Error::Disabled);
         }
-        // Error when RTC is selected
-        if self.reg.ram.load() & 0x08 != 0 {
-            return Err(Error::Disabled);
+        // Read from RTC when selected
+        if let Some(clock) = self.clock() {
+            let (rtc, idx) = clock?;
+            return Ok(rtc.borrow().read(idx));
         }
         // Perform adjusted read
         let index = self.adjust(addr);
@@ -313,9 +410,11 @@ impl Memory for Ram {
         if self.reg.ena.load() == 0 {
             return Err(Error::Disabled);
         }
-        // Error when RTC is selected
-        if self.reg.ram.load() & 0x08 != 0 {
-            return Err(Error::Disabled);
+        // Write to RTC when selected
+        if let Some(clock) = self.clock() {
+            let (rtc, idx) = clock?;
+            rtc.borrow_mut().write(idx, data);
+            return Ok(());
         }
         // Perform adjusted write
         let index = self.adjust(addr);
@@ -325,5 +424,370 @@ impl Memory for Ram {
 }
 
 /// MBC3 real-time clock.
-#[derive(Clone, Debug)]
-pub struct Rtc;
+///
+/// |  Bank | Size | Name | Description                       |
+/// |:-----:|------|------|-----------------------------------|
+/// | `$08` | 6bit | S    | Seconds.                          |
+/// | `$09` | 6bit | M    | Minutes.                          |
+/// | `$0A` | 5bit | H    | Hours.                            |
+/// | `$0B` | 8bit | DL   | Day Counter (lower).              |
+/// | `$0C` | 3bit | DH   | Day Counter (upper), Halt, Carry. |
+///
+/// # Note
+///
+/// The clock is driven by a dedicated 32.768 kHz crystal oscillator. Reads
+/// observe the latched copy of each register, whereas writes modify the live
+/// registers directly.
+#[derive(Debug, Default)]
+pub struct Rtc {
+    /// Live registers.
+    reg: [u8; 5],
+    /// Latched registers.
+    lat: [u8; 5],
+    /// Sub-second counter.
+    sub: u16,
+}
+
+impl Rtc {
+    /// Oscillator frequency.
+    pub const FREQ: u16 = 0x8000;
+
+    /// Register masks.
+    const MASK: [u8; 5] = [0x3f, 0x3f, 0x1f, 0xff, 0xc1];
+
+    /// Seconds register.
+    const SEC: usize = 0;
+    /// Minutes register.
+    const MIN: usize = 1;
+    /// Hours register.
+    const HRS: usize = 2;
+    /// Day counter (lower) register.
+    const DL: usize = 3;
+    /// Day counter (upper) register.
+    const DH: usize = 4;
+
+    /// Day counter (upper) bit.
+    const DAY: u8 = 0x01;
+    /// Halt bit.
+    const HALT: u8 = 0x40;
+    /// Day counter carry bit.
+    const CARRY: u8 = 0x80;
+
+    /// Size of save file trailers.
+    const TRAILER: usize = 48;
+
+    /// Reads a latched register.
+    fn read(&self, idx: usize) -> u8 {
+        self.lat[idx] & Self::MASK[idx]
+    }
+
+    /// Writes a live register.
+    fn write(&mut self, idx: usize, data: u8) {
+        self.reg[idx] = data & Self::MASK[idx];
+        // Writing seconds resets the sub-second counter
+        if idx == Self::SEC {
+            self.sub = 0;
+        }
+        debug!("RTC[{idx}] <- {data:#04x}");
+    }
+
+    /// Latches the live registers.
+    fn latch(&mut self) {
+        self.lat = self.reg;
+    }
+
+    /// Gets the live registers.
+    pub(super) fn regs(&mut self) -> &mut [u8] {
+        &mut self.reg
+    }
+
+    /// Advances the clock by a second.
+    fn tick(&mut self) {
+        let [sec, min, hrs, dl, dh] = &mut self.reg;
+        // Seconds
+        *sec = (*sec + 1) & Self::MASK[Self::SEC];
+        if *sec != 60 {
+            return;
+        }
+        *sec = 0;
+        // Minutes
+        *min = (*min + 1) & Self::MASK[Self::MIN];
+        if *min != 60 {
+            return;
+        }
+        *min = 0;
+        // Hours
+        *hrs = (*hrs + 1) & Self::MASK[Self::HRS];
+        if *hrs != 24 {
+            return;
+        }
+        *hrs = 0;
+        // Days
+        let day = u16::from_le_bytes([*dl, *dh & Self::DAY]) + 1;
+        let [lo, hi] = (day & 0x01ff).to_le_bytes();
+        *dl = lo;
+        *dh = (*dh & !Self::DAY) | hi;
+        if day > 0x01ff {
+            *dh |= Self::CARRY;
+        }
+    }
+
+    /// Advances the clock by several seconds.
+    fn advance(&mut self, mut secs: u64) {
+        // Tick individually until registers hold in-range values
+        while secs > 0
+            && (self.reg[Self::SEC] >= 60 || self.reg[Self::MIN] >= 60 || self.reg[Self::HRS] >= 24)
+        {
+            self.tick();
+            secs -= 1;
+        }
+        if secs == 0 {
+            return;
+        }
+        // Compute the remaining duration arithmetically
+        let day = u64::from(u16::from_le_bytes([
+            self.reg[Self::DL],
+            self.reg[Self::DH] & Self::DAY,
+        ]));
+        let time = ((day * 24 + u64::from(self.reg[Self::HRS])) * 60
+            + u64::from(self.reg[Self::MIN]))
+            * 60
+            + u64::from(self.reg[Self::SEC])
+            + secs;
+        let (sec, time) = (time % 60, time / 60);
+        let (min, time) = (time % 60, time / 60);
+        let (hrs, day) = (time % 24, time / 24);
+        #[expect(clippy::cast_possible_truncation)]
+        {
+            self.reg[Self::SEC] = sec as u8;
+            self.reg[Self::MIN] = min as u8;
+            self.reg[Self::HRS] = hrs as u8;
+            let [lo, hi] = ((day & 0x01ff) as u16).to_le_bytes();
+            self.reg[Self::DL] = lo;
+            self.reg[Self::DH] = (self.reg[Self::DH] & !Self::DAY) | hi;
+        }
+        if day > 0x01ff {
+            self.reg[Self::DH] |= Self::CARRY;
+        }
+    }
+
+    /// Loads the clock from a save file trailer.
+    ///
+    /// # Note
+    ///
+    /// Uses the format shared by most emulators: the live, then latched
+    /// registers as 32-bit words, followed by a 32- or 64-bit UNIX timestamp.
+    /// Time elapsed since the timestamp is applied to the live registers.
+    pub(super) fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        let mut data = Vec::with_capacity(Self::TRAILER);
+        buf.read_to_end(&mut data)?;
+        // Parse the trailer
+        let word = |idx: usize| {
+            data[4 * idx..4 * (idx + 1)]
+                .try_into()
+                .map(u32::from_le_bytes)
+        };
+        let time = match data.len() {
+            0 => return Ok(0),
+            44 => word(10).map(u64::from),
+            48 => data[40..48].try_into().map(u64::from_le_bytes),
+            len => {
+                warn!(
+                    "ignoring unrecognized clock data: {}",
+                    bfmt::Size::from(len)
+                );
+                return Ok(len);
+            }
+        }
+        .map_err(io::Error::other)?;
+        for idx in 0..5 {
+            #[expect(clippy::cast_possible_truncation)]
+            let live = word(idx).map_err(io::Error::other)? as u8;
+            #[expect(clippy::cast_possible_truncation)]
+            let held = word(idx + 5).map_err(io::Error::other)? as u8;
+            self.reg[idx] = live & Self::MASK[idx];
+            self.lat[idx] = held & Self::MASK[idx];
+        }
+        // Catch up on elapsed time
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |dur| dur.as_secs());
+        if self.reg[Self::DH] & Self::HALT == 0 {
+            self.advance(now.saturating_sub(time));
+        }
+        debug!("loaded clock: {:02x?}", self.reg);
+        Ok(data.len())
+    }
+
+    /// Dumps the clock as a save file trailer.
+    ///
+    /// See [`Rtc::flash`] for details on the format.
+    pub(super) fn dump(&self, buf: &mut impl io::Write) -> io::Result<usize> {
+        let mut data = Vec::with_capacity(Self::TRAILER);
+        for reg in self.reg.iter().chain(&self.lat) {
+            data.extend_from_slice(&u32::from(*reg).to_le_bytes());
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |dur| dur.as_secs());
+        data.extend_from_slice(&now.to_le_bytes());
+        buf.write_all(&data)?;
+        Ok(data.len())
+    }
+}
+
+impl Block for Rtc {
+    fn ready(&self) -> bool {
+        self.reg[Self::DH] & Self::HALT == 0
+    }
+
+    fn cycle(&mut self) {
+        self.sub += 1;
+        if self.sub >= Self::FREQ {
+            self.sub = 0;
+            self.tick();
+        }
+    }
+}
+
+impl State for Rtc {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.lat).save(&self.sub);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.lat)?
+            .load(&mut self.sub)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Mbc3 {
+        let mut mbc = Mbc3::with_rtc(vec![0; 0x8000].into(), vec![0; 0x2000].into());
+        // Enable RAM + Timer
+        mbc.rom.write(0x0000, 0x0a).unwrap();
+        mbc
+    }
+
+    /// Latches the clock, then reads the selected register.
+    fn peek(mbc: &mut Mbc3, reg: u8) -> u8 {
+        mbc.rom.write(0x6000, 0x00).unwrap();
+        mbc.rom.write(0x6000, 0x01).unwrap();
+        mbc.rom.write(0x4000, reg).unwrap();
+        mbc.ram.read(0x0000).unwrap()
+    }
+
+    #[test]
+    fn rtc_tick_works() {
+        let mut mbc = setup();
+
+        // Set 23:59:58 on day 511
+        for (reg, data) in [
+            (0x08, 58),
+            (0x09, 59),
+            (0x0a, 23),
+            (0x0b, 0xff),
+            (0x0c, 0x01),
+        ] {
+            mbc.rom.write(0x4000, reg).unwrap();
+            mbc.ram.write(0x0000, data).unwrap();
+        }
+        // Tick one second
+        (0..Rtc::FREQ).for_each(|_| mbc.cycle());
+        assert_eq!(peek(&mut mbc, 0x08), 59);
+        assert_eq!(peek(&mut mbc, 0x0c), 0x01);
+        // Tick another second, overflowing the day counter
+        (0..Rtc::FREQ).for_each(|_| mbc.cycle());
+        assert_eq!(peek(&mut mbc, 0x08), 0);
+        assert_eq!(peek(&mut mbc, 0x09), 0);
+        assert_eq!(peek(&mut mbc, 0x0a), 0);
+        assert_eq!(peek(&mut mbc, 0x0b), 0);
+        assert_eq!(peek(&mut mbc, 0x0c), 0x80);
+    }
+
+    #[test]
+    fn rtc_halt_works() {
+        let mut mbc = setup();
+
+        // Halt the clock
+        mbc.rom.write(0x4000, 0x0c).unwrap();
+        mbc.ram.write(0x0000, 0x40).unwrap();
+        assert!(!mbc.ready());
+        // Resume the clock
+        mbc.ram.write(0x0000, 0x00).unwrap();
+        assert!(mbc.ready());
+    }
+
+    #[test]
+    fn rtc_latch_works() {
+        let mut mbc = setup();
+
+        // Latch at zero
+        assert_eq!(peek(&mut mbc, 0x08), 0);
+        // Latched value holds while ticking
+        (0..Rtc::FREQ).for_each(|_| mbc.cycle());
+        assert_eq!(mbc.ram.read(0x0000).unwrap(), 0);
+        // Relatching observes the update
+        assert_eq!(peek(&mut mbc, 0x08), 1);
+    }
+
+    #[test]
+    fn rtc_trailer_works() {
+        let mut mbc = setup();
+        mbc.rom.write(0x4000, 0x09).unwrap();
+        mbc.ram.write(0x0000, 42).unwrap();
+        // Halt to prevent catching up on elapsed time
+        mbc.rom.write(0x4000, 0x0c).unwrap();
+        mbc.ram.write(0x0000, 0x40).unwrap();
+        mbc.rom.write(0x6000, 0x00).unwrap();
+        mbc.rom.write(0x6000, 0x01).unwrap();
+
+        // Dump the clock
+        let mut buf = Vec::new();
+        let rtc = mbc.rtc.clone().unwrap();
+        assert_eq!(rtc.borrow().dump(&mut buf).unwrap(), Rtc::TRAILER);
+        assert_eq!(buf[4..8], 42u32.to_le_bytes());
+        assert_eq!(buf[24..28], 42u32.to_le_bytes());
+
+        // Flash it onto a new clock
+        let mut other = Rtc::default();
+        assert_eq!(other.flash(&mut buf.as_slice()).unwrap(), Rtc::TRAILER);
+        assert_eq!(other.reg, rtc.borrow().reg);
+        assert_eq!(other.lat, rtc.borrow().lat);
+    }
+
+    #[test]
+    fn rtc_short_save_rejected() {
+        let mut chip = super::super::Chip::Mbc3(setup());
+
+        // Dump a save with its trailer
+        let mut buf = Vec::new();
+        chip.dump(&mut buf).unwrap();
+        assert_eq!(buf.len(), 0x2000 + Rtc::TRAILER);
+
+        // Truncated RAM must not shift the trailer
+        let mut short = &buf[..0x1000];
+        assert!(chip.flash(&mut short).is_err());
+        // Empty saves are tolerated
+        assert_eq!(chip.flash(&mut [].as_slice()).unwrap(), 0);
+        // Complete saves are accepted
+        assert_eq!(chip.flash(&mut buf.as_slice()).unwrap(), buf.len());
+    }
+
+    #[test]
+    fn rtc_advance_works() {
+        let mut rtc = Rtc::default();
+
+        // Advance across days
+        rtc.advance(3 * 86_400 + 3_723);
+        assert_eq!(rtc.reg, [3, 2, 1, 3, 0]);
+        // Advance past the day counter limit
+        rtc.advance(512 * 86_400);
+        assert_eq!(rtc.reg, [3, 2, 1, 3, 0x80]);
+    }
+}
@@ -1,6 +1,7 @@
 use log::{debug, trace};
 use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::{Data, Device, Mbc};
@@ -13,24 +14,73 @@ pub struct Mbc5 {
     reg: File,
     pub(super) rom: Shared<Rom>,
     pub(super) ram: Shared<Ram>,
+    pub(super) motor: Option<Shared<Motor>>,
 }
 
 impl Mbc5 {
     /// Constructs a new `Mbc5`.
     #[must_use]
     pub fn new(rom: Data, ram: Data) -> Self {
+        Self::build(rom, ram, None)
+    }
+
+    /// Constructs a new `Mbc5` with a rumble motor.
+    #[must_use]
+    pub fn with_motor(rom: Data, ram: Data) -> Self {
+        Self::build(rom, ram, Some(Shared::new(Motor::default())))
+    }
+
+    /// Constructs a new `Mbc5` from its parts.
+    fn build(rom: Data, ram: Data, motor: Option<Shared<Motor>>) -> Self {
         let reg = File::default();
         Self {
-            rom: Shared::new(Rom::new(reg.clone(), rom)),
+            rom: Shared::new(Rom::new(reg.clone(), motor.clone(), rom)),
             ram: Shared::new(Ram::new(reg.clone(), ram)),
             reg,
+            motor,
         }
     }
 }
 
 impl Block for Mbc5 {
+    fn ready(&self) -> bool {
+        self.motor.is_some()
+    }
+
+    fn cycle(&mut self) {
+        if let Some(motor) = self.motor.as_mut() {
+            motor.cycle();
+        }
+    }
+
     fn reset(&mut self) {
         self.reg.reset();
+        if let Some(motor) = self.motor.as_mut() {
+            motor.reset();
+        }
+    }
+}
+
+impl State for Mbc5 {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg)
+            .save(&self.ram.borrow().mem)
+            .save(&self.motor.is_some());
+        if let Some(motor) = &self.motor {
+            out.save(motor);
+        }
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut some = bool::default();
+        inp.load(&mut self.reg)?
+            .load(&mut self.ram.borrow_mut().mem)?
+            .load(&mut some)?;
+        match (self.motor.as_mut(), some) {
+            (Some(motor), true) => motor.restore(inp),
+            (None, false) => Ok(()),
+            _ => Err(state::Error::Invalid("Motor")),
+        }
     }
 }
 
@@ -42,6 +92,10 @@ impl Mbc for Mbc5 {
     fn ram(&self) -> Device {
         self.ram.clone().into()
     }
+
+    fn bank(&self) -> usize {
+        self.rom.borrow().adjust(0x4000) >> 14
+    }
 }
 
 /// MBC5 registers.
@@ -52,6 +106,11 @@ impl Mbc for Mbc5 {
 /// | `$2000..=$2FFF` | 8bit | LO   | ROM Bank Number (LO). |
 /// | `$3000..=$3FFF` | 1bit | HI   | ROM Bank Number (HI). |
 /// | `$4000..=$7FFF` | 4bit | RAM  | RAM Bank Number.      |
+///
+/// # Note
+///
+/// On cartridges with a rumble motor, bit 3 of the RAM Bank Number instead
+/// drives the [motor](Motor), leaving only 3 bits for RAM banking.
 #[rustfmt::skip]
 #[derive(Clone, Debug, Default)]
 struct File {
@@ -63,6 +122,23 @@ struct File {
     ram: Shared<RamBank>,
 }
 
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena.borrow().0)
+            .save(&self.rom.0.borrow().0)
+            .save(&self.rom.1.borrow().0)
+            .save(&self.ram.borrow().0);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena.borrow_mut().0)?
+            .load(&mut self.rom.0.borrow_mut().0)?
+            .load(&mut self.rom.1.borrow_mut().0)?
+            .load(&mut self.ram.borrow_mut().0)?;
+        Ok(())
+    }
+}
+
 impl Block for File {
     fn reset(&mut self) {
         self.ena.take();
@@ -193,17 +269,104 @@ impl Register for RamBank {
     }
 }
 
+/// MBC5 rumble motor.
+///
+/// # Note
+///
+/// The motor is sampled at the cartridge's 32 kHz clock, with its duty cycle
+/// measured across each frame.
+#[derive(Debug, Default)]
+pub struct Motor {
+    /// Motor enable.
+    on: bool,
+    /// Active samples.
+    act: u16,
+    /// Total samples.
+    len: u16,
+    /// Duty cycle over the previous frame.
+    duty: f32,
+}
+
+impl Motor {
+    /// Samples per frame.
+    ///
+    /// Each frame lasts 70224 cycles of the 4 MiHz system clock, of which the
+    /// cartridge clock is a 128th.
+    const FRAME: u16 = 548;
+
+    /// Gets the motor's strength over the previous frame.
+    #[must_use]
+    pub fn strength(&self) -> f32 {
+        self.duty
+    }
+}
+
+impl Block for Motor {
+    fn ready(&self) -> bool {
+        true
+    }
+
+    fn cycle(&mut self) {
+        // Sample the motor
+        self.act += u16::from(self.on);
+        self.len += 1;
+        // Measure the duty cycle once per frame
+        if self.len == Self::FRAME {
+            self.duty = f32::from(self.act) / f32::from(self.len);
+            self.act = 0;
+            self.len = 0;
+        }
+    }
+
+    fn reset(&mut self) {
+        std::mem::take(self);
+    }
+}
+
+impl Register for Motor {
+    type Value = bool;
+
+    fn load(&self) -> Self::Value {
+        self.on
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        if self.on != value {
+            trace!("Rumble Motor: {value}");
+        }
+        self.on = value;
+    }
+}
+
+impl State for Motor {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.on)
+            .save(&self.act)
+            .save(&self.len)
+            .save(&self.duty);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.on)?
+            .load(&mut self.act)?
+            .load(&mut self.len)?
+            .load(&mut self.duty)?;
+        Ok(())
+    }
+}
+
 /// MBC5 ROM.
 #[derive(Debug)]
 pub(super) struct Rom {
     reg: File,
+    mtr: Option<Shared<Motor>>,
     pub(super) mem: Data,
 }
 
 impl Rom {
     /// Constructs a new `Rom`.
-    fn new(reg: File, mem: Data) -> Self {
-        Self { reg, mem }
+    fn new(reg: File, mtr: Option<Shared<Motor>>, mem: Data) -> Self {
+        Self { reg, mtr, mem }
     }
 
     /// Adjusts addresses by internal bank number.
@@ -248,8 +411,15 @@ impl Memory for Rom {
             }
             // RAM Bank Number
             0x4000..=0x5fff => {
-                // reg.ram[2:0] <- data[1:0]
-                self.reg.ram.store(data);
+                if let Some(mtr) = self.mtr.as_mut() {
+                    // mtr <- data[3]
+                    mtr.store(data & 0x08 != 0);
+                    // reg.ram[2:0] <- data[2:0]
+                    self.reg.ram.store(data & 0x07);
+                } else {
+                    // reg.ram[3:0] <- data[3:0]
+                    self.reg.ram.store(data);
+                }
             }
             _ => return Err(Error::Range),
         }
@@ -300,3 +470,35 @@ impl Memory for Ram {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn motor_works() {
+        let mut mbc = Mbc5::with_motor(vec![0; 0x8000].into(), vec![0; 0x8000].into());
+        let motor = mbc.motor.clone().unwrap();
+        // Spin the motor for a full frame
+        mbc.rom.write(0x4000, 0x0b).unwrap();
+        assert!(motor.load());
+        (0..Motor::FRAME).for_each(|_| mbc.cycle());
+        assert!((motor.borrow().strength() - 1.).abs() < f32::EPSILON);
+        // Motor bit does not select RAM banks
+        assert_eq!(mbc.reg.ram.load(), 0x03);
+        // Spin the motor for half a frame
+        (0..Motor::FRAME / 2).for_each(|_| mbc.cycle());
+        mbc.rom.write(0x4000, 0x00).unwrap();
+        (0..Motor::FRAME / 2).for_each(|_| mbc.cycle());
+        assert!((motor.borrow().strength() - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn no_motor_works() {
+        let mut mbc = Mbc5::new(vec![0; 0x8000].into(), vec![0; 0x20000].into());
+        assert!(!mbc.ready());
+        // Bit 3 selects RAM banks
+        mbc.rom.write(0x4000, 0x0b).unwrap();
+        assert_eq!(mbc.reg.ram.load(), 0x0b);
+    }
+}
@@ -0,0 +1,712 @@
+use std::io;
+
+use log::{debug, trace, warn};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::state::{self, Reader, State, Writer};
+use rugby_arch::{Block, Shared};
+
+use super::{Data, Device, Mbc};
+
+/// [MBC7][mbc7] cartridge type.
+///
+/// In place of RAM, the MBC7 has a two-axis accelerometer and a 256 byte
+/// serial EEPROM, both accessed through registers.
+///
+/// [mbc7]: https://gbdev.io/pandocs/MBC7.html
+#[derive(Clone, Debug)]
+pub struct Mbc7 {
+    reg: Shared<File>,
+    pub(super) rom: Shared<Rom>,
+    pub(super) ram: Shared<Ram>,
+}
+
+impl Mbc7 {
+    /// Constructs a new `Mbc7`.
+    ///
+    /// # Note
+    ///
+    /// The provided RAM is used as the contents of the EEPROM.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let reg = Shared::new(File::default());
+        Self {
+            rom: Shared::new(Rom::new(reg.clone(), rom)),
+            ram: Shared::new(Ram::new(reg.clone(), ram)),
+            reg,
+        }
+    }
+
+    /// Tilts the accelerometer.
+    ///
+    /// See [`Motion::tilt`](crate::api::motion::Motion::tilt) for details.
+    pub fn tilt(&mut self, x: f32, y: f32) {
+        self.ram.borrow_mut().acc.tilt = (x, y);
+    }
+}
+
+impl Block for Mbc7 {
+    fn reset(&mut self) {
+        self.reg.take();
+        let mut ram = self.ram.borrow_mut();
+        ram.acc.reset();
+        ram.rom.reset();
+    }
+}
+
+impl State for Mbc7 {
+    fn save(&self, out: &mut Writer) {
+        let ram = self.ram.borrow();
+        out.save(&self.reg).save(&ram.acc).save(&ram.rom);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut ram = self.ram.borrow_mut();
+        inp.load(&mut self.reg)?
+            .load(&mut ram.acc)?
+            .load(&mut ram.rom)?;
+        Ok(())
+    }
+}
+
+impl Mbc for Mbc7 {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn bank(&self) -> usize {
+        self.rom.borrow().adjust(0x4000) >> 14
+    }
+}
+
+/// MBC7 registers.
+///
+/// |     Address     | Size | Name | Description                  |
+/// |:---------------:|------|------|------------------------------|
+/// | `$0000..=$1FFF` | 1bit | EN1  | RAM Enable 1 (`$0A`).        |
+/// | `$2000..=$3FFF` | 7bit | ROM  | ROM Bank Number.             |
+/// | `$4000..=$5FFF` | 1bit | EN2  | RAM Enable 2 (`$40`).        |
+#[derive(Debug, Default)]
+struct File {
+    /// RAM Enable 1.
+    en1: bool,
+    /// ROM Bank Number.
+    rom: u8,
+    /// RAM Enable 2.
+    en2: bool,
+}
+
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.en1).save(&self.rom).save(&self.en2);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.en1)?
+            .load(&mut self.rom)?
+            .load(&mut self.en2)?;
+        Ok(())
+    }
+}
+
+/// MBC7 ROM.
+#[derive(Debug)]
+pub(super) struct Rom {
+    reg: Shared<File>,
+    pub(super) mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(reg: Shared<File>, mem: Data) -> Self {
+        Self { reg, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: u16) -> usize {
+        let bank = usize::from(self.reg.borrow().rom);
+        let addr = usize::from(addr);
+        ((bank << 14) | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: u16) -> Result<u8> {
+        let index = match addr {
+            0x0000..=0x3fff => usize::from(addr),
+            0x4000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        trace!("Mbc7::write(${addr:04x}, {data:#04x})");
+        let mut reg = self.reg.borrow_mut();
+        match addr {
+            // RAM Enable 1
+            0x0000..=0x1fff => {
+                // reg.en1 <- data == 0x0A
+                reg.en1 = data == 0x0a;
+                debug!("RAM Enable 1: {}", reg.en1);
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // reg.rom[6:0] <- data[6:0]
+                reg.rom = data & 0x7f;
+                debug!("ROM Bank Number: {:#04x}", reg.rom);
+            }
+            // RAM Enable 2
+            0x4000..=0x5fff => {
+                // reg.en2 <- data == 0x40
+                reg.en2 = data == 0x40;
+                debug!("RAM Enable 2: {}", reg.en2);
+            }
+            // Unused
+            0x6000..=0x7fff => (),
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// MBC7 RAM.
+///
+/// Once enabled, registers are mapped to `$A000..=$AFFF`, selected by bits
+/// `7:4` of the address:
+///
+/// | Register | Description                                   |
+/// |:--------:|-----------------------------------------------|
+/// |   `$0`   | Erase accelerometer latch (write `$55`).      |
+/// |   `$1`   | Latch accelerometer (write `$AA`).            |
+/// |   `$2`   | Accelerometer X (low).                        |
+/// |   `$3`   | Accelerometer X (high).                       |
+/// |   `$4`   | Accelerometer Y (low).                        |
+/// |   `$5`   | Accelerometer Y (high).                       |
+/// |   `$6`   | Unused (`$00`).                               |
+/// |   `$7`   | Unused (`$FF`).                               |
+/// |   `$8`   | [EEPROM](Eeprom) pins.                        |
+#[derive(Debug)]
+pub(super) struct Ram {
+    reg: Shared<File>,
+    acc: Accel,
+    pub(super) rom: Eeprom,
+}
+
+impl Ram {
+    /// Constructs a new `Ram`.
+    fn new(reg: Shared<File>, mem: Data) -> Self {
+        Self {
+            reg,
+            acc: Accel::default(),
+            rom: Eeprom::new(mem),
+        }
+    }
+
+    /// Checks if the registers are enabled.
+    fn enabled(&self) -> bool {
+        let reg = self.reg.borrow();
+        reg.en1 && reg.en2
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: u16) -> Result<u8> {
+        // Error when disabled
+        if !self.enabled() {
+            return Err(Error::Disabled);
+        }
+        // Read selected register
+        let [xlo, xhi] = self.acc.x.to_le_bytes();
+        let [ylo, yhi] = self.acc.y.to_le_bytes();
+        Ok(match addr {
+            0x0000..=0x0fff => match (addr >> 4) & 0xf {
+                0x2 => xlo,
+                0x3 => xhi,
+                0x4 => ylo,
+                0x5 => yhi,
+                0x6 => 0x00,
+                0x8 => self.rom.read(),
+                _ => 0xff,
+            },
+            _ => 0xff,
+        })
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        // Error when disabled
+        if !self.enabled() {
+            return Err(Error::Disabled);
+        }
+        // Write selected register
+        if let 0x0000..=0x0fff = addr {
+            match (addr >> 4) & 0xf {
+                0x0 if data == 0x55 => self.acc.erase(),
+                0x1 if data == 0xaa => self.acc.latch(),
+                0x8 => self.rom.write(data),
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// MBC7 accelerometer.
+///
+/// Each axis is measured as a 16-bit value centered around `$81D0`, changing
+/// by roughly `$70` per unit of standard gravity.
+#[derive(Debug)]
+struct Accel {
+    /// Current tilt.
+    tilt: (f32, f32),
+    /// Latched X-axis.
+    x: u16,
+    /// Latched Y-axis.
+    y: u16,
+    /// Latch enable.
+    ///
+    /// Set after being erased, and cleared after latching.
+    arm: bool,
+}
+
+impl Accel {
+    /// Resting value (`$81D0`).
+    const ZERO: f32 = 33232.;
+    /// Change per unit of gravity (`$70`).
+    const GRAV: f32 = 112.;
+
+    /// Erases the latched values.
+    fn erase(&mut self) {
+        self.x = 0x8000;
+        self.y = 0x8000;
+        self.arm = true;
+    }
+
+    /// Latches the current tilt.
+    #[expect(clippy::cast_possible_truncation)]
+    #[expect(clippy::cast_sign_loss)]
+    fn latch(&mut self) {
+        if !self.arm {
+            return;
+        }
+        let (x, y) = self.tilt;
+        self.x = (Self::ZERO + Self::GRAV * x.clamp(-4., 4.)) as u16;
+        self.y = (Self::ZERO + Self::GRAV * y.clamp(-4., 4.)) as u16;
+        self.arm = false;
+        trace!("latched accelerometer: ({:#06x}, {:#06x})", self.x, self.y);
+    }
+}
+
+impl Default for Accel {
+    fn default() -> Self {
+        Self {
+            tilt: (0., 0.),
+            x: 0x8000,
+            y: 0x8000,
+            arm: bool::default(),
+        }
+    }
+}
+
+impl Block for Accel {
+    fn reset(&mut self) {
+        let tilt = self.tilt;
+        *self = Self {
+            tilt,
+            ..Default::default()
+        };
+    }
+}
+
+impl State for Accel {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.x).save(&self.y).save(&self.arm);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.x)?
+            .load(&mut self.y)?
+            .load(&mut self.arm)?;
+        Ok(())
+    }
+}
+
+/// MBC7 EEPROM.
+///
+/// A 93LC56 serial EEPROM, organized as 128 16-bit words, and accessed by
+/// bit-banging its pins:
+///
+/// | Bit | Name | Description      |
+/// |:---:|------|------------------|
+/// |  7  | CS   | Chip select.     |
+/// |  6  | CLK  | Serial clock.    |
+/// |  1  | DI   | Data in.         |
+/// |  0  | DO   | Data out.        |
+///
+/// While selected, bits are shifted in on each rising edge of the clock. A
+/// start bit is followed by a 2-bit opcode and an 8-bit address, then data if
+/// the command requires it.
+#[derive(Debug)]
+#[expect(clippy::struct_excessive_bools)]
+pub(super) struct Eeprom {
+    /// Chip select.
+    cs: bool,
+    /// Serial clock.
+    clk: bool,
+    /// Data in.
+    di: bool,
+    /// Data out.
+    dout: bool,
+    /// Write enable.
+    wen: bool,
+    /// Serial state.
+    mode: Mode,
+    /// Shift register.
+    buf: u16,
+    /// Shifted bits.
+    len: u8,
+    /// Memory contents.
+    pub(super) mem: Data,
+}
+
+impl Eeprom {
+    /// Constructs a new `Eeprom`.
+    ///
+    /// Sized as either a 93LC56 (256 bytes) or a 93LC66 (512 bytes), falling
+    /// back to the former.
+    fn new(mut mem: Data) -> Self {
+        if !matches!(mem.len(), 0x100 | 0x200) {
+            mem = vec![0xff; 0x100].into_boxed_slice();
+        }
+        Self {
+            cs: false,
+            clk: false,
+            di: false,
+            dout: true,
+            wen: false,
+            mode: Mode::Idle,
+            buf: 0,
+            len: 0,
+            mem,
+        }
+    }
+
+    /// Flashes the memory contents from a save file.
+    ///
+    /// The chip is identified by the size of the save, such that 93LC66 saves
+    /// resize the memory accordingly.
+    pub(super) fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
+        let mut data = Vec::with_capacity(0x200);
+        buf.read_to_end(&mut data)?;
+        match data.len() {
+            0 => return Ok(0),
+            0x100 | 0x200 => self.mem = data.into_boxed_slice(),
+            len => {
+                warn!(
+                    "ignoring unrecognized EEPROM data: {}",
+                    bfmt::Size::from(len)
+                );
+                return Ok(len);
+            }
+        }
+        debug!("loaded {size}", size = bfmt::Size::from(self.mem.len()));
+        trace!("cart RAM:\n{}", hexd::Printer::<u8>::new(0, &self.mem));
+        Ok(self.mem.len())
+    }
+
+    /// Locates a word.
+    ///
+    /// Unused upper address bits are ignored.
+    fn index(&self, addr: u8) -> usize {
+        2 * (usize::from(addr) % (self.mem.len() / 2))
+    }
+
+    /// Reads a word.
+    fn load(&self, addr: u8) -> u16 {
+        let idx = self.index(addr);
+        u16::from_le_bytes([self.mem[idx], self.mem[idx + 1]])
+    }
+
+    /// Writes a word, if enabled.
+    fn store(&mut self, addr: u8, data: u16) {
+        if !self.wen {
+            return;
+        }
+        let idx = self.index(addr);
+        self.mem[idx..idx + 2].copy_from_slice(&data.to_le_bytes());
+    }
+
+    /// Writes every word, if enabled.
+    fn fill(&mut self, data: u16) {
+        if !self.wen {
+            return;
+        }
+        self.mem
+            .chunks_exact_mut(2)
+            .for_each(|word| word.copy_from_slice(&data.to_le_bytes()));
+    }
+
+    /// Reads the pins.
+    fn read(&self) -> u8 {
+        u8::from(self.cs) << 7
+            | u8::from(self.clk) << 6
+            | u8::from(self.di) << 1
+            | u8::from(self.dout)
+    }
+
+    /// Writes the pins.
+    fn write(&mut self, data: u8) {
+        let cs = data & 0x80 != 0;
+        let clk = data & 0x40 != 0;
+        self.di = data & 0x02 != 0;
+        // Deselecting aborts the current command
+        if !cs {
+            self.mode = Mode::Idle;
+        }
+        // Shift on rising edge of the clock
+        if cs && clk && !self.clk {
+            self.shift();
+        }
+        self.cs = cs;
+        self.clk = clk;
+    }
+
+    /// Shifts a bit.
+    fn shift(&mut self) {
+        match self.mode {
+            Mode::Idle => {
+                // Wait for start bit
+                if self.di {
+                    self.mode = Mode::Command;
+                    self.buf = 0;
+                    self.len = 0;
+                }
+            }
+            Mode::Command => {
+                self.buf = self.buf << 1 | u16::from(self.di);
+                self.len += 1;
+                if self.len == 10 {
+                    self.command();
+                }
+            }
+            Mode::Read(addr) => {
+                // Output the next bit
+                self.dout = self.buf & 0x8000 != 0;
+                self.buf <<= 1;
+                self.len += 1;
+                // Continue reading sequential words
+                if self.len == 16 {
+                    let addr = addr.wrapping_add(1);
+                    self.mode = Mode::Read(addr);
+                    self.buf = self.load(addr);
+                    self.len = 0;
+                }
+            }
+            Mode::Write(addr) => {
+                self.buf = self.buf << 1 | u16::from(self.di);
+                self.len += 1;
+                if self.len == 16 {
+                    match addr {
+                        Some(addr) => self.store(addr, self.buf),
+                        None => self.fill(self.buf),
+                    }
+                    debug!("EEPROM write: {addr:02x?} <- {:#06x}", self.buf);
+                    self.mode = Mode::Idle;
+                    self.dout = true;
+                }
+            }
+        }
+    }
+
+    /// Executes a command.
+    #[expect(clippy::cast_possible_truncation)]
+    fn command(&mut self) {
+        let (op, addr) = (self.buf >> 8, self.buf as u8);
+        trace!("EEPROM command: op: {op:#04b}, addr: {addr:#04x}");
+        self.buf = 0;
+        self.len = 0;
+        self.mode = Mode::Idle;
+        match (op, addr >> 6) {
+            // READ
+            (0b10, _) => {
+                self.mode = Mode::Read(addr);
+                self.buf = self.load(addr);
+                // Output a dummy zero bit
+                self.dout = false;
+            }
+            // WRITE
+            (0b01, _) => self.mode = Mode::Write(Some(addr)),
+            // ERASE
+            (0b11, _) => {
+                self.store(addr, 0xffff);
+                self.dout = true;
+            }
+            // EWDS
+            (0b00, 0b00) => self.wen = false,
+            // WRAL
+            (0b00, 0b01) => self.mode = Mode::Write(None),
+            // ERAL
+            (0b00, 0b10) => {
+                self.fill(0xffff);
+                self.dout = true;
+            }
+            // EWEN
+            (0b00, 0b11) => self.wen = true,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Block for Eeprom {
+    fn reset(&mut self) {
+        self.cs = false;
+        self.clk = false;
+        self.di = false;
+        self.dout = true;
+        self.wen = false;
+        self.mode = Mode::Idle;
+    }
+}
+
+impl State for Eeprom {
+    fn save(&self, out: &mut Writer) {
+        let (mode, addr) = match self.mode {
+            Mode::Idle => (0u8, 0),
+            Mode::Command => (1, 0),
+            Mode::Read(addr) => (2, addr),
+            Mode::Write(Some(addr)) => (3, addr),
+            Mode::Write(None) => (4, 0),
+        };
+        out.save(&self.cs)
+            .save(&self.clk)
+            .save(&self.di)
+            .save(&self.dout)
+            .save(&self.wen)
+            .save(&mode)
+            .save(&addr)
+            .save(&self.buf)
+            .save(&self.len)
+            .save(&self.mem);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut mode = u8::default();
+        let mut addr = u8::default();
+        inp.load(&mut self.cs)?
+            .load(&mut self.clk)?
+            .load(&mut self.di)?
+            .load(&mut self.dout)?
+            .load(&mut self.wen)?
+            .load(&mut mode)?
+            .load(&mut addr)?
+            .load(&mut self.buf)?
+            .load(&mut self.len)?
+            .load(&mut self.mem)?;
+        self.mode = match mode {
+            0 => Mode::Idle,
+            1 => Mode::Command,
+            2 => Mode::Read(addr),
+            3 => Mode::Write(Some(addr)),
+            4 => Mode::Write(None),
+            _ => return Err(state::Error::Invalid("Mode")),
+        };
+        Ok(())
+    }
+}
+
+/// EEPROM serial state.
+#[derive(Copy, Clone, Debug)]
+enum Mode {
+    /// Awaiting a start bit.
+    Idle,
+    /// Shifting in a command.
+    Command,
+    /// Shifting out words from an address.
+    Read(u8),
+    /// Shifting in a word for an address, or all addresses.
+    Write(Option<u8>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Mbc7 {
+        let mut mbc = Mbc7::new(vec![0; 0x8000].into(), vec![0xff; 0x100].into());
+        // Enable RAM
+        mbc.rom.write(0x0000, 0x0a).unwrap();
+        mbc.rom.write(0x4000, 0x40).unwrap();
+        mbc
+    }
+
+    /// Shifts bits into the EEPROM, returning the bits shifted out.
+    fn serial(mbc: &mut Mbc7, bits: &[bool]) -> Vec<bool> {
+        bits.iter()
+            .map(|&bit| {
+                let di = u8::from(bit) << 1;
+                mbc.ram.write(0x0080, 0x80 | di).unwrap();
+                mbc.ram.write(0x0080, 0xc0 | di).unwrap();
+                mbc.ram.read(0x0080).unwrap() & 0x01 != 0
+            })
+            .collect()
+    }
+
+    /// Encodes a value as bits.
+    fn bits(data: u16, len: u8) -> Vec<bool> {
+        (0..len).rev().map(|idx| data >> idx & 1 != 0).collect()
+    }
+
+    #[test]
+    fn accel_works() {
+        let mut mbc = setup();
+        mbc.tilt(1., -1.);
+        // Latching requires an erase first
+        mbc.ram.write(0x0010, 0xaa).unwrap();
+        assert_eq!(mbc.ram.read(0x0020).unwrap(), 0x00);
+        assert_eq!(mbc.ram.read(0x0030).unwrap(), 0x80);
+        // Erase, then latch
+        mbc.ram.write(0x0000, 0x55).unwrap();
+        mbc.ram.write(0x0010, 0xaa).unwrap();
+        assert_eq!(mbc.ram.read(0x0020).unwrap(), 0x40);
+        assert_eq!(mbc.ram.read(0x0030).unwrap(), 0x82);
+        assert_eq!(mbc.ram.read(0x0040).unwrap(), 0x60);
+        assert_eq!(mbc.ram.read(0x0050).unwrap(), 0x81);
+    }
+
+    #[test]
+    fn eeprom_works() {
+        let mut mbc = setup();
+        // EWEN
+        serial(&mut mbc, &[&[true][..], &bits(0b00_1100_0000, 10)].concat());
+        mbc.ram.write(0x0080, 0x00).unwrap();
+        // WRITE
+        serial(
+            &mut mbc,
+            &[&[true][..], &bits(0b01_0000_0011, 10), &bits(0x1234, 16)].concat(),
+        );
+        mbc.ram.write(0x0080, 0x00).unwrap();
+        assert_eq!(&mbc.ram.borrow().rom.mem[6..8], &[0x34, 0x12]);
+        // READ
+        let out = serial(
+            &mut mbc,
+            &[&[true][..], &bits(0b10_0000_0011, 10), &[false; 16]].concat(),
+        );
+        assert_eq!(out[11..], bits(0x1234, 16));
+    }
+
+    #[test]
+    fn eeprom_sizing_works() {
+        let mbc = setup();
+        // 93LC66 saves are 512 bytes
+        let mut save = vec![0xff; 0x200];
+        save[0x1fe..].copy_from_slice(&[0x78, 0x56]);
+        let eeprom = &mut mbc.ram.borrow_mut().rom;
+        assert_eq!(eeprom.flash(&mut save.as_slice()).unwrap(), 0x200);
+        assert_eq!(eeprom.mem.len(), 0x200);
+        // Upper words are addressable
+        assert_eq!(eeprom.load(0xff), 0x5678);
+    }
+}
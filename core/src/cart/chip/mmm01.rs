@@ -0,0 +1,352 @@
+use log::{debug, trace};
+use rugby_arch::mem::{Error, Memory, Result};
+use rugby_arch::state::{self, Reader, State, Writer};
+use rugby_arch::{Block, Shared};
+
+use super::{Data, Device, Mbc};
+
+/// [MMM01][mmm01] cartridge type.
+///
+/// Used by multi-game compilations, the MMM01 emulates an MBC1 for each of the
+/// contained games.
+///
+/// Upon power-on, the controller starts out "unmapped", with the final 32 KiB
+/// of ROM (holding the compilation's menu) mapped to `$0000..=$7FFF`. The menu
+/// then configures the outer banks and masks of a game, before mapping it in
+/// place, which locks the configuration until the next reset.
+///
+/// [mmm01]: https://gbdev.io/pandocs/MMM01.html
+#[derive(Clone, Debug)]
+pub struct Mmm01 {
+    reg: Shared<File>,
+    pub(super) rom: Shared<Rom>,
+    pub(super) ram: Shared<Ram>,
+}
+
+impl Mmm01 {
+    /// Constructs a new `Mmm01`.
+    #[must_use]
+    pub fn new(rom: Data, ram: Data) -> Self {
+        let reg = Shared::new(File::default());
+        Self {
+            rom: Shared::new(Rom::new(reg.clone(), rom)),
+            ram: Shared::new(Ram::new(reg.clone(), ram)),
+            reg,
+        }
+    }
+}
+
+impl Block for Mmm01 {
+    fn reset(&mut self) {
+        self.reg.take();
+    }
+}
+
+impl State for Mmm01 {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.ram.borrow().mem);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.ram.borrow_mut().mem)?;
+        Ok(())
+    }
+}
+
+impl Mbc for Mmm01 {
+    fn rom(&self) -> Device {
+        self.rom.clone().into()
+    }
+
+    fn ram(&self) -> Device {
+        self.ram.clone().into()
+    }
+
+    fn bank(&self) -> usize {
+        self.rom.borrow().adjust(0x4000) >> 14
+    }
+}
+
+/// MMM01 registers.
+///
+/// |     Address     | Bits  | Description                                |
+/// |:---------------:|-------|--------------------------------------------|
+/// | `$0000..=$1FFF` | `3:0` | RAM Enable.                                |
+/// |                 | `5:4` | RAM Bank Mask. (unmapped)                  |
+/// |                 |   `6` | Map Enable. (unmapped)                     |
+/// | `$2000..=$3FFF` | `4:0` | ROM Bank Number (low).                     |
+/// |                 | `6:5` | ROM Bank Number (mid). (unmapped)          |
+/// | `$4000..=$5FFF` | `1:0` | RAM Bank Number (low).                     |
+/// |                 | `3:2` | RAM Bank Number (high). (unmapped)         |
+/// |                 | `5:4` | ROM Bank Number (high). (unmapped)         |
+/// |                 |   `6` | Banking Mode Lock. (unmapped)              |
+/// | `$6000..=$7FFF` |   `0` | Banking Mode Select.                       |
+/// |                 | `5:2` | ROM Bank Mask. (unmapped)                  |
+///
+/// # Note
+///
+/// Fields marked as "unmapped" may only be written before the game is mapped.
+/// Afterwards, bits set in either of the masks also can no longer be changed
+/// in the corresponding bank number.
+#[derive(Debug, Default)]
+#[expect(clippy::struct_excessive_bools)]
+struct File {
+    /// Map Enable.
+    map: bool,
+    /// RAM Enable.
+    ena: bool,
+    /// ROM Bank Number.
+    rom: u16,
+    /// ROM Bank Mask.
+    rmsk: u8,
+    /// RAM Bank Number.
+    ram: u8,
+    /// RAM Bank Mask.
+    amsk: u8,
+    /// Banking Mode Select.
+    sel: bool,
+    /// Banking Mode Lock.
+    lock: bool,
+}
+
+impl File {
+    /// Gets the locked bits of the lower ROM bank number.
+    fn rom_lock(&self) -> u16 {
+        if self.map {
+            u16::from(self.rmsk) << 1
+        } else {
+            0
+        }
+    }
+
+    /// Gets the locked bits of the lower RAM bank number.
+    fn ram_lock(&self) -> u8 {
+        if self.map { self.amsk } else { 0 }
+    }
+}
+
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.map)
+            .save(&self.ena)
+            .save(&self.rom)
+            .save(&self.rmsk)
+            .save(&self.ram)
+            .save(&self.amsk)
+            .save(&self.sel)
+            .save(&self.lock);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.map)?
+            .load(&mut self.ena)?
+            .load(&mut self.rom)?
+            .load(&mut self.rmsk)?
+            .load(&mut self.ram)?
+            .load(&mut self.amsk)?
+            .load(&mut self.sel)?
+            .load(&mut self.lock)?;
+        Ok(())
+    }
+}
+
+/// MMM01 ROM.
+#[derive(Debug)]
+pub(super) struct Rom {
+    reg: Shared<File>,
+    pub(super) mem: Data,
+}
+
+impl Rom {
+    /// Constructs a new `Rom`.
+    fn new(reg: Shared<File>, mem: Data) -> Self {
+        Self { reg, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: u16) -> usize {
+        let reg = self.reg.borrow();
+        let bank = if reg.map {
+            let lock = reg.rom_lock();
+            match addr {
+                // Lower bank holds only the locked bits
+                0x0000..=0x3fff => reg.rom & !(0x1f & !lock),
+                // Upper bank remaps zero to one, as on the MBC1
+                _ if reg.rom & 0x1f & !lock == 0 => reg.rom | 1,
+                _ => reg.rom,
+            }
+        } else {
+            // Map the final 32 KiB while unmapped
+            0x1fe | (addr >> 14)
+        };
+        let addr = usize::from(addr);
+        ((usize::from(bank) << 14) | addr & 0x3fff) % self.mem.len().max(0x8000)
+    }
+}
+
+impl Memory for Rom {
+    fn read(&self, addr: u16) -> Result<u8> {
+        let index = match addr {
+            0x0000..=0x7fff => self.adjust(addr),
+            _ => return Err(Error::Range),
+        };
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        trace!("Mmm01::write(${addr:04x}, {data:#04x})");
+        let mut reg = self.reg.borrow_mut();
+        let map = reg.map;
+        match addr {
+            // RAM Enable
+            0x0000..=0x1fff => {
+                // reg.ena <- data[3:0] == 0xA
+                reg.ena = data & 0x0f == 0x0a;
+                if !map {
+                    // reg.amsk[1:0] <- data[5:4]
+                    reg.amsk = (data >> 4) & 0x03;
+                    // reg.map <- data[6]
+                    reg.map = data & 0x40 != 0;
+                }
+                debug!("RAM Enable: {}", reg.ena);
+                if reg.map && !map {
+                    debug!("mapped game: ROM: {:#05x}, RAM: {:#03x}", reg.rom, reg.ram);
+                }
+            }
+            // ROM Bank Number
+            0x2000..=0x3fff => {
+                // reg.rom[4:0] <- data[4:0] (unlocked)
+                let lock = reg.rom_lock();
+                let mask = 0x1f & !lock;
+                reg.rom = (reg.rom & !mask) | (u16::from(data) & mask);
+                if !map {
+                    // reg.rom[6:5] <- data[6:5]
+                    reg.rom = (reg.rom & !0x060) | (u16::from(data) & 0x60);
+                }
+                debug!("ROM Bank Number: {:#05x}", reg.rom);
+            }
+            // RAM Bank Number
+            0x4000..=0x5fff => {
+                // reg.ram[1:0] <- data[1:0] (unlocked)
+                let mask = 0x03 & !reg.ram_lock();
+                reg.ram = (reg.ram & !mask) | (data & mask);
+                if !map {
+                    // reg.ram[3:2] <- data[3:2]
+                    reg.ram = (reg.ram & 0x03) | (data & 0x0c);
+                    // reg.rom[8:7] <- data[5:4]
+                    reg.rom = (reg.rom & 0x7f) | (u16::from(data) & 0x30) << 3;
+                    // reg.lock <- data[6]
+                    reg.lock = data & 0x40 != 0;
+                }
+                debug!("RAM Bank Number: {:#03x}", reg.ram);
+            }
+            // Banking Mode Select
+            0x6000..=0x7fff => {
+                if !reg.lock {
+                    // reg.sel <- data[0]
+                    reg.sel = data & 0x01 != 0;
+                }
+                if !map {
+                    // reg.rmsk[3:0] <- data[5:2]
+                    reg.rmsk = (data >> 2) & 0x0f;
+                }
+                debug!("Banking Mode Select: {}", reg.sel);
+            }
+            _ => return Err(Error::Range),
+        }
+        Ok(())
+    }
+}
+
+/// MMM01 RAM.
+#[derive(Debug)]
+pub(super) struct Ram {
+    reg: Shared<File>,
+    pub(super) mem: Data,
+}
+
+impl Ram {
+    /// Constructs a new `Ram`.
+    fn new(reg: Shared<File>, mem: Data) -> Self {
+        Self { reg, mem }
+    }
+
+    /// Adjusts addresses by internal bank number.
+    fn adjust(&self, addr: u16) -> usize {
+        let reg = self.reg.borrow();
+        let bank = if reg.sel {
+            reg.ram
+        } else {
+            // Simple banking holds only the locked bits
+            reg.ram & !(0x03 & !reg.ram_lock())
+        };
+        let addr = usize::from(addr);
+        ((usize::from(bank) << 13) | addr & 0x1fff) % self.mem.len().max(1)
+    }
+}
+
+impl Memory for Ram {
+    fn read(&self, addr: u16) -> Result<u8> {
+        // Error when disabled
+        if !self.reg.borrow().ena {
+            return Err(Error::Disabled);
+        }
+        // Perform adjusted read
+        let index = self.adjust(addr);
+        self.mem.get(index).ok_or(Error::Range).copied()
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        // Error when disabled
+        if !self.reg.borrow().ena {
+            return Err(Error::Disabled);
+        }
+        // Perform adjusted write
+        let index = self.adjust(addr);
+        *self.mem.get_mut(index).ok_or(Error::Range)? = data;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Constructs a 512 KiB ROM, tagging each bank with its number.
+    fn setup() -> Mmm01 {
+        let mut rom = vec![0; 0x80000].into_boxed_slice();
+        for (bank, data) in rom.chunks_mut(0x4000).enumerate() {
+            data[0] = u8::try_from(bank).unwrap();
+        }
+        Mmm01::new(rom, vec![0; 0x8000].into())
+    }
+
+    #[test]
+    fn unmapped_works() {
+        let mbc = setup();
+        // Menu is mapped from the end of ROM
+        assert_eq!(mbc.rom.read(0x0000).unwrap(), 0x1e);
+        assert_eq!(mbc.rom.read(0x4000).unwrap(), 0x1f);
+    }
+
+    #[test]
+    fn mapped_works() {
+        let mut mbc = setup();
+        // Select a 128 KiB game at bank 0x10
+        mbc.rom.write(0x2000, 0x10).unwrap();
+        mbc.rom.write(0x6000, 0x30).unwrap();
+        // Map the game
+        mbc.rom.write(0x0000, 0x40).unwrap();
+        assert_eq!(mbc.rom.read(0x0000).unwrap(), 0x10);
+        assert_eq!(mbc.rom.read(0x4000).unwrap(), 0x11);
+        // Games bank within their own ROM
+        mbc.rom.write(0x2000, 0x07).unwrap();
+        assert_eq!(mbc.rom.read(0x4000).unwrap(), 0x17);
+        // Outer bank is locked
+        mbc.rom.write(0x2000, 0x1f).unwrap();
+        assert_eq!(mbc.rom.read(0x4000).unwrap(), 0x17);
+        mbc.rom.write(0x0000, 0x00).unwrap();
+        assert_eq!(mbc.rom.read(0x0000).unwrap(), 0x10);
+    }
+}
@@ -4,25 +4,38 @@
 
 #![allow(clippy::module_name_repetitions)]
 
+use std::cell::RefMut;
 use std::fmt::Debug;
-use std::io;
+use std::io::{self, Read};
 
 use log::{debug, trace};
 use rugby_arch::mem::{self, Memory};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::{Board, Error, Header, Result};
+use crate::api::camera::ImageSource;
 
+mod camera;
+mod huc1;
+mod huc3;
 mod mbc1;
 mod mbc2;
 mod mbc3;
 mod mbc5;
+mod mbc7;
+mod mmm01;
 mod none;
 
+pub use self::camera::{Camera, Sensor};
+pub use self::huc1::{HuC1, Ir};
+pub use self::huc3::HuC3;
 pub use self::mbc1::Mbc1;
 pub use self::mbc2::Mbc2;
-pub use self::mbc3::Mbc3;
-pub use self::mbc5::Mbc5;
+pub use self::mbc3::{Mbc3, Rtc};
+pub use self::mbc5::{Mbc5, Motor};
+pub use self::mbc7::Mbc7;
+pub use self::mmm01::Mmm01;
 pub use self::none::None;
 
 /// Memory data.
@@ -38,6 +51,9 @@ pub trait Mbc {
 
     /// Gets the contents of the cartridge's RAM.
     fn ram(&self) -> Device;
+
+    /// Gets the ROM bank mapped into the switchable region (`$4000..=$7FFF`).
+    fn bank(&self) -> usize;
 }
 
 /// Cartridge body.
@@ -51,6 +67,11 @@ pub(crate) enum Chip {
     Mbc2(Mbc2),
     Mbc3(Mbc3),
     Mbc5(Mbc5),
+    Mbc7(Mbc7),
+    Mmm01(Mmm01),
+    HuC1(HuC1),
+    HuC3(HuC3),
+    Camera(Camera),
 }
 
 impl Chip {
@@ -71,10 +92,21 @@ impl Chip {
         // Construct body
         match &head.board {
             &Board::None { .. } => Ok(Chip::None(None::new(rom, ram))),
+            &Board::Mbc1 { .. } if Mbc1::detect(&rom) => {
+                debug!("detected MBC1 multicart");
+                Ok(Chip::Mbc1(Mbc1::multicart(rom, ram)))
+            }
             &Board::Mbc1 { .. } => Ok(Chip::Mbc1(Mbc1::new(rom, ram))),
             &Board::Mbc2 { .. } => Ok(Chip::Mbc2(Mbc2::new(rom, ram))),
-            &Board::Mbc3 { .. } => Ok(Chip::Mbc3(Mbc3::new(rom, ram))),
-            &Board::Mbc5 { .. } => Ok(Chip::Mbc5(Mbc5::new(rom, ram))),
+            &Board::Mbc3 { clock: false, .. } => Ok(Chip::Mbc3(Mbc3::new(rom, ram))),
+            &Board::Mbc3 { clock: true, .. } => Ok(Chip::Mbc3(Mbc3::with_rtc(rom, ram))),
+            &Board::Mbc5 { motor: false, .. } => Ok(Chip::Mbc5(Mbc5::new(rom, ram))),
+            &Board::Mbc5 { motor: true, .. } => Ok(Chip::Mbc5(Mbc5::with_motor(rom, ram))),
+            &Board::Mbc7 => Ok(Chip::Mbc7(Mbc7::new(rom, ram))),
+            &Board::Mmm01 { .. } => Ok(Chip::Mmm01(Mmm01::new(rom, ram))),
+            &Board::HuC1 => Ok(Chip::HuC1(HuC1::new(rom, ram))),
+            &Board::HuC3 => Ok(Chip::HuC3(HuC3::new(rom, ram))),
+            &Board::Camera => Ok(Chip::Camera(Camera::new(rom, ram))),
             kind => Err(Error::Unsupported(kind.clone())),
         }
     }
@@ -92,7 +124,12 @@ impl Chip {
             | Board::Mbc1 { .. }
             | Board::Mbc2 { .. }
             | Board::Mbc3 { .. }
-            | Board::Mbc5 { .. } => Ok(()),
+            | Board::Mbc5 { .. }
+            | Board::Mbc7
+            | Board::Mmm01 { .. }
+            | Board::HuC1
+            | Board::HuC3
+            | Board::Camera => Ok(()),
             kind => Err(Error::Unsupported(kind.clone())),
         }
     }
@@ -106,6 +143,11 @@ impl Block for Chip {
             Chip::Mbc2(mbc) => mbc.ready(),
             Chip::Mbc3(mbc) => mbc.ready(),
             Chip::Mbc5(mbc) => mbc.ready(),
+            Chip::Mbc7(mbc) => mbc.ready(),
+            Chip::Mmm01(mbc) => mbc.ready(),
+            Chip::HuC1(mbc) => mbc.ready(),
+            Chip::HuC3(mbc) => mbc.ready(),
+            Chip::Camera(mbc) => mbc.ready(),
         }
     }
 
@@ -116,6 +158,11 @@ impl Block for Chip {
             Chip::Mbc2(mbc) => mbc.cycle(),
             Chip::Mbc3(mbc) => mbc.cycle(),
             Chip::Mbc5(mbc) => mbc.cycle(),
+            Chip::Mbc7(mbc) => mbc.cycle(),
+            Chip::Mmm01(mbc) => mbc.cycle(),
+            Chip::HuC1(mbc) => mbc.cycle(),
+            Chip::HuC3(mbc) => mbc.cycle(),
+            Chip::Camera(mbc) => mbc.cycle(),
         }
     }
 
@@ -126,6 +173,46 @@ impl Block for Chip {
             Chip::Mbc2(mbc) => mbc.reset(),
             Chip::Mbc3(mbc) => mbc.reset(),
             Chip::Mbc5(mbc) => mbc.reset(),
+            Chip::Mbc7(mbc) => mbc.reset(),
+            Chip::Mmm01(mbc) => mbc.reset(),
+            Chip::HuC1(mbc) => mbc.reset(),
+            Chip::HuC3(mbc) => mbc.reset(),
+            Chip::Camera(mbc) => mbc.reset(),
+        }
+    }
+}
+
+impl State for Chip {
+    fn save(&self, out: &mut Writer) {
+        match self {
+            Chip::None(mbc) => out.save(&0u8).save(mbc),
+            Chip::Mbc1(mbc) => out.save(&1u8).save(mbc),
+            Chip::Mbc2(mbc) => out.save(&2u8).save(mbc),
+            Chip::Mbc3(mbc) => out.save(&3u8).save(mbc),
+            Chip::Mbc5(mbc) => out.save(&5u8).save(mbc),
+            Chip::Mbc7(mbc) => out.save(&7u8).save(mbc),
+            Chip::Mmm01(mbc) => out.save(&0x0bu8).save(mbc),
+            Chip::HuC1(mbc) => out.save(&0xffu8).save(mbc),
+            Chip::HuC3(mbc) => out.save(&0xfeu8).save(mbc),
+            Chip::Camera(mbc) => out.save(&0xfcu8).save(mbc),
+        };
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut kind = u8::default();
+        inp.load(&mut kind)?;
+        match (self, kind) {
+            (Chip::None(mbc), 0) => mbc.restore(inp),
+            (Chip::Mbc1(mbc), 1) => mbc.restore(inp),
+            (Chip::Mbc2(mbc), 2) => mbc.restore(inp),
+            (Chip::Mbc3(mbc), 3) => mbc.restore(inp),
+            (Chip::Mbc5(mbc), 5) => mbc.restore(inp),
+            (Chip::Mbc7(mbc), 7) => mbc.restore(inp),
+            (Chip::Mmm01(mbc), 0x0b) => mbc.restore(inp),
+            (Chip::HuC1(mbc), 0xff) => mbc.restore(inp),
+            (Chip::HuC3(mbc), 0xfe) => mbc.restore(inp),
+            (Chip::Camera(mbc), 0xfc) => mbc.restore(inp),
+            _ => Err(state::Error::Invalid("Chip")),
         }
     }
 }
@@ -138,6 +225,11 @@ impl Mbc for Chip {
             Chip::Mbc2(mbc) => mbc.rom(),
             Chip::Mbc3(mbc) => mbc.rom(),
             Chip::Mbc5(mbc) => mbc.rom(),
+            Chip::Mbc7(mbc) => mbc.rom(),
+            Chip::Mmm01(mbc) => mbc.rom(),
+            Chip::HuC1(mbc) => mbc.rom(),
+            Chip::HuC3(mbc) => mbc.rom(),
+            Chip::Camera(mbc) => mbc.rom(),
         }
     }
 
@@ -148,6 +240,26 @@ impl Mbc for Chip {
             Chip::Mbc2(mbc) => mbc.ram(),
             Chip::Mbc3(mbc) => mbc.ram(),
             Chip::Mbc5(mbc) => mbc.ram(),
+            Chip::Mbc7(mbc) => mbc.ram(),
+            Chip::Mmm01(mbc) => mbc.ram(),
+            Chip::HuC1(mbc) => mbc.ram(),
+            Chip::HuC3(mbc) => mbc.ram(),
+            Chip::Camera(mbc) => mbc.ram(),
+        }
+    }
+
+    fn bank(&self) -> usize {
+        match self {
+            Chip::None(mbc) => mbc.bank(),
+            Chip::Mbc1(mbc) => mbc.bank(),
+            Chip::Mbc2(mbc) => mbc.bank(),
+            Chip::Mbc3(mbc) => mbc.bank(),
+            Chip::Mbc5(mbc) => mbc.bank(),
+            Chip::Mbc7(mbc) => mbc.bank(),
+            Chip::Mmm01(mbc) => mbc.bank(),
+            Chip::HuC1(mbc) => mbc.bank(),
+            Chip::HuC3(mbc) => mbc.bank(),
+            Chip::Camera(mbc) => mbc.bank(),
         }
     }
 }
@@ -162,6 +274,11 @@ impl Memory for Chip {
                 Chip::Mbc2(mbc) => mbc.rom.read(addr),
                 Chip::Mbc3(mbc) => mbc.rom.read(addr),
                 Chip::Mbc5(mbc) => mbc.rom.read(addr),
+                Chip::Mbc7(mbc) => mbc.rom.read(addr),
+                Chip::Mmm01(mbc) => mbc.rom.read(addr),
+                Chip::HuC1(mbc) => mbc.rom.read(addr),
+                Chip::HuC3(mbc) => mbc.rom.read(addr),
+                Chip::Camera(mbc) => mbc.rom.read(addr),
             },
             // Cartridge RAM
             0xa000..=0xbfff => {
@@ -172,6 +289,11 @@ impl Memory for Chip {
                     Chip::Mbc2(mbc) => mbc.ram.read(addr),
                     Chip::Mbc3(mbc) => mbc.ram.read(addr),
                     Chip::Mbc5(mbc) => mbc.ram.read(addr),
+                    Chip::Mbc7(mbc) => mbc.ram.read(addr),
+                    Chip::Mmm01(mbc) => mbc.ram.read(addr),
+                    Chip::HuC1(mbc) => mbc.ram.read(addr),
+                    Chip::HuC3(mbc) => mbc.ram.read(addr),
+                    Chip::Camera(mbc) => mbc.ram.read(addr),
                 }
             }
             _ => Err(mem::Error::Range),
@@ -187,6 +309,11 @@ impl Memory for Chip {
                 Chip::Mbc2(mbc) => mbc.rom.write(addr, data),
                 Chip::Mbc3(mbc) => mbc.rom.write(addr, data),
                 Chip::Mbc5(mbc) => mbc.rom.write(addr, data),
+                Chip::Mbc7(mbc) => mbc.rom.write(addr, data),
+                Chip::Mmm01(mbc) => mbc.rom.write(addr, data),
+                Chip::HuC1(mbc) => mbc.rom.write(addr, data),
+                Chip::HuC3(mbc) => mbc.rom.write(addr, data),
+                Chip::Camera(mbc) => mbc.rom.write(addr, data),
             },
             // Cartridge RAM
             0xa000..=0xbfff => {
@@ -197,6 +324,11 @@ impl Memory for Chip {
                     Chip::Mbc2(mbc) => mbc.ram.write(addr, data),
                     Chip::Mbc3(mbc) => mbc.ram.write(addr, data),
                     Chip::Mbc5(mbc) => mbc.ram.write(addr, data),
+                    Chip::Mbc7(mbc) => mbc.ram.write(addr, data),
+                    Chip::Mmm01(mbc) => mbc.ram.write(addr, data),
+                    Chip::HuC1(mbc) => mbc.ram.write(addr, data),
+                    Chip::HuC3(mbc) => mbc.ram.write(addr, data),
+                    Chip::Camera(mbc) => mbc.ram.write(addr, data),
                 }
             }
             _ => Err(mem::Error::Range),
@@ -205,6 +337,90 @@ impl Memory for Chip {
 }
 
 impl Chip {
+    /// Borrows the contents of the cartridge's RAM.
+    pub fn sram(&self) -> RefMut<'_, [u8]> {
+        match self {
+            Chip::None(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.inner_mut().as_mut()),
+            Chip::Mbc1(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.mem.as_mut()),
+            Chip::Mbc2(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.mem.as_mut()),
+            Chip::Mbc3(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.mem.as_mut()),
+            Chip::Mbc5(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.mem.as_mut()),
+            Chip::Mbc7(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.rom.mem.as_mut()),
+            Chip::Mmm01(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.mem.as_mut()),
+            Chip::HuC1(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.mem.as_mut()),
+            Chip::HuC3(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.mem.as_mut()),
+            Chip::Camera(mbc) => RefMut::map(mbc.ram.borrow_mut(), |ram| ram.mem.as_mut()),
+        }
+    }
+
+    /// Borrows the live registers of the cartridge's real-time clock, if any.
+    pub fn rtc(&self) -> Option<RefMut<'_, [u8]>> {
+        match self {
+            Chip::Mbc3(Mbc3 { rtc: Some(rtc), .. }) => {
+                Some(RefMut::map(rtc.borrow_mut(), Rtc::regs))
+            }
+            _ => Option::None,
+        }
+    }
+
+    /// Gets the strength of the cartridge's rumble motor, if any.
+    pub fn rumble(&self) -> f32 {
+        match self {
+            Chip::Mbc5(Mbc5 {
+                motor: Some(motor), ..
+            }) => motor.borrow().strength(),
+            _ => 0.,
+        }
+    }
+
+    /// Checks if the cartridge is wired as an MBC1 multicart.
+    pub fn multicart(&self) -> bool {
+        match self {
+            Chip::Mbc1(mbc) => mbc.multi(),
+            _ => false,
+        }
+    }
+
+    /// Overrides the wiring of an MBC1 multicart.
+    pub fn set_multicart(&mut self, multi: bool) {
+        if let Chip::Mbc1(mbc) = self {
+            mbc.set_multi(multi);
+        }
+    }
+
+    /// Checks if the cartridge's infrared LED is emitting light, if any.
+    pub fn infrared(&self) -> bool {
+        match self {
+            Chip::HuC1(mbc) => mbc.ir.borrow().emit(),
+            Chip::HuC3(mbc) => mbc.ir.borrow().emit(),
+            _ => false,
+        }
+    }
+
+    /// Signals whether light is reaching the cartridge's infrared receiver, if
+    /// any.
+    pub fn set_infrared(&mut self, light: bool) {
+        match self {
+            Chip::HuC1(mbc) => mbc.ir.borrow_mut().detect(light),
+            Chip::HuC3(mbc) => mbc.ir.borrow_mut().detect(light),
+            _ => (),
+        }
+    }
+
+    /// Tilts the cartridge's accelerometer, if any.
+    pub fn tilt(&mut self, x: f32, y: f32) {
+        if let Chip::Mbc7(mbc) = self {
+            mbc.tilt(x, y);
+        }
+    }
+
+    /// Connects an image source to the cartridge's camera, if any.
+    pub fn set_camera(&mut self, src: Box<dyn ImageSource>) {
+        if let Chip::Camera(mbc) = self {
+            mbc.set_source(src);
+        }
+    }
+
     /// Flashes data onto the cartridge's RAM.
     ///
     /// # Errors
@@ -213,17 +429,40 @@ impl Chip {
     /// completed. If an error is returned then no bytes were read.
     pub fn flash(&mut self, buf: &mut impl io::Read) -> io::Result<usize> {
         let mut flash = |ram: &mut [u8]| {
-            buf.read(ram).inspect(|&nbytes| {
-                debug!("loaded {size}", size = bfmt::Size::from(nbytes));
-                trace!("cart RAM:\n{}", hexd::Printer::<u8>::new(0, ram));
-            })
+            // Read the entire RAM, unless the save is empty
+            let mut data = Vec::with_capacity(ram.len());
+            buf.by_ref().take(ram.len() as u64).read_to_end(&mut data)?;
+            match data.len() {
+                0 => return Ok(0),
+                len if len < ram.len() => return Err(io::ErrorKind::UnexpectedEof.into()),
+                _ => ram.copy_from_slice(&data),
+            }
+            debug!("loaded {size}", size = bfmt::Size::from(data.len()));
+            trace!("cart RAM:\n{}", hexd::Printer::<u8>::new(0, ram));
+            Ok(data.len())
         };
         match self {
             Chip::None(mbc) => flash(mbc.ram.borrow_mut().inner_mut()),
             Chip::Mbc1(mbc) => flash(mbc.ram.borrow_mut().mem.as_mut()),
             Chip::Mbc2(mbc) => flash(mbc.ram.borrow_mut().mem.as_mut()),
-            Chip::Mbc3(mbc) => flash(mbc.ram.borrow_mut().mem.as_mut()),
+            Chip::Mbc3(mbc) => {
+                let nbytes = flash(mbc.ram.borrow_mut().mem.as_mut())?;
+                // Clock data is appended as a trailer
+                match &mbc.rtc {
+                    Some(rtc) => Ok(nbytes + rtc.borrow_mut().flash(buf)?),
+                    Option::None => Ok(nbytes),
+                }
+            }
             Chip::Mbc5(mbc) => flash(mbc.ram.borrow_mut().mem.as_mut()),
+            Chip::Mbc7(mbc) => mbc.ram.borrow_mut().rom.flash(buf),
+            Chip::Mmm01(mbc) => flash(mbc.ram.borrow_mut().mem.as_mut()),
+            Chip::HuC1(mbc) => flash(mbc.ram.borrow_mut().mem.as_mut()),
+            Chip::HuC3(mbc) => {
+                let nbytes = flash(mbc.ram.borrow_mut().mem.as_mut())?;
+                // Clock data is appended as a trailer
+                Ok(nbytes + mbc.rtc.borrow_mut().flash(buf)?)
+            }
+            Chip::Camera(mbc) => flash(mbc.ram.borrow_mut().mem.as_mut()),
         }
     }
 
@@ -244,8 +483,24 @@ impl Chip {
             Chip::None(mbc) => dump(mbc.ram.borrow_mut().inner_mut()),
             Chip::Mbc1(mbc) => dump(mbc.ram.borrow_mut().mem.as_mut()),
             Chip::Mbc2(mbc) => dump(mbc.ram.borrow_mut().mem.as_mut()),
-            Chip::Mbc3(mbc) => dump(mbc.ram.borrow_mut().mem.as_mut()),
+            Chip::Mbc3(mbc) => {
+                let nbytes = dump(mbc.ram.borrow_mut().mem.as_mut())?;
+                // Clock data is appended as a trailer
+                match &mbc.rtc {
+                    Some(rtc) => Ok(nbytes + rtc.borrow().dump(buf)?),
+                    Option::None => Ok(nbytes),
+                }
+            }
             Chip::Mbc5(mbc) => dump(mbc.ram.borrow_mut().mem.as_mut()),
+            Chip::Mbc7(mbc) => dump(mbc.ram.borrow_mut().rom.mem.as_mut()),
+            Chip::Mmm01(mbc) => dump(mbc.ram.borrow_mut().mem.as_mut()),
+            Chip::HuC1(mbc) => dump(mbc.ram.borrow_mut().mem.as_mut()),
+            Chip::HuC3(mbc) => {
+                let nbytes = dump(mbc.ram.borrow_mut().mem.as_mut())?;
+                // Clock data is appended as a trailer
+                Ok(nbytes + mbc.rtc.borrow().dump(buf)?)
+            }
+            Chip::Camera(mbc) => dump(mbc.ram.borrow_mut().mem.as_mut()),
         }
     }
 }
@@ -295,6 +550,11 @@ mod init {
         if let Board::Mbc2 { .. } = head.board {
             return vec![0; 0x200].into_boxed_slice();
         }
+        // NOTE: MBC7 contains a 256 or 512 byte EEPROM, which is not reported
+        //       by the header. The larger size is inferred from a save.
+        if let Board::Mbc7 = head.board {
+            return vec![0xff; 0x100].into_boxed_slice();
+        }
         if head.board.has_ram() && head.memory.ramsz == 0 {
             warn!("cartridge supports RAM, but specified size is zero");
         }
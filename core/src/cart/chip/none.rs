@@ -1,3 +1,4 @@
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::{Data, Device, Mbc};
@@ -29,6 +30,16 @@ impl None {
 
 impl Block for None {}
 
+impl State for None {
+    fn save(&self, out: &mut Writer) {
+        self.ram.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.ram.restore(inp)
+    }
+}
+
 impl Mbc for None {
     fn rom(&self) -> Device {
         self.rom.clone().into()
@@ -37,4 +48,8 @@ impl Mbc for None {
     fn ram(&self) -> Device {
         self.ram.clone().into()
     }
+
+    fn bank(&self) -> usize {
+        1
+    }
 }
@@ -0,0 +1,397 @@
+//! Game Boy Sound System (GBS) rips.
+//!
+//! A GBS file contains the sound driver and music data ripped from a game,
+//! preceded by a header describing how the driver should be called. See more
+//! details [here][gbs].
+//!
+//! As the console can only run cartridges, playback requires building a
+//! minimal cartridge image around the ripped data. This contains a small
+//! program that calls the rip's INIT routine for the selected track, then
+//! repeatedly calls its PLAY routine from either the timer or vertical blank
+//! interrupt.
+//!
+//! [gbs]: https://ocremix.org/info/GBS_Format_Specification
+
+use std::array::TryFromSliceError;
+use std::fmt::Display;
+
+use super::{Cartridge, head};
+
+/// Header size.
+const HEADER: usize = 0x70;
+
+/// Driver address.
+///
+/// The driver is placed directly following the cartridge header.
+const DRIVER: u16 = 0x0150;
+
+/// Maximum cartridge size.
+///
+/// Largest ROM addressable by an MBC5.
+const MAXIMUM: usize = 0x80_0000;
+
+/// GBS rip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gbs {
+    /// Number of tracks.
+    pub songs: u8,
+    /// Default track (zero-indexed).
+    pub first: u8,
+    /// Load address.
+    ///
+    /// Address at which the data is loaded into the cartridge ROM.
+    pub load: u16,
+    /// Init address.
+    ///
+    /// Routine called once with the selected track in `A`.
+    pub init: u16,
+    /// Play address.
+    ///
+    /// Routine called periodically to advance playback.
+    pub play: u16,
+    /// Initial stack pointer.
+    pub sp: u16,
+    /// Timer modulo.
+    pub tma: u8,
+    /// Timer control.
+    ///
+    /// When the timer is enabled, it drives calls to the play routine in
+    /// place of vertical blank.
+    pub tac: u8,
+    /// Title string.
+    pub title: String,
+    /// Author string.
+    pub author: String,
+    /// Copyright string.
+    pub copyright: String,
+    /// Ripped data.
+    data: Box<[u8]>,
+}
+
+impl Gbs {
+    /// Constructs a new `Gbs`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file contained an invalid header.
+    pub fn new(file: &[u8]) -> Result<Self> {
+        // Extract header bytes
+        let head: &[u8; HEADER] = file.get(..HEADER).ok_or(Error::Missing)?.try_into()?;
+        if &head[0x00..0x03] != b"GBS" {
+            return Err(Error::Signature);
+        }
+        if head[0x03] != 1 {
+            return Err(Error::Version(head[0x03]));
+        }
+
+        // Construct rip
+        let word = |idx: usize| u16::from_le_bytes([head[idx], head[idx + 1]]);
+        let text = |idx: usize| {
+            String::from_utf8_lossy(&head[idx..idx + 0x20])
+                .split('\0')
+                .next()
+                .map(String::from)
+                .unwrap_or_default()
+        };
+        let this = Self {
+            songs: head[0x04],
+            first: head[0x05].saturating_sub(1),
+            load: word(0x06),
+            init: word(0x08),
+            play: word(0x0a),
+            sp: word(0x0c),
+            tma: head[0x0e],
+            tac: head[0x0f],
+            title: text(0x10),
+            author: text(0x30),
+            copyright: text(0x50),
+            data: file[HEADER..].into(),
+        };
+        if this.load < 0x0400 {
+            return Err(Error::Load(this.load));
+        }
+
+        Ok(this)
+    }
+
+    /// Checks if the play routine is driven by the timer.
+    ///
+    /// Otherwise, it is driven by vertical blank.
+    #[must_use]
+    pub fn timer(&self) -> bool {
+        self.tac & 0x04 != 0
+    }
+
+    /// Builds a cartridge ROM image which plays the specified track.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the track does not exist, or if the data does not
+    /// fit within a cartridge.
+    pub fn rom(&self, track: u8) -> Result<Vec<u8>> {
+        if track >= self.songs {
+            return Err(Error::Track(track));
+        }
+
+        // Allocate ROM
+        let load = usize::from(self.load);
+        let end = load + self.data.len();
+        let size = end.next_power_of_two().max(0x8000);
+        if size > MAXIMUM {
+            return Err(Error::Size(end));
+        }
+        let mut rom = vec![0; size];
+        rom[load..end].copy_from_slice(&self.data);
+
+        // Relocate restart vectors
+        for rst in (0x00..0x40).step_by(8) {
+            let [lo, hi] = (self.load + rst).to_le_bytes();
+            let rst = usize::from(rst);
+            rom[rst..rst + 3].copy_from_slice(&[0xc3, lo, hi]); // JP load+rst
+        }
+        // Prepare interrupt handlers
+        let [lo, hi] = self.play.to_le_bytes();
+        for int in [0x40, 0x48, 0x50, 0x58, 0x60] {
+            rom[int] = 0xd9; // RETI
+        }
+        rom[0x40..0x44].copy_from_slice(&[0xcd, lo, hi, 0xd9]); // CALL play; RETI
+        rom[0x50..0x54].copy_from_slice(&[0xcd, lo, hi, 0xd9]); // CALL play; RETI
+
+        // Write header
+        let [lo, hi] = DRIVER.to_le_bytes();
+        rom[0x100..0x104].copy_from_slice(&[0x00, 0xc3, lo, hi]); // NOP; JP driver
+        rom[0x104..0x134].copy_from_slice(&head::LOGO);
+        let title = self
+            .title
+            .bytes()
+            .filter(|byte| byte.is_ascii_graphic() || *byte == b' ')
+            .take(15)
+            .collect::<Vec<_>>();
+        rom[0x134..0x134 + title.len()].copy_from_slice(&title);
+        rom[0x147] = 0x1a; // MBC5+RAM
+        #[expect(clippy::cast_possible_truncation)]
+        let romsz = (size / 0x8000).trailing_zeros() as u8;
+        rom[0x148] = romsz;
+        rom[0x149] = 0x02; // 8 KiB
+        rom[0x14a] = 0x01; // overseas
+
+        // Write driver
+        let driver = self.driver(track);
+        let base = usize::from(DRIVER);
+        rom[base..base + driver.len()].copy_from_slice(&driver);
+
+        // Compute checksums
+        rom[0x14d] = head::hchk(&rom);
+        let gchk = head::gchk(&rom).to_be_bytes();
+        rom[0x14e..0x150].copy_from_slice(&gchk);
+
+        Ok(rom)
+    }
+
+    /// Builds a cartridge which plays the specified track.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cartridge could not be built.
+    pub fn cartridge(&self, track: u8) -> Result<Cartridge> {
+        Cartridge::checked(&self.rom(track)?).map_err(Into::into)
+    }
+
+    /// Assembles the driver program.
+    ///
+    /// Initializes the hardware and selected track, then halts awaiting
+    /// interrupts to call the play routine.
+    #[rustfmt::skip]
+    fn driver(&self, track: u8) -> Vec<u8> {
+        let [sp_lo, sp_hi] = self.sp.to_le_bytes();
+        let [init_lo, init_hi] = self.init.to_le_bytes();
+        let int = if self.timer() { 0x04 } else { 0x01 };
+        vec![
+            0xf3,                     // DI
+            0x31, sp_lo, sp_hi,       // LD SP, sp
+            0x21, 0x00, 0xc0,         // LD HL, $C000
+            0xaf,                     // XOR A
+            0x22,                     // .clear: LD (HL+), A
+            0xcb, 0x6c,               // BIT 5, H
+            0x28, 0xfb,               // JR Z, .clear
+            0x3e, 0x0a,               // LD A, $0A
+            0xea, 0x00, 0x00,         // LD ($0000), A     ; enable RAM
+            0x3e, self.tma,           // LD A, tma
+            0xe0, 0x06,               // LDH (TMA), A
+            0x3e, self.tac & 0x07,    // LD A, tac
+            0xe0, 0x07,               // LDH (TAC), A
+            0x3e, 0x80,               // LD A, $80
+            0xe0, 0x26,               // LDH (NR52), A     ; enable APU
+            0x3e, 0x77,               // LD A, $77
+            0xe0, 0x24,               // LDH (NR50), A     ; full volume
+            0x3e, 0xff,               // LD A, $FF
+            0xe0, 0x25,               // LDH (NR51), A     ; pan centre
+            0x3e, int,                // LD A, int
+            0xe0, 0xff,               // LDH (IE), A
+            0x3e, track,              // LD A, track
+            0xcd, init_lo, init_hi,   // CALL init
+            0xaf,                     // XOR A
+            0xe0, 0x0f,               // LDH (IF), A
+            0xfb,                     // EI
+            0x76,                     // .idle: HALT
+            0x18, 0xfd,               // JR .idle
+        ]
+    }
+}
+
+impl Display for Gbs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unknown = |s: &str| if s.is_empty() { "Unknown" } else { s }.to_string();
+        write!(
+            f,
+            "{title} by {author} ({copyright})",
+            title = unknown(&self.title),
+            author = unknown(&self.author),
+            copyright = unknown(&self.copyright),
+        )
+    }
+}
+
+/// A convenient type alias for [`Result`](std::result::Result).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error caused by loading a [GBS rip](Gbs).
+#[derive(Debug)]
+#[derive(thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Missing header data.
+    #[error("missing header data")]
+    Missing,
+    /// Slice conversion failed.
+    #[error(transparent)]
+    Slice(#[from] TryFromSliceError),
+    /// Bad file signature.
+    #[error("bad file signature")]
+    Signature,
+    /// Unsupported version.
+    #[error("unsupported version: {0}")]
+    Version(u8),
+    /// Invalid load address.
+    #[error("invalid load address: ${0:04x}")]
+    Load(u16),
+    /// Invalid track.
+    #[error("invalid track: {0}")]
+    Track(u8),
+    /// Data too large.
+    #[error("data too large: {0} bytes")]
+    Size(usize),
+    /// Bad cartridge.
+    #[error("bad cartridge")]
+    Cartridge(#[from] super::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use rugby_arch::Block;
+    use rugby_arch::mem::Memory;
+
+    use super::*;
+    use crate::chip::ppu;
+    use crate::dmg::{GameBoy, rev};
+
+    /// Builds a sample rip.
+    ///
+    /// The init routine stores the track to `$C000`, and the play routine
+    /// increments a counter at `$C001`.
+    fn sample(tac: u8) -> Vec<u8> {
+        let mut file = vec![0; HEADER];
+        file[0x00..0x04].copy_from_slice(b"GBS\x01");
+        file[0x04] = 3; // songs
+        file[0x05] = 1; // first
+        file[0x06..0x08].copy_from_slice(&0x0400_u16.to_le_bytes()); // load
+        file[0x08..0x0a].copy_from_slice(&0x0400_u16.to_le_bytes()); // init
+        file[0x0a..0x0c].copy_from_slice(&0x0404_u16.to_le_bytes()); // play
+        file[0x0c..0x0e].copy_from_slice(&0xfffe_u16.to_le_bytes()); // sp
+        file[0x0e] = 0x00; // tma
+        file[0x0f] = tac;
+        file[0x10..0x14].copy_from_slice(b"Test");
+        file.extend([0xea, 0x00, 0xc0, 0xc9]); // LD ($C000), A; RET
+        file.extend([0x21, 0x01, 0xc0, 0x34, 0xc9]); // LD HL, $C001; INC (HL); RET
+        file
+    }
+
+    #[test]
+    fn new_works() {
+        let gbs = Gbs::new(&sample(0x00)).unwrap();
+        assert_eq!(gbs.songs, 3);
+        assert_eq!(gbs.first, 0);
+        assert_eq!(gbs.load, 0x0400);
+        assert_eq!(gbs.init, 0x0400);
+        assert_eq!(gbs.play, 0x0404);
+        assert_eq!(gbs.sp, 0xfffe);
+        assert_eq!(gbs.title, "Test");
+        assert_eq!(gbs.author, "");
+        assert!(!gbs.timer());
+    }
+
+    #[test]
+    fn new_fails() {
+        assert!(matches!(Gbs::new(&[0; 0x10]), Err(Error::Missing)));
+        let mut file = sample(0x00);
+        file[0x00] = b'X';
+        assert!(matches!(Gbs::new(&file), Err(Error::Signature)));
+        let mut file = sample(0x00);
+        file[0x03] = 2;
+        assert!(matches!(Gbs::new(&file), Err(Error::Version(2))));
+        let mut file = sample(0x00);
+        file[0x07] = 0x03;
+        assert!(matches!(Gbs::new(&file), Err(Error::Load(0x0300))));
+    }
+
+    #[test]
+    fn rom_works() {
+        let gbs = Gbs::new(&sample(0x00)).unwrap();
+        let rom = gbs.rom(1).unwrap();
+        assert_eq!(rom.len(), 0x8000);
+        assert_eq!(&rom[0x0400..0x0409], &sample(0x00)[HEADER..]);
+        assert_eq!(&rom[0x0038..0x003b], &[0xc3, 0x38, 0x04]);
+        assert_eq!(&rom[0x0040..0x0044], &[0xcd, 0x04, 0x04, 0xd9]);
+        assert!(matches!(gbs.rom(3), Err(Error::Track(3))));
+
+        let cart = gbs.cartridge(1).unwrap();
+        assert_eq!(cart.title(), "Test");
+    }
+
+    fn play(tac: u8) {
+        let gbs = Gbs::new(&sample(tac)).unwrap();
+        let mut emu = GameBoy::<rev::C>::new();
+        emu.insert(gbs.cartridge(2).unwrap());
+        for _ in 0..20 * ppu::FRAME {
+            emu.cycle();
+        }
+
+        let wram = emu.wram();
+        let wram = wram.borrow();
+        // Check the track was initialized
+        assert_eq!(wram.read(0x0000).unwrap(), 2);
+        // Check the play routine was called
+        assert!(wram.read(0x0001).unwrap() >= 10);
+
+        // Check switching tracks
+        drop(wram);
+        emu.eject();
+        emu.insert(gbs.cartridge(0).unwrap());
+        emu.reset();
+        for _ in 0..5 * ppu::FRAME {
+            emu.cycle();
+        }
+        assert_eq!(emu.wram().borrow().read(0x0000).unwrap(), 0);
+    }
+
+    #[test]
+    fn vblank_works() {
+        play(0x00);
+    }
+
+    #[test]
+    fn timer_works() {
+        // 65536 Hz, overflowing at 256 Hz
+        play(0x06);
+    }
+}
@@ -482,10 +482,17 @@ pub mod parts {
                 | Board::Mbc3 { power, .. }
                 | Board::Mbc5 { power, .. }
                 | Board::Mmm01 { power, .. } => *power,
+                Board::Mbc7 | Board::HuC1 | Board::HuC3 | Board::Camera => true,
                 _ => false,
             }
         }
 
+        /// Check if the cartridge has a real-time clock.
+        #[must_use]
+        pub fn has_clock(&self) -> bool {
+            matches!(self, Board::Mbc3 { clock: true, .. } | Board::HuC3)
+        }
+
         /// Check if the cartridge has any RAM.
         #[must_use]
         pub fn has_ram(&self) -> bool {
@@ -495,8 +502,11 @@ pub mod parts {
                 | Board::Mbc3 { exram, .. }
                 | Board::Mbc5 { exram, .. }
                 | Board::Mmm01 { exram, .. } => *exram,
-                // NOTE: MBC2 always has built-in RAM.
-                Board::Mbc2 { .. } => true,
+                // NOTE: MBC2 always has built-in RAM, and MBC7 always has a
+                //       built-in EEPROM.
+                Board::Mbc2 { .. } | Board::Mbc7 | Board::HuC1 | Board::HuC3 | Board::Camera => {
+                    true
+                }
                 _ => false,
             }
         }
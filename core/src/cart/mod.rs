@@ -10,15 +10,20 @@
 //! [header]: https://gbdev.io/pandocs/The_Cartridge_Header.html
 //! [mbcs]:   https://gbdev.io/pandocs/MBCs.html
 
+use std::cell::{Ref, RefMut};
 use std::io;
 
 use rugby_arch::mem::{self, Memory};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
-use self::chip::Chip;
+use self::chip::{Chip, Mbc};
 use self::head::parts::Board;
+use crate::api::camera::ImageSource;
+use crate::api::cheat::Code;
 
 pub mod chip;
+pub mod gbs;
 pub mod head;
 
 pub use self::head::Header;
@@ -27,8 +32,16 @@ pub use self::head::Header;
 ///
 /// Dispatches accesses to the inserted cartridge, leaving an empty slot
 /// unmapped.
+///
+/// Any active cheat codes are applied by the slot, as cheat devices are
+/// physically inserted between the console and its cartridge.
 #[derive(Clone, Debug, Default)]
-pub struct Slot(Shared<Option<Cartridge>>);
+pub struct Slot {
+    /// Inserted cartridge.
+    cart: Shared<Option<Cartridge>>,
+    /// Active cheat codes.
+    cheat: Shared<Vec<Code>>,
+}
 
 impl Slot {
     /// Constructs a new, empty `Slot`.
@@ -40,38 +53,147 @@ impl Slot {
     /// Gets the inserted cartridge, if any.
     #[must_use]
     pub fn get(&self) -> Option<Cartridge> {
-        self.0.borrow().clone()
+        self.cart.borrow().clone()
     }
 
     /// Inserts a cartridge into the slot.
     pub fn insert(&mut self, cart: Cartridge) {
-        *self.0.borrow_mut() = Some(cart);
+        *self.cart.borrow_mut() = Some(cart);
     }
 
     /// Ejects the inserted cartridge, if any.
     pub fn eject(&mut self) -> Option<Cartridge> {
-        self.0.borrow_mut().take()
+        self.cart.borrow_mut().take()
+    }
+
+    /// Gets the active cheat codes.
+    #[must_use]
+    pub fn cheats(&self) -> Vec<Code> {
+        self.cheat.borrow().clone()
+    }
+
+    /// Activates a cheat code.
+    pub fn add_cheat(&mut self, code: Code) {
+        self.cheat.borrow_mut().push(code);
+    }
+
+    /// Deactivates all cheat codes.
+    pub fn clear_cheats(&mut self) {
+        self.cheat.borrow_mut().clear();
+    }
+
+    /// Borrows the active cheat codes.
+    ///
+    /// Unlike [`cheats`](Self::cheats), the codes are not cloned, such that
+    /// they may be applied by the console once per frame.
+    #[must_use]
+    pub fn codes(&self) -> Ref<'_, [Code]> {
+        Ref::map(self.cheat.borrow(), Vec::as_slice)
+    }
+
+    /// Gets the ROM bank currently mapped by the inserted cartridge into the
+    /// switchable region (`$4000..=$7FFF`), if any.
+    #[must_use]
+    pub fn bank(&self) -> Option<usize> {
+        self.cart.borrow().as_ref().map(Cartridge::bank)
+    }
+
+    /// Gets the strength of the inserted cartridge's rumble motor.
+    #[must_use]
+    pub fn rumble(&self) -> f32 {
+        self.cart.borrow().as_ref().map_or(0., Cartridge::rumble)
+    }
+
+    /// Checks if the inserted cartridge's infrared LED is emitting light.
+    #[must_use]
+    pub fn infrared(&self) -> bool {
+        self.cart.borrow().as_ref().is_some_and(Cartridge::infrared)
+    }
+
+    /// Signals whether light is reaching the inserted cartridge's infrared
+    /// receiver.
+    pub fn set_infrared(&mut self, light: bool) {
+        if let Some(cart) = self.cart.borrow_mut().as_mut() {
+            cart.set_infrared(light);
+        }
+    }
+
+    /// Tilts the inserted cartridge's accelerometer.
+    pub fn tilt(&mut self, x: f32, y: f32) {
+        if let Some(cart) = self.cart.borrow_mut().as_mut() {
+            cart.tilt(x, y);
+        }
+    }
+
+    /// Connects an image source to the inserted cartridge's camera.
+    pub fn set_camera(&mut self, src: Box<dyn ImageSource>) {
+        if let Some(cart) = self.cart.borrow_mut().as_mut() {
+            cart.set_camera(src);
+        }
     }
 }
 
 impl Block for Slot {
+    fn ready(&self) -> bool {
+        self.cart.borrow().as_ref().is_some_and(Block::ready)
+    }
+
+    fn cycle(&mut self) {
+        if let Some(cart) = self.cart.borrow_mut().as_mut() {
+            cart.cycle();
+        }
+    }
+
     fn reset(&mut self) {
-        if let Some(cart) = self.0.borrow_mut().as_mut() {
+        if let Some(cart) = self.cart.borrow_mut().as_mut() {
             cart.reset();
         }
     }
 }
 
+impl State for Slot {
+    fn save(&self, out: &mut Writer) {
+        let cart = self.cart.borrow();
+        out.save(&cart.is_some());
+        if let Some(cart) = cart.as_ref() {
+            cart.save(out);
+        }
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut some = bool::default();
+        inp.load(&mut some)?;
+        match (self.cart.borrow_mut().as_mut(), some) {
+            (Some(cart), true) => cart.restore(inp),
+            (None, false) => Ok(()),
+            _ => Err(state::Error::Invalid("Cartridge")),
+        }
+    }
+}
+
 impl Memory for Slot {
     fn read(&self, addr: u16) -> mem::Result<u8> {
-        self.0
+        let data = self
+            .cart
             .borrow()
             .as_ref()
-            .map_or(Err(mem::Error::Range), |cart| cart.read(addr))
+            .map_or(Err(mem::Error::Range), |cart| cart.read(addr))?;
+        // Patch ROM reads with Game Genie codes
+        let cheat = self.cheat.borrow();
+        if addr > 0x7fff || cheat.is_empty() {
+            return Ok(data);
+        }
+        Ok(cheat
+            .iter()
+            .find_map(|code| match code {
+                Code::Genie(code) => code.patch(addr, data),
+                Code::Shark(_) => None,
+            })
+            .unwrap_or(data))
     }
 
     fn write(&mut self, addr: u16, data: u8) -> mem::Result<()> {
-        self.0
+        self.cart
             .borrow_mut()
             .as_mut()
             .map_or(Err(mem::Error::Range), |cart| cart.write(addr, data))
@@ -162,8 +284,98 @@ impl Cartridge {
         &self.head
     }
 
+    /// Borrows the contents of the cartridge's RAM.
+    ///
+    /// # Note
+    ///
+    /// As cartridges share their hardware when cloned, the returned RAM will
+    /// also be visible to any emulator the cartridge is inserted into.
+    #[must_use]
+    pub fn sram(&self) -> RefMut<'_, [u8]> {
+        self.chip.sram()
+    }
+
+    /// Borrows the live registers of the cartridge's real-time clock, if any.
+    ///
+    /// Registers are ordered as seconds, minutes, hours, then the lower and
+    /// upper bytes of the day counter.
+    #[must_use]
+    pub fn rtc(&self) -> Option<RefMut<'_, [u8]>> {
+        self.chip.rtc()
+    }
+
+    /// Gets the strength of the cartridge's rumble motor.
+    ///
+    /// See [`Rumble::rumble`](crate::api::rumble::Rumble::rumble) for details.
+    #[must_use]
+    pub fn rumble(&self) -> f32 {
+        self.chip.rumble()
+    }
+
+    /// Checks if the cartridge's infrared LED is emitting light.
+    ///
+    /// See [`Infrared::emit`](crate::api::infrared::Infrared::emit) for
+    /// details.
+    #[must_use]
+    pub fn infrared(&self) -> bool {
+        self.chip.infrared()
+    }
+
+    /// Signals whether light is reaching the cartridge's infrared receiver.
+    ///
+    /// See [`Infrared::detect`](crate::api::infrared::Infrared::detect) for
+    /// details.
+    pub fn set_infrared(&mut self, light: bool) {
+        self.chip.set_infrared(light);
+    }
+
+    /// Tilts the cartridge's accelerometer.
+    ///
+    /// See [`Motion::tilt`](crate::api::motion::Motion::tilt) for details.
+    pub fn tilt(&mut self, x: f32, y: f32) {
+        self.chip.tilt(x, y);
+    }
+
+    /// Connects an image source to the cartridge's camera.
+    ///
+    /// See [`Camera::set_source`](crate::api::camera::Camera::set_source) for
+    /// details.
+    pub fn set_camera(&mut self, src: Box<dyn ImageSource>) {
+        self.chip.set_camera(src);
+    }
+
+    /// Gets the ROM bank currently mapped into the switchable region
+    /// (`$4000..=$7FFF`).
+    #[must_use]
+    pub fn bank(&self) -> usize {
+        self.chip.bank()
+    }
+
+    /// Checks if the cartridge is wired as an MBC1 multicart (MBC1M).
+    ///
+    /// # Note
+    ///
+    /// Multicarts are detected heuristically upon construction, which can be
+    /// overridden using [`Cartridge::set_multicart`].
+    #[must_use]
+    pub fn multicart(&self) -> bool {
+        self.chip.multicart()
+    }
+
+    /// Overrides the detected MBC1 multicart (MBC1M) wiring.
+    ///
+    /// Has no effect on cartridges without an MBC1.
+    pub fn set_multicart(&mut self, multi: bool) {
+        self.chip.set_multicart(multi);
+    }
+
     /// Flashes data onto the cartridge's RAM.
     ///
+    /// # Note
+    ///
+    /// Cartridges with a real-time clock additionally load its state from a
+    /// trailer following the RAM contents.
+    ///
     /// # Errors
     ///
     /// May generate an I/O error indicating that the operation could not be
@@ -174,6 +386,11 @@ impl Cartridge {
 
     /// Dumps contents of the cartridge's RAM.
     ///
+    /// # Note
+    ///
+    /// Cartridges with a real-time clock additionally dump its state as a
+    /// trailer following the RAM contents.
+    ///
     /// # Errors
     ///
     /// May generate an I/O error indicating that the operation could not be
@@ -184,11 +401,31 @@ impl Cartridge {
 }
 
 impl Block for Cartridge {
+    fn ready(&self) -> bool {
+        self.chip.ready()
+    }
+
+    fn cycle(&mut self) {
+        self.chip.cycle();
+    }
+
     fn reset(&mut self) {
         self.chip.reset();
     }
 }
 
+impl State for Cartridge {
+    // NOTE: Only the cartridge's mutable hardware state is saved. The ROM is
+    //       expected to be provided separately.
+    fn save(&self, out: &mut Writer) {
+        self.chip.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.chip.restore(inp)
+    }
+}
+
 impl Memory for Cartridge {
     fn read(&self, addr: u16) -> mem::Result<u8> {
         self.chip.read(addr)
@@ -0,0 +1,425 @@
+//! Band-limited synthesis.
+//!
+//! Rather than point-sampling the APU's output, each change in its amplitude
+//! is recorded as a band-limited step at the precise (fractional) output
+//! sample in which it occurred, in the manner of [blip_buf]. Output samples are
+//! later produced by integrating these steps, such that their cost scales with
+//! the number of amplitude changes rather than with the number of cycles.
+//!
+//! [blip_buf]: https://github.com/gasman/blip_buf
+
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::sync::LazyLock;
+
+use rugby_arch::state::{self, Reader, State, Writer};
+
+use crate::api::audio::{Capacitor, Capture, Sample};
+
+/// Fractional bits of fixed-point time.
+const FRAC: u32 = 32;
+
+/// Phase resolution of the step kernel (in bits).
+const PHASE: u32 = 6;
+
+/// Width of the step kernel (in output samples).
+const WIDTH: usize = 16;
+
+/// Band-limited impulse kernel, by phase.
+///
+/// Each phase is a windowed sinc, delayed by half the kernel's width and
+/// normalized to unity gain, such that its integral forms an exact step.
+#[expect(clippy::cast_precision_loss)]
+static KERNEL: LazyLock<Vec<[f32; WIDTH]>> = LazyLock::new(|| {
+    (0..1 << PHASE)
+        .map(|phase| {
+            let frac = f64::from(phase) / f64::from(1 << PHASE);
+            let half = (WIDTH / 2) as f64;
+            // Compute windowed sinc
+            let mut taps = [0.; WIDTH];
+            for (idx, tap) in taps.iter_mut().enumerate() {
+                let x = idx as f64 - half - frac;
+                let sinc = if x == 0. {
+                    1.
+                } else {
+                    (PI * x).sin() / (PI * x)
+                };
+                // Blackman window
+                let t = x / half;
+                let window = 0.42 + 0.5 * (PI * t).cos() + 0.08 * (2. * PI * t).cos();
+                *tap = sinc * window;
+            }
+            // Normalize gain
+            let gain = taps.iter().sum::<f64>();
+            #[expect(clippy::cast_possible_truncation)]
+            taps.map(|tap| (tap / gain) as f32)
+        })
+        .collect()
+});
+
+/// Band-limited step buffer.
+///
+/// Clocked once per input cycle, with output samples pulled at the configured
+/// output rate.
+///
+/// # Note
+///
+/// Until an output rate has been [configured](Self::set_rate), the buffer is
+/// disabled and ignores all input.
+#[derive(Debug, Default)]
+pub struct Buffer {
+    /// Output samples per input cycle.
+    ///
+    /// Stored in fixed-point, with [`FRAC`] fractional bits.
+    step: u64,
+    /// Time elapsed since the first buffered sample.
+    ///
+    /// Stored in fixed-point, with [`FRAC`] fractional bits.
+    time: u64,
+    /// Maximum buffered samples.
+    ///
+    /// Should output not be pulled, older samples will be discarded beyond
+    /// this limit.
+    cap: usize,
+    /// Buffered amplitude deltas.
+    buf: VecDeque<Sample>,
+    /// Integrated output amplitude.
+    sum: Sample,
+    /// Current input amplitude.
+    amp: Sample,
+    /// Output high-pass filter.
+    hpf: Capacitor,
+    /// Output filter enable.
+    filter: bool,
+}
+
+impl Buffer {
+    /// Constructs a new `Buffer`.
+    ///
+    /// When [enabled](Self::set_filter), output is filtered by the provided
+    /// high-pass capacitor.
+    #[must_use]
+    pub fn new(hpf: Capacitor) -> Self {
+        Self {
+            hpf,
+            ..Default::default()
+        }
+    }
+
+    /// Configures the input and output sample rates.
+    ///
+    /// Buffered output is discarded. A zero output rate disables the buffer.
+    pub fn set_rate(&mut self, ifrq: u32, ofrq: u32) {
+        self.step = (u64::from(ofrq) << FRAC)
+            .checked_div(u64::from(ifrq))
+            .unwrap_or_default();
+        self.cap = ofrq as usize / 4;
+        self.time = 0;
+        self.buf.clear();
+        self.sum = self.amp;
+        self.hpf.set_rate(ifrq, ofrq);
+    }
+
+    /// Enables or disables the output filter.
+    pub fn set_filter(&mut self, enable: bool) {
+        self.filter = enable;
+    }
+
+    /// Checks if the buffer is enabled.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.step != 0
+    }
+
+    /// Advances time by a single input cycle.
+    pub fn tick(&mut self) {
+        self.time += self.step;
+    }
+
+    /// Updates the input amplitude.
+    ///
+    /// Any change is recorded as a band-limited step at the current time.
+    pub fn update(&mut self, amp: Sample) {
+        if !self.enabled() || amp == self.amp {
+            return;
+        }
+        let delta = amp - self.amp;
+        self.amp = amp;
+
+        // Discard unpulled samples past capacity
+        let pos = self.ready();
+        if pos > self.cap {
+            self.skip(pos - self.cap);
+        }
+
+        // Add step across the kernel's width
+        let pos = self.ready();
+        let phase = (self.time >> (FRAC - PHASE)) as usize & ((1 << PHASE) - 1);
+        if self.buf.len() < pos + WIDTH {
+            self.buf.resize(pos + WIDTH, Sample::default());
+        }
+        for (out, &tap) in self.buf.range_mut(pos..).zip(&KERNEL[phase]) {
+            *out = *out + delta * tap;
+        }
+    }
+
+    /// Pulls ready output samples.
+    ///
+    /// Appends to `out` all samples synthesized prior to the current time.
+    pub fn pull(&mut self, out: &mut Vec<Sample>) {
+        let count = self.ready();
+        out.reserve(count);
+        for _ in 0..count {
+            self.sum = self.sum + self.buf.pop_front().unwrap_or_default();
+            out.push(if self.filter {
+                self.hpf.filter(self.sum)
+            } else {
+                self.sum
+            });
+        }
+        self.time -= (count as u64) << FRAC;
+    }
+
+    /// Number of output samples ready to be pulled.
+    fn ready(&self) -> usize {
+        usize::try_from(self.time >> FRAC).unwrap_or(usize::MAX)
+    }
+
+    /// Discards ready output samples.
+    fn skip(&mut self, count: usize) {
+        for _ in 0..count {
+            self.sum = self.sum + self.buf.pop_front().unwrap_or_default();
+        }
+        self.time -= (count as u64) << FRAC;
+    }
+}
+
+impl State for Buffer {
+    // NOTE: Buffered output is transient, and is discarded whenever the rate is
+    //       configured. Only the output capacitor's charge is saved.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.hpf);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.hpf)?;
+        Ok(())
+    }
+}
+
+/// Band-limited capture buffers.
+///
+/// Records the output alongside each channel's contribution to it, such that
+/// they may be captured independently of playback.
+#[derive(Debug, Default)]
+pub struct Recorder {
+    /// Mixed output.
+    mix: Buffer,
+    /// Channel outputs.
+    chs: [Buffer; 4],
+}
+
+impl Recorder {
+    /// Constructs a new `Recorder`.
+    ///
+    /// When [enabled](Self::set_filter), mixed output is filtered by the
+    /// provided high-pass capacitor.
+    #[must_use]
+    pub fn new(hpf: Capacitor) -> Self {
+        Self {
+            mix: Buffer::new(hpf),
+            ..Default::default()
+        }
+    }
+
+    /// Configures the input and output sample rates.
+    ///
+    /// Buffered output is discarded. A zero output rate disables the recorder.
+    pub fn set_rate(&mut self, ifrq: u32, ofrq: u32) {
+        self.mix.set_rate(ifrq, ofrq);
+        for buf in &mut self.chs {
+            buf.set_rate(ifrq, ofrq);
+        }
+    }
+
+    /// Enables or disables the mixed output filter.
+    ///
+    /// Channel outputs are never filtered.
+    pub fn set_filter(&mut self, enable: bool) {
+        self.mix.set_filter(enable);
+    }
+
+    /// Checks if the recorder is enabled.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.mix.enabled()
+    }
+
+    /// Advances time by a single input cycle.
+    pub fn tick(&mut self) {
+        self.mix.tick();
+        for buf in &mut self.chs {
+            buf.tick();
+        }
+    }
+
+    /// Updates the input amplitudes.
+    pub fn update(&mut self, mix: Sample, chs: [Sample; 4]) {
+        self.mix.update(mix);
+        for (buf, amp) in self.chs.iter_mut().zip(chs) {
+            buf.update(amp);
+        }
+    }
+
+    /// Pulls ready output samples.
+    ///
+    /// Appends to `out` all samples synthesized prior to the current time.
+    pub fn pull(&mut self, out: &mut Capture) {
+        self.mix.pull(&mut out.mix);
+        for (buf, out) in self.chs.iter_mut().zip(&mut out.chs) {
+            buf.pull(out);
+        }
+    }
+}
+
+impl State for Recorder {
+    // NOTE: Channel outputs are never filtered, so have no charge to save.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.mix);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.mix)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Input sample rate.
+    const IFRQ: u32 = 4_194_304;
+
+    /// Output sample rate.
+    const OFRQ: u32 = 48_000;
+
+    fn setup() -> Buffer {
+        let mut buf = Buffer::default();
+        buf.set_rate(IFRQ, OFRQ);
+        buf
+    }
+
+    #[test]
+    fn disabled_works() {
+        let mut buf = Buffer::default();
+        buf.update(Sample::from((1., 1.)));
+        (0..IFRQ).for_each(|_| buf.tick());
+
+        let mut out = Vec::new();
+        buf.pull(&mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn rate_works() {
+        let mut buf = setup();
+        (0..IFRQ).for_each(|_| buf.tick());
+
+        let mut out = Vec::new();
+        buf.pull(&mut out);
+        assert!(out.len().abs_diff(OFRQ as usize) <= 1);
+    }
+
+    #[test]
+    fn step_works() {
+        let mut buf = setup();
+        (0..1000).for_each(|_| buf.tick());
+        buf.update(Sample::from((1., -0.5)));
+        (0..10_000).for_each(|_| buf.tick());
+
+        let mut out = Vec::new();
+        buf.pull(&mut out);
+        // Silent before the step
+        assert_eq!(out[0], Sample::default());
+        // Settled after the step
+        let last = out.last().copied().unwrap();
+        assert!((last.lt - 1.).abs() < 1e-4);
+        assert!((last.rt + 0.5).abs() < 1e-4);
+        // Rises gradually
+        assert!(out.iter().any(|sample| 0.1 < sample.lt && sample.lt < 0.9));
+    }
+
+    #[test]
+    fn filter_works() {
+        let mut buf = Buffer::new(Capacitor::DMGC);
+        buf.set_rate(IFRQ, OFRQ);
+        buf.set_filter(true);
+        buf.update(Sample::from((1., 1.)));
+        (0..IFRQ / 8).for_each(|_| buf.tick());
+
+        let mut out = Vec::new();
+        buf.pull(&mut out);
+        // Offset decays over time
+        let peak = out.iter().map(|sample| sample.lt).fold(0., f32::max);
+        let last = out.last().copied().unwrap();
+        assert!(peak > 0.9);
+        assert!(last.lt < 0.01);
+    }
+
+    #[test]
+    fn filter_state_works() {
+        let mut buf = Buffer::new(Capacitor::DMGC);
+        buf.set_rate(IFRQ, OFRQ);
+        buf.set_filter(true);
+        buf.update(Sample::from((1., 1.)));
+        (0..IFRQ / 8).for_each(|_| buf.tick());
+        buf.pull(&mut Vec::new());
+
+        // Restore the charge into a fresh buffer
+        let mut out = Writer::new();
+        out.save(&buf);
+        let out = out.finish();
+        let mut new = Buffer::new(Capacitor::DMGC);
+        new.set_rate(IFRQ, OFRQ);
+        Reader::new(&out).load(&mut new).unwrap();
+
+        // Charged capacitors filter identically
+        let inp = Sample::from((1., 1.));
+        let out = buf.hpf.filter(inp);
+        assert_eq!(new.hpf.filter(inp), out);
+        assert_ne!(Buffer::default().hpf.filter(inp), out);
+    }
+
+    #[test]
+    fn capacity_works() {
+        let mut buf = setup();
+        (0..IFRQ).for_each(|_| buf.tick());
+        buf.update(Sample::from((1., 1.)));
+
+        let mut out = Vec::new();
+        buf.pull(&mut out);
+        assert!(out.len() <= OFRQ as usize / 4);
+    }
+
+    #[test]
+    fn recorder_works() {
+        let mut rec = Recorder::default();
+        rec.set_rate(IFRQ, OFRQ);
+        let chs = [(0.25, 0.), (0., 0.25), (0.125, 0.125), (0., 0.)].map(Sample::from);
+        rec.update(chs.into_iter().sum(), chs);
+        (0..IFRQ / 8).for_each(|_| rec.tick());
+
+        let mut out = Capture::default();
+        rec.pull(&mut out);
+        // Channels are captured alongside the mix
+        assert!(out.chs.iter().all(|ch| ch.len() == out.mix.len()));
+        // Channels settle to their contribution
+        let last = |out: &[Sample]| out.last().copied().unwrap();
+        for (out, amp) in out.chs.iter().zip(chs) {
+            assert!((last(out).lt - amp.lt).abs() < 1e-4);
+            assert!((last(out).rt - amp.rt).abs() < 1e-4);
+        }
+        assert!((last(&out.mix).lt - 0.375).abs() < 1e-4);
+    }
+}
@@ -3,6 +3,7 @@
 use std::ops::BitAnd;
 
 use log::{debug, trace};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 pub use super::{Nr10, Nr11, Nr12, Nr13, Nr14};
@@ -71,6 +72,63 @@ pub struct Envelope {
     pub(super) vol: u8,
 }
 
+impl State for Channel {
+    // NOTE: Registers are shared with the APU, and are saved there.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.out).save(&self.etc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.out)?.load(&mut self.etc)?;
+        Ok(())
+    }
+}
+
+impl State for Internal {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena)
+            .save(&self.clk)
+            .save(&self.swp)
+            .save(&self.len)
+            .save(&self.env)
+            .save(&self.pos);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena)?
+            .load(&mut self.clk)?
+            .load(&mut self.swp)?
+            .load(&mut self.len)?
+            .load(&mut self.env)?
+            .load(&mut self.pos)?;
+        Ok(())
+    }
+}
+
+impl State for Sweep {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena).save(&self.frq).save(&self.len);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena)?
+            .load(&mut self.frq)?
+            .load(&mut self.len)?;
+        Ok(())
+    }
+}
+
+impl State for Envelope {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.len).save(&self.vol);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.len)?.load(&mut self.vol)?;
+        Ok(())
+    }
+}
+
 impl Channel {
     /// Triggers this channel.
     pub fn trigger(&mut self) {
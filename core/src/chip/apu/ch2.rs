@@ -3,6 +3,7 @@
 use std::ops::BitAnd;
 
 use log::{debug, trace};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 pub use super::ch1::WAVE;
@@ -37,6 +38,37 @@ pub struct Internal {
 /// Channel 2 envelope.
 pub type Envelope = super::ch1::Envelope;
 
+impl State for Channel {
+    // NOTE: Registers are shared with the APU, and are saved there.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.out).save(&self.etc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.out)?.load(&mut self.etc)?;
+        Ok(())
+    }
+}
+
+impl State for Internal {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena)
+            .save(&self.clk)
+            .save(&self.len)
+            .save(&self.env)
+            .save(&self.pos);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena)?
+            .load(&mut self.clk)?
+            .load(&mut self.len)?
+            .load(&mut self.env)?
+            .load(&mut self.pos)?;
+        Ok(())
+    }
+}
+
 impl Channel {
     /// Triggers this channel.
     pub fn trigger(&mut self) {
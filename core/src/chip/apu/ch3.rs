@@ -4,6 +4,7 @@ use std::ops::BitAnd;
 
 use log::{debug, trace};
 use rugby_arch::mem::Memory;
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::Bank;
@@ -35,6 +36,35 @@ pub struct Internal {
     idx: u8,
 }
 
+impl State for Channel {
+    // NOTE: Registers and wave RAM are shared with the APU, and are saved there.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.out).save(&self.etc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.out)?.load(&mut self.etc)?;
+        Ok(())
+    }
+}
+
+impl State for Internal {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena)
+            .save(&self.clk)
+            .save(&self.len)
+            .save(&self.idx);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena)?
+            .load(&mut self.clk)?
+            .load(&mut self.len)?
+            .load(&mut self.idx)?;
+        Ok(())
+    }
+}
+
 impl Channel {
     /// Triggers this channel.
     pub fn trigger(&mut self) {
@@ -4,6 +4,7 @@ use std::ops::BitAnd;
 
 use bitfield_struct::bitfield;
 use log::{debug, trace};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 pub use super::reg::{Nr41, Nr42, Nr43, Nr44};
@@ -66,6 +67,37 @@ impl Block for Random {
     }
 }
 
+impl State for Channel {
+    // NOTE: Registers are shared with the APU, and are saved there.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.out).save(&self.etc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.out)?.load(&mut self.etc)?;
+        Ok(())
+    }
+}
+
+impl State for Internal {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.ena)
+            .save(&self.clk)
+            .save(&self.len)
+            .save(&self.env)
+            .save(&self.rng);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.ena)?
+            .load(&mut self.clk)?
+            .load(&mut self.len)?
+            .load(&mut self.env)?
+            .load(&mut self.rng)?;
+        Ok(())
+    }
+}
+
 impl Channel {
     /// Triggers this channel.
     pub fn trigger(&mut self) {
@@ -293,3 +325,5 @@ impl File {
         }
     }
 }
+
+bitfield_state!(Random);
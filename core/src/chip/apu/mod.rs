@@ -2,13 +2,15 @@
 
 use rugby_arch::mem::{Memory, Ram};
 use rugby_arch::reg::{Port, Register};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::tma;
-use crate::api::audio::{Audio as Api, Chiptune, Sample};
+use crate::api::audio::{Audio as Api, Capture, Chiptune, Mixer, Sample};
 
 mod reg;
 
+pub mod blip;
 pub mod ch1;
 pub mod ch2;
 pub mod ch3;
@@ -31,13 +33,17 @@ pub type Wave = Ram<[u8; 0x0010]>;
 /// channel's features should be clocked.
 ///
 /// It is driven by the [timer](super::tma), specifically the falling edge of
-/// the [divider register](tma::reg::Div)'s bit 4.
+/// the [divider register](tma::reg::Div)'s bit 4 (bit 5 in double speed mode).
 #[derive(Debug)]
 pub struct Sequencer {
     /// `DIV[4]` last measured value.
     ///
     /// Used to detect the falling edge.
     pub bit: bool,
+    /// Double speed mode.
+    ///
+    /// As the divider ticks twice as fast, `DIV[5]` is used instead.
+    pub fast: bool,
     /// `DIV-APU` sequence value.
     ///
     /// This will tick at approx. 512 Hz, with extra ticks potentially
@@ -54,18 +60,40 @@ impl Sequencer {
     ///
     /// APU will clock a cycle whenever the masked bit falls.
     const MASK: u8 = 0b0001_0000;
+
+    /// Measures the clocking bit.
+    fn next(&self) -> bool {
+        let mask = if self.fast {
+            Self::MASK << 1
+        } else {
+            Self::MASK
+        };
+        self.div.load() & mask != 0
+    }
+}
+
+impl State for Sequencer {
+    // NOTE: The clock driver is owned by the timer, and is saved there.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.bit).save(&self.clk);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.bit)?.load(&mut self.clk)?;
+        Ok(())
+    }
 }
 
 impl Block for Sequencer {
     fn ready(&self) -> bool {
         // Check for falling edge
-        let next = self.div.load() & Self::MASK != 0;
+        let next = self.next();
         self.bit && !next
     }
 
     fn cycle(&mut self) {
         // Fetch current clock bit
-        let next = self.div.load() & Self::MASK != 0;
+        let next = self.next();
         // Check for falling edge
         if self.bit && !next {
             // Only tick the internal clock value on the falling edge of the
@@ -147,16 +175,191 @@ pub struct Apu {
     pub seq: Sequencer,
     /// Audio internals.
     pub etc: Internal,
+    /// Band-limited output.
+    pub out: blip::Buffer,
+    /// Band-limited capture.
+    pub rec: blip::Recorder,
+    /// Output mixer.
+    pub mix: Mixer,
+}
+
+impl Apu {
+    /// Clock frequency.
+    ///
+    /// Used as the input rate of band-limited output.
+    const CLOCK: u32 = 4_194_304;
+
+    /// Mixes the analog output stage.
+    ///
+    /// Only the pre-filter level is produced; the output capacitor is applied
+    /// by the band-limited [buffer](blip::Buffer).
+    fn analog(&self) -> Sample {
+        // Extract control values
+        let nr50 = *self.reg.nr50.borrow();
+        let nr51 = *self.reg.nr51.borrow();
+        let nr12 = *self.reg.nr12.borrow();
+        let nr22 = *self.reg.nr22.borrow();
+        let nr30 = *self.reg.nr30.borrow();
+        let nr42 = *self.reg.nr42.borrow();
+
+        // Convert each channel's level through its DAC
+        //
+        // While a DAC is enabled, a silent channel is received as a digital
+        // zero, which is converted to the lowest analog level. Only once
+        // disabled does a DAC's output return to zero.
+        let dac = |dac: bool, ready: bool, out: f32| match (dac, ready) {
+            (false, _) => 0.,
+            (true, false) => -1.,
+            (true, true) => out,
+        };
+        let ch1 = dac(
+            nr12.ivol() > 0 || nr12.sign(),
+            self.ch1.ready(),
+            self.ch1.out,
+        );
+        let ch2 = dac(
+            nr22.ivol() > 0 || nr22.sign(),
+            self.ch2.ready(),
+            self.ch2.out,
+        );
+        let ch3 = dac(nr30.dac(), self.ch3.ready(), self.ch3.out);
+        let ch4 = dac(
+            nr42.ivol() > 0 || nr42.sign(),
+            self.ch4.ready(),
+            self.ch4.out,
+        );
+
+        // Route each channel to either side
+        //
+        // Channels are mixed with equal weight, such that the full mix spans
+        // the same range as a single channel.
+        let pan = |lt: bool, rt: bool, out: f32| Sample {
+            lt: if lt { out } else { 0. },
+            rt: if rt { out } else { 0. },
+        };
+        let mix = [
+            pan(nr51.ch1_l(), nr51.ch1_r(), ch1),
+            pan(nr51.ch2_l(), nr51.ch2_r(), ch2),
+            pan(nr51.ch3_l(), nr51.ch3_r(), ch3),
+            pan(nr51.ch4_l(), nr51.ch4_r(), ch4),
+        ]
+        .into_iter()
+        .sum::<Sample>()
+            / 4.;
+
+        // Apply master volume
+        //
+        // The amplifier scales by one more than its volume, and so never
+        // mutes its input.
+        //
+        // NOTE: Cartridge audio (VIN) is not modelled, as no supported
+        //       cartridge drives it. Its NR50 routing is thus ignored.
+        mix * Sample {
+            lt: f32::from(nr50.vol_l() + 1) / 8.,
+            rt: f32::from(nr50.vol_r() + 1) / 8.,
+        }
+    }
+
+    /// Packs the controls of the output stage.
+    fn controls(&self) -> [u8; 3] {
+        let dacs = [
+            self.reg.nr12.borrow().ivol() > 0 || self.reg.nr12.borrow().sign(),
+            self.reg.nr22.borrow().ivol() > 0 || self.reg.nr22.borrow().sign(),
+            self.reg.nr30.borrow().dac(),
+            self.reg.nr42.borrow().ivol() > 0 || self.reg.nr42.borrow().sign(),
+        ]
+        .into_iter()
+        .enumerate()
+        .fold(0, |dacs, (idx, dac)| dacs | u8::from(dac) << idx);
+        [self.reg.nr50.load(), self.reg.nr51.load(), dacs]
+    }
+
+    /// Emits changes in amplitude to the band-limited buffers.
+    ///
+    /// Captured channels are always mixed digitally, as the analog output
+    /// stage cannot be separated by channel.
+    fn emit(&mut self) {
+        let tune = self.sample();
+        let chs = tune.split();
+        let amp = match self.mix {
+            Mixer::Digital => tune.mix(),
+            Mixer::Analog => self.analog(),
+        };
+        self.out.update(amp);
+        self.rec.update(amp, chs);
+    }
 }
 
 /// Audio internals.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Internal {
     /// Master clock divider.
     ///
     /// While the APU is externally clocked at 4 MiHz, channels are clocked at
     /// either 1 MiHz (CH1, CH2, CH4) or 2 MiHz (CH3).
     div: u8,
+    /// Output stage controls.
+    ///
+    /// Packs `NR50`, `NR51`, and each channel's DAC enable, such that writes
+    /// affecting the output can be detected.
+    ctl: [u8; 3],
+    /// Output changed.
+    ///
+    /// Set whenever an input to the output stage changes, until the change is
+    /// emitted to the band-limited buffers.
+    edit: bool,
+}
+
+impl Default for Internal {
+    fn default() -> Self {
+        Self {
+            div: u8::default(),
+            ctl: <[u8; 3]>::default(),
+            // Emit initial output
+            edit: true,
+        }
+    }
+}
+
+impl State for Apu {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg)
+            .save(&self.mem)
+            .save(&self.ch1)
+            .save(&self.ch2)
+            .save(&self.ch3)
+            .save(&self.ch4)
+            .save(&self.seq)
+            .save(&self.etc)
+            .save(&self.out)
+            .save(&self.rec);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.mem)?
+            .load(&mut self.ch1)?
+            .load(&mut self.ch2)?
+            .load(&mut self.ch3)?
+            .load(&mut self.ch4)?
+            .load(&mut self.seq)?
+            .load(&mut self.etc)?
+            .load(&mut self.out)?
+            .load(&mut self.rec)?;
+        Ok(())
+    }
+}
+
+impl State for Internal {
+    fn save(&self, out: &mut Writer) {
+        self.div.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        // Emit restored output
+        self.edit = true;
+        self.div.restore(inp)
+    }
 }
 
 impl Api for Apu {
@@ -222,6 +425,29 @@ impl Api for Apu {
             },
         }
     }
+
+    fn set_rate(&mut self, rate: u32) {
+        self.out.set_rate(Self::CLOCK, rate);
+    }
+
+    fn pull(&mut self, out: &mut Vec<Sample>) {
+        self.out.pull(out);
+    }
+
+    fn set_mixer(&mut self, mix: Mixer) {
+        self.mix = mix;
+        self.etc.edit = true;
+        self.out.set_filter(mix == Mixer::Analog);
+        self.rec.set_filter(mix == Mixer::Analog);
+    }
+
+    fn set_capture(&mut self, rate: u32) {
+        self.rec.set_rate(Self::CLOCK, rate);
+    }
+
+    fn capture(&mut self, out: &mut Capture) {
+        self.rec.pull(out);
+    }
 }
 
 impl Block for Apu {
@@ -266,6 +492,9 @@ impl Block for Apu {
 
             // Cycle channels
             //
+            // Triggers and envelope updates take effect as a channel is next
+            // clocked, and so are observed through its output.
+            let outs = [self.ch1.out, self.ch2.out, self.ch3.out, self.ch4.out];
             // Channel 1: 1 MiHz
             if self.ch1.ready() && self.etc.div.is_multiple_of(4) {
                 self.ch1.cycle();
@@ -282,6 +511,9 @@ impl Block for Apu {
             if self.ch4.ready() && self.etc.div.is_multiple_of(4) {
                 self.ch4.cycle();
             }
+            #[expect(clippy::float_cmp)]
+            let edit = outs != [self.ch1.out, self.ch2.out, self.ch3.out, self.ch4.out];
+            self.etc.edit |= edit;
         } else {
             // When disabled, all registers are reset and in read-only mode. We
             // can emulate this by constantly resetting these components.
@@ -298,19 +530,46 @@ impl Block for Apu {
             self.seq.clk = 0;
         }
 
+        // Update channel status
+        //
+        // As the analog output stage converts silent channels differently,
+        // changes in status also affect the output.
+        {
+            let mut nr52 = self.reg.nr52.borrow_mut();
+            let prev = [nr52.ch1_on(), nr52.ch2_on(), nr52.ch3_on(), nr52.ch4_on()];
+            nr52.set_ch1_on(self.ch1.ready());
+            nr52.set_ch2_on(self.ch2.ready());
+            nr52.set_ch3_on(self.ch3.ready());
+            nr52.set_ch4_on(self.ch4.ready());
+            let next = [nr52.ch1_on(), nr52.ch2_on(), nr52.ch3_on(), nr52.ch4_on()];
+            self.etc.edit |= prev != next;
+        }
+
+        // Synthesize output
+        //
+        // Changes in amplitude are recorded as band-limited steps, emitted
+        // only as the output stage's inputs change.
+        if self.out.enabled() || self.rec.enabled() {
+            // Detect control register writes
+            let ctl = self.controls();
+            if ctl != self.etc.ctl {
+                self.etc.ctl = ctl;
+                self.etc.edit = true;
+            }
+            // Emit changes
+            if std::mem::take(&mut self.etc.edit) {
+                self.emit();
+            }
+            self.out.tick();
+            self.rec.tick();
+        }
+
         // Cycle frame sequencer
         //
         // This ensures we can detect falling edges as they occur. The frame
         // sequencer is always cycled, even while the APU is disabled.
         self.seq.cycle();
 
-        // Update channel status
-        let mut nr52 = self.reg.nr52.borrow_mut();
-        nr52.set_ch1_on(self.ch1.ready());
-        nr52.set_ch2_on(self.ch2.ready());
-        nr52.set_ch3_on(self.ch3.ready());
-        nr52.set_ch4_on(self.ch4.ready());
-
         // Cycle internal clock divider
         self.etc.div = self.etc.div.wrapping_add(1);
     }
@@ -393,6 +652,16 @@ pub struct Bank {
     pub wave: Shared<Wave>,
 }
 
+impl State for Bank {
+    fn save(&self, out: &mut Writer) {
+        self.wave.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.wave.restore(inp)
+    }
+}
+
 impl Default for Bank {
     fn default() -> Self {
         Self {
@@ -496,6 +765,57 @@ pub struct File {
     pub nr44: Shared<Nr44>,
 }
 
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.nr52)
+            .save(&self.nr51)
+            .save(&self.nr50)
+            .save(&self.nr10)
+            .save(&self.nr11)
+            .save(&self.nr12)
+            .save(&self.nr13)
+            .save(&self.nr14)
+            .save(&self.nr21)
+            .save(&self.nr22)
+            .save(&self.nr23)
+            .save(&self.nr24)
+            .save(&self.nr30)
+            .save(&self.nr31)
+            .save(&self.nr32)
+            .save(&self.nr33)
+            .save(&self.nr34)
+            .save(&self.nr41)
+            .save(&self.nr42)
+            .save(&self.nr43)
+            .save(&self.nr44);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.nr52)?
+            .load(&mut self.nr51)?
+            .load(&mut self.nr50)?
+            .load(&mut self.nr10)?
+            .load(&mut self.nr11)?
+            .load(&mut self.nr12)?
+            .load(&mut self.nr13)?
+            .load(&mut self.nr14)?
+            .load(&mut self.nr21)?
+            .load(&mut self.nr22)?
+            .load(&mut self.nr23)?
+            .load(&mut self.nr24)?
+            .load(&mut self.nr30)?
+            .load(&mut self.nr31)?
+            .load(&mut self.nr32)?
+            .load(&mut self.nr33)?
+            .load(&mut self.nr34)?
+            .load(&mut self.nr41)?
+            .load(&mut self.nr42)?
+            .load(&mut self.nr43)?
+            .load(&mut self.nr44)?;
+        Ok(())
+    }
+}
+
 impl File {
     /// Disables the registers.
     ///
@@ -560,3 +880,139 @@ impl Block for File {
         self.nr44.take();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::audio::Capacitor;
+
+    /// Output sample rate.
+    const RATE: u32 = 48_000;
+
+    fn setup() -> Apu {
+        let mem = Bank::default();
+        let reg = File::default();
+        let mut apu = Apu {
+            ch1: ch1::Channel {
+                out: f32::default(),
+                reg: ch1::File::with(&reg),
+                etc: ch1::Internal::default(),
+            },
+            ch2: ch2::Channel {
+                out: f32::default(),
+                reg: ch2::File::with(&reg),
+                etc: ch2::Internal::default(),
+            },
+            ch3: ch3::Channel {
+                out: f32::default(),
+                reg: ch3::File::with(&reg),
+                mem: mem.clone(),
+                etc: ch3::Internal::default(),
+            },
+            ch4: ch4::Channel {
+                out: f32::default(),
+                reg: ch4::File::with(&reg),
+                etc: ch4::Internal::default(),
+            },
+            reg,
+            mem,
+            seq: Sequencer {
+                bit: bool::default(),
+                fast: bool::default(),
+                clk: u8::default(),
+                div: Shared::new(tma::reg::Div::default()),
+            },
+            etc: Internal::default(),
+            out: blip::Buffer::new(Capacitor::DMGC),
+            rec: blip::Recorder::new(Capacitor::DMGC),
+            mix: Mixer::default(),
+        };
+        apu.set_rate(RATE);
+        // Power on, routing all channels to both sides at full volume
+        apu.reg.write(0xff26, 0x80).unwrap();
+        apu.reg.write(0xff25, 0xff).unwrap();
+        apu.reg.write(0xff24, 0x77).unwrap();
+        apu
+    }
+
+    fn run(apu: &mut Apu, cycles: u32) -> Vec<Sample> {
+        (0..cycles).for_each(|_| apu.cycle());
+        let mut out = Vec::new();
+        apu.pull(&mut out);
+        out
+    }
+
+    #[test]
+    fn analog_works() {
+        let mut apu = setup();
+        // Enable each DAC, triggering each channel
+        for (dac, ctl) in [
+            (0xff12, 0xff14),
+            (0xff17, 0xff19),
+            (0xff1a, 0xff1e),
+            (0xff21, 0xff23),
+        ] {
+            apu.reg.write(dac, 0xf0).unwrap();
+            apu.reg.write(ctl, 0x80).unwrap();
+        }
+        // Full-scale channels mix to full scale
+        apu.ch1.out = 1.;
+        apu.ch2.out = 1.;
+        apu.ch3.out = 1.;
+        apu.ch4.out = 1.;
+        assert_eq!(apu.analog(), Sample::from((1., 1.)));
+        // Master volume never mutes
+        apu.reg.write(0xff24, 0x00).unwrap();
+        assert_eq!(apu.analog(), Sample::from((0.125, 0.125)));
+        // Unrouted channels are silent
+        apu.reg.write(0xff24, 0x77).unwrap();
+        apu.reg.write(0xff25, 0x0f).unwrap();
+        assert_eq!(apu.analog(), Sample::from((0., 1.)));
+    }
+
+    #[test]
+    fn analog_volume_works() {
+        let mut apu = setup();
+        // Enable a full-scale channel
+        apu.reg.write(0xff12, 0xf0).unwrap();
+        apu.reg.write(0xff14, 0x80).unwrap();
+        apu.ch1.out = 1.;
+        // Each side is scaled by its own master volume
+        apu.reg.write(0xff24, 0x30).unwrap();
+        assert_eq!(apu.analog(), Sample::from((0.125, 0.03125)));
+        // Cartridge audio routing has no effect
+        apu.reg.write(0xff24, 0x8b).unwrap();
+        assert_eq!(apu.analog(), Sample::from((0.03125, 0.125)));
+    }
+
+    #[test]
+    fn dac_pop_works() {
+        let mut apu = setup();
+        apu.set_mixer(Mixer::Analog);
+        run(&mut apu, Apu::CLOCK / 8);
+        // Enabling a DAC of a silent channel pops
+        apu.reg.write(0xff12, 0xf0).unwrap();
+        let out = run(&mut apu, Apu::CLOCK / 8);
+        let peak = out.iter().map(|sample| sample.lt).fold(0., f32::min);
+        assert!((peak + 0.25).abs() < 0.01);
+        // ... then decays through the output capacitor
+        let last = out.last().copied().unwrap();
+        assert!(last.lt.abs() < 0.01);
+        // Disabling the DAC pops back
+        apu.reg.write(0xff12, 0x00).unwrap();
+        let out = run(&mut apu, Apu::CLOCK / 8);
+        let peak = out.iter().map(|sample| sample.lt).fold(0., f32::max);
+        assert!((peak - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn dac_pop_digital_works() {
+        let mut apu = setup();
+        apu.set_mixer(Mixer::Digital);
+        run(&mut apu, Apu::CLOCK / 8);
+        // Enabling a DAC of a silent channel does not pop
+        apu.reg.write(0xff12, 0xf0).unwrap();
+        let out = run(&mut apu, Apu::CLOCK / 8);
+        assert!(out.iter().all(|sample| *sample == Sample::default()));
+    }
+}
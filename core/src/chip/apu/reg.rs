@@ -871,3 +871,7 @@ impl Register for Nr44 {
         self.0 = value;
     }
 }
+
+bitfield_state!(
+    Nr52, Nr51, Nr50, Nr10, Nr11, Nr12, Nr13, Nr14, Nr30, Nr31, Nr32, Nr33, Nr34, Nr41, Nr43, Nr44,
+);
@@ -3,26 +3,45 @@
 use log::warn;
 use rugby_arch::mem::Memory;
 
-use crate::dmg::bus;
+use crate::{cgb, dmg};
 
 /// Processor bus.
+///
+/// Drives the model-specific memory view through which the processor reaches
+/// the rest of the system.
 #[derive(Debug)]
 pub struct Bus {
     /// Bus view.
-    bus: bus::view::Cpu,
+    bus: View,
 }
 
 impl Bus {
     /// Constructs a new `Bus`.
     #[must_use]
-    pub fn new(bus: bus::view::Cpu) -> Self {
-        Self { bus }
+    pub fn new(bus: impl Into<View>) -> Self {
+        Self { bus: bus.into() }
     }
 
     /// Gets the underlying bus view.
     #[cfg(test)]
-    pub(crate) fn view(&mut self) -> &mut bus::view::Cpu {
-        &mut self.bus
+    pub(crate) fn view(&mut self) -> &mut dyn Memory {
+        match &mut self.bus {
+            View::Dmg(bus) => bus,
+            View::Cgb(bus) => bus,
+        }
+    }
+
+    /// Gets an unprobed view of the bus.
+    ///
+    /// Accesses through this view are not observed by the debugger, so it may
+    /// be used to inspect memory on its behalf.
+    #[cfg(feature = "debug")]
+    #[must_use]
+    pub fn peek(&self) -> &dyn Memory {
+        match &self.bus {
+            View::Dmg(bus) => &bus.view,
+            View::Cgb(bus) => bus,
+        }
     }
 
     /// Read the byte at the given address.
@@ -41,4 +60,76 @@ impl Bus {
             .write(addr, data)
             .inspect_err(|err| warn!("failed to write [${addr:04x}] <- {data:#04x}: {err}"));
     }
+
+    /// Write to the byte at the given address, unprobed.
+    ///
+    /// Unlike [`write`](Self::write), the write is not observed by the debugger
+    /// or profiler, so it may be used by hardware acting outside the processor.
+    pub fn poke(&mut self, addr: u16, data: u8) {
+        let res = match &mut self.bus {
+            #[cfg(feature = "debug")]
+            View::Dmg(bus) => bus.view.write(addr, data),
+            #[cfg(not(feature = "debug"))]
+            View::Dmg(bus) => bus.write(addr, data),
+            View::Cgb(bus) => bus.write(addr, data),
+        };
+        let _ = res.inspect_err(|err| warn!("failed to poke [${addr:04x}] <- {data:#04x}: {err}"));
+    }
+}
+
+impl Memory for Bus {
+    fn read(&self, addr: u16) -> rugby_arch::mem::Result<u8> {
+        self.bus.read(addr)
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        self.bus.write(addr, data)
+    }
+}
+
+/// DMG processor view.
+#[cfg(feature = "debug")]
+type Dmg = dmg::bus::view::Probe<dmg::bus::view::Cpu>;
+/// DMG processor view.
+#[cfg(not(feature = "debug"))]
+type Dmg = dmg::bus::view::Cpu;
+
+/// Bus view.
+///
+/// Model-specific views are enumerated, rather than boxed, such that accesses
+/// are statically dispatched.
+#[derive(Debug)]
+pub enum View {
+    /// DMG memory view.
+    Dmg(Dmg),
+    /// CGB memory view.
+    Cgb(cgb::bus::view::Cpu),
+}
+
+impl From<Dmg> for View {
+    fn from(value: Dmg) -> Self {
+        Self::Dmg(value)
+    }
+}
+
+impl From<cgb::bus::view::Cpu> for View {
+    fn from(value: cgb::bus::view::Cpu) -> Self {
+        Self::Cgb(value)
+    }
+}
+
+impl Memory for View {
+    fn read(&self, addr: u16) -> rugby_arch::mem::Result<u8> {
+        match self {
+            View::Dmg(bus) => bus.read(addr),
+            View::Cgb(bus) => bus.read(addr),
+        }
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        match self {
+            View::Dmg(bus) => bus.write(addr, data),
+            View::Cgb(bus) => bus.write(addr, data),
+        }
+    }
 }
@@ -0,0 +1,299 @@
+//! Instruction disassembler.
+//!
+//! Decodes instructions from memory into their full textual representation,
+//! with immediate operands and jump targets resolved.
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use rugby_arch::mem::Memory;
+
+use super::Instruction;
+
+/// Decodes the instruction at an address.
+///
+/// Reads which fail are treated as an open bus, producing `0xff`.
+#[must_use]
+pub fn decode<M: Memory + ?Sized>(mem: &M, addr: u16) -> Disasm {
+    let read = |idx: u16| mem.read(addr.wrapping_add(idx)).unwrap_or(0xff);
+    // Decode the opcode
+    let code = read(0);
+    let (insn, size) = if code == 0xcb {
+        (Instruction::prefix(read(1)), 2)
+    } else {
+        let insn = Instruction::decode(code);
+        (insn, 1 + Operand::size(insn.repr))
+    };
+    // Fetch the operands
+    let mut data = [code, 0, 0];
+    for (idx, byte) in (1..size).zip(&mut data[1..]) {
+        *byte = read(idx);
+    }
+
+    Disasm {
+        addr,
+        data,
+        size,
+        insn,
+    }
+}
+
+/// Disassembles all instructions beginning within a range of addresses.
+///
+/// Instructions are decoded sequentially, such that an instruction beginning
+/// at the end of the range may extend beyond it.
+pub fn disassemble<M: Memory + ?Sized>(
+    mem: &M,
+    range: RangeInclusive<u16>,
+) -> impl Iterator<Item = Disasm> + use<'_, M> {
+    let (start, end) = range.into_inner();
+    let mut next = Some(start).filter(|&addr| addr <= end);
+    std::iter::from_fn(move || {
+        let insn = decode(mem, next?);
+        // Advance until the end of the range (or address space)
+        next = insn.addr.checked_add(insn.size).filter(|&addr| addr <= end);
+        Some(insn)
+    })
+}
+
+/// Disassembled instruction.
+#[derive(Clone, Copy, Debug)]
+pub struct Disasm {
+    addr: u16,
+    data: [u8; 3],
+    size: u16,
+    insn: Instruction,
+}
+
+impl Disasm {
+    /// Gets the instruction's address.
+    #[must_use]
+    pub fn addr(&self) -> u16 {
+        self.addr
+    }
+
+    /// Gets the instruction's encoded bytes.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.data[..self.size.into()]
+    }
+
+    /// Gets the decoded instruction.
+    #[must_use]
+    pub fn insn(&self) -> Instruction {
+        self.insn
+    }
+
+    /// Gets the address of the following instruction.
+    #[must_use]
+    pub fn next(&self) -> u16 {
+        self.addr.wrapping_add(self.size)
+    }
+
+    /// Gets the instruction's immediate operand, if any.
+    #[must_use]
+    pub fn operand(&self) -> Option<Operand> {
+        let [_, lo, hi] = self.data;
+        let word = u16::from_le_bytes([lo, hi]);
+        // Prefixed instructions never have an operand
+        if self.data[0] == 0xcb {
+            return None;
+        }
+        Some(match Operand::find(self.insn.repr)?.1 {
+            "n8" => Operand::Byte(lo),
+            "n16" => Operand::Word(word),
+            "a8" => Operand::High(lo),
+            "a16" => Operand::Addr(word),
+            "e8" => Operand::Rel(lo.cast_signed()),
+            _ => unreachable!(),
+        })
+    }
+
+    /// Gets the instruction's control flow target, if any.
+    ///
+    /// This is only defined for jumps, calls, and restarts to a statically
+    /// known address.
+    #[must_use]
+    pub fn target(&self) -> Option<u16> {
+        let code = self.data[0];
+        match code {
+            // JR
+            0x18 | 0x20 | 0x28 | 0x30 | 0x38 => match self.operand()? {
+                Operand::Rel(off) => Some(self.next().wrapping_add_signed(off.into())),
+                _ => None,
+            },
+            // JP, CALL
+            0xc2 | 0xc3 | 0xc4 | 0xca | 0xcc | 0xcd | 0xd2 | 0xd4 | 0xda | 0xdc => {
+                match self.operand()? {
+                    Operand::Addr(addr) => Some(addr),
+                    _ => None,
+                }
+            }
+            // RST
+            0xc7 | 0xcf | 0xd7 | 0xdf | 0xe7 | 0xef | 0xf7 | 0xff => Some((code & 0x38).into()),
+            _ => None,
+        }
+    }
+}
+
+impl Disasm {
+    /// Formats the instruction, substituting addressed operands with labels.
+    ///
+    /// Labels are looked up by `label`, falling back to the address when none
+    /// is found.
+    pub fn display<F>(&self, label: F) -> impl Display + use<F>
+    where
+        F: Fn(u16) -> Option<String>,
+    {
+        let repr = self.insn.repr;
+        // Substitute the operand placeholder
+        let (Some(op), Some((idx, pat))) = (self.operand(), Operand::find(repr)) else {
+            return repr.to_string();
+        };
+        let (head, tail) = (&repr[..idx], &repr[idx + pat.len()..]);
+        // Look up addressed operands
+        let addr = match op {
+            _ if self.target().is_some() => self.target(),
+            Operand::Word(addr) | Operand::Addr(addr) => Some(addr),
+            Operand::High(addr) => Some(0xff00 | u16::from(addr)),
+            Operand::Byte(_) | Operand::Rel(_) => None,
+        };
+        if let Some(name) = addr.and_then(label) {
+            return format!("{head}{name}{tail}");
+        }
+        match (op, self.target()) {
+            // Resolve relative jumps
+            (_, Some(addr)) => format!("{head}${addr:04x}{tail}"),
+            // Fold sign into an offset from SP
+            (Operand::Rel(off), None) if head.ends_with("+ ") && off < 0 => {
+                let head = head.trim_end_matches("+ ");
+                format!("{head}- ${:02x}{tail}", off.unsigned_abs())
+            }
+            _ => format!("{head}{op}{tail}"),
+        }
+    }
+}
+
+impl Display for Disasm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display(|_| None))
+    }
+}
+
+/// Immediate operand.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Operand {
+    /// 8-bit data.
+    Byte(u8),
+    /// 16-bit data.
+    Word(u16),
+    /// 8-bit address, offset into high memory at `$ff00`.
+    High(u8),
+    /// 16-bit address.
+    Addr(u16),
+    /// 8-bit signed offset.
+    Rel(i8),
+}
+
+impl Operand {
+    /// Operand placeholders used by instruction representations.
+    const PATS: [&str; 5] = ["n16", "a16", "n8", "a8", "e8"];
+
+    /// Finds the operand placeholder within an instruction representation.
+    fn find(repr: &str) -> Option<(usize, &'static str)> {
+        Self::PATS
+            .iter()
+            .find_map(|&pat| repr.find(pat).map(|idx| (idx, pat)))
+    }
+
+    /// Calculates the size of the operand within an instruction
+    /// representation.
+    fn size(repr: &str) -> u16 {
+        match Self::find(repr) {
+            Some((_, "n16" | "a16")) => 2,
+            Some(_) => 1,
+            // STOP is followed by a padding byte
+            None if repr == "STOP" => 1,
+            None => 0,
+        }
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Operand::Byte(data) => write!(f, "${data:02x}"),
+            Operand::Word(data) | Operand::Addr(data) => write!(f, "${data:04x}"),
+            Operand::High(addr) => write!(f, "$ff{addr:02x}"),
+            Operand::Rel(off) if off < 0 => write!(f, "-${:02x}", off.unsigned_abs()),
+            Operand::Rel(off) => write!(f, "${off:02x}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_works() {
+        let prog: &[u8] = &[
+            0x00, //             NOP
+            0x3e, 0x3c, //       LD A, $3c
+            0x21, 0x00, 0xc0, // LD HL, $c000
+            0xe0, 0x44, //       LDH ($ff44), A
+            0xcb, 0x7c, //       BIT 7, H
+            0x20, 0xfc, //       JR NZ, $0008
+            0xf8, 0xfe, //       LD HL, SP - $02
+            0xcd, 0x34, 0x12, // CALL $1234
+            0xef, //             RST 28H
+            0x10, 0x00, //       STOP
+        ];
+        let dis: Vec<_> = disassemble(prog, 0x0000..=0xffff)
+            .take_while(|insn| usize::from(insn.addr()) < prog.len())
+            .collect();
+        let text: Vec<_> = dis.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            text,
+            [
+                "NOP",
+                "LD A, $3c",
+                "LD HL, $c000",
+                "LDH ($ff44), A",
+                "BIT 7, H",
+                "JR NZ, $0008",
+                "LD HL, SP - $02",
+                "CALL $1234",
+                "RST 28H",
+                "STOP",
+            ]
+        );
+        // Check instruction encodings
+        assert_eq!(dis[2].bytes(), [0x21, 0x00, 0xc0]);
+        assert_eq!(dis[4].bytes(), [0xcb, 0x7c]);
+        assert_eq!(dis[9].next(), 0x0014);
+        // Check control flow targets
+        assert_eq!(dis[5].target(), Some(0x0008));
+        assert_eq!(dis[7].target(), Some(0x1234));
+        assert_eq!(dis[8].target(), Some(0x0028));
+        assert_eq!(dis[1].target(), None);
+        // Check labelled operands
+        let label = |addr| (addr == 0x1234 || addr == 0xff44).then(|| format!("L{addr:04x}"));
+        assert_eq!(dis[7].display(label).to_string(), "CALL L1234");
+        assert_eq!(dis[3].display(label).to_string(), "LDH (Lff44), A");
+        assert_eq!(dis[2].display(label).to_string(), "LD HL, $c000");
+    }
+
+    #[test]
+    fn disassemble_bounded() {
+        let prog = [0x00u8; 0x10];
+        // Stops at the end of the range
+        assert_eq!(disassemble(&prog, 0x0004..=0x0007).count(), 4);
+        // Stops at the end of the address space
+        assert_eq!(disassemble(&prog, 0xfffe..=0xffff).count(), 2);
+        // Empty ranges produce nothing
+        #[expect(clippy::reversed_empty_ranges)]
+        let none = disassemble(&prog, 0x0008..=0x0004).count();
+        assert_eq!(none, 0);
+    }
+}
@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -31,7 +30,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             cpu.reg.f.set_c(carry);
 
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // ADD SP, e8
         0xe8 => {
@@ -40,7 +39,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             cpu.reg.z.store(z);
 
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         code => unreachable!("unexpected opcode: {code:#04X}"),
     }
@@ -92,7 +91,7 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             cpu.reg.f.set_c(0xff < u16::from(op1) + u16::from(op2));
 
             // Proceed
-            cpu.step(cycle4)
+            cpu.step(2)
         }
         code => unreachable!("unexpected opcode: {code:#04X}"),
     }
@@ -112,7 +111,7 @@ fn cycle4(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.w.store(res);
 
     // Proceed
-    cpu.step(cycle5)
+    cpu.step(3)
 }
 
 fn cycle5(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
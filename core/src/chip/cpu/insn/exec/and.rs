@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction, help};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -14,14 +13,14 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.readbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         0xe6 => {
             // Fetch Z <- [PC++]
             let z = cpu.fetchbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         0xa0..=0xa7 => {
             // Prepare Z
@@ -1,10 +1,11 @@
 use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
+#[cfg(feature = "debug")]
+use crate::chip::cpu::stack::{Frame, Kind};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5, cycle6, cycle7];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -18,7 +19,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.z.store(z);
 
     // Proceed
-    cpu.step(cycle3)
+    cpu.step(1)
 }
 
 fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -40,10 +41,10 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check condition
     if cond {
         // Proceed
-        cpu.step(cycle4)
+        cpu.step(2)
     } else {
         // Proceed
-        cpu.step(cycle7)
+        cpu.step(5)
     }
 }
 
@@ -54,7 +55,7 @@ fn cycle4(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.sp.store(sp);
 
     // Proceed
-    cpu.step(cycle5)
+    cpu.step(3)
 }
 
 fn cycle5(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -67,20 +68,29 @@ fn cycle5(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.sp.store(sp);
 
     // Proceed
-    cpu.step(cycle6)
+    cpu.step(4)
 }
 
 fn cycle6(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Write [SP] <- lower(PC)
     let sp = cpu.reg.sp.load();
-    let pc = cpu.reg.pc.load().to_le_bytes();
-    cpu.blk.bus.write(sp, pc[0]);
+    let pc = cpu.reg.pc.load();
+    cpu.blk.bus.write(sp, pc.to_le_bytes()[0]);
     // Perform jump PC <- WZ
     let wz = cpu.reg.wz().load();
     cpu.reg.pc.store(wz);
+    // Record call frame
+    #[cfg(feature = "debug")]
+    cpu.etc.stack.enter(Frame {
+        kind: Kind::Call,
+        site: pc.wrapping_sub(3),
+        addr: wz,
+        sp,
+        bank: cpu.etc.stack.bank(),
+    });
 
     // Proceed
-    cpu.step(cycle7)
+    cpu.step(5)
 }
 
 fn cycle7(_: u8, _: &mut Cpu) -> Option<Instruction> {
@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction, Status};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, wait];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -31,13 +30,13 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.etc.run = Status::Halted;
 
     // Await an interrupt
-    cpu.step(wait)
+    cpu.step(1)
 }
 
 fn wait(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Keep waiting while halted
     if cpu.etc.run == Status::Halted {
-        return cpu.step(wait);
+        return cpu.step(1);
     }
 
     // Finish
@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -14,7 +13,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.readbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         0x04 | 0x0c | 0x14 | 0x1c | 0x24 | 0x2c | 0x3c => {
             // Prepare Z
@@ -48,7 +47,7 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             // Write [HL]
             cpu.writebyte(res);
             // Proceed
-            return cpu.step(cycle4);
+            return cpu.step(2);
         }
         0x04 => cpu.reg.b.store(res),
         0x0c => cpu.reg.c.store(res),
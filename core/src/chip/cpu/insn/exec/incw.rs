@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Load r16
@@ -27,7 +26,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     }
 
     // Proceed
-    cpu.step(cycle3)
+    cpu.step(1)
 }
 
 fn cycle3(_: u8, _: &mut Cpu) -> Option<Instruction> {
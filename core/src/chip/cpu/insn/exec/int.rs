@@ -1,17 +1,18 @@
 use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
+#[cfg(feature = "debug")]
+use crate::chip::cpu::stack::{Frame, Kind};
 use crate::chip::irq::Interrupt;
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5, cycle6];
 
 fn cycle2(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Delay by 1 cycle
 
     // Proceed
-    cpu.step(cycle3)
+    cpu.step(1)
 }
 
 fn cycle3(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -25,7 +26,7 @@ fn cycle3(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.z.store(cpu.irq.ena());
 
     // Proceed
-    cpu.step(cycle4)
+    cpu.step(2)
 }
 
 fn cycle4(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -40,7 +41,7 @@ fn cycle4(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.z.store(z);
 
     // Proceed
-    cpu.step(cycle5)
+    cpu.step(3)
 }
 
 fn cycle5(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -53,6 +54,8 @@ fn cycle5(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     }
 
     // Jump to the handler
+    #[cfg(feature = "debug")]
+    let pc = cpu.reg.pc.load();
     cpu.reg.pc.store(u16::from_be_bytes([
         0x00,
         int
@@ -61,9 +64,20 @@ fn cycle5(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
             // Resolve to zero if cancelled
             .unwrap_or_default(),
     ]));
+    // Record interrupt frame
+    #[cfg(feature = "debug")]
+    if let Some(int) = int {
+        cpu.etc.stack.enter(Frame {
+            kind: Kind::Int(int),
+            site: pc,
+            addr: cpu.reg.pc.load(),
+            sp: cpu.reg.sp.load(),
+            bank: cpu.etc.stack.bank(),
+        });
+    }
 
     // Proceed
-    cpu.step(cycle6)
+    cpu.step(4)
 }
 
 fn cycle6(_: u8, _: &mut Cpu) -> Option<Instruction> {
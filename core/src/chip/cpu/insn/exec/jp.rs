@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -14,7 +13,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.fetchbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         0xe9 => {
             // Perform jump PC <- HL
@@ -46,10 +45,10 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check condition
     if cond {
         // Proceed
-        cpu.step(cycle4)
+        cpu.step(2)
     } else {
         // Proceed
-        cpu.step(cycle5)
+        cpu.step(3)
     }
 }
 
@@ -59,7 +58,7 @@ fn cycle4(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.pc.store(wz);
 
     // Proceed
-    cpu.step(cycle5)
+    cpu.step(3)
 }
 
 fn cycle5(_: u8, _: &mut Cpu) -> Option<Instruction> {
@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Fetch Z <- [PC++]
@@ -25,10 +24,10 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check condition
     if cond {
         // Proceed
-        cpu.step(cycle3)
+        cpu.step(1)
     } else {
         // Proceed
-        cpu.step(cycle5)
+        cpu.step(3)
     }
 }
 
@@ -52,7 +51,7 @@ fn cycle3(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.w.store(pc[1].wrapping_add(adj));
 
     // Proceed
-    cpu.step(cycle4)
+    cpu.step(2)
 }
 
 fn cycle4(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
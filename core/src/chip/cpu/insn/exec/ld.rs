@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction, help};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -37,7 +36,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
                 _ => {}
             }
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // LD A, [r16]
         0x0a | 0x1a | 0x2a | 0x3a => {
@@ -65,7 +64,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
                 _ => {}
             }
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // LD r8, n8
         0x06 | 0x0e | 0x16 | 0x1e | 0x26 | 0x2e | 0x3e => {
@@ -73,7 +72,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.fetchbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // LD [HL], n8
         0x36 => {
@@ -81,7 +80,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.fetchbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // LD r8, [HL]
         0x46 | 0x4e | 0x56 | 0x5e | 0x66 | 0x6e | 0x7e => {
@@ -89,7 +88,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.readbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // HALT (unexpected opcode)
         0x76 => unreachable!("unexpected opcode: {code:#04X}"),
@@ -102,7 +101,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             // Write op2
             cpu.blk.bus.write(addr, op2);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // LD r8, r8
         0x40..=0x7f => {
@@ -119,7 +118,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.fetchbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         code => unreachable!("unexpected opcode: {code:#04X}"),
     }
@@ -142,7 +141,7 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.reg.z.load();
             cpu.writebyte(z);
             // Proceed
-            cpu.step(cycle4)
+            cpu.step(2)
         }
         // LD [a16], A
         // LD A, [a16]
@@ -151,7 +150,7 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let w = cpu.fetchbyte();
             cpu.reg.w.store(w);
             // Proceed
-            cpu.step(cycle4)
+            cpu.step(2)
         }
         // Store r8 <- Z
         _ => {
@@ -190,7 +189,7 @@ fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let op2 = cpu.reg.a.load();
             cpu.blk.bus.write(wz, op2);
             // Proceed
-            cpu.step(cycle5)
+            cpu.step(3)
         }
         // LD A, [a16]
         0xfa => {
@@ -198,7 +197,7 @@ fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.blk.bus.read(wz);
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle5)
+            cpu.step(3)
         }
         code => unreachable!("unexpected opcode: {code:#04X}"),
     }
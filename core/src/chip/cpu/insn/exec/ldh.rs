@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -16,7 +15,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.fetchbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // LDH [C], A
         0xe2 => {
@@ -26,7 +25,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             // Write [$FF00 + Z] <- A
             write(cpu);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // LDH A, [C]
         0xf2 => {
@@ -36,7 +35,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             // Read Z <- [$FF00 + Z]
             read(cpu);
             // Proceed
-            cpu.step(cycle4)
+            cpu.step(2)
         }
         code => unreachable!("unexpected opcode: {code:#04X}"),
     }
@@ -50,14 +49,14 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             // Write [$FF00 + Z] <- A
             write(cpu);
             // Proceed
-            cpu.step(cycle4)
+            cpu.step(2)
         }
         // LDH A, [a8]
         0xf0 => {
             // Read Z <- [$FF00 + Z]
             read(cpu);
             // Proceed
-            cpu.step(cycle4)
+            cpu.step(2)
         }
         // LDH [C], A
         0xe2 => {
@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5, cycle6];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -17,7 +16,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.fetchbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // LD HL, SP + e8
         0xf8 => {
@@ -25,7 +24,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.fetchbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // LD SP, HL
         0xf9 => {
@@ -33,7 +32,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let hl = cpu.reg.hl().load();
             cpu.reg.sp.store(hl);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         code => unreachable!("unexpected opcode: {code:#04X}"),
     }
@@ -56,7 +55,7 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let w = cpu.fetchbyte();
             cpu.reg.w.store(w);
             // Proceed
-            cpu.step(cycle4)
+            cpu.step(2)
         }
         // LD HL, SP + e8
         0xf8 => {
@@ -75,7 +74,7 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             cpu.reg.f.set_c(0xff < u16::from(op1) + u16::from(op2));
 
             // Proceed
-            cpu.step(cycle4)
+            cpu.step(2)
         }
         code => unreachable!("unexpected opcode: {code:#04X}"),
     }
@@ -108,7 +107,7 @@ fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let wz = cpu.blk.idu.inc(wz);
             cpu.reg.wz_mut().store(wz);
             // Proceed
-            cpu.step(cycle5)
+            cpu.step(3)
         }
         // LD HL, SP + e8
         0xf8 => {
@@ -136,7 +135,7 @@ fn cycle5(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.blk.bus.write(wz, sp.to_le_bytes()[1]);
 
     // Proceed
-    cpu.step(cycle6)
+    cpu.step(4)
 }
 
 fn cycle6(_: u8, _: &mut Cpu) -> Option<Instruction> {
@@ -1,8 +1,7 @@
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2];
 
 fn cycle2(code: u8, _: &mut Cpu) -> Option<Instruction> {
     // Check opcode
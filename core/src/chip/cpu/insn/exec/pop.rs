@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -18,7 +17,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.z.store(z);
 
     // Proceed
-    cpu.step(cycle3)
+    cpu.step(1)
 }
 
 fn cycle3(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -27,7 +26,7 @@ fn cycle3(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.w.store(w);
 
     // Proceed
-    cpu.step(cycle4)
+    cpu.step(2)
 }
 
 fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
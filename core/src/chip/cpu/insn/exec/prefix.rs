@@ -3,9 +3,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle1
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle1];
 
 /// Fetches and decodes a prefixed instruction.
 ///
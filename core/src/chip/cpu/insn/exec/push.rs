@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -19,7 +18,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.sp.store(sp);
 
     // Proceed
-    cpu.step(cycle3)
+    cpu.step(1)
 }
 
 fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -41,7 +40,7 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.sp.store(sp);
 
     // Proceed
-    cpu.step(cycle4)
+    cpu.step(2)
 }
 
 fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -59,7 +58,7 @@ fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.blk.bus.write(sp, lsb);
 
     // Proceed
-    cpu.step(cycle5)
+    cpu.step(3)
 }
 
 fn cycle5(_: u8, _: &mut Cpu) -> Option<Instruction> {
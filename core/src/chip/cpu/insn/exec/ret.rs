@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5, cycle6];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -15,7 +14,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.popbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle3)
+            cpu.step(1)
         }
         // RET cc
         0xc0 | 0xc8 | 0xd0 | 0xd8 => {
@@ -31,10 +30,10 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             // Check condition
             if cond {
                 // Proceed
-                cpu.step(cycle3)
+                cpu.step(1)
             } else {
                 // Proceed
-                cpu.step(cycle6)
+                cpu.step(4)
             }
         }
         code => unreachable!("unexpected opcode: {code:#04X}"),
@@ -54,7 +53,7 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     }
 
     // Proceed
-    cpu.step(cycle4)
+    cpu.step(2)
 }
 
 fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -63,14 +62,17 @@ fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
         // Perform jump PC <- WZ
         let wz = cpu.reg.wz().load();
         cpu.reg.pc.store(wz);
+        // Unwind call frame
+        #[cfg(feature = "debug")]
+        cpu.etc.stack.leave(cpu.reg.sp.load());
         // Proceed
-        cpu.step(cycle6)
+        cpu.step(4)
     } else {
         // Pop W <- [SP++]
         let w = cpu.popbyte();
         cpu.reg.w.store(w);
         // Proceed
-        cpu.step(cycle5)
+        cpu.step(3)
     }
 }
 
@@ -78,9 +80,12 @@ fn cycle5(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Perform jump PC <- WZ
     let wz = cpu.reg.wz().load();
     cpu.reg.pc.store(wz);
+    // Unwind call frame
+    #[cfg(feature = "debug")]
+    cpu.etc.stack.leave(cpu.reg.sp.load());
 
     // Proceed
-    cpu.step(cycle6)
+    cpu.step(4)
 }
 
 fn cycle6(_: u8, _: &mut Cpu) -> Option<Instruction> {
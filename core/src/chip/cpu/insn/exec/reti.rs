@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Ime, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -17,7 +16,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.z.store(z);
 
     // Proceed
-    cpu.step(cycle3)
+    cpu.step(1)
 }
 
 fn cycle3(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -26,13 +25,16 @@ fn cycle3(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.w.store(w);
 
     // Proceed
-    cpu.step(cycle4)
+    cpu.step(2)
 }
 
 fn cycle4(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Perform jump PC <- WZ
     let wz = cpu.reg.wz().load();
     cpu.reg.pc.store(wz);
+    // Unwind interrupt frame
+    #[cfg(feature = "debug")]
+    cpu.etc.stack.leave(cpu.reg.sp.load());
 
     // Enable interrupts.
     //
@@ -42,7 +44,7 @@ fn cycle4(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.etc.ime = Ime::Enabled;
 
     // Proceed
-    cpu.step(cycle5)
+    cpu.step(3)
 }
 
 fn cycle5(_: u8, _: &mut Cpu) -> Option<Instruction> {
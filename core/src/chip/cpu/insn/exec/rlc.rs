@@ -2,9 +2,8 @@ use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction, help};
 
-pub const fn default() -> Exec {
-    cycle3
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle3, cycle4, cycle5];
 
 fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -14,7 +13,7 @@ fn cycle3(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             let z = cpu.readbyte();
             cpu.reg.z.store(z);
             // Proceed
-            cpu.step(cycle4)
+            cpu.step(1)
         }
         0x00..=0x07 => {
             // Prepare Z
@@ -45,7 +44,7 @@ fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
             // Write [HL]
             cpu.writebyte(res);
             // Proceed
-            cpu.step(cycle5)
+            cpu.step(2)
         }
         0x00..=0x07 => {
             // Store r8
@@ -1,10 +1,11 @@
 use rugby_arch::reg::Register;
 
 use super::{Cpu, Exec, Instruction};
+#[cfg(feature = "debug")]
+use crate::chip::cpu::stack::{Frame, Kind};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, cycle3, cycle4, cycle5];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -19,7 +20,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.sp.store(sp);
 
     // Proceed
-    cpu.step(cycle3)
+    cpu.step(1)
 }
 
 fn cycle3(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
@@ -32,14 +33,14 @@ fn cycle3(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     cpu.reg.sp.store(sp);
 
     // Proceed
-    cpu.step(cycle4)
+    cpu.step(2)
 }
 
 fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Write [SP] <- lower(PC)
     let sp = cpu.reg.sp.load();
-    let pc = cpu.reg.pc.load().to_le_bytes();
-    cpu.blk.bus.write(sp, pc[0]);
+    let pc = cpu.reg.pc.load();
+    cpu.blk.bus.write(sp, pc.to_le_bytes()[0]);
 
     // Decode vector
     let v8 = match code {
@@ -56,9 +57,18 @@ fn cycle4(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Perform jump PC <- vector
     let v16 = u16::from_le_bytes([v8, 0x00]);
     cpu.reg.pc.store(v16);
+    // Record call frame
+    #[cfg(feature = "debug")]
+    cpu.etc.stack.enter(Frame {
+        kind: Kind::Rst,
+        site: pc.wrapping_sub(1),
+        addr: v16,
+        sp,
+        bank: cpu.etc.stack.bank(),
+    });
 
     // Proceed
-    cpu.step(cycle5)
+    cpu.step(3)
 }
 
 fn cycle5(_: u8, _: &mut Cpu) -> Option<Instruction> {
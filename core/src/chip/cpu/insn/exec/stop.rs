@@ -2,9 +2,8 @@ use rugby_arch::Block;
 
 use super::{Cpu, Exec, Instruction, Status};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, wait];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Check opcode
@@ -26,7 +25,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
 
     // Park until woken
     if cpu.etc.run == Status::Stopped {
-        return cpu.step(wait);
+        return cpu.step(1);
     }
 
     // Release the blocks for the composed fetch
@@ -38,7 +37,7 @@ fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
 fn wait(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Keep waiting while stopped
     if cpu.etc.run == Status::Stopped {
-        return cpu.step(wait);
+        return cpu.step(1);
     }
 
     // Finish
@@ -2,18 +2,17 @@ use log::error;
 
 use super::{Cpu, Exec, Instruction};
 
-pub const fn default() -> Exec {
-    cycle2
-}
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle2, hang];
 
 fn cycle2(code: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Log the illegal instruction
     error!("illegal instruction: {code:#04X}");
     // Hang the processor
-    cpu.step(hang)
+    cpu.step(1)
 }
 
 fn hang(_: u8, cpu: &mut Cpu) -> Option<Instruction> {
     // Hang forever
-    cpu.step(hang)
+    cpu.step(1)
 }
@@ -10,8 +10,12 @@
 use log::{debug, trace};
 use rugby_arch::reg::Register;
 
+use super::exec::Exec;
 use super::{Cpu, Ime, Instruction};
 
+/// Instruction stages.
+pub const STAGES: &[Exec] = &[cycle1];
+
 /// Fetches and decodes the next instruction.
 ///
 /// Checks for pending interrupts, reads the opcode at `PC`, and decodes
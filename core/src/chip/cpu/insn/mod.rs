@@ -4,11 +4,13 @@ use std::fmt::{Debug, Display};
 
 use log::trace;
 use rugby_arch::Block;
+use rugby_arch::state::{self, Reader, State, Writer};
 
 use self::exec::Exec;
 use super::{Cpu, Ime, Status};
 use crate::chip::irq::Interrupt;
 
+pub mod dis;
 mod exec;
 mod fetch;
 mod table;
@@ -25,24 +27,24 @@ pub fn cycle(cpu: &mut Cpu) {
     cpu.blk.cycle();
 
     // Execute the in-flight stage
-    let Instruction { code, exec, .. } = cpu.etc.insn;
-    cpu.etc.insn = if let Some(next) = exec(code, cpu) {
+    let insn = cpu.etc.insn;
+    cpu.etc.insn = if let Some(next) = (insn.stage())(insn.code, cpu) {
         // Proceed with in-flight instruction
         cpu.etc.busy = true;
         next
     } else {
         // Concurrently fetch next instruction
         cpu.etc.busy = false;
-        fetch::cycle1(code, cpu).expect("fetch will always succeed")
+        fetch::cycle1(insn.code, cpu).expect("fetch will always succeed")
     }
 }
 
 impl Cpu {
     /// Proceeds to the given stage of the in-flight instruction.
     #[allow(clippy::unnecessary_wraps)]
-    fn step(&self, exec: Exec) -> Option<Instruction> {
+    fn step(&self, stage: u8) -> Option<Instruction> {
         Some(Instruction {
-            exec,
+            stage,
             ..self.etc.insn
         })
     }
@@ -55,7 +57,8 @@ impl Cpu {
 #[derive(Clone, Copy)]
 pub struct Instruction {
     code: u8,
-    exec: Exec,
+    stages: &'static [Exec],
+    stage: u8,
     repr: &'static str,
 }
 
@@ -63,7 +66,8 @@ impl Default for Instruction {
     fn default() -> Self {
         Self {
             code: 0x00,
-            exec: fetch::cycle1,
+            stages: fetch::STAGES,
+            stage: 0,
             repr: "FETCH",
         }
     }
@@ -87,7 +91,8 @@ impl Instruction {
     pub fn vector(int: Interrupt) -> Self {
         Self {
             code: int as u8,
-            exec: exec::int::default(),
+            stages: exec::int::STAGES,
+            stage: 0,
             repr: int.repr(),
         }
     }
@@ -98,6 +103,11 @@ impl Instruction {
         self.code
     }
 
+    /// Gets the in-flight stage of the instruction.
+    fn stage(&self) -> Exec {
+        self.stages[usize::from(self.stage)]
+    }
+
     /// Executes a single stage of the instruction.
     pub fn exec(self, cpu: &mut Cpu) -> Option<Self> {
         // Install the instruction
@@ -107,16 +117,76 @@ impl Instruction {
         // Execute operation
         trace!("{self:?}");
         // Return the next stage
-        (self.exec)(self.code, cpu)
+        (self.stage())(self.code, cpu)
     }
 }
 
+impl State for Instruction {
+    fn save(&self, out: &mut Writer) {
+        // Identify the instruction
+        let kind = if self.repr == Self::default().repr {
+            Kind::Fetch
+        } else if self.repr == Self::decode(self.code).repr {
+            Kind::Decode
+        } else if self.repr == Self::prefix(self.code).repr {
+            Kind::Prefix
+        } else {
+            Kind::Vector
+        };
+        out.save(&(kind as u8)).save(&self.code);
+        // Identify the in-flight stage
+        if kind != Kind::Fetch {
+            out.save(&self.stage);
+        }
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut kind = u8::default();
+        let mut code = u8::default();
+        inp.load(&mut kind)?.load(&mut code)?;
+        // Restore the instruction
+        *self = match kind {
+            0 => {
+                *self = Self::default();
+                return Ok(());
+            }
+            1 => Self::decode(code),
+            2 => Self::prefix(code),
+            3 => Interrupt::try_from(code)
+                .map(Self::vector)
+                .map_err(|_| state::Error::Invalid("Interrupt"))?,
+            _ => return Err(state::Error::Invalid("Instruction")),
+        };
+        // Restore the in-flight stage
+        let mut stage = u8::default();
+        inp.load(&mut stage)?;
+        if usize::from(stage) >= self.stages.len() {
+            return Err(state::Error::Invalid("Exec"));
+        }
+        self.stage = stage;
+        Ok(())
+    }
+}
+
+/// Instruction kind.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Kind {
+    /// Instruction fetch.
+    Fetch,
+    /// Decoded instruction.
+    Decode,
+    /// Prefixed instruction.
+    Prefix,
+    /// Interrupt dispatch.
+    Vector,
+}
+
 impl Debug for Instruction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct(std::any::type_name::<Self>())
             .field("code", &format_args!("{:02X?}", self.code))
             .field("repr", &self.repr)
-            .field("exec", &format_args!("{:02X?}", self.exec))
+            .field("stage", &self.stage)
             .finish_non_exhaustive()
     }
 }
@@ -129,11 +199,7 @@ impl Display for Instruction {
 
 impl From<Interrupt> for Instruction {
     fn from(value: Interrupt) -> Self {
-        Self {
-            code: value as u8,
-            exec: exec::int::default(),
-            repr: value.repr(),
-        }
+        Self::vector(value)
     }
 }
 
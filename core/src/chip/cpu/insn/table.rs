@@ -3,521 +3,521 @@ use super::{Instruction, exec};
 /// Instruction lookup table.
 #[rustfmt::skip]
 pub const DECODE: [Instruction; 0x100] = [
-    Instruction { code: 0x00, exec: exec::nop::default(),    repr: "NOP",            },
-    Instruction { code: 0x01, exec: exec::ldw::default(),    repr: "LD BC, n16",     },
-    Instruction { code: 0x02, exec: exec::ld::default(),     repr: "LD (BC), A",     },
-    Instruction { code: 0x03, exec: exec::incw::default(),   repr: "INC BC",         },
-    Instruction { code: 0x04, exec: exec::inc::default(),    repr: "INC B",          },
-    Instruction { code: 0x05, exec: exec::dec::default(),    repr: "DEC B",          },
-    Instruction { code: 0x06, exec: exec::ld::default(),     repr: "LD B, n8",       },
-    Instruction { code: 0x07, exec: exec::rlca::default(),   repr: "RLCA",           },
-    Instruction { code: 0x08, exec: exec::ldw::default(),    repr: "LD (a16), SP",   },
-    Instruction { code: 0x09, exec: exec::addw::default(),   repr: "ADD HL, BC",     },
-    Instruction { code: 0x0a, exec: exec::ld::default(),     repr: "LD A, (BC)",     },
-    Instruction { code: 0x0b, exec: exec::decw::default(),   repr: "DEC BC",         },
-    Instruction { code: 0x0c, exec: exec::inc::default(),    repr: "INC C",          },
-    Instruction { code: 0x0d, exec: exec::dec::default(),    repr: "DEC C",          },
-    Instruction { code: 0x0e, exec: exec::ld::default(),     repr: "LD C, n8",       },
-    Instruction { code: 0x0f, exec: exec::rrca::default(),   repr: "RRCA",           },
-    Instruction { code: 0x10, exec: exec::stop::default(),   repr: "STOP",           },
-    Instruction { code: 0x11, exec: exec::ldw::default(),    repr: "LD DE, (n16)",   },
-    Instruction { code: 0x12, exec: exec::ld::default(),     repr: "LD (DE), A",     },
-    Instruction { code: 0x13, exec: exec::incw::default(),   repr: "INC DE",         },
-    Instruction { code: 0x14, exec: exec::inc::default(),    repr: "INC D",          },
-    Instruction { code: 0x15, exec: exec::dec::default(),    repr: "DEC D",          },
-    Instruction { code: 0x16, exec: exec::ld::default(),     repr: "LD D, n8",       },
-    Instruction { code: 0x17, exec: exec::rla::default(),    repr: "RLA",            },
-    Instruction { code: 0x18, exec: exec::jr::default(),     repr: "JR e8",          },
-    Instruction { code: 0x19, exec: exec::addw::default(),   repr: "ADD HL, DE",     },
-    Instruction { code: 0x1a, exec: exec::ld::default(),     repr: "LD A, (DE)",     },
-    Instruction { code: 0x1b, exec: exec::decw::default(),   repr: "DEC DE",         },
-    Instruction { code: 0x1c, exec: exec::inc::default(),    repr: "INC E",          },
-    Instruction { code: 0x1d, exec: exec::dec::default(),    repr: "DEC E",          },
-    Instruction { code: 0x1e, exec: exec::ld::default(),     repr: "LD E, n8",       },
-    Instruction { code: 0x1f, exec: exec::rra::default(),    repr: "RRA",            },
-    Instruction { code: 0x20, exec: exec::jr::default(),     repr: "JR NZ, e8",      },
-    Instruction { code: 0x21, exec: exec::ldw::default(),    repr: "LD HL, n16",     },
-    Instruction { code: 0x22, exec: exec::ld::default(),     repr: "LD (HL+), A",    },
-    Instruction { code: 0x23, exec: exec::incw::default(),   repr: "INC HL",         },
-    Instruction { code: 0x24, exec: exec::inc::default(),    repr: "INC H",          },
-    Instruction { code: 0x25, exec: exec::dec::default(),    repr: "DEC H",          },
-    Instruction { code: 0x26, exec: exec::ld::default(),     repr: "LD H, n8",       },
-    Instruction { code: 0x27, exec: exec::daa::default(),    repr: "DAA",            },
-    Instruction { code: 0x28, exec: exec::jr::default(),     repr: "JR Z, e8",       },
-    Instruction { code: 0x29, exec: exec::addw::default(),   repr: "ADD HL, HL",     },
-    Instruction { code: 0x2a, exec: exec::ld::default(),     repr: "LD A, (HL+)",    },
-    Instruction { code: 0x2b, exec: exec::decw::default(),   repr: "DEC HL",         },
-    Instruction { code: 0x2c, exec: exec::inc::default(),    repr: "INC L",          },
-    Instruction { code: 0x2d, exec: exec::dec::default(),    repr: "DEC L",          },
-    Instruction { code: 0x2e, exec: exec::ld::default(),     repr: "LD L, n8",       },
-    Instruction { code: 0x2f, exec: exec::cpl::default(),    repr: "CPL",            },
-    Instruction { code: 0x30, exec: exec::jr::default(),     repr: "JR NC, e8",      },
-    Instruction { code: 0x31, exec: exec::ldw::default(),    repr: "LD SP, n16",     },
-    Instruction { code: 0x32, exec: exec::ld::default(),     repr: "LD (HL-), A",    },
-    Instruction { code: 0x33, exec: exec::incw::default(),   repr: "INC SP",         },
-    Instruction { code: 0x34, exec: exec::inc::default(),    repr: "INC (HL)",       },
-    Instruction { code: 0x35, exec: exec::dec::default(),    repr: "DEC (HL)",       },
-    Instruction { code: 0x36, exec: exec::ld::default(),     repr: "LD (HL), n8",    },
-    Instruction { code: 0x37, exec: exec::scf::default(),    repr: "SCF",            },
-    Instruction { code: 0x38, exec: exec::jr::default(),     repr: "JR C, e8",       },
-    Instruction { code: 0x39, exec: exec::addw::default(),   repr: "ADD HL, SP",     },
-    Instruction { code: 0x3a, exec: exec::ld::default(),     repr: "LD A, (HL-)",    },
-    Instruction { code: 0x3b, exec: exec::decw::default(),   repr: "DEC SP",         },
-    Instruction { code: 0x3c, exec: exec::inc::default(),    repr: "INC A",          },
-    Instruction { code: 0x3d, exec: exec::dec::default(),    repr: "DEC A",          },
-    Instruction { code: 0x3e, exec: exec::ld::default(),     repr: "LD A, n8",       },
-    Instruction { code: 0x3f, exec: exec::ccf::default(),    repr: "CCP",            },
-    Instruction { code: 0x40, exec: exec::ld::default(),     repr: "LD B, B",        },
-    Instruction { code: 0x41, exec: exec::ld::default(),     repr: "LD B, C",        },
-    Instruction { code: 0x42, exec: exec::ld::default(),     repr: "LD B, D",        },
-    Instruction { code: 0x43, exec: exec::ld::default(),     repr: "LD B, E",        },
-    Instruction { code: 0x44, exec: exec::ld::default(),     repr: "LD B, H",        },
-    Instruction { code: 0x45, exec: exec::ld::default(),     repr: "LD B, L",        },
-    Instruction { code: 0x46, exec: exec::ld::default(),     repr: "LD B, (HL)",     },
-    Instruction { code: 0x47, exec: exec::ld::default(),     repr: "LD B, A",        },
-    Instruction { code: 0x48, exec: exec::ld::default(),     repr: "LD C, B",        },
-    Instruction { code: 0x49, exec: exec::ld::default(),     repr: "LD C, C",        },
-    Instruction { code: 0x4a, exec: exec::ld::default(),     repr: "LD C, D",        },
-    Instruction { code: 0x4b, exec: exec::ld::default(),     repr: "LD C, E",        },
-    Instruction { code: 0x4c, exec: exec::ld::default(),     repr: "LD C, H",        },
-    Instruction { code: 0x4d, exec: exec::ld::default(),     repr: "LD C, L",        },
-    Instruction { code: 0x4e, exec: exec::ld::default(),     repr: "LD C, (HL)",     },
-    Instruction { code: 0x4f, exec: exec::ld::default(),     repr: "LD C, A",        },
-    Instruction { code: 0x50, exec: exec::ld::default(),     repr: "LD D, B",        },
-    Instruction { code: 0x51, exec: exec::ld::default(),     repr: "LD D, C",        },
-    Instruction { code: 0x52, exec: exec::ld::default(),     repr: "LD D, D",        },
-    Instruction { code: 0x53, exec: exec::ld::default(),     repr: "LD D, E",        },
-    Instruction { code: 0x54, exec: exec::ld::default(),     repr: "LD D, H",        },
-    Instruction { code: 0x55, exec: exec::ld::default(),     repr: "LD D, L",        },
-    Instruction { code: 0x56, exec: exec::ld::default(),     repr: "LD D, (HL)",     },
-    Instruction { code: 0x57, exec: exec::ld::default(),     repr: "LD D, A",        },
-    Instruction { code: 0x58, exec: exec::ld::default(),     repr: "LD E, B",        },
-    Instruction { code: 0x59, exec: exec::ld::default(),     repr: "LD E, C",        },
-    Instruction { code: 0x5a, exec: exec::ld::default(),     repr: "LD E, D",        },
-    Instruction { code: 0x5b, exec: exec::ld::default(),     repr: "LD E, E",        },
-    Instruction { code: 0x5c, exec: exec::ld::default(),     repr: "LD E, H",        },
-    Instruction { code: 0x5d, exec: exec::ld::default(),     repr: "LD E, L",        },
-    Instruction { code: 0x5e, exec: exec::ld::default(),     repr: "LD E, (HL)",     },
-    Instruction { code: 0x5f, exec: exec::ld::default(),     repr: "LD E, A",        },
-    Instruction { code: 0x60, exec: exec::ld::default(),     repr: "LD H, B",        },
-    Instruction { code: 0x61, exec: exec::ld::default(),     repr: "LD H, C",        },
-    Instruction { code: 0x62, exec: exec::ld::default(),     repr: "LD H, D",        },
-    Instruction { code: 0x63, exec: exec::ld::default(),     repr: "LD H, E",        },
-    Instruction { code: 0x64, exec: exec::ld::default(),     repr: "LD H, H",        },
-    Instruction { code: 0x65, exec: exec::ld::default(),     repr: "LD H, L",        },
-    Instruction { code: 0x66, exec: exec::ld::default(),     repr: "LD H, (HL)",     },
-    Instruction { code: 0x67, exec: exec::ld::default(),     repr: "LD H, A",        },
-    Instruction { code: 0x68, exec: exec::ld::default(),     repr: "LD L, B",        },
-    Instruction { code: 0x69, exec: exec::ld::default(),     repr: "LD L, C",        },
-    Instruction { code: 0x6a, exec: exec::ld::default(),     repr: "LD L, D",        },
-    Instruction { code: 0x6b, exec: exec::ld::default(),     repr: "LD L, E",        },
-    Instruction { code: 0x6c, exec: exec::ld::default(),     repr: "LD L, H",        },
-    Instruction { code: 0x6d, exec: exec::ld::default(),     repr: "LD L, L",        },
-    Instruction { code: 0x6e, exec: exec::ld::default(),     repr: "LD L, (HL)",     },
-    Instruction { code: 0x6f, exec: exec::ld::default(),     repr: "LD L, A",        },
-    Instruction { code: 0x70, exec: exec::ld::default(),     repr: "LD (HL), B",     },
-    Instruction { code: 0x71, exec: exec::ld::default(),     repr: "LD (HL), C",     },
-    Instruction { code: 0x72, exec: exec::ld::default(),     repr: "LD (HL), D",     },
-    Instruction { code: 0x73, exec: exec::ld::default(),     repr: "LD (HL), E",     },
-    Instruction { code: 0x74, exec: exec::ld::default(),     repr: "LD (HL), H",     },
-    Instruction { code: 0x75, exec: exec::ld::default(),     repr: "LD (HL), L",     },
-    Instruction { code: 0x76, exec: exec::halt::default(),   repr: "HALT",           },
-    Instruction { code: 0x77, exec: exec::ld::default(),     repr: "LD (HL), A",     },
-    Instruction { code: 0x78, exec: exec::ld::default(),     repr: "LD A, B",        },
-    Instruction { code: 0x79, exec: exec::ld::default(),     repr: "LD A, C",        },
-    Instruction { code: 0x7a, exec: exec::ld::default(),     repr: "LD A, D",        },
-    Instruction { code: 0x7b, exec: exec::ld::default(),     repr: "LD A, E",        },
-    Instruction { code: 0x7c, exec: exec::ld::default(),     repr: "LD A, H",        },
-    Instruction { code: 0x7d, exec: exec::ld::default(),     repr: "LD A, L",        },
-    Instruction { code: 0x7e, exec: exec::ld::default(),     repr: "LD A, (HL)",     },
-    Instruction { code: 0x7f, exec: exec::ld::default(),     repr: "LD A, A",        },
-    Instruction { code: 0x80, exec: exec::add::default(),    repr: "ADD A, B",       },
-    Instruction { code: 0x81, exec: exec::add::default(),    repr: "ADD A, C",       },
-    Instruction { code: 0x82, exec: exec::add::default(),    repr: "ADD A, D",       },
-    Instruction { code: 0x83, exec: exec::add::default(),    repr: "ADD A, E",       },
-    Instruction { code: 0x84, exec: exec::add::default(),    repr: "ADD A, H",       },
-    Instruction { code: 0x85, exec: exec::add::default(),    repr: "ADD A, L",       },
-    Instruction { code: 0x86, exec: exec::add::default(),    repr: "ADD A, (HL)",    },
-    Instruction { code: 0x87, exec: exec::add::default(),    repr: "ADD A, A",       },
-    Instruction { code: 0x88, exec: exec::adc::default(),    repr: "ADC A, B",       },
-    Instruction { code: 0x89, exec: exec::adc::default(),    repr: "ADC A, C",       },
-    Instruction { code: 0x8a, exec: exec::adc::default(),    repr: "ADC A, D",       },
-    Instruction { code: 0x8b, exec: exec::adc::default(),    repr: "ADC A, E",       },
-    Instruction { code: 0x8c, exec: exec::adc::default(),    repr: "ADC A, H",       },
-    Instruction { code: 0x8d, exec: exec::adc::default(),    repr: "ADC A, L",       },
-    Instruction { code: 0x8e, exec: exec::adc::default(),    repr: "ADC A, (HL)",    },
-    Instruction { code: 0x8f, exec: exec::adc::default(),    repr: "ADC A, A",       },
-    Instruction { code: 0x90, exec: exec::sub::default(),    repr: "SUB A, B",       },
-    Instruction { code: 0x91, exec: exec::sub::default(),    repr: "SUB A, C",       },
-    Instruction { code: 0x92, exec: exec::sub::default(),    repr: "SUB A, D",       },
-    Instruction { code: 0x93, exec: exec::sub::default(),    repr: "SUB A, E",       },
-    Instruction { code: 0x94, exec: exec::sub::default(),    repr: "SUB A, H",       },
-    Instruction { code: 0x95, exec: exec::sub::default(),    repr: "SUB A, L",       },
-    Instruction { code: 0x96, exec: exec::sub::default(),    repr: "SUB A, (HL)",    },
-    Instruction { code: 0x97, exec: exec::sub::default(),    repr: "SUB A, A",       },
-    Instruction { code: 0x98, exec: exec::sbc::default(),    repr: "SBC A, B",       },
-    Instruction { code: 0x99, exec: exec::sbc::default(),    repr: "SBC A, C",       },
-    Instruction { code: 0x9a, exec: exec::sbc::default(),    repr: "SBC A, D",       },
-    Instruction { code: 0x9b, exec: exec::sbc::default(),    repr: "SBC A, E",       },
-    Instruction { code: 0x9c, exec: exec::sbc::default(),    repr: "SBC A, H",       },
-    Instruction { code: 0x9d, exec: exec::sbc::default(),    repr: "SBC A, L",       },
-    Instruction { code: 0x9e, exec: exec::sbc::default(),    repr: "SBC A, (HL)",    },
-    Instruction { code: 0x9f, exec: exec::sbc::default(),    repr: "SBC A, A",       },
-    Instruction { code: 0xa0, exec: exec::and::default(),    repr: "AND A, B",       },
-    Instruction { code: 0xa1, exec: exec::and::default(),    repr: "AND A, C",       },
-    Instruction { code: 0xa2, exec: exec::and::default(),    repr: "AND A, D",       },
-    Instruction { code: 0xa3, exec: exec::and::default(),    repr: "AND A, E",       },
-    Instruction { code: 0xa4, exec: exec::and::default(),    repr: "AND A, H",       },
-    Instruction { code: 0xa5, exec: exec::and::default(),    repr: "AND A, L",       },
-    Instruction { code: 0xa6, exec: exec::and::default(),    repr: "AND A, (HL)",    },
-    Instruction { code: 0xa7, exec: exec::and::default(),    repr: "AND A, A",       },
-    Instruction { code: 0xa8, exec: exec::xor::default(),    repr: "XOR A, B",       },
-    Instruction { code: 0xa9, exec: exec::xor::default(),    repr: "XOR A, C",       },
-    Instruction { code: 0xaa, exec: exec::xor::default(),    repr: "XOR A, D",       },
-    Instruction { code: 0xab, exec: exec::xor::default(),    repr: "XOR A, E",       },
-    Instruction { code: 0xac, exec: exec::xor::default(),    repr: "XOR A, H",       },
-    Instruction { code: 0xad, exec: exec::xor::default(),    repr: "XOR A, L",       },
-    Instruction { code: 0xae, exec: exec::xor::default(),    repr: "XOR A, (HL)",    },
-    Instruction { code: 0xaf, exec: exec::xor::default(),    repr: "XOR A, A",       },
-    Instruction { code: 0xb0, exec: exec::or::default(),     repr: "OR A, B",        },
-    Instruction { code: 0xb1, exec: exec::or::default(),     repr: "OR A, C",        },
-    Instruction { code: 0xb2, exec: exec::or::default(),     repr: "OR A, D",        },
-    Instruction { code: 0xb3, exec: exec::or::default(),     repr: "OR A, E",        },
-    Instruction { code: 0xb4, exec: exec::or::default(),     repr: "OR A, H",        },
-    Instruction { code: 0xb5, exec: exec::or::default(),     repr: "OR A, L",        },
-    Instruction { code: 0xb6, exec: exec::or::default(),     repr: "OR A, (HL)",     },
-    Instruction { code: 0xb7, exec: exec::or::default(),     repr: "OR A, A",        },
-    Instruction { code: 0xb8, exec: exec::cp::default(),     repr: "CP A, B",        },
-    Instruction { code: 0xb9, exec: exec::cp::default(),     repr: "CP A, C",        },
-    Instruction { code: 0xba, exec: exec::cp::default(),     repr: "CP A, D",        },
-    Instruction { code: 0xbb, exec: exec::cp::default(),     repr: "CP A, E",        },
-    Instruction { code: 0xbc, exec: exec::cp::default(),     repr: "CP A, H",        },
-    Instruction { code: 0xbd, exec: exec::cp::default(),     repr: "CP A, L",        },
-    Instruction { code: 0xbe, exec: exec::cp::default(),     repr: "CP A, (HL)",     },
-    Instruction { code: 0xbf, exec: exec::cp::default(),     repr: "CP A, A",        },
-    Instruction { code: 0xc0, exec: exec::ret::default(),    repr: "RET NZ",         },
-    Instruction { code: 0xc1, exec: exec::pop::default(),    repr: "POP BC",         },
-    Instruction { code: 0xc2, exec: exec::jp::default(),     repr: "JP NZ, a16",     },
-    Instruction { code: 0xc3, exec: exec::jp::default(),     repr: "JP a16",         },
-    Instruction { code: 0xc4, exec: exec::call::default(),   repr: "CALL NZ, a16",   },
-    Instruction { code: 0xc5, exec: exec::push::default(),   repr: "PUSH BC",        },
-    Instruction { code: 0xc6, exec: exec::add::default(),    repr: "ADD A, n8",      },
-    Instruction { code: 0xc7, exec: exec::rst::default(),    repr: "RST 00H",        },
-    Instruction { code: 0xc8, exec: exec::ret::default(),    repr: "RET Z",          },
-    Instruction { code: 0xc9, exec: exec::ret::default(),    repr: "RET",            },
-    Instruction { code: 0xca, exec: exec::jp::default(),     repr: "JP Z, a16",      },
-    Instruction { code: 0xcb, exec: exec::prefix::default(), repr: "PREFIX",         },
-    Instruction { code: 0xcc, exec: exec::call::default(),   repr: "CALL Z, a16",    },
-    Instruction { code: 0xcd, exec: exec::call::default(),   repr: "CALL a16",       },
-    Instruction { code: 0xce, exec: exec::adc::default(),    repr: "ADC A, n8",      },
-    Instruction { code: 0xcf, exec: exec::rst::default(),    repr: "RST 08H",        },
-    Instruction { code: 0xd0, exec: exec::ret::default(),    repr: "RET NC",         },
-    Instruction { code: 0xd1, exec: exec::pop::default(),    repr: "POP DE",         },
-    Instruction { code: 0xd2, exec: exec::jp::default(),     repr: "JP NC, a16",     },
-    Instruction { code: 0xd3, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xd4, exec: exec::call::default(),   repr: "CALL NC, a16",   },
-    Instruction { code: 0xd5, exec: exec::push::default(),   repr: "PUSH DE",        },
-    Instruction { code: 0xd6, exec: exec::sub::default(),    repr: "SUB n8",         },
-    Instruction { code: 0xd7, exec: exec::rst::default(),    repr: "RST 10H",        },
-    Instruction { code: 0xd8, exec: exec::ret::default(),    repr: "RET C",          },
-    Instruction { code: 0xd9, exec: exec::reti::default(),   repr: "RETI",           },
-    Instruction { code: 0xda, exec: exec::jp::default(),     repr: "JP C, a16",      },
-    Instruction { code: 0xdb, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xdc, exec: exec::call::default(),   repr: "CALL C, a16",    },
-    Instruction { code: 0xdd, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xde, exec: exec::sbc::default(),    repr: "SBC A, n8",      },
-    Instruction { code: 0xdf, exec: exec::rst::default(),    repr: "RST 18H",        },
-    Instruction { code: 0xe0, exec: exec::ldh::default(),    repr: "LDH (a8), A",    },
-    Instruction { code: 0xe1, exec: exec::pop::default(),    repr: "POP HL",         },
-    Instruction { code: 0xe2, exec: exec::ldh::default(),    repr: "LD (C), A",      },
-    Instruction { code: 0xe3, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xe4, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xe5, exec: exec::push::default(),   repr: "PUSH HL",        },
-    Instruction { code: 0xe6, exec: exec::and::default(),    repr: "AND A, n8",      },
-    Instruction { code: 0xe7, exec: exec::rst::default(),    repr: "RST 20H",        },
-    Instruction { code: 0xe8, exec: exec::addw::default(),   repr: "ADD SP, e8",     },
-    Instruction { code: 0xe9, exec: exec::jp::default(),     repr: "JP HL",          },
-    Instruction { code: 0xea, exec: exec::ld::default(),     repr: "LD (a16), A",    },
-    Instruction { code: 0xeb, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xec, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xed, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xee, exec: exec::xor::default(),    repr: "XOR A, n8",      },
-    Instruction { code: 0xef, exec: exec::rst::default(),    repr: "RST 28H",        },
-    Instruction { code: 0xf0, exec: exec::ldh::default(),    repr: "LDH A, (a8)",    },
-    Instruction { code: 0xf1, exec: exec::pop::default(),    repr: "POP AF",         },
-    Instruction { code: 0xf2, exec: exec::ldh::default(),    repr: "LD A, (C)",      },
-    Instruction { code: 0xf3, exec: exec::di::default(),     repr: "DI",             },
-    Instruction { code: 0xf4, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xf5, exec: exec::push::default(),   repr: "PUSH AF",        },
-    Instruction { code: 0xf6, exec: exec::or::default(),     repr: "OR A, n8",       },
-    Instruction { code: 0xf7, exec: exec::rst::default(),    repr: "RST 30H",        },
-    Instruction { code: 0xf8, exec: exec::ldw::default(),    repr: "LD HL, SP + e8", },
-    Instruction { code: 0xf9, exec: exec::ldw::default(),    repr: "LD SP, HL",      },
-    Instruction { code: 0xfa, exec: exec::ld::default(),     repr: "LD A, (a16)",    },
-    Instruction { code: 0xfb, exec: exec::ei::default(),     repr: "EI",             },
-    Instruction { code: 0xfc, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xfd, exec: exec::unused::default(), repr: "UNUSED",         },
-    Instruction { code: 0xfe, exec: exec::cp::default(),     repr: "CP A, n8",       },
-    Instruction { code: 0xff, exec: exec::rst::default(),    repr: "RST 38H",        },
+    Instruction { code: 0x00, stages: exec::nop::STAGES,     stage: 0, repr: "NOP",            },
+    Instruction { code: 0x01, stages: exec::ldw::STAGES,     stage: 0, repr: "LD BC, n16",     },
+    Instruction { code: 0x02, stages: exec::ld::STAGES,      stage: 0, repr: "LD (BC), A",     },
+    Instruction { code: 0x03, stages: exec::incw::STAGES,    stage: 0, repr: "INC BC",         },
+    Instruction { code: 0x04, stages: exec::inc::STAGES,     stage: 0, repr: "INC B",          },
+    Instruction { code: 0x05, stages: exec::dec::STAGES,     stage: 0, repr: "DEC B",          },
+    Instruction { code: 0x06, stages: exec::ld::STAGES,      stage: 0, repr: "LD B, n8",       },
+    Instruction { code: 0x07, stages: exec::rlca::STAGES,    stage: 0, repr: "RLCA",           },
+    Instruction { code: 0x08, stages: exec::ldw::STAGES,     stage: 0, repr: "LD (a16), SP",   },
+    Instruction { code: 0x09, stages: exec::addw::STAGES,    stage: 0, repr: "ADD HL, BC",     },
+    Instruction { code: 0x0a, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, (BC)",     },
+    Instruction { code: 0x0b, stages: exec::decw::STAGES,    stage: 0, repr: "DEC BC",         },
+    Instruction { code: 0x0c, stages: exec::inc::STAGES,     stage: 0, repr: "INC C",          },
+    Instruction { code: 0x0d, stages: exec::dec::STAGES,     stage: 0, repr: "DEC C",          },
+    Instruction { code: 0x0e, stages: exec::ld::STAGES,      stage: 0, repr: "LD C, n8",       },
+    Instruction { code: 0x0f, stages: exec::rrca::STAGES,    stage: 0, repr: "RRCA",           },
+    Instruction { code: 0x10, stages: exec::stop::STAGES,    stage: 0, repr: "STOP",           },
+    Instruction { code: 0x11, stages: exec::ldw::STAGES,     stage: 0, repr: "LD DE, n16",     },
+    Instruction { code: 0x12, stages: exec::ld::STAGES,      stage: 0, repr: "LD (DE), A",     },
+    Instruction { code: 0x13, stages: exec::incw::STAGES,    stage: 0, repr: "INC DE",         },
+    Instruction { code: 0x14, stages: exec::inc::STAGES,     stage: 0, repr: "INC D",          },
+    Instruction { code: 0x15, stages: exec::dec::STAGES,     stage: 0, repr: "DEC D",          },
+    Instruction { code: 0x16, stages: exec::ld::STAGES,      stage: 0, repr: "LD D, n8",       },
+    Instruction { code: 0x17, stages: exec::rla::STAGES,     stage: 0, repr: "RLA",            },
+    Instruction { code: 0x18, stages: exec::jr::STAGES,      stage: 0, repr: "JR e8",          },
+    Instruction { code: 0x19, stages: exec::addw::STAGES,    stage: 0, repr: "ADD HL, DE",     },
+    Instruction { code: 0x1a, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, (DE)",     },
+    Instruction { code: 0x1b, stages: exec::decw::STAGES,    stage: 0, repr: "DEC DE",         },
+    Instruction { code: 0x1c, stages: exec::inc::STAGES,     stage: 0, repr: "INC E",          },
+    Instruction { code: 0x1d, stages: exec::dec::STAGES,     stage: 0, repr: "DEC E",          },
+    Instruction { code: 0x1e, stages: exec::ld::STAGES,      stage: 0, repr: "LD E, n8",       },
+    Instruction { code: 0x1f, stages: exec::rra::STAGES,     stage: 0, repr: "RRA",            },
+    Instruction { code: 0x20, stages: exec::jr::STAGES,      stage: 0, repr: "JR NZ, e8",      },
+    Instruction { code: 0x21, stages: exec::ldw::STAGES,     stage: 0, repr: "LD HL, n16",     },
+    Instruction { code: 0x22, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL+), A",    },
+    Instruction { code: 0x23, stages: exec::incw::STAGES,    stage: 0, repr: "INC HL",         },
+    Instruction { code: 0x24, stages: exec::inc::STAGES,     stage: 0, repr: "INC H",          },
+    Instruction { code: 0x25, stages: exec::dec::STAGES,     stage: 0, repr: "DEC H",          },
+    Instruction { code: 0x26, stages: exec::ld::STAGES,      stage: 0, repr: "LD H, n8",       },
+    Instruction { code: 0x27, stages: exec::daa::STAGES,     stage: 0, repr: "DAA",            },
+    Instruction { code: 0x28, stages: exec::jr::STAGES,      stage: 0, repr: "JR Z, e8",       },
+    Instruction { code: 0x29, stages: exec::addw::STAGES,    stage: 0, repr: "ADD HL, HL",     },
+    Instruction { code: 0x2a, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, (HL+)",    },
+    Instruction { code: 0x2b, stages: exec::decw::STAGES,    stage: 0, repr: "DEC HL",         },
+    Instruction { code: 0x2c, stages: exec::inc::STAGES,     stage: 0, repr: "INC L",          },
+    Instruction { code: 0x2d, stages: exec::dec::STAGES,     stage: 0, repr: "DEC L",          },
+    Instruction { code: 0x2e, stages: exec::ld::STAGES,      stage: 0, repr: "LD L, n8",       },
+    Instruction { code: 0x2f, stages: exec::cpl::STAGES,     stage: 0, repr: "CPL",            },
+    Instruction { code: 0x30, stages: exec::jr::STAGES,      stage: 0, repr: "JR NC, e8",      },
+    Instruction { code: 0x31, stages: exec::ldw::STAGES,     stage: 0, repr: "LD SP, n16",     },
+    Instruction { code: 0x32, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL-), A",    },
+    Instruction { code: 0x33, stages: exec::incw::STAGES,    stage: 0, repr: "INC SP",         },
+    Instruction { code: 0x34, stages: exec::inc::STAGES,     stage: 0, repr: "INC (HL)",       },
+    Instruction { code: 0x35, stages: exec::dec::STAGES,     stage: 0, repr: "DEC (HL)",       },
+    Instruction { code: 0x36, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL), n8",    },
+    Instruction { code: 0x37, stages: exec::scf::STAGES,     stage: 0, repr: "SCF",            },
+    Instruction { code: 0x38, stages: exec::jr::STAGES,      stage: 0, repr: "JR C, e8",       },
+    Instruction { code: 0x39, stages: exec::addw::STAGES,    stage: 0, repr: "ADD HL, SP",     },
+    Instruction { code: 0x3a, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, (HL-)",    },
+    Instruction { code: 0x3b, stages: exec::decw::STAGES,    stage: 0, repr: "DEC SP",         },
+    Instruction { code: 0x3c, stages: exec::inc::STAGES,     stage: 0, repr: "INC A",          },
+    Instruction { code: 0x3d, stages: exec::dec::STAGES,     stage: 0, repr: "DEC A",          },
+    Instruction { code: 0x3e, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, n8",       },
+    Instruction { code: 0x3f, stages: exec::ccf::STAGES,     stage: 0, repr: "CCF",            },
+    Instruction { code: 0x40, stages: exec::ld::STAGES,      stage: 0, repr: "LD B, B",        },
+    Instruction { code: 0x41, stages: exec::ld::STAGES,      stage: 0, repr: "LD B, C",        },
+    Instruction { code: 0x42, stages: exec::ld::STAGES,      stage: 0, repr: "LD B, D",        },
+    Instruction { code: 0x43, stages: exec::ld::STAGES,      stage: 0, repr: "LD B, E",        },
+    Instruction { code: 0x44, stages: exec::ld::STAGES,      stage: 0, repr: "LD B, H",        },
+    Instruction { code: 0x45, stages: exec::ld::STAGES,      stage: 0, repr: "LD B, L",        },
+    Instruction { code: 0x46, stages: exec::ld::STAGES,      stage: 0, repr: "LD B, (HL)",     },
+    Instruction { code: 0x47, stages: exec::ld::STAGES,      stage: 0, repr: "LD B, A",        },
+    Instruction { code: 0x48, stages: exec::ld::STAGES,      stage: 0, repr: "LD C, B",        },
+    Instruction { code: 0x49, stages: exec::ld::STAGES,      stage: 0, repr: "LD C, C",        },
+    Instruction { code: 0x4a, stages: exec::ld::STAGES,      stage: 0, repr: "LD C, D",        },
+    Instruction { code: 0x4b, stages: exec::ld::STAGES,      stage: 0, repr: "LD C, E",        },
+    Instruction { code: 0x4c, stages: exec::ld::STAGES,      stage: 0, repr: "LD C, H",        },
+    Instruction { code: 0x4d, stages: exec::ld::STAGES,      stage: 0, repr: "LD C, L",        },
+    Instruction { code: 0x4e, stages: exec::ld::STAGES,      stage: 0, repr: "LD C, (HL)",     },
+    Instruction { code: 0x4f, stages: exec::ld::STAGES,      stage: 0, repr: "LD C, A",        },
+    Instruction { code: 0x50, stages: exec::ld::STAGES,      stage: 0, repr: "LD D, B",        },
+    Instruction { code: 0x51, stages: exec::ld::STAGES,      stage: 0, repr: "LD D, C",        },
+    Instruction { code: 0x52, stages: exec::ld::STAGES,      stage: 0, repr: "LD D, D",        },
+    Instruction { code: 0x53, stages: exec::ld::STAGES,      stage: 0, repr: "LD D, E",        },
+    Instruction { code: 0x54, stages: exec::ld::STAGES,      stage: 0, repr: "LD D, H",        },
+    Instruction { code: 0x55, stages: exec::ld::STAGES,      stage: 0, repr: "LD D, L",        },
+    Instruction { code: 0x56, stages: exec::ld::STAGES,      stage: 0, repr: "LD D, (HL)",     },
+    Instruction { code: 0x57, stages: exec::ld::STAGES,      stage: 0, repr: "LD D, A",        },
+    Instruction { code: 0x58, stages: exec::ld::STAGES,      stage: 0, repr: "LD E, B",        },
+    Instruction { code: 0x59, stages: exec::ld::STAGES,      stage: 0, repr: "LD E, C",        },
+    Instruction { code: 0x5a, stages: exec::ld::STAGES,      stage: 0, repr: "LD E, D",        },
+    Instruction { code: 0x5b, stages: exec::ld::STAGES,      stage: 0, repr: "LD E, E",        },
+    Instruction { code: 0x5c, stages: exec::ld::STAGES,      stage: 0, repr: "LD E, H",        },
+    Instruction { code: 0x5d, stages: exec::ld::STAGES,      stage: 0, repr: "LD E, L",        },
+    Instruction { code: 0x5e, stages: exec::ld::STAGES,      stage: 0, repr: "LD E, (HL)",     },
+    Instruction { code: 0x5f, stages: exec::ld::STAGES,      stage: 0, repr: "LD E, A",        },
+    Instruction { code: 0x60, stages: exec::ld::STAGES,      stage: 0, repr: "LD H, B",        },
+    Instruction { code: 0x61, stages: exec::ld::STAGES,      stage: 0, repr: "LD H, C",        },
+    Instruction { code: 0x62, stages: exec::ld::STAGES,      stage: 0, repr: "LD H, D",        },
+    Instruction { code: 0x63, stages: exec::ld::STAGES,      stage: 0, repr: "LD H, E",        },
+    Instruction { code: 0x64, stages: exec::ld::STAGES,      stage: 0, repr: "LD H, H",        },
+    Instruction { code: 0x65, stages: exec::ld::STAGES,      stage: 0, repr: "LD H, L",        },
+    Instruction { code: 0x66, stages: exec::ld::STAGES,      stage: 0, repr: "LD H, (HL)",     },
+    Instruction { code: 0x67, stages: exec::ld::STAGES,      stage: 0, repr: "LD H, A",        },
+    Instruction { code: 0x68, stages: exec::ld::STAGES,      stage: 0, repr: "LD L, B",        },
+    Instruction { code: 0x69, stages: exec::ld::STAGES,      stage: 0, repr: "LD L, C",        },
+    Instruction { code: 0x6a, stages: exec::ld::STAGES,      stage: 0, repr: "LD L, D",        },
+    Instruction { code: 0x6b, stages: exec::ld::STAGES,      stage: 0, repr: "LD L, E",        },
+    Instruction { code: 0x6c, stages: exec::ld::STAGES,      stage: 0, repr: "LD L, H",        },
+    Instruction { code: 0x6d, stages: exec::ld::STAGES,      stage: 0, repr: "LD L, L",        },
+    Instruction { code: 0x6e, stages: exec::ld::STAGES,      stage: 0, repr: "LD L, (HL)",     },
+    Instruction { code: 0x6f, stages: exec::ld::STAGES,      stage: 0, repr: "LD L, A",        },
+    Instruction { code: 0x70, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL), B",     },
+    Instruction { code: 0x71, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL), C",     },
+    Instruction { code: 0x72, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL), D",     },
+    Instruction { code: 0x73, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL), E",     },
+    Instruction { code: 0x74, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL), H",     },
+    Instruction { code: 0x75, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL), L",     },
+    Instruction { code: 0x76, stages: exec::halt::STAGES,    stage: 0, repr: "HALT",           },
+    Instruction { code: 0x77, stages: exec::ld::STAGES,      stage: 0, repr: "LD (HL), A",     },
+    Instruction { code: 0x78, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, B",        },
+    Instruction { code: 0x79, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, C",        },
+    Instruction { code: 0x7a, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, D",        },
+    Instruction { code: 0x7b, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, E",        },
+    Instruction { code: 0x7c, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, H",        },
+    Instruction { code: 0x7d, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, L",        },
+    Instruction { code: 0x7e, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, (HL)",     },
+    Instruction { code: 0x7f, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, A",        },
+    Instruction { code: 0x80, stages: exec::add::STAGES,     stage: 0, repr: "ADD A, B",       },
+    Instruction { code: 0x81, stages: exec::add::STAGES,     stage: 0, repr: "ADD A, C",       },
+    Instruction { code: 0x82, stages: exec::add::STAGES,     stage: 0, repr: "ADD A, D",       },
+    Instruction { code: 0x83, stages: exec::add::STAGES,     stage: 0, repr: "ADD A, E",       },
+    Instruction { code: 0x84, stages: exec::add::STAGES,     stage: 0, repr: "ADD A, H",       },
+    Instruction { code: 0x85, stages: exec::add::STAGES,     stage: 0, repr: "ADD A, L",       },
+    Instruction { code: 0x86, stages: exec::add::STAGES,     stage: 0, repr: "ADD A, (HL)",    },
+    Instruction { code: 0x87, stages: exec::add::STAGES,     stage: 0, repr: "ADD A, A",       },
+    Instruction { code: 0x88, stages: exec::adc::STAGES,     stage: 0, repr: "ADC A, B",       },
+    Instruction { code: 0x89, stages: exec::adc::STAGES,     stage: 0, repr: "ADC A, C",       },
+    Instruction { code: 0x8a, stages: exec::adc::STAGES,     stage: 0, repr: "ADC A, D",       },
+    Instruction { code: 0x8b, stages: exec::adc::STAGES,     stage: 0, repr: "ADC A, E",       },
+    Instruction { code: 0x8c, stages: exec::adc::STAGES,     stage: 0, repr: "ADC A, H",       },
+    Instruction { code: 0x8d, stages: exec::adc::STAGES,     stage: 0, repr: "ADC A, L",       },
+    Instruction { code: 0x8e, stages: exec::adc::STAGES,     stage: 0, repr: "ADC A, (HL)",    },
+    Instruction { code: 0x8f, stages: exec::adc::STAGES,     stage: 0, repr: "ADC A, A",       },
+    Instruction { code: 0x90, stages: exec::sub::STAGES,     stage: 0, repr: "SUB A, B",       },
+    Instruction { code: 0x91, stages: exec::sub::STAGES,     stage: 0, repr: "SUB A, C",       },
+    Instruction { code: 0x92, stages: exec::sub::STAGES,     stage: 0, repr: "SUB A, D",       },
+    Instruction { code: 0x93, stages: exec::sub::STAGES,     stage: 0, repr: "SUB A, E",       },
+    Instruction { code: 0x94, stages: exec::sub::STAGES,     stage: 0, repr: "SUB A, H",       },
+    Instruction { code: 0x95, stages: exec::sub::STAGES,     stage: 0, repr: "SUB A, L",       },
+    Instruction { code: 0x96, stages: exec::sub::STAGES,     stage: 0, repr: "SUB A, (HL)",    },
+    Instruction { code: 0x97, stages: exec::sub::STAGES,     stage: 0, repr: "SUB A, A",       },
+    Instruction { code: 0x98, stages: exec::sbc::STAGES,     stage: 0, repr: "SBC A, B",       },
+    Instruction { code: 0x99, stages: exec::sbc::STAGES,     stage: 0, repr: "SBC A, C",       },
+    Instruction { code: 0x9a, stages: exec::sbc::STAGES,     stage: 0, repr: "SBC A, D",       },
+    Instruction { code: 0x9b, stages: exec::sbc::STAGES,     stage: 0, repr: "SBC A, E",       },
+    Instruction { code: 0x9c, stages: exec::sbc::STAGES,     stage: 0, repr: "SBC A, H",       },
+    Instruction { code: 0x9d, stages: exec::sbc::STAGES,     stage: 0, repr: "SBC A, L",       },
+    Instruction { code: 0x9e, stages: exec::sbc::STAGES,     stage: 0, repr: "SBC A, (HL)",    },
+    Instruction { code: 0x9f, stages: exec::sbc::STAGES,     stage: 0, repr: "SBC A, A",       },
+    Instruction { code: 0xa0, stages: exec::and::STAGES,     stage: 0, repr: "AND A, B",       },
+    Instruction { code: 0xa1, stages: exec::and::STAGES,     stage: 0, repr: "AND A, C",       },
+    Instruction { code: 0xa2, stages: exec::and::STAGES,     stage: 0, repr: "AND A, D",       },
+    Instruction { code: 0xa3, stages: exec::and::STAGES,     stage: 0, repr: "AND A, E",       },
+    Instruction { code: 0xa4, stages: exec::and::STAGES,     stage: 0, repr: "AND A, H",       },
+    Instruction { code: 0xa5, stages: exec::and::STAGES,     stage: 0, repr: "AND A, L",       },
+    Instruction { code: 0xa6, stages: exec::and::STAGES,     stage: 0, repr: "AND A, (HL)",    },
+    Instruction { code: 0xa7, stages: exec::and::STAGES,     stage: 0, repr: "AND A, A",       },
+    Instruction { code: 0xa8, stages: exec::xor::STAGES,     stage: 0, repr: "XOR A, B",       },
+    Instruction { code: 0xa9, stages: exec::xor::STAGES,     stage: 0, repr: "XOR A, C",       },
+    Instruction { code: 0xaa, stages: exec::xor::STAGES,     stage: 0, repr: "XOR A, D",       },
+    Instruction { code: 0xab, stages: exec::xor::STAGES,     stage: 0, repr: "XOR A, E",       },
+    Instruction { code: 0xac, stages: exec::xor::STAGES,     stage: 0, repr: "XOR A, H",       },
+    Instruction { code: 0xad, stages: exec::xor::STAGES,     stage: 0, repr: "XOR A, L",       },
+    Instruction { code: 0xae, stages: exec::xor::STAGES,     stage: 0, repr: "XOR A, (HL)",    },
+    Instruction { code: 0xaf, stages: exec::xor::STAGES,     stage: 0, repr: "XOR A, A",       },
+    Instruction { code: 0xb0, stages: exec::or::STAGES,      stage: 0, repr: "OR A, B",        },
+    Instruction { code: 0xb1, stages: exec::or::STAGES,      stage: 0, repr: "OR A, C",        },
+    Instruction { code: 0xb2, stages: exec::or::STAGES,      stage: 0, repr: "OR A, D",        },
+    Instruction { code: 0xb3, stages: exec::or::STAGES,      stage: 0, repr: "OR A, E",        },
+    Instruction { code: 0xb4, stages: exec::or::STAGES,      stage: 0, repr: "OR A, H",        },
+    Instruction { code: 0xb5, stages: exec::or::STAGES,      stage: 0, repr: "OR A, L",        },
+    Instruction { code: 0xb6, stages: exec::or::STAGES,      stage: 0, repr: "OR A, (HL)",     },
+    Instruction { code: 0xb7, stages: exec::or::STAGES,      stage: 0, repr: "OR A, A",        },
+    Instruction { code: 0xb8, stages: exec::cp::STAGES,      stage: 0, repr: "CP A, B",        },
+    Instruction { code: 0xb9, stages: exec::cp::STAGES,      stage: 0, repr: "CP A, C",        },
+    Instruction { code: 0xba, stages: exec::cp::STAGES,      stage: 0, repr: "CP A, D",        },
+    Instruction { code: 0xbb, stages: exec::cp::STAGES,      stage: 0, repr: "CP A, E",        },
+    Instruction { code: 0xbc, stages: exec::cp::STAGES,      stage: 0, repr: "CP A, H",        },
+    Instruction { code: 0xbd, stages: exec::cp::STAGES,      stage: 0, repr: "CP A, L",        },
+    Instruction { code: 0xbe, stages: exec::cp::STAGES,      stage: 0, repr: "CP A, (HL)",     },
+    Instruction { code: 0xbf, stages: exec::cp::STAGES,      stage: 0, repr: "CP A, A",        },
+    Instruction { code: 0xc0, stages: exec::ret::STAGES,     stage: 0, repr: "RET NZ",         },
+    Instruction { code: 0xc1, stages: exec::pop::STAGES,     stage: 0, repr: "POP BC",         },
+    Instruction { code: 0xc2, stages: exec::jp::STAGES,      stage: 0, repr: "JP NZ, a16",     },
+    Instruction { code: 0xc3, stages: exec::jp::STAGES,      stage: 0, repr: "JP a16",         },
+    Instruction { code: 0xc4, stages: exec::call::STAGES,    stage: 0, repr: "CALL NZ, a16",   },
+    Instruction { code: 0xc5, stages: exec::push::STAGES,    stage: 0, repr: "PUSH BC",        },
+    Instruction { code: 0xc6, stages: exec::add::STAGES,     stage: 0, repr: "ADD A, n8",      },
+    Instruction { code: 0xc7, stages: exec::rst::STAGES,     stage: 0, repr: "RST 00H",        },
+    Instruction { code: 0xc8, stages: exec::ret::STAGES,     stage: 0, repr: "RET Z",          },
+    Instruction { code: 0xc9, stages: exec::ret::STAGES,     stage: 0, repr: "RET",            },
+    Instruction { code: 0xca, stages: exec::jp::STAGES,      stage: 0, repr: "JP Z, a16",      },
+    Instruction { code: 0xcb, stages: exec::prefix::STAGES,  stage: 0, repr: "PREFIX",         },
+    Instruction { code: 0xcc, stages: exec::call::STAGES,    stage: 0, repr: "CALL Z, a16",    },
+    Instruction { code: 0xcd, stages: exec::call::STAGES,    stage: 0, repr: "CALL a16",       },
+    Instruction { code: 0xce, stages: exec::adc::STAGES,     stage: 0, repr: "ADC A, n8",      },
+    Instruction { code: 0xcf, stages: exec::rst::STAGES,     stage: 0, repr: "RST 08H",        },
+    Instruction { code: 0xd0, stages: exec::ret::STAGES,     stage: 0, repr: "RET NC",         },
+    Instruction { code: 0xd1, stages: exec::pop::STAGES,     stage: 0, repr: "POP DE",         },
+    Instruction { code: 0xd2, stages: exec::jp::STAGES,      stage: 0, repr: "JP NC, a16",     },
+    Instruction { code: 0xd3, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xd4, stages: exec::call::STAGES,    stage: 0, repr: "CALL NC, a16",   },
+    Instruction { code: 0xd5, stages: exec::push::STAGES,    stage: 0, repr: "PUSH DE",        },
+    Instruction { code: 0xd6, stages: exec::sub::STAGES,     stage: 0, repr: "SUB n8",         },
+    Instruction { code: 0xd7, stages: exec::rst::STAGES,     stage: 0, repr: "RST 10H",        },
+    Instruction { code: 0xd8, stages: exec::ret::STAGES,     stage: 0, repr: "RET C",          },
+    Instruction { code: 0xd9, stages: exec::reti::STAGES,    stage: 0, repr: "RETI",           },
+    Instruction { code: 0xda, stages: exec::jp::STAGES,      stage: 0, repr: "JP C, a16",      },
+    Instruction { code: 0xdb, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xdc, stages: exec::call::STAGES,    stage: 0, repr: "CALL C, a16",    },
+    Instruction { code: 0xdd, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xde, stages: exec::sbc::STAGES,     stage: 0, repr: "SBC A, n8",      },
+    Instruction { code: 0xdf, stages: exec::rst::STAGES,     stage: 0, repr: "RST 18H",        },
+    Instruction { code: 0xe0, stages: exec::ldh::STAGES,     stage: 0, repr: "LDH (a8), A",    },
+    Instruction { code: 0xe1, stages: exec::pop::STAGES,     stage: 0, repr: "POP HL",         },
+    Instruction { code: 0xe2, stages: exec::ldh::STAGES,     stage: 0, repr: "LD (C), A",      },
+    Instruction { code: 0xe3, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xe4, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xe5, stages: exec::push::STAGES,    stage: 0, repr: "PUSH HL",        },
+    Instruction { code: 0xe6, stages: exec::and::STAGES,     stage: 0, repr: "AND A, n8",      },
+    Instruction { code: 0xe7, stages: exec::rst::STAGES,     stage: 0, repr: "RST 20H",        },
+    Instruction { code: 0xe8, stages: exec::addw::STAGES,    stage: 0, repr: "ADD SP, e8",     },
+    Instruction { code: 0xe9, stages: exec::jp::STAGES,      stage: 0, repr: "JP HL",          },
+    Instruction { code: 0xea, stages: exec::ld::STAGES,      stage: 0, repr: "LD (a16), A",    },
+    Instruction { code: 0xeb, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xec, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xed, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xee, stages: exec::xor::STAGES,     stage: 0, repr: "XOR A, n8",      },
+    Instruction { code: 0xef, stages: exec::rst::STAGES,     stage: 0, repr: "RST 28H",        },
+    Instruction { code: 0xf0, stages: exec::ldh::STAGES,     stage: 0, repr: "LDH A, (a8)",    },
+    Instruction { code: 0xf1, stages: exec::pop::STAGES,     stage: 0, repr: "POP AF",         },
+    Instruction { code: 0xf2, stages: exec::ldh::STAGES,     stage: 0, repr: "LD A, (C)",      },
+    Instruction { code: 0xf3, stages: exec::di::STAGES,      stage: 0, repr: "DI",             },
+    Instruction { code: 0xf4, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xf5, stages: exec::push::STAGES,    stage: 0, repr: "PUSH AF",        },
+    Instruction { code: 0xf6, stages: exec::or::STAGES,      stage: 0, repr: "OR A, n8",       },
+    Instruction { code: 0xf7, stages: exec::rst::STAGES,     stage: 0, repr: "RST 30H",        },
+    Instruction { code: 0xf8, stages: exec::ldw::STAGES,     stage: 0, repr: "LD HL, SP + e8", },
+    Instruction { code: 0xf9, stages: exec::ldw::STAGES,     stage: 0, repr: "LD SP, HL",      },
+    Instruction { code: 0xfa, stages: exec::ld::STAGES,      stage: 0, repr: "LD A, (a16)",    },
+    Instruction { code: 0xfb, stages: exec::ei::STAGES,      stage: 0, repr: "EI",             },
+    Instruction { code: 0xfc, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xfd, stages: exec::unused::STAGES,  stage: 0, repr: "UNUSED",         },
+    Instruction { code: 0xfe, stages: exec::cp::STAGES,      stage: 0, repr: "CP A, n8",       },
+    Instruction { code: 0xff, stages: exec::rst::STAGES,     stage: 0, repr: "RST 38H",        },
 ];
 
 /// Prefix lookup table.
 #[rustfmt::skip]
 pub const PREFIX: [Instruction; 0x100] = [
-    Instruction { code: 0x00, exec: exec::rlc::default(),  repr: "RLC B",       },
-    Instruction { code: 0x01, exec: exec::rlc::default(),  repr: "RLC C",       },
-    Instruction { code: 0x02, exec: exec::rlc::default(),  repr: "RLC D",       },
-    Instruction { code: 0x03, exec: exec::rlc::default(),  repr: "RLC E",       },
-    Instruction { code: 0x04, exec: exec::rlc::default(),  repr: "RLC H",       },
-    Instruction { code: 0x05, exec: exec::rlc::default(),  repr: "RLC L",       },
-    Instruction { code: 0x06, exec: exec::rlc::default(),  repr: "RLC (HL)",    },
-    Instruction { code: 0x07, exec: exec::rlc::default(),  repr: "RLC A",       },
-    Instruction { code: 0x08, exec: exec::rrc::default(),  repr: "RRC B",       },
-    Instruction { code: 0x09, exec: exec::rrc::default(),  repr: "RRC C",       },
-    Instruction { code: 0x0a, exec: exec::rrc::default(),  repr: "RRC D",       },
-    Instruction { code: 0x0b, exec: exec::rrc::default(),  repr: "RRC E",       },
-    Instruction { code: 0x0c, exec: exec::rrc::default(),  repr: "RRC H",       },
-    Instruction { code: 0x0d, exec: exec::rrc::default(),  repr: "RRC L",       },
-    Instruction { code: 0x0e, exec: exec::rrc::default(),  repr: "RRC (HL)",    },
-    Instruction { code: 0x0f, exec: exec::rrc::default(),  repr: "RRC A",       },
-    Instruction { code: 0x10, exec: exec::rl::default(),   repr: "RL B",        },
-    Instruction { code: 0x11, exec: exec::rl::default(),   repr: "RL C",        },
-    Instruction { code: 0x12, exec: exec::rl::default(),   repr: "RL D",        },
-    Instruction { code: 0x13, exec: exec::rl::default(),   repr: "RL E",        },
-    Instruction { code: 0x14, exec: exec::rl::default(),   repr: "RL H",        },
-    Instruction { code: 0x15, exec: exec::rl::default(),   repr: "RL L",        },
-    Instruction { code: 0x16, exec: exec::rl::default(),   repr: "RL (HL)",     },
-    Instruction { code: 0x17, exec: exec::rl::default(),   repr: "RL A",        },
-    Instruction { code: 0x18, exec: exec::rr::default(),   repr: "RR B",        },
-    Instruction { code: 0x19, exec: exec::rr::default(),   repr: "RR C",        },
-    Instruction { code: 0x1a, exec: exec::rr::default(),   repr: "RR D",        },
-    Instruction { code: 0x1b, exec: exec::rr::default(),   repr: "RR E",        },
-    Instruction { code: 0x1c, exec: exec::rr::default(),   repr: "RR H",        },
-    Instruction { code: 0x1d, exec: exec::rr::default(),   repr: "RR L",        },
-    Instruction { code: 0x1e, exec: exec::rr::default(),   repr: "RR (HL)",     },
-    Instruction { code: 0x1f, exec: exec::rr::default(),   repr: "RR A",        },
-    Instruction { code: 0x20, exec: exec::sla::default(),  repr: "SLA B",       },
-    Instruction { code: 0x21, exec: exec::sla::default(),  repr: "SLA C",       },
-    Instruction { code: 0x22, exec: exec::sla::default(),  repr: "SLA D",       },
-    Instruction { code: 0x23, exec: exec::sla::default(),  repr: "SLA E",       },
-    Instruction { code: 0x24, exec: exec::sla::default(),  repr: "SLA H",       },
-    Instruction { code: 0x25, exec: exec::sla::default(),  repr: "SLA L",       },
-    Instruction { code: 0x26, exec: exec::sla::default(),  repr: "SLA (HL)",    },
-    Instruction { code: 0x27, exec: exec::sla::default(),  repr: "SLA A",       },
-    Instruction { code: 0x28, exec: exec::sra::default(),  repr: "SRA B",       },
-    Instruction { code: 0x29, exec: exec::sra::default(),  repr: "SRA C",       },
-    Instruction { code: 0x2a, exec: exec::sra::default(),  repr: "SRA D",       },
-    Instruction { code: 0x2b, exec: exec::sra::default(),  repr: "SRA E",       },
-    Instruction { code: 0x2c, exec: exec::sra::default(),  repr: "SRA H",       },
-    Instruction { code: 0x2d, exec: exec::sra::default(),  repr: "SRA L",       },
-    Instruction { code: 0x2e, exec: exec::sra::default(),  repr: "SRA (HL)",    },
-    Instruction { code: 0x2f, exec: exec::sra::default(),  repr: "SRA A",       },
-    Instruction { code: 0x30, exec: exec::swap::default(), repr: "SWAP B",      },
-    Instruction { code: 0x31, exec: exec::swap::default(), repr: "SWAP C",      },
-    Instruction { code: 0x32, exec: exec::swap::default(), repr: "SWAP D",      },
-    Instruction { code: 0x33, exec: exec::swap::default(), repr: "SWAP E",      },
-    Instruction { code: 0x34, exec: exec::swap::default(), repr: "SWAP H",      },
-    Instruction { code: 0x35, exec: exec::swap::default(), repr: "SWAP L",      },
-    Instruction { code: 0x36, exec: exec::swap::default(), repr: "SWAP (HL)",   },
-    Instruction { code: 0x37, exec: exec::swap::default(), repr: "SWAP A",      },
-    Instruction { code: 0x38, exec: exec::srl::default(),  repr: "SRL B",       },
-    Instruction { code: 0x39, exec: exec::srl::default(),  repr: "SRL C",       },
-    Instruction { code: 0x3a, exec: exec::srl::default(),  repr: "SRL D",       },
-    Instruction { code: 0x3b, exec: exec::srl::default(),  repr: "SRL E",       },
-    Instruction { code: 0x3c, exec: exec::srl::default(),  repr: "SRL H",       },
-    Instruction { code: 0x3d, exec: exec::srl::default(),  repr: "SRL L",       },
-    Instruction { code: 0x3e, exec: exec::srl::default(),  repr: "SRL (HL)",    },
-    Instruction { code: 0x3f, exec: exec::srl::default(),  repr: "SRL A",       },
-    Instruction { code: 0x40, exec: exec::bit::default(),  repr: "BIT 0, B",    },
-    Instruction { code: 0x41, exec: exec::bit::default(),  repr: "BIT 0, C",    },
-    Instruction { code: 0x42, exec: exec::bit::default(),  repr: "BIT 0, D",    },
-    Instruction { code: 0x43, exec: exec::bit::default(),  repr: "BIT 0, E",    },
-    Instruction { code: 0x44, exec: exec::bit::default(),  repr: "BIT 0, H",    },
-    Instruction { code: 0x45, exec: exec::bit::default(),  repr: "BIT 0, L",    },
-    Instruction { code: 0x46, exec: exec::bit::default(),  repr: "BIT 0, (HL)", },
-    Instruction { code: 0x47, exec: exec::bit::default(),  repr: "BIT 0, A",    },
-    Instruction { code: 0x48, exec: exec::bit::default(),  repr: "BIT 1, B",    },
-    Instruction { code: 0x49, exec: exec::bit::default(),  repr: "BIT 1, C",    },
-    Instruction { code: 0x4a, exec: exec::bit::default(),  repr: "BIT 1, D",    },
-    Instruction { code: 0x4b, exec: exec::bit::default(),  repr: "BIT 1, E",    },
-    Instruction { code: 0x4c, exec: exec::bit::default(),  repr: "BIT 1, H",    },
-    Instruction { code: 0x4d, exec: exec::bit::default(),  repr: "BIT 1, L",    },
-    Instruction { code: 0x4e, exec: exec::bit::default(),  repr: "BIT 1, (HL)", },
-    Instruction { code: 0x4f, exec: exec::bit::default(),  repr: "BIT 1, A",    },
-    Instruction { code: 0x50, exec: exec::bit::default(),  repr: "BIT 2, B",    },
-    Instruction { code: 0x51, exec: exec::bit::default(),  repr: "BIT 2, C",    },
-    Instruction { code: 0x52, exec: exec::bit::default(),  repr: "BIT 2, D",    },
-    Instruction { code: 0x53, exec: exec::bit::default(),  repr: "BIT 2, E",    },
-    Instruction { code: 0x54, exec: exec::bit::default(),  repr: "BIT 2, H",    },
-    Instruction { code: 0x55, exec: exec::bit::default(),  repr: "BIT 2, L",    },
-    Instruction { code: 0x56, exec: exec::bit::default(),  repr: "BIT 2, (HL)", },
-    Instruction { code: 0x57, exec: exec::bit::default(),  repr: "BIT 2, A",    },
-    Instruction { code: 0x58, exec: exec::bit::default(),  repr: "BIT 3, B",    },
-    Instruction { code: 0x59, exec: exec::bit::default(),  repr: "BIT 3, C",    },
-    Instruction { code: 0x5a, exec: exec::bit::default(),  repr: "BIT 3, D",    },
-    Instruction { code: 0x5b, exec: exec::bit::default(),  repr: "BIT 3, E",    },
-    Instruction { code: 0x5c, exec: exec::bit::default(),  repr: "BIT 3, H",    },
-    Instruction { code: 0x5d, exec: exec::bit::default(),  repr: "BIT 3, L",    },
-    Instruction { code: 0x5e, exec: exec::bit::default(),  repr: "BIT 3, (HL)", },
-    Instruction { code: 0x5f, exec: exec::bit::default(),  repr: "BIT 3, A",    },
-    Instruction { code: 0x60, exec: exec::bit::default(),  repr: "BIT 4, B",    },
-    Instruction { code: 0x61, exec: exec::bit::default(),  repr: "BIT 4, C",    },
-    Instruction { code: 0x62, exec: exec::bit::default(),  repr: "BIT 4, D",    },
-    Instruction { code: 0x63, exec: exec::bit::default(),  repr: "BIT 4, E",    },
-    Instruction { code: 0x64, exec: exec::bit::default(),  repr: "BIT 4, H",    },
-    Instruction { code: 0x65, exec: exec::bit::default(),  repr: "BIT 4, L",    },
-    Instruction { code: 0x66, exec: exec::bit::default(),  repr: "BIT 4, (HL)", },
-    Instruction { code: 0x67, exec: exec::bit::default(),  repr: "BIT 4, A",    },
-    Instruction { code: 0x68, exec: exec::bit::default(),  repr: "BIT 5, B",    },
-    Instruction { code: 0x69, exec: exec::bit::default(),  repr: "BIT 5, C",    },
-    Instruction { code: 0x6a, exec: exec::bit::default(),  repr: "BIT 5, D",    },
-    Instruction { code: 0x6b, exec: exec::bit::default(),  repr: "BIT 5, E",    },
-    Instruction { code: 0x6c, exec: exec::bit::default(),  repr: "BIT 5, H",    },
-    Instruction { code: 0x6d, exec: exec::bit::default(),  repr: "BIT 5, L",    },
-    Instruction { code: 0x6e, exec: exec::bit::default(),  repr: "BIT 5, (HL)", },
-    Instruction { code: 0x6f, exec: exec::bit::default(),  repr: "BIT 5, A",    },
-    Instruction { code: 0x70, exec: exec::bit::default(),  repr: "BIT 6, B",    },
-    Instruction { code: 0x71, exec: exec::bit::default(),  repr: "BIT 6, C",    },
-    Instruction { code: 0x72, exec: exec::bit::default(),  repr: "BIT 6, D",    },
-    Instruction { code: 0x73, exec: exec::bit::default(),  repr: "BIT 6, E",    },
-    Instruction { code: 0x74, exec: exec::bit::default(),  repr: "BIT 6, H",    },
-    Instruction { code: 0x75, exec: exec::bit::default(),  repr: "BIT 6, L",    },
-    Instruction { code: 0x76, exec: exec::bit::default(),  repr: "BIT 6, (HL)", },
-    Instruction { code: 0x77, exec: exec::bit::default(),  repr: "BIT 6, A",    },
-    Instruction { code: 0x78, exec: exec::bit::default(),  repr: "BIT 7, B",    },
-    Instruction { code: 0x79, exec: exec::bit::default(),  repr: "BIT 7, C",    },
-    Instruction { code: 0x7a, exec: exec::bit::default(),  repr: "BIT 7, D",    },
-    Instruction { code: 0x7b, exec: exec::bit::default(),  repr: "BIT 7, E",    },
-    Instruction { code: 0x7c, exec: exec::bit::default(),  repr: "BIT 7, H",    },
-    Instruction { code: 0x7d, exec: exec::bit::default(),  repr: "BIT 7, L",    },
-    Instruction { code: 0x7e, exec: exec::bit::default(),  repr: "BIT 7, (HL)", },
-    Instruction { code: 0x7f, exec: exec::bit::default(),  repr: "BIT 7, A",    },
-    Instruction { code: 0x80, exec: exec::res::default(),  repr: "RES 0, B",    },
-    Instruction { code: 0x81, exec: exec::res::default(),  repr: "RES 0, C",    },
-    Instruction { code: 0x82, exec: exec::res::default(),  repr: "RES 0, D",    },
-    Instruction { code: 0x83, exec: exec::res::default(),  repr: "RES 0, E",    },
-    Instruction { code: 0x84, exec: exec::res::default(),  repr: "RES 0, H",    },
-    Instruction { code: 0x85, exec: exec::res::default(),  repr: "RES 0, L",    },
-    Instruction { code: 0x86, exec: exec::res::default(),  repr: "RES 0, (HL)", },
-    Instruction { code: 0x87, exec: exec::res::default(),  repr: "RES 0, A",    },
-    Instruction { code: 0x88, exec: exec::res::default(),  repr: "RES 1, B",    },
-    Instruction { code: 0x89, exec: exec::res::default(),  repr: "RES 1, C",    },
-    Instruction { code: 0x8a, exec: exec::res::default(),  repr: "RES 1, D",    },
-    Instruction { code: 0x8b, exec: exec::res::default(),  repr: "RES 1, E",    },
-    Instruction { code: 0x8c, exec: exec::res::default(),  repr: "RES 1, H",    },
-    Instruction { code: 0x8d, exec: exec::res::default(),  repr: "RES 1, L",    },
-    Instruction { code: 0x8e, exec: exec::res::default(),  repr: "RES 1, (HL)", },
-    Instruction { code: 0x8f, exec: exec::res::default(),  repr: "RES 1, A",    },
-    Instruction { code: 0x90, exec: exec::res::default(),  repr: "RES 2, B",    },
-    Instruction { code: 0x91, exec: exec::res::default(),  repr: "RES 2, C",    },
-    Instruction { code: 0x92, exec: exec::res::default(),  repr: "RES 2, D",    },
-    Instruction { code: 0x93, exec: exec::res::default(),  repr: "RES 2, E",    },
-    Instruction { code: 0x94, exec: exec::res::default(),  repr: "RES 2, H",    },
-    Instruction { code: 0x95, exec: exec::res::default(),  repr: "RES 2, L",    },
-    Instruction { code: 0x96, exec: exec::res::default(),  repr: "RES 2, (HL)", },
-    Instruction { code: 0x97, exec: exec::res::default(),  repr: "RES 2, A",    },
-    Instruction { code: 0x98, exec: exec::res::default(),  repr: "RES 3, B",    },
-    Instruction { code: 0x99, exec: exec::res::default(),  repr: "RES 3, C",    },
-    Instruction { code: 0x9a, exec: exec::res::default(),  repr: "RES 3, D",    },
-    Instruction { code: 0x9b, exec: exec::res::default(),  repr: "RES 3, E",    },
-    Instruction { code: 0x9c, exec: exec::res::default(),  repr: "RES 3, H",    },
-    Instruction { code: 0x9d, exec: exec::res::default(),  repr: "RES 3, L",    },
-    Instruction { code: 0x9e, exec: exec::res::default(),  repr: "RES 3, (HL)", },
-    Instruction { code: 0x9f, exec: exec::res::default(),  repr: "RES 3, A",    },
-    Instruction { code: 0xa0, exec: exec::res::default(),  repr: "RES 4, B",    },
-    Instruction { code: 0xa1, exec: exec::res::default(),  repr: "RES 4, C",    },
-    Instruction { code: 0xa2, exec: exec::res::default(),  repr: "RES 4, D",    },
-    Instruction { code: 0xa3, exec: exec::res::default(),  repr: "RES 4, E",    },
-    Instruction { code: 0xa4, exec: exec::res::default(),  repr: "RES 4, H",    },
-    Instruction { code: 0xa5, exec: exec::res::default(),  repr: "RES 4, L",    },
-    Instruction { code: 0xa6, exec: exec::res::default(),  repr: "RES 4, (HL)", },
-    Instruction { code: 0xa7, exec: exec::res::default(),  repr: "RES 4, A",    },
-    Instruction { code: 0xa8, exec: exec::res::default(),  repr: "RES 5, B",    },
-    Instruction { code: 0xa9, exec: exec::res::default(),  repr: "RES 5, C",    },
-    Instruction { code: 0xaa, exec: exec::res::default(),  repr: "RES 5, D",    },
-    Instruction { code: 0xab, exec: exec::res::default(),  repr: "RES 5, E",    },
-    Instruction { code: 0xac, exec: exec::res::default(),  repr: "RES 5, H",    },
-    Instruction { code: 0xad, exec: exec::res::default(),  repr: "RES 5, L",    },
-    Instruction { code: 0xae, exec: exec::res::default(),  repr: "RES 5, (HL)", },
-    Instruction { code: 0xaf, exec: exec::res::default(),  repr: "RES 5, A",    },
-    Instruction { code: 0xb0, exec: exec::res::default(),  repr: "RES 6, B",    },
-    Instruction { code: 0xb1, exec: exec::res::default(),  repr: "RES 6, C",    },
-    Instruction { code: 0xb2, exec: exec::res::default(),  repr: "RES 6, D",    },
-    Instruction { code: 0xb3, exec: exec::res::default(),  repr: "RES 6, E",    },
-    Instruction { code: 0xb4, exec: exec::res::default(),  repr: "RES 6, H",    },
-    Instruction { code: 0xb5, exec: exec::res::default(),  repr: "RES 6, L",    },
-    Instruction { code: 0xb6, exec: exec::res::default(),  repr: "RES 6, (HL)", },
-    Instruction { code: 0xb7, exec: exec::res::default(),  repr: "RES 6, A",    },
-    Instruction { code: 0xb8, exec: exec::res::default(),  repr: "RES 7, B",    },
-    Instruction { code: 0xb9, exec: exec::res::default(),  repr: "RES 7, C",    },
-    Instruction { code: 0xba, exec: exec::res::default(),  repr: "RES 7, D",    },
-    Instruction { code: 0xbb, exec: exec::res::default(),  repr: "RES 7, E",    },
-    Instruction { code: 0xbc, exec: exec::res::default(),  repr: "RES 7, H",    },
-    Instruction { code: 0xbd, exec: exec::res::default(),  repr: "RES 7, L",    },
-    Instruction { code: 0xbe, exec: exec::res::default(),  repr: "RES 7, (HL)", },
-    Instruction { code: 0xbf, exec: exec::res::default(),  repr: "RES 7, A",    },
-    Instruction { code: 0xc0, exec: exec::set::default(),  repr: "SET 0, B",    },
-    Instruction { code: 0xc1, exec: exec::set::default(),  repr: "SET 0, C",    },
-    Instruction { code: 0xc2, exec: exec::set::default(),  repr: "SET 0, D",    },
-    Instruction { code: 0xc3, exec: exec::set::default(),  repr: "SET 0, E",    },
-    Instruction { code: 0xc4, exec: exec::set::default(),  repr: "SET 0, H",    },
-    Instruction { code: 0xc5, exec: exec::set::default(),  repr: "SET 0, L",    },
-    Instruction { code: 0xc6, exec: exec::set::default(),  repr: "SET 0, (HL)", },
-    Instruction { code: 0xc7, exec: exec::set::default(),  repr: "SET 0, A",    },
-    Instruction { code: 0xc8, exec: exec::set::default(),  repr: "SET 1, B",    },
-    Instruction { code: 0xc9, exec: exec::set::default(),  repr: "SET 1, C",    },
-    Instruction { code: 0xca, exec: exec::set::default(),  repr: "SET 1, D",    },
-    Instruction { code: 0xcb, exec: exec::set::default(),  repr: "SET 1, E",    },
-    Instruction { code: 0xcc, exec: exec::set::default(),  repr: "SET 1, H",    },
-    Instruction { code: 0xcd, exec: exec::set::default(),  repr: "SET 1, L",    },
-    Instruction { code: 0xce, exec: exec::set::default(),  repr: "SET 1, (HL)", },
-    Instruction { code: 0xcf, exec: exec::set::default(),  repr: "SET 1, A",    },
-    Instruction { code: 0xd0, exec: exec::set::default(),  repr: "SET 2, B",    },
-    Instruction { code: 0xd1, exec: exec::set::default(),  repr: "SET 2, C",    },
-    Instruction { code: 0xd2, exec: exec::set::default(),  repr: "SET 2, D",    },
-    Instruction { code: 0xd3, exec: exec::set::default(),  repr: "SET 2, E",    },
-    Instruction { code: 0xd4, exec: exec::set::default(),  repr: "SET 2, H",    },
-    Instruction { code: 0xd5, exec: exec::set::default(),  repr: "SET 2, L",    },
-    Instruction { code: 0xd6, exec: exec::set::default(),  repr: "SET 2, (HL)", },
-    Instruction { code: 0xd7, exec: exec::set::default(),  repr: "SET 2, A",    },
-    Instruction { code: 0xd8, exec: exec::set::default(),  repr: "SET 3, B",    },
-    Instruction { code: 0xd9, exec: exec::set::default(),  repr: "SET 3, C",    },
-    Instruction { code: 0xda, exec: exec::set::default(),  repr: "SET 3, D",    },
-    Instruction { code: 0xdb, exec: exec::set::default(),  repr: "SET 3, E",    },
-    Instruction { code: 0xdc, exec: exec::set::default(),  repr: "SET 3, H",    },
-    Instruction { code: 0xdd, exec: exec::set::default(),  repr: "SET 3, L",    },
-    Instruction { code: 0xde, exec: exec::set::default(),  repr: "SET 3, (HL)", },
-    Instruction { code: 0xdf, exec: exec::set::default(),  repr: "SET 3, A",    },
-    Instruction { code: 0xe0, exec: exec::set::default(),  repr: "SET 4, B",    },
-    Instruction { code: 0xe1, exec: exec::set::default(),  repr: "SET 4, C",    },
-    Instruction { code: 0xe2, exec: exec::set::default(),  repr: "SET 4, D",    },
-    Instruction { code: 0xe3, exec: exec::set::default(),  repr: "SET 4, E",    },
-    Instruction { code: 0xe4, exec: exec::set::default(),  repr: "SET 4, H",    },
-    Instruction { code: 0xe5, exec: exec::set::default(),  repr: "SET 4, L",    },
-    Instruction { code: 0xe6, exec: exec::set::default(),  repr: "SET 4, (HL)", },
-    Instruction { code: 0xe7, exec: exec::set::default(),  repr: "SET 4, A",    },
-    Instruction { code: 0xe8, exec: exec::set::default(),  repr: "SET 5, B",    },
-    Instruction { code: 0xe9, exec: exec::set::default(),  repr: "SET 5, C",    },
-    Instruction { code: 0xea, exec: exec::set::default(),  repr: "SET 5, D",    },
-    Instruction { code: 0xeb, exec: exec::set::default(),  repr: "SET 5, E",    },
-    Instruction { code: 0xec, exec: exec::set::default(),  repr: "SET 5, H",    },
-    Instruction { code: 0xed, exec: exec::set::default(),  repr: "SET 5, L",    },
-    Instruction { code: 0xee, exec: exec::set::default(),  repr: "SET 5, (HL)", },
-    Instruction { code: 0xef, exec: exec::set::default(),  repr: "SET 5, A",    },
-    Instruction { code: 0xf0, exec: exec::set::default(),  repr: "SET 6, B",    },
-    Instruction { code: 0xf1, exec: exec::set::default(),  repr: "SET 6, C",    },
-    Instruction { code: 0xf2, exec: exec::set::default(),  repr: "SET 6, D",    },
-    Instruction { code: 0xf3, exec: exec::set::default(),  repr: "SET 6, E",    },
-    Instruction { code: 0xf4, exec: exec::set::default(),  repr: "SET 6, H",    },
-    Instruction { code: 0xf5, exec: exec::set::default(),  repr: "SET 6, L",    },
-    Instruction { code: 0xf6, exec: exec::set::default(),  repr: "SET 6, (HL)", },
-    Instruction { code: 0xf7, exec: exec::set::default(),  repr: "SET 6, A",    },
-    Instruction { code: 0xf8, exec: exec::set::default(),  repr: "SET 7, B",    },
-    Instruction { code: 0xf9, exec: exec::set::default(),  repr: "SET 7, C",    },
-    Instruction { code: 0xfa, exec: exec::set::default(),  repr: "SET 7, D",    },
-    Instruction { code: 0xfb, exec: exec::set::default(),  repr: "SET 7, E",    },
-    Instruction { code: 0xfc, exec: exec::set::default(),  repr: "SET 7, H",    },
-    Instruction { code: 0xfd, exec: exec::set::default(),  repr: "SET 7, L",    },
-    Instruction { code: 0xfe, exec: exec::set::default(),  repr: "SET 7, (HL)", },
-    Instruction { code: 0xff, exec: exec::set::default(),  repr: "SET 7, A",    },
+    Instruction { code: 0x00, stages: exec::rlc::STAGES,   stage: 0, repr: "RLC B",       },
+    Instruction { code: 0x01, stages: exec::rlc::STAGES,   stage: 0, repr: "RLC C",       },
+    Instruction { code: 0x02, stages: exec::rlc::STAGES,   stage: 0, repr: "RLC D",       },
+    Instruction { code: 0x03, stages: exec::rlc::STAGES,   stage: 0, repr: "RLC E",       },
+    Instruction { code: 0x04, stages: exec::rlc::STAGES,   stage: 0, repr: "RLC H",       },
+    Instruction { code: 0x05, stages: exec::rlc::STAGES,   stage: 0, repr: "RLC L",       },
+    Instruction { code: 0x06, stages: exec::rlc::STAGES,   stage: 0, repr: "RLC (HL)",    },
+    Instruction { code: 0x07, stages: exec::rlc::STAGES,   stage: 0, repr: "RLC A",       },
+    Instruction { code: 0x08, stages: exec::rrc::STAGES,   stage: 0, repr: "RRC B",       },
+    Instruction { code: 0x09, stages: exec::rrc::STAGES,   stage: 0, repr: "RRC C",       },
+    Instruction { code: 0x0a, stages: exec::rrc::STAGES,   stage: 0, repr: "RRC D",       },
+    Instruction { code: 0x0b, stages: exec::rrc::STAGES,   stage: 0, repr: "RRC E",       },
+    Instruction { code: 0x0c, stages: exec::rrc::STAGES,   stage: 0, repr: "RRC H",       },
+    Instruction { code: 0x0d, stages: exec::rrc::STAGES,   stage: 0, repr: "RRC L",       },
+    Instruction { code: 0x0e, stages: exec::rrc::STAGES,   stage: 0, repr: "RRC (HL)",    },
+    Instruction { code: 0x0f, stages: exec::rrc::STAGES,   stage: 0, repr: "RRC A",       },
+    Instruction { code: 0x10, stages: exec::rl::STAGES,    stage: 0, repr: "RL B",        },
+    Instruction { code: 0x11, stages: exec::rl::STAGES,    stage: 0, repr: "RL C",        },
+    Instruction { code: 0x12, stages: exec::rl::STAGES,    stage: 0, repr: "RL D",        },
+    Instruction { code: 0x13, stages: exec::rl::STAGES,    stage: 0, repr: "RL E",        },
+    Instruction { code: 0x14, stages: exec::rl::STAGES,    stage: 0, repr: "RL H",        },
+    Instruction { code: 0x15, stages: exec::rl::STAGES,    stage: 0, repr: "RL L",        },
+    Instruction { code: 0x16, stages: exec::rl::STAGES,    stage: 0, repr: "RL (HL)",     },
+    Instruction { code: 0x17, stages: exec::rl::STAGES,    stage: 0, repr: "RL A",        },
+    Instruction { code: 0x18, stages: exec::rr::STAGES,    stage: 0, repr: "RR B",        },
+    Instruction { code: 0x19, stages: exec::rr::STAGES,    stage: 0, repr: "RR C",        },
+    Instruction { code: 0x1a, stages: exec::rr::STAGES,    stage: 0, repr: "RR D",        },
+    Instruction { code: 0x1b, stages: exec::rr::STAGES,    stage: 0, repr: "RR E",        },
+    Instruction { code: 0x1c, stages: exec::rr::STAGES,    stage: 0, repr: "RR H",        },
+    Instruction { code: 0x1d, stages: exec::rr::STAGES,    stage: 0, repr: "RR L",        },
+    Instruction { code: 0x1e, stages: exec::rr::STAGES,    stage: 0, repr: "RR (HL)",     },
+    Instruction { code: 0x1f, stages: exec::rr::STAGES,    stage: 0, repr: "RR A",        },
+    Instruction { code: 0x20, stages: exec::sla::STAGES,   stage: 0, repr: "SLA B",       },
+    Instruction { code: 0x21, stages: exec::sla::STAGES,   stage: 0, repr: "SLA C",       },
+    Instruction { code: 0x22, stages: exec::sla::STAGES,   stage: 0, repr: "SLA D",       },
+    Instruction { code: 0x23, stages: exec::sla::STAGES,   stage: 0, repr: "SLA E",       },
+    Instruction { code: 0x24, stages: exec::sla::STAGES,   stage: 0, repr: "SLA H",       },
+    Instruction { code: 0x25, stages: exec::sla::STAGES,   stage: 0, repr: "SLA L",       },
+    Instruction { code: 0x26, stages: exec::sla::STAGES,   stage: 0, repr: "SLA (HL)",    },
+    Instruction { code: 0x27, stages: exec::sla::STAGES,   stage: 0, repr: "SLA A",       },
+    Instruction { code: 0x28, stages: exec::sra::STAGES,   stage: 0, repr: "SRA B",       },
+    Instruction { code: 0x29, stages: exec::sra::STAGES,   stage: 0, repr: "SRA C",       },
+    Instruction { code: 0x2a, stages: exec::sra::STAGES,   stage: 0, repr: "SRA D",       },
+    Instruction { code: 0x2b, stages: exec::sra::STAGES,   stage: 0, repr: "SRA E",       },
+    Instruction { code: 0x2c, stages: exec::sra::STAGES,   stage: 0, repr: "SRA H",       },
+    Instruction { code: 0x2d, stages: exec::sra::STAGES,   stage: 0, repr: "SRA L",       },
+    Instruction { code: 0x2e, stages: exec::sra::STAGES,   stage: 0, repr: "SRA (HL)",    },
+    Instruction { code: 0x2f, stages: exec::sra::STAGES,   stage: 0, repr: "SRA A",       },
+    Instruction { code: 0x30, stages: exec::swap::STAGES,  stage: 0, repr: "SWAP B",      },
+    Instruction { code: 0x31, stages: exec::swap::STAGES,  stage: 0, repr: "SWAP C",      },
+    Instruction { code: 0x32, stages: exec::swap::STAGES,  stage: 0, repr: "SWAP D",      },
+    Instruction { code: 0x33, stages: exec::swap::STAGES,  stage: 0, repr: "SWAP E",      },
+    Instruction { code: 0x34, stages: exec::swap::STAGES,  stage: 0, repr: "SWAP H",      },
+    Instruction { code: 0x35, stages: exec::swap::STAGES,  stage: 0, repr: "SWAP L",      },
+    Instruction { code: 0x36, stages: exec::swap::STAGES,  stage: 0, repr: "SWAP (HL)",   },
+    Instruction { code: 0x37, stages: exec::swap::STAGES,  stage: 0, repr: "SWAP A",      },
+    Instruction { code: 0x38, stages: exec::srl::STAGES,   stage: 0, repr: "SRL B",       },
+    Instruction { code: 0x39, stages: exec::srl::STAGES,   stage: 0, repr: "SRL C",       },
+    Instruction { code: 0x3a, stages: exec::srl::STAGES,   stage: 0, repr: "SRL D",       },
+    Instruction { code: 0x3b, stages: exec::srl::STAGES,   stage: 0, repr: "SRL E",       },
+    Instruction { code: 0x3c, stages: exec::srl::STAGES,   stage: 0, repr: "SRL H",       },
+    Instruction { code: 0x3d, stages: exec::srl::STAGES,   stage: 0, repr: "SRL L",       },
+    Instruction { code: 0x3e, stages: exec::srl::STAGES,   stage: 0, repr: "SRL (HL)",    },
+    Instruction { code: 0x3f, stages: exec::srl::STAGES,   stage: 0, repr: "SRL A",       },
+    Instruction { code: 0x40, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 0, B",    },
+    Instruction { code: 0x41, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 0, C",    },
+    Instruction { code: 0x42, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 0, D",    },
+    Instruction { code: 0x43, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 0, E",    },
+    Instruction { code: 0x44, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 0, H",    },
+    Instruction { code: 0x45, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 0, L",    },
+    Instruction { code: 0x46, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 0, (HL)", },
+    Instruction { code: 0x47, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 0, A",    },
+    Instruction { code: 0x48, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 1, B",    },
+    Instruction { code: 0x49, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 1, C",    },
+    Instruction { code: 0x4a, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 1, D",    },
+    Instruction { code: 0x4b, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 1, E",    },
+    Instruction { code: 0x4c, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 1, H",    },
+    Instruction { code: 0x4d, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 1, L",    },
+    Instruction { code: 0x4e, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 1, (HL)", },
+    Instruction { code: 0x4f, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 1, A",    },
+    Instruction { code: 0x50, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 2, B",    },
+    Instruction { code: 0x51, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 2, C",    },
+    Instruction { code: 0x52, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 2, D",    },
+    Instruction { code: 0x53, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 2, E",    },
+    Instruction { code: 0x54, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 2, H",    },
+    Instruction { code: 0x55, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 2, L",    },
+    Instruction { code: 0x56, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 2, (HL)", },
+    Instruction { code: 0x57, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 2, A",    },
+    Instruction { code: 0x58, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 3, B",    },
+    Instruction { code: 0x59, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 3, C",    },
+    Instruction { code: 0x5a, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 3, D",    },
+    Instruction { code: 0x5b, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 3, E",    },
+    Instruction { code: 0x5c, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 3, H",    },
+    Instruction { code: 0x5d, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 3, L",    },
+    Instruction { code: 0x5e, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 3, (HL)", },
+    Instruction { code: 0x5f, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 3, A",    },
+    Instruction { code: 0x60, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 4, B",    },
+    Instruction { code: 0x61, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 4, C",    },
+    Instruction { code: 0x62, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 4, D",    },
+    Instruction { code: 0x63, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 4, E",    },
+    Instruction { code: 0x64, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 4, H",    },
+    Instruction { code: 0x65, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 4, L",    },
+    Instruction { code: 0x66, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 4, (HL)", },
+    Instruction { code: 0x67, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 4, A",    },
+    Instruction { code: 0x68, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 5, B",    },
+    Instruction { code: 0x69, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 5, C",    },
+    Instruction { code: 0x6a, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 5, D",    },
+    Instruction { code: 0x6b, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 5, E",    },
+    Instruction { code: 0x6c, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 5, H",    },
+    Instruction { code: 0x6d, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 5, L",    },
+    Instruction { code: 0x6e, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 5, (HL)", },
+    Instruction { code: 0x6f, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 5, A",    },
+    Instruction { code: 0x70, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 6, B",    },
+    Instruction { code: 0x71, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 6, C",    },
+    Instruction { code: 0x72, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 6, D",    },
+    Instruction { code: 0x73, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 6, E",    },
+    Instruction { code: 0x74, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 6, H",    },
+    Instruction { code: 0x75, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 6, L",    },
+    Instruction { code: 0x76, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 6, (HL)", },
+    Instruction { code: 0x77, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 6, A",    },
+    Instruction { code: 0x78, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 7, B",    },
+    Instruction { code: 0x79, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 7, C",    },
+    Instruction { code: 0x7a, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 7, D",    },
+    Instruction { code: 0x7b, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 7, E",    },
+    Instruction { code: 0x7c, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 7, H",    },
+    Instruction { code: 0x7d, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 7, L",    },
+    Instruction { code: 0x7e, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 7, (HL)", },
+    Instruction { code: 0x7f, stages: exec::bit::STAGES,   stage: 0, repr: "BIT 7, A",    },
+    Instruction { code: 0x80, stages: exec::res::STAGES,   stage: 0, repr: "RES 0, B",    },
+    Instruction { code: 0x81, stages: exec::res::STAGES,   stage: 0, repr: "RES 0, C",    },
+    Instruction { code: 0x82, stages: exec::res::STAGES,   stage: 0, repr: "RES 0, D",    },
+    Instruction { code: 0x83, stages: exec::res::STAGES,   stage: 0, repr: "RES 0, E",    },
+    Instruction { code: 0x84, stages: exec::res::STAGES,   stage: 0, repr: "RES 0, H",    },
+    Instruction { code: 0x85, stages: exec::res::STAGES,   stage: 0, repr: "RES 0, L",    },
+    Instruction { code: 0x86, stages: exec::res::STAGES,   stage: 0, repr: "RES 0, (HL)", },
+    Instruction { code: 0x87, stages: exec::res::STAGES,   stage: 0, repr: "RES 0, A",    },
+    Instruction { code: 0x88, stages: exec::res::STAGES,   stage: 0, repr: "RES 1, B",    },
+    Instruction { code: 0x89, stages: exec::res::STAGES,   stage: 0, repr: "RES 1, C",    },
+    Instruction { code: 0x8a, stages: exec::res::STAGES,   stage: 0, repr: "RES 1, D",    },
+    Instruction { code: 0x8b, stages: exec::res::STAGES,   stage: 0, repr: "RES 1, E",    },
+    Instruction { code: 0x8c, stages: exec::res::STAGES,   stage: 0, repr: "RES 1, H",    },
+    Instruction { code: 0x8d, stages: exec::res::STAGES,   stage: 0, repr: "RES 1, L",    },
+    Instruction { code: 0x8e, stages: exec::res::STAGES,   stage: 0, repr: "RES 1, (HL)", },
+    Instruction { code: 0x8f, stages: exec::res::STAGES,   stage: 0, repr: "RES 1, A",    },
+    Instruction { code: 0x90, stages: exec::res::STAGES,   stage: 0, repr: "RES 2, B",    },
+    Instruction { code: 0x91, stages: exec::res::STAGES,   stage: 0, repr: "RES 2, C",    },
+    Instruction { code: 0x92, stages: exec::res::STAGES,   stage: 0, repr: "RES 2, D",    },
+    Instruction { code: 0x93, stages: exec::res::STAGES,   stage: 0, repr: "RES 2, E",    },
+    Instruction { code: 0x94, stages: exec::res::STAGES,   stage: 0, repr: "RES 2, H",    },
+    Instruction { code: 0x95, stages: exec::res::STAGES,   stage: 0, repr: "RES 2, L",    },
+    Instruction { code: 0x96, stages: exec::res::STAGES,   stage: 0, repr: "RES 2, (HL)", },
+    Instruction { code: 0x97, stages: exec::res::STAGES,   stage: 0, repr: "RES 2, A",    },
+    Instruction { code: 0x98, stages: exec::res::STAGES,   stage: 0, repr: "RES 3, B",    },
+    Instruction { code: 0x99, stages: exec::res::STAGES,   stage: 0, repr: "RES 3, C",    },
+    Instruction { code: 0x9a, stages: exec::res::STAGES,   stage: 0, repr: "RES 3, D",    },
+    Instruction { code: 0x9b, stages: exec::res::STAGES,   stage: 0, repr: "RES 3, E",    },
+    Instruction { code: 0x9c, stages: exec::res::STAGES,   stage: 0, repr: "RES 3, H",    },
+    Instruction { code: 0x9d, stages: exec::res::STAGES,   stage: 0, repr: "RES 3, L",    },
+    Instruction { code: 0x9e, stages: exec::res::STAGES,   stage: 0, repr: "RES 3, (HL)", },
+    Instruction { code: 0x9f, stages: exec::res::STAGES,   stage: 0, repr: "RES 3, A",    },
+    Instruction { code: 0xa0, stages: exec::res::STAGES,   stage: 0, repr: "RES 4, B",    },
+    Instruction { code: 0xa1, stages: exec::res::STAGES,   stage: 0, repr: "RES 4, C",    },
+    Instruction { code: 0xa2, stages: exec::res::STAGES,   stage: 0, repr: "RES 4, D",    },
+    Instruction { code: 0xa3, stages: exec::res::STAGES,   stage: 0, repr: "RES 4, E",    },
+    Instruction { code: 0xa4, stages: exec::res::STAGES,   stage: 0, repr: "RES 4, H",    },
+    Instruction { code: 0xa5, stages: exec::res::STAGES,   stage: 0, repr: "RES 4, L",    },
+    Instruction { code: 0xa6, stages: exec::res::STAGES,   stage: 0, repr: "RES 4, (HL)", },
+    Instruction { code: 0xa7, stages: exec::res::STAGES,   stage: 0, repr: "RES 4, A",    },
+    Instruction { code: 0xa8, stages: exec::res::STAGES,   stage: 0, repr: "RES 5, B",    },
+    Instruction { code: 0xa9, stages: exec::res::STAGES,   stage: 0, repr: "RES 5, C",    },
+    Instruction { code: 0xaa, stages: exec::res::STAGES,   stage: 0, repr: "RES 5, D",    },
+    Instruction { code: 0xab, stages: exec::res::STAGES,   stage: 0, repr: "RES 5, E",    },
+    Instruction { code: 0xac, stages: exec::res::STAGES,   stage: 0, repr: "RES 5, H",    },
+    Instruction { code: 0xad, stages: exec::res::STAGES,   stage: 0, repr: "RES 5, L",    },
+    Instruction { code: 0xae, stages: exec::res::STAGES,   stage: 0, repr: "RES 5, (HL)", },
+    Instruction { code: 0xaf, stages: exec::res::STAGES,   stage: 0, repr: "RES 5, A",    },
+    Instruction { code: 0xb0, stages: exec::res::STAGES,   stage: 0, repr: "RES 6, B",    },
+    Instruction { code: 0xb1, stages: exec::res::STAGES,   stage: 0, repr: "RES 6, C",    },
+    Instruction { code: 0xb2, stages: exec::res::STAGES,   stage: 0, repr: "RES 6, D",    },
+    Instruction { code: 0xb3, stages: exec::res::STAGES,   stage: 0, repr: "RES 6, E",    },
+    Instruction { code: 0xb4, stages: exec::res::STAGES,   stage: 0, repr: "RES 6, H",    },
+    Instruction { code: 0xb5, stages: exec::res::STAGES,   stage: 0, repr: "RES 6, L",    },
+    Instruction { code: 0xb6, stages: exec::res::STAGES,   stage: 0, repr: "RES 6, (HL)", },
+    Instruction { code: 0xb7, stages: exec::res::STAGES,   stage: 0, repr: "RES 6, A",    },
+    Instruction { code: 0xb8, stages: exec::res::STAGES,   stage: 0, repr: "RES 7, B",    },
+    Instruction { code: 0xb9, stages: exec::res::STAGES,   stage: 0, repr: "RES 7, C",    },
+    Instruction { code: 0xba, stages: exec::res::STAGES,   stage: 0, repr: "RES 7, D",    },
+    Instruction { code: 0xbb, stages: exec::res::STAGES,   stage: 0, repr: "RES 7, E",    },
+    Instruction { code: 0xbc, stages: exec::res::STAGES,   stage: 0, repr: "RES 7, H",    },
+    Instruction { code: 0xbd, stages: exec::res::STAGES,   stage: 0, repr: "RES 7, L",    },
+    Instruction { code: 0xbe, stages: exec::res::STAGES,   stage: 0, repr: "RES 7, (HL)", },
+    Instruction { code: 0xbf, stages: exec::res::STAGES,   stage: 0, repr: "RES 7, A",    },
+    Instruction { code: 0xc0, stages: exec::set::STAGES,   stage: 0, repr: "SET 0, B",    },
+    Instruction { code: 0xc1, stages: exec::set::STAGES,   stage: 0, repr: "SET 0, C",    },
+    Instruction { code: 0xc2, stages: exec::set::STAGES,   stage: 0, repr: "SET 0, D",    },
+    Instruction { code: 0xc3, stages: exec::set::STAGES,   stage: 0, repr: "SET 0, E",    },
+    Instruction { code: 0xc4, stages: exec::set::STAGES,   stage: 0, repr: "SET 0, H",    },
+    Instruction { code: 0xc5, stages: exec::set::STAGES,   stage: 0, repr: "SET 0, L",    },
+    Instruction { code: 0xc6, stages: exec::set::STAGES,   stage: 0, repr: "SET 0, (HL)", },
+    Instruction { code: 0xc7, stages: exec::set::STAGES,   stage: 0, repr: "SET 0, A",    },
+    Instruction { code: 0xc8, stages: exec::set::STAGES,   stage: 0, repr: "SET 1, B",    },
+    Instruction { code: 0xc9, stages: exec::set::STAGES,   stage: 0, repr: "SET 1, C",    },
+    Instruction { code: 0xca, stages: exec::set::STAGES,   stage: 0, repr: "SET 1, D",    },
+    Instruction { code: 0xcb, stages: exec::set::STAGES,   stage: 0, repr: "SET 1, E",    },
+    Instruction { code: 0xcc, stages: exec::set::STAGES,   stage: 0, repr: "SET 1, H",    },
+    Instruction { code: 0xcd, stages: exec::set::STAGES,   stage: 0, repr: "SET 1, L",    },
+    Instruction { code: 0xce, stages: exec::set::STAGES,   stage: 0, repr: "SET 1, (HL)", },
+    Instruction { code: 0xcf, stages: exec::set::STAGES,   stage: 0, repr: "SET 1, A",    },
+    Instruction { code: 0xd0, stages: exec::set::STAGES,   stage: 0, repr: "SET 2, B",    },
+    Instruction { code: 0xd1, stages: exec::set::STAGES,   stage: 0, repr: "SET 2, C",    },
+    Instruction { code: 0xd2, stages: exec::set::STAGES,   stage: 0, repr: "SET 2, D",    },
+    Instruction { code: 0xd3, stages: exec::set::STAGES,   stage: 0, repr: "SET 2, E",    },
+    Instruction { code: 0xd4, stages: exec::set::STAGES,   stage: 0, repr: "SET 2, H",    },
+    Instruction { code: 0xd5, stages: exec::set::STAGES,   stage: 0, repr: "SET 2, L",    },
+    Instruction { code: 0xd6, stages: exec::set::STAGES,   stage: 0, repr: "SET 2, (HL)", },
+    Instruction { code: 0xd7, stages: exec::set::STAGES,   stage: 0, repr: "SET 2, A",    },
+    Instruction { code: 0xd8, stages: exec::set::STAGES,   stage: 0, repr: "SET 3, B",    },
+    Instruction { code: 0xd9, stages: exec::set::STAGES,   stage: 0, repr: "SET 3, C",    },
+    Instruction { code: 0xda, stages: exec::set::STAGES,   stage: 0, repr: "SET 3, D",    },
+    Instruction { code: 0xdb, stages: exec::set::STAGES,   stage: 0, repr: "SET 3, E",    },
+    Instruction { code: 0xdc, stages: exec::set::STAGES,   stage: 0, repr: "SET 3, H",    },
+    Instruction { code: 0xdd, stages: exec::set::STAGES,   stage: 0, repr: "SET 3, L",    },
+    Instruction { code: 0xde, stages: exec::set::STAGES,   stage: 0, repr: "SET 3, (HL)", },
+    Instruction { code: 0xdf, stages: exec::set::STAGES,   stage: 0, repr: "SET 3, A",    },
+    Instruction { code: 0xe0, stages: exec::set::STAGES,   stage: 0, repr: "SET 4, B",    },
+    Instruction { code: 0xe1, stages: exec::set::STAGES,   stage: 0, repr: "SET 4, C",    },
+    Instruction { code: 0xe2, stages: exec::set::STAGES,   stage: 0, repr: "SET 4, D",    },
+    Instruction { code: 0xe3, stages: exec::set::STAGES,   stage: 0, repr: "SET 4, E",    },
+    Instruction { code: 0xe4, stages: exec::set::STAGES,   stage: 0, repr: "SET 4, H",    },
+    Instruction { code: 0xe5, stages: exec::set::STAGES,   stage: 0, repr: "SET 4, L",    },
+    Instruction { code: 0xe6, stages: exec::set::STAGES,   stage: 0, repr: "SET 4, (HL)", },
+    Instruction { code: 0xe7, stages: exec::set::STAGES,   stage: 0, repr: "SET 4, A",    },
+    Instruction { code: 0xe8, stages: exec::set::STAGES,   stage: 0, repr: "SET 5, B",    },
+    Instruction { code: 0xe9, stages: exec::set::STAGES,   stage: 0, repr: "SET 5, C",    },
+    Instruction { code: 0xea, stages: exec::set::STAGES,   stage: 0, repr: "SET 5, D",    },
+    Instruction { code: 0xeb, stages: exec::set::STAGES,   stage: 0, repr: "SET 5, E",    },
+    Instruction { code: 0xec, stages: exec::set::STAGES,   stage: 0, repr: "SET 5, H",    },
+    Instruction { code: 0xed, stages: exec::set::STAGES,   stage: 0, repr: "SET 5, L",    },
+    Instruction { code: 0xee, stages: exec::set::STAGES,   stage: 0, repr: "SET 5, (HL)", },
+    Instruction { code: 0xef, stages: exec::set::STAGES,   stage: 0, repr: "SET 5, A",    },
+    Instruction { code: 0xf0, stages: exec::set::STAGES,   stage: 0, repr: "SET 6, B",    },
+    Instruction { code: 0xf1, stages: exec::set::STAGES,   stage: 0, repr: "SET 6, C",    },
+    Instruction { code: 0xf2, stages: exec::set::STAGES,   stage: 0, repr: "SET 6, D",    },
+    Instruction { code: 0xf3, stages: exec::set::STAGES,   stage: 0, repr: "SET 6, E",    },
+    Instruction { code: 0xf4, stages: exec::set::STAGES,   stage: 0, repr: "SET 6, H",    },
+    Instruction { code: 0xf5, stages: exec::set::STAGES,   stage: 0, repr: "SET 6, L",    },
+    Instruction { code: 0xf6, stages: exec::set::STAGES,   stage: 0, repr: "SET 6, (HL)", },
+    Instruction { code: 0xf7, stages: exec::set::STAGES,   stage: 0, repr: "SET 6, A",    },
+    Instruction { code: 0xf8, stages: exec::set::STAGES,   stage: 0, repr: "SET 7, B",    },
+    Instruction { code: 0xf9, stages: exec::set::STAGES,   stage: 0, repr: "SET 7, C",    },
+    Instruction { code: 0xfa, stages: exec::set::STAGES,   stage: 0, repr: "SET 7, D",    },
+    Instruction { code: 0xfb, stages: exec::set::STAGES,   stage: 0, repr: "SET 7, E",    },
+    Instruction { code: 0xfc, stages: exec::set::STAGES,   stage: 0, repr: "SET 7, H",    },
+    Instruction { code: 0xfd, stages: exec::set::STAGES,   stage: 0, repr: "SET 7, L",    },
+    Instruction { code: 0xfe, stages: exec::set::STAGES,   stage: 0, repr: "SET 7, (HL)", },
+    Instruction { code: 0xff, stages: exec::set::STAGES,   stage: 0, repr: "SET 7, A",    },
 ];
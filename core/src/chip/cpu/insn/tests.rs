@@ -3,8 +3,15 @@ use crate::dmg::bus;
 
 fn setup() -> Cpu {
     let irq = irq::Irq::default();
+    let bus = bus::view::Cpu::default();
+    #[cfg(feature = "debug")]
+    let bus = bus::view::Probe {
+        view: bus,
+        hook: rugby_arch::Shared::default(),
+        prof: rugby_arch::Shared::default(),
+    };
     Cpu {
-        blk: Hardware::new(blk::Bus::new(bus::view::Cpu::default())),
+        blk: Hardware::new(blk::Bus::new(bus)),
         reg: File::default(),
         mem: Bank::default(),
         etc: Internal::default(),
@@ -76,6 +83,61 @@ fn prefix_cycle_count() {
     }
 }
 
+#[test]
+fn stage_state() {
+    use rugby_arch::state::{Reader, Writer};
+
+    // Test each instruction
+    for code in 0..=0xff {
+        // Create CPU model, decode instruction
+        let mut cpu = setup();
+        let mut insn = Instruction::decode(code);
+        // Round-trip every in-flight stage
+        loop {
+            let mut out = Writer::new();
+            out.save(&insn);
+            let buf = out.finish();
+            let mut load = Instruction::default();
+            Reader::new(&buf).load(&mut load).unwrap();
+            assert_eq!(
+                (load.code, load.stage, load.repr),
+                (insn.code, insn.stage, insn.repr),
+                "mismatch in restored stage for insn: {code:#04X} ; {insn}",
+            );
+            // Advance to the next stage, stopping once parked
+            match insn.exec(&mut cpu) {
+                Some(next) if next.stage != insn.stage => insn = next,
+                _ => break,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn call_stack() {
+    use crate::chip::cpu::stack::Kind;
+
+    let mut cpu = setup();
+    cpu.reg.sp.store(0xfffe);
+    // Opcode at $0150 has already been fetched
+    cpu.reg.pc.store(0x0151);
+    // Enter frames with `CALL a16` then `RST 38H`
+    cpu.exec(0xcd);
+    cpu.exec(0xff);
+    let frames = cpu.stack().frames();
+    assert_eq!(frames.len(), 2);
+    assert!(matches!(frames[0].kind, Kind::Call));
+    assert_eq!((frames[0].site, frames[0].sp), (0x0150, 0xfffc));
+    assert!(matches!(frames[1].kind, Kind::Rst));
+    assert_eq!((frames[1].addr, frames[1].sp), (0x0038, 0xfffa));
+    // Leave frames with `RET` then `RETI`
+    cpu.exec(0xc9);
+    assert_eq!(cpu.stack().depth(), 1);
+    cpu.exec(0xd9);
+    assert_eq!(cpu.stack().depth(), 0);
+}
+
 const CYCLES: [usize; 0x100] = [
     1, 3, 2, 2, 1, 1, 2, 1, 5, 2, 2, 2, 1, 1, 2, 1, // 0x00
     0, 3, 2, 2, 1, 1, 2, 1, 3, 2, 2, 2, 1, 1, 2, 1, // 0x10
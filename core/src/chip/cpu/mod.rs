@@ -8,6 +8,7 @@ use std::fmt::{Debug, Display};
 
 use rugby_arch::mem::{Memory, Ram};
 use rugby_arch::reg::{Port, Register};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use self::blk::Hardware;
@@ -17,6 +18,8 @@ use crate::chip::irq;
 pub mod blk;
 pub mod insn;
 pub mod reg;
+#[cfg(feature = "debug")]
+pub mod stack;
 
 /// High RAM.
 ///
@@ -99,6 +102,9 @@ pub struct Internal {
     ///
     /// [bug]: https://gbdev.io/pandocs/halt.html#halt-bug
     halt_bug: bool,
+    /// Call stack.
+    #[cfg(feature = "debug")]
+    stack: stack::Stack,
 }
 
 impl Internal {
@@ -107,6 +113,42 @@ impl Internal {
     }
 }
 
+impl State for Internal {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.insn)
+            .save(&self.busy)
+            .save(&(self.run as u8))
+            .save(&(self.ime as u8))
+            .save(&self.halt_bug);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut run = u8::default();
+        let mut ime = u8::default();
+        inp.load(&mut self.insn)?
+            .load(&mut self.busy)?
+            .load(&mut run)?
+            .load(&mut ime)?
+            .load(&mut self.halt_bug)?;
+        // Call stack is not saved, so is unknown once restored
+        #[cfg(feature = "debug")]
+        self.stack.clear();
+        self.run = match run {
+            0 => Status::Enabled,
+            1 => Status::Halted,
+            2 => Status::Stopped,
+            _ => return Err(state::Error::Invalid("Status")),
+        };
+        self.ime = match ime {
+            0 => Ime::Disabled,
+            1 => Ime::Enabled,
+            2 => Ime::WillEnable,
+            _ => return Err(state::Error::Invalid("Ime")),
+        };
+        Ok(())
+    }
+}
+
 impl Cpu {
     /// Checks whether the processor is mid-instruction.
     #[must_use]
@@ -114,6 +156,20 @@ impl Cpu {
         self.etc.busy
     }
 
+    /// Returns the call stack.
+    #[cfg(feature = "debug")]
+    #[must_use]
+    pub fn stack(&self) -> &stack::Stack {
+        &self.etc.stack
+    }
+
+    /// Returns the call stack mutably.
+    #[cfg(feature = "debug")]
+    #[must_use]
+    pub fn stack_mut(&mut self) -> &mut stack::Stack {
+        &mut self.etc.stack
+    }
+
     /// Fetch the next byte after PC.
     fn fetchbyte(&mut self) -> u8 {
         // Load PC
@@ -182,6 +238,19 @@ impl Block for Cpu {
     }
 }
 
+impl State for Cpu {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.mem).save(&self.etc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.mem)?
+            .load(&mut self.etc)?;
+        Ok(())
+    }
+}
+
 impl Port<u8> for Cpu {
     type Select = Select8;
 
@@ -305,6 +374,16 @@ impl Default for Bank {
     }
 }
 
+impl State for Bank {
+    fn save(&self, out: &mut Writer) {
+        self.hram.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.hram.restore(inp)
+    }
+}
+
 /// Processor registers.
 ///
 /// | Size | Name | Description                   |
@@ -421,6 +500,35 @@ impl Block for File {
     }
 }
 
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&[
+            self.a,
+            self.f.load(),
+            self.b,
+            self.c,
+            self.d,
+            self.e,
+            self.h,
+            self.l,
+            self.w,
+            self.z,
+        ])
+        .save(&self.sp)
+        .save(&self.pc);
+    }
+
+    #[expect(clippy::many_single_char_names)]
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let [a, f, b, c, d, e, h, l, w, z] = inp.array()?;
+        (self.a, self.b, self.c, self.d, self.e) = (a, b, c, d, e);
+        (self.h, self.l, self.w, self.z) = (h, l, w, z);
+        self.f.store(f);
+        inp.load(&mut self.sp)?.load(&mut self.pc)?;
+        Ok(())
+    }
+}
+
 impl Display for File {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "┌───┬────┬───┬────┐")?;
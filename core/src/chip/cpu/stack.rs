@@ -0,0 +1,174 @@
+//! Call stack tracking.
+
+use std::fmt::Display;
+
+use crate::chip::irq::Interrupt;
+
+/// Maximum tracked frames.
+///
+/// Beyond this, the outermost frames are discarded.
+const DEPTH: usize = 0x100;
+
+/// Call frame kind.
+#[derive(Copy, Clone, Debug)]
+pub enum Kind {
+    /// Subroutine call (`CALL`).
+    Call,
+    /// Restart vector call (`RST`).
+    Rst,
+    /// Interrupt dispatch.
+    Int(Interrupt),
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kind::Call => f.write_str("CALL"),
+            Kind::Rst => f.write_str("RST"),
+            Kind::Int(int) => int.fmt(f),
+        }
+    }
+}
+
+/// Call stack frame.
+#[derive(Copy, Clone, Debug)]
+pub struct Frame {
+    /// Frame kind.
+    pub kind: Kind,
+    /// Address of the call site.
+    ///
+    /// For interrupts, this is the address of the interrupted instruction.
+    pub site: u16,
+    /// Address of the callee.
+    pub addr: u16,
+    /// Stack pointer to the return address.
+    pub sp: u16,
+    /// ROM bank mapped as the frame was entered.
+    ///
+    /// Locates both the call site and the callee, as entering a frame cannot
+    /// itself switch banks.
+    pub bank: u16,
+}
+
+/// Processor call stack.
+///
+/// Records frames as they are entered by calls and interrupt dispatches, and
+/// unwinds them as their return addresses are popped by returns.
+///
+/// As frames are tracked by observing execution, manipulation of the stack by
+/// other means (e.g. discarding a return address with `POP`) is only noticed
+/// once a subsequent return unwinds past it.
+#[derive(Debug, Default)]
+pub struct Stack {
+    /// Active frames, outermost first.
+    frames: Vec<Frame>,
+    /// Mapped ROM bank.
+    bank: u16,
+    /// Bank tracking.
+    ///
+    /// Set while frames are observed (e.g. by a debugger or profiler), as the
+    /// mapped bank need only be kept current for them.
+    watch: bool,
+}
+
+impl Stack {
+    /// Enters a new frame.
+    pub fn enter(&mut self, frame: Frame) {
+        // Discard the outermost frame once full
+        if self.frames.len() >= DEPTH {
+            self.frames.remove(0);
+        }
+        self.frames.push(frame);
+    }
+
+    /// Leaves frames whose return addresses have been popped.
+    ///
+    /// Frames are unwound until the return address of the innermost lies at or
+    /// above the stack pointer.
+    pub fn leave(&mut self, sp: u16) {
+        while self.frames.last().is_some_and(|frame| frame.sp < sp) {
+            self.frames.pop();
+        }
+    }
+
+    /// Gets the mapped ROM bank.
+    #[must_use]
+    pub fn bank(&self) -> u16 {
+        self.bank
+    }
+
+    /// Maps the ROM bank in which subsequent frames are entered.
+    pub fn map(&mut self, bank: u16) {
+        self.bank = bank;
+    }
+
+    /// Sets whether frames are observed.
+    ///
+    /// While unobserved, the console need not [map](Self::map) the bank in
+    /// which frames are entered.
+    pub fn watch(&mut self, enable: bool) {
+        self.watch = enable;
+    }
+
+    /// Checks if frames are observed.
+    #[must_use]
+    pub fn watched(&self) -> bool {
+        self.watch
+    }
+
+    /// Discards all frames.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Returns the active frames, outermost first.
+    #[must_use]
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Returns the number of active frames.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(site: u16, sp: u16) -> Frame {
+        Frame {
+            kind: Kind::Call,
+            site,
+            addr: 0x0200,
+            sp,
+            bank: 1,
+        }
+    }
+
+    #[test]
+    fn leave_works() {
+        let mut stack = Stack::default();
+        stack.enter(frame(0x0150, 0xfffc));
+        stack.enter(frame(0x0203, 0xfffa));
+        stack.enter(frame(0x0203, 0xfff8));
+        // Returns unwind the innermost frame
+        stack.leave(0xfffa);
+        assert_eq!(stack.depth(), 2);
+        // Discarded return addresses are unwound by outer returns
+        stack.leave(0xfffe);
+        assert_eq!(stack.depth(), 0);
+    }
+
+    #[test]
+    fn enter_bounded() {
+        let mut stack = Stack::default();
+        for sp in 0..=u16::try_from(DEPTH).unwrap() {
+            stack.enter(frame(sp, u16::MAX - sp));
+        }
+        assert_eq!(stack.depth(), DEPTH);
+        assert_eq!(stack.frames()[0].site, 1);
+    }
+}
@@ -3,22 +3,61 @@
 use log::{debug, trace};
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 pub use super::ppu::Oam;
-use crate::dmg::bus;
+use crate::{cgb, dmg};
 
 /// Direct memory access unit.
 #[derive(Debug)]
 pub struct Dma {
     /// DMA bus.
-    pub bus: bus::view::Dma,
+    pub bus: View,
     /// DMA register.
     pub reg: Shared<Control>,
     /// DMA memory.
     pub mem: Shared<Oam>,
 }
 
+/// DMA bus view.
+///
+/// Model-specific views are enumerated, rather than boxed, such that accesses
+/// are statically dispatched.
+#[derive(Debug)]
+pub enum View {
+    /// DMG memory view.
+    Dmg(dmg::bus::view::Dma),
+    /// CGB memory view.
+    Cgb(cgb::bus::view::Dma),
+}
+
+impl Memory for View {
+    fn read(&self, addr: u16) -> rugby_arch::mem::Result<u8> {
+        match self {
+            View::Dmg(bus) => bus.read(addr),
+            View::Cgb(bus) => bus.read(addr),
+        }
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        match self {
+            View::Dmg(bus) => bus.write(addr, data),
+            View::Cgb(bus) => bus.write(addr, data),
+        }
+    }
+}
+
+impl State for Dma {
+    fn save(&self, out: &mut Writer) {
+        self.reg.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.reg.restore(inp)
+    }
+}
+
 impl Block for Dma {
     fn ready(&self) -> bool {
         !matches!(self.reg.borrow().mode, Mode::Off)
@@ -98,6 +137,28 @@ impl Block for Control {
     }
 }
 
+impl State for Control {
+    fn save(&self, out: &mut Writer) {
+        let mode = match self.mode {
+            Mode::Off => [0, 0, 0],
+            Mode::Req(page) => [1, page, 0],
+            Mode::On { hi, lo } => [2, hi, lo],
+        };
+        out.save(&mode).save(&self.page).save(&self.rst);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.mode = match inp.array()? {
+            [0, _, _] => Mode::Off,
+            [1, page, _] => Mode::Req(page),
+            [2, hi, lo] => Mode::On { hi, lo },
+            _ => return Err(state::Error::Invalid("Mode")),
+        };
+        inp.load(&mut self.page)?.load(&mut self.rst)?;
+        Ok(())
+    }
+}
+
 impl Memory for Control {
     fn read(&self, _: u16) -> rugby_arch::mem::Result<u8> {
         Ok(self.load())
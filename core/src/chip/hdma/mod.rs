@@ -0,0 +1,304 @@
+//! VRAM direct memory access.
+//!
+//! Present only on the CGB, this unit copies data into video RAM in blocks of
+//! 16 bytes, using one of two modes:
+//!
+//! - General-purpose DMA (GDMA): Copies all blocks at once.
+//! - HBlank DMA (HDMA): Copies a single block during each horizontal blank.
+//!
+//! The processor is paused while a block is being copied.
+//!
+//! See more details [here][vdma].
+//!
+//! [vdma]: https://gbdev.io/pandocs/CGB_Registers.html#lcd-vram-dma-transfers
+
+use log::{debug, trace};
+use rugby_arch::mem::Memory;
+use rugby_arch::state::{self, Reader, State, Writer};
+use rugby_arch::{Block, Shared};
+
+/// VRAM direct memory access unit.
+#[derive(Debug)]
+pub struct Hdma {
+    /// HDMA bus.
+    pub bus: Box<dyn Memory>,
+    /// HDMA registers.
+    pub reg: Shared<Control>,
+}
+
+impl Hdma {
+    /// Signals the current horizontal blank status.
+    ///
+    /// In HBlank mode, a block is copied upon entering each horizontal blank.
+    pub fn hblank(&mut self, hblank: bool) {
+        let mut reg = self.reg.borrow_mut();
+        // Trigger on rising edge
+        if hblank && !reg.hbl && matches!(reg.mode, Mode::HBlank { .. }) {
+            reg.mode = Mode::HBlank { run: true };
+        }
+        reg.hbl = hblank;
+    }
+}
+
+impl State for Hdma {
+    fn save(&self, out: &mut Writer) {
+        self.reg.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.reg.restore(inp)
+    }
+}
+
+impl Block for Hdma {
+    fn ready(&self) -> bool {
+        matches!(
+            self.reg.borrow().mode,
+            Mode::General | Mode::HBlank { run: true }
+        )
+    }
+
+    fn cycle(&mut self) {
+        let mut reg = self.reg.borrow_mut();
+        // Transfer single byte
+        let src = reg.src;
+        let dst = 0x8000 | (reg.dst & 0x1fff);
+        let data = self.bus.read(src).unwrap_or(0xff);
+        self.bus.write(dst, data).ok();
+        trace!("copied: ${dst:04x} <- ${src:04x}, data: {data:#04x}");
+        // Increment transfer addresses
+        reg.src = src.wrapping_add(1);
+        reg.dst = (reg.dst + 1) & 0x1fff;
+        // Complete blocks every 16 bytes
+        if reg.dst.is_multiple_of(0x10) {
+            let (len, done) = reg.len.overflowing_sub(1);
+            reg.len = len & 0x7f;
+            if done {
+                // Complete transfer
+                debug!("finished: ${dst:04x}");
+                reg.mode = Mode::Off;
+            } else if let Mode::HBlank { run } = &mut reg.mode {
+                // Wait for next horizontal blank
+                *run = false;
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.reg.reset();
+    }
+}
+
+/// HDMA control registers.
+///
+/// | Offset | Name  | Description                  |
+/// |:------:|-------|------------------------------|
+/// |   `1`  | HDMA1 | Source address (high)        |
+/// |   `2`  | HDMA2 | Source address (low)         |
+/// |   `3`  | HDMA3 | Destination address (high)   |
+/// |   `4`  | HDMA4 | Destination address (low)    |
+/// |   `5`  | HDMA5 | Length/mode/start            |
+#[derive(Debug)]
+pub struct Control {
+    /// Transfer mode.
+    mode: Mode,
+    /// Source address.
+    src: u16,
+    /// Destination address.
+    dst: u16,
+    /// Remaining blocks.
+    ///
+    /// Counts down from one less than the number of blocks.
+    len: u8,
+    /// Horizontal blank status.
+    hbl: bool,
+}
+
+impl Default for Control {
+    fn default() -> Self {
+        Self {
+            mode: Mode::default(),
+            src: u16::default(),
+            dst: u16::default(),
+            len: 0x7f,
+            hbl: bool::default(),
+        }
+    }
+}
+
+impl Block for Control {
+    fn reset(&mut self) {
+        std::mem::take(self);
+    }
+}
+
+impl Memory for Control {
+    fn read(&self, addr: u16) -> rugby_arch::mem::Result<u8> {
+        Ok(match addr {
+            // Length/mode/start
+            5 => u8::from(matches!(self.mode, Mode::Off)) << 7 | self.len,
+            // Addresses are write-only
+            _ => 0xff,
+        })
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        let [src_hi, src_lo] = self.src.to_be_bytes();
+        let [dst_hi, dst_lo] = self.dst.to_be_bytes();
+        match addr {
+            1 => self.src = u16::from_be_bytes([data, src_lo]),
+            2 => self.src = u16::from_be_bytes([src_hi, data & 0xf0]),
+            3 => self.dst = u16::from_be_bytes([data & 0x1f, dst_lo]),
+            4 => self.dst = u16::from_be_bytes([dst_hi, data & 0xf0]),
+            5 => {
+                let hblank = data & 0x80 != 0;
+                if !hblank && matches!(self.mode, Mode::HBlank { .. }) {
+                    // Cancel the active transfer
+                    debug!("cancelled: remaining: {len}", len = self.len);
+                    self.mode = Mode::Off;
+                } else {
+                    // Start a new transfer
+                    self.len = data & 0x7f;
+                    self.mode = if hblank {
+                        Mode::HBlank { run: false }
+                    } else {
+                        Mode::General
+                    };
+                    debug!(
+                        "request: ${dst:04x} <- ${src:04x}, blocks: {len}, mode: {mode:?}",
+                        dst = 0x8000 | self.dst,
+                        src = self.src,
+                        len = u16::from(self.len) + 1,
+                        mode = self.mode,
+                    );
+                }
+            }
+            _ => return Err(rugby_arch::mem::Error::Range),
+        }
+        Ok(())
+    }
+}
+
+impl State for Control {
+    fn save(&self, out: &mut Writer) {
+        let mode: u8 = match self.mode {
+            Mode::Off => 0,
+            Mode::General => 1,
+            Mode::HBlank { run: false } => 2,
+            Mode::HBlank { run: true } => 3,
+        };
+        out.save(&mode)
+            .save(&self.src)
+            .save(&self.dst)
+            .save(&self.len)
+            .save(&self.hbl);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.mode = match u8::from_le_bytes(inp.array()?) {
+            0 => Mode::Off,
+            1 => Mode::General,
+            2 => Mode::HBlank { run: false },
+            3 => Mode::HBlank { run: true },
+            _ => return Err(state::Error::Invalid("Mode")),
+        };
+        inp.load(&mut self.src)?
+            .load(&mut self.dst)?
+            .load(&mut self.len)?
+            .load(&mut self.hbl)?;
+        Ok(())
+    }
+}
+
+/// HDMA transfer mode.
+#[derive(Debug, Default)]
+enum Mode {
+    /// Disabled.
+    #[default]
+    Off,
+    /// General-purpose DMA.
+    General,
+    /// HBlank DMA.
+    HBlank {
+        /// Block in progress.
+        run: bool,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (Hdma, Shared<Box<[u8]>>) {
+        let mem = Shared::new(vec![0; 0x10000].into_boxed_slice());
+        (mem.borrow_mut().iter_mut())
+            .zip((0..=u8::MAX).cycle())
+            .for_each(|(byte, data)| *byte = data);
+        let hdma = Hdma {
+            bus: Box::new(mem.clone()),
+            reg: Shared::default(),
+        };
+        (hdma, mem)
+    }
+
+    fn start(hdma: &mut Hdma, src: u16, dst: u16, ctl: u8) {
+        let mut reg = hdma.reg.borrow_mut();
+        let [hi, lo] = src.to_be_bytes();
+        reg.write(1, hi).unwrap();
+        reg.write(2, lo).unwrap();
+        let [hi, lo] = dst.to_be_bytes();
+        reg.write(3, hi).unwrap();
+        reg.write(4, lo).unwrap();
+        reg.write(5, ctl).unwrap();
+    }
+
+    #[test]
+    fn general_works() {
+        let (mut hdma, mem) = setup();
+        // Copy 2 blocks
+        start(&mut hdma, 0xc123, 0x8010, 0x01);
+        assert_eq!(hdma.reg.borrow().read(5).unwrap(), 0x01);
+        let mut cycles = 0;
+        while hdma.ready() {
+            hdma.cycle();
+            cycles += 1;
+        }
+        assert_eq!(cycles, 0x20);
+        assert_eq!(hdma.reg.borrow().read(5).unwrap(), 0xff);
+        // Source address ignores its lower nibble
+        (0..0x20).for_each(|idx| assert_eq!(mem.borrow()[0x8010 + usize::from(idx)], idx + 0x20));
+    }
+
+    #[test]
+    fn hblank_works() {
+        let (mut hdma, mem) = setup();
+        // Copy 2 blocks
+        start(&mut hdma, 0xd000, 0x9ff0, 0x81);
+        assert!(!hdma.ready());
+        // Copy a block each horizontal blank
+        for blk in 0..2 {
+            hdma.hblank(true);
+            (0..0x10).for_each(|_| hdma.cycle());
+            assert!(!hdma.ready());
+            hdma.hblank(false);
+            assert_eq!(hdma.reg.borrow().read(5).unwrap(), [0x00, 0xff][blk]);
+        }
+        // Destination address wraps within VRAM
+        (0..0x10).for_each(|idx| assert_eq!(mem.borrow()[0x9ff0 + usize::from(idx)], idx));
+        (0..0x10).for_each(|idx| assert_eq!(mem.borrow()[0x8000 + usize::from(idx)], idx + 0x10));
+    }
+
+    #[test]
+    fn hblank_cancel_works() {
+        let (mut hdma, _) = setup();
+        start(&mut hdma, 0xd000, 0x8000, 0x82);
+        hdma.hblank(true);
+        (0..0x10).for_each(|_| hdma.cycle());
+        // Cancel the transfer
+        hdma.reg.borrow_mut().write(5, 0x00).unwrap();
+        assert_eq!(hdma.reg.borrow().read(5).unwrap(), 0x81);
+        hdma.hblank(false);
+        hdma.hblank(true);
+        assert!(!hdma.ready());
+    }
+}
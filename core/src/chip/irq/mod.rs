@@ -5,6 +5,7 @@ use std::fmt::Display;
 use log::trace;
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::{Port, Register as _};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 /// Interrupt source.
@@ -165,6 +166,16 @@ impl Block for Irq {
     }
 }
 
+impl State for Irq {
+    fn save(&self, out: &mut Writer) {
+        self.reg.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.reg.restore(inp)
+    }
+}
+
 impl Port<u8> for Irq {
     type Select = Select;
 
@@ -207,6 +218,18 @@ impl Block for File {
     }
 }
 
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.flg.borrow().0).save(&self.ena);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.flg.borrow_mut().0)?
+            .load(&mut self.ena)?;
+        Ok(())
+    }
+}
+
 /// Interrupt flag register.
 ///
 /// Each [interrupt kind](Interrupt) has a corresponding bit position in a
@@ -6,6 +6,7 @@ use std::ops::BitOr;
 use log::{debug, trace};
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::irq::{self, Interrupt};
@@ -45,6 +46,20 @@ impl Button {
     }
 }
 
+impl Button {
+    /// All joypad inputs.
+    const ALL: [Self; 8] = [
+        Self::A,
+        Self::B,
+        Self::Select,
+        Self::Start,
+        Self::Right,
+        Self::Left,
+        Self::Up,
+        Self::Down,
+    ];
+}
+
 impl crate::api::input::Button for Button {}
 
 /// Joypad register mode.
@@ -117,6 +132,16 @@ impl Api for Joypad {
     }
 }
 
+impl state::State for Joypad {
+    fn save(&self, out: &mut Writer) {
+        self.reg.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.reg.restore(inp)
+    }
+}
+
 impl Block for Joypad {
     fn reset(&mut self) {
         self.reg.reset();
@@ -130,12 +155,50 @@ pub struct Control {
     keys: HashSet<Button>,
 }
 
+impl Control {
+    /// Gets the pressed keys.
+    #[must_use]
+    pub fn keys(&self) -> &HashSet<Button> {
+        &self.keys
+    }
+}
+
 impl Block for Control {
     fn reset(&mut self) {
         std::mem::take(self);
     }
 }
 
+impl state::State for Control {
+    fn save(&self, out: &mut Writer) {
+        // Pack pressed keys into a bitmask
+        let keys = Button::ALL
+            .iter()
+            .enumerate()
+            .filter(|(_, btn)| self.keys.contains(btn))
+            .fold(0u8, |keys, (idx, _)| keys | (1 << idx));
+        out.save(&(self.mode as u8)).save(&keys);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let [mode, keys] = inp.array()?;
+        self.mode = match mode {
+            0b0000_0000 => Mode::None,
+            0b0001_1111 => Mode::DPad,
+            0b0010_1111 => Mode::Keys,
+            0b0011_1111 => Mode::Both,
+            _ => return Err(state::Error::Invalid("Mode")),
+        };
+        self.keys = Button::ALL
+            .into_iter()
+            .enumerate()
+            .filter(|(idx, _)| keys & (1 << idx) != 0)
+            .map(|(_, btn)| btn)
+            .collect();
+        Ok(())
+    }
+}
+
 impl Memory for Control {
     fn read(&self, _: u16) -> rugby_arch::mem::Result<u8> {
         Ok(self.load())
@@ -4,9 +4,31 @@
 #![allow(clippy::cast_possible_wrap)]
 #![allow(clippy::cast_sign_loss)]
 
+/// Implements [`State`](rugby_arch::state::State) for bitfield registers.
+macro_rules! bitfield_state {
+    ($($ty:ty),* $(,)?) => {$(
+        impl rugby_arch::state::State for $ty {
+            fn save(&self, out: &mut rugby_arch::state::Writer) {
+                out.save(&self.into_bits());
+            }
+
+            fn restore(
+                &mut self,
+                inp: &mut rugby_arch::state::Reader,
+            ) -> rugby_arch::state::Result<()> {
+                let mut bits = self.into_bits();
+                inp.load(&mut bits)?;
+                *self = Self::from_bits(bits);
+                Ok(())
+            }
+        }
+    )*};
+}
+
 pub mod apu;
 pub mod cpu;
 pub mod dma;
+pub mod hdma;
 pub mod irq;
 pub mod joy;
 pub mod ppu;
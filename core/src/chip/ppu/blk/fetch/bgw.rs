@@ -1,6 +1,7 @@
 use rugby_arch::Block;
+use rugby_arch::state::{self, Reader, State, Writer};
 
-use super::meta::{Layer, Meta, Row};
+use super::meta::{Attributes, Layer, Meta, Row};
 use super::{Fifo, Ppu, Step};
 
 /// Background fetcher.
@@ -14,6 +15,10 @@ pub struct Fetcher {
     pub xpos: u8,
     /// Graphics layer.
     pub layer: Layer,
+    /// Tile attributes.
+    ///
+    /// Only used in CGB mode.
+    pub attr: Attributes,
 }
 
 impl Default for Fetcher {
@@ -23,6 +28,7 @@ impl Default for Fetcher {
             step: Step::Fetch,
             xpos: u8::default(),
             layer: Layer::Background,
+            attr: Attributes::default(),
         }
     }
 }
@@ -39,11 +45,31 @@ impl Fetcher {
     }
 }
 
+impl State for Fetcher {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.fifo)
+            .save(&self.step)
+            .save(&self.xpos)
+            .save(&self.layer)
+            .save(&self.attr);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.fifo)?
+            .load(&mut self.step)?
+            .load(&mut self.xpos)?
+            .load(&mut self.layer)?
+            .load(&mut self.attr)?;
+        Ok(())
+    }
+}
+
 impl Block for Fetcher {
     fn reset(&mut self) {
         std::mem::take(&mut self.fifo);
         std::mem::take(&mut self.step);
         std::mem::take(&mut self.xpos);
+        std::mem::take(&mut self.attr);
     }
 }
 
@@ -53,7 +79,7 @@ pub(super) mod exec {
     use rugby_arch::mem::Memory;
     use rugby_arch::reg::Register;
 
-    use super::{Fetcher, Layer, Meta, Ppu, Row, Step};
+    use super::{Attributes, Fetcher, Layer, Meta, Ppu, Row, Step};
 
     /// Executes fetch tile step.
     pub fn fetch(ppu: &Ppu, fetch: &mut Fetcher) -> Step {
@@ -86,7 +112,24 @@ pub(super) mod exec {
         let tnum = ppu.mem.vram.read(addr).unwrap();
         trace!("read tile index: VRAM[${addr:04x}] -> #{tnum}");
         // Calculate the tile data address
-        let tdat = ppu.tdat(fetch.layer, tnum, ppu.reg.scy.load());
+        let mut tdat = ppu.tdat(fetch.layer, tnum, ppu.reg.scy.load());
+        // Apply tile attributes
+        fetch.attr = match &ppu.cgb {
+            Some(cgb) if ppu.cgb_mode() => {
+                let attr = Attributes::from(cgb.vram.read(addr).unwrap());
+                trace!("read tile attributes: VRAM1[${addr:04x}] -> {attr:?}");
+                attr
+            }
+            _ => Attributes::default(),
+        };
+        // Perform vertical flip
+        if fetch.attr.yflip {
+            tdat ^= 0b0000_1110;
+        }
+        // Select VRAM bank
+        if fetch.attr.bank {
+            tdat |= 0x2000;
+        }
 
         // Progress to next step
         Step::Read0 { tdat }
@@ -95,7 +138,7 @@ pub(super) mod exec {
     /// Executes read tile data low.
     pub fn read0(ppu: &Ppu, tdat: u16) -> Step {
         // Fetch the low byte of the tile
-        let data = ppu.mem.vram.read(tdat).unwrap();
+        let data = ppu.vram(tdat);
         trace!("read lower byte: VRAM[${tdat:04x}] -> {data:#04x}");
 
         // Progress to next step
@@ -106,7 +149,7 @@ pub(super) mod exec {
     /// Executes read tile data high.
     pub fn read1(ppu: &Ppu, tdat: u16, data0: u8) -> Step {
         // Fetch the high byte of the tile
-        let data1 = ppu.mem.vram.read(tdat).unwrap();
+        let data1 = ppu.vram(tdat);
         trace!("read upper byte: VRAM[${tdat:04x}] -> {data1:#04x}");
 
         // Progress to next step
@@ -118,8 +161,15 @@ pub(super) mod exec {
     /// Executes push to FIFO.
     pub fn push(fetch: &mut Fetcher, data: [u8; 2]) -> Step {
         // Decode pixel row from bytes
-        let row = Row::from(data);
-        let meta = Meta::Bgw;
+        let mut row = Row::from(data);
+        let meta = Meta::Bgw {
+            cpal: fetch.attr.cpal,
+            prty: fetch.attr.prty,
+        };
+        // Perform horizontal flip
+        if fetch.attr.xflip {
+            row.xflip();
+        }
 
         // Only push when the FIFO is empty
         if fetch.fifo.is_empty() {
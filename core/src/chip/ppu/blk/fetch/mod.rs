@@ -1,4 +1,5 @@
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 
 use super::Ppu;
 use super::fifo::Fifo;
@@ -76,3 +77,33 @@ impl Ppu {
         tidx | toff
     }
 }
+
+impl State for Step {
+    fn save(&self, out: &mut Writer) {
+        let (step, tdat, data) = match *self {
+            Step::Fetch => (0, 0, [0; 2]),
+            Step::Read0 { tdat } => (1, tdat, [0; 2]),
+            Step::Read1 { tdat, data } => (2, tdat, [data, 0]),
+            Step::Push { data } => (3, 0, data),
+        };
+        out.save(&step).save(&tdat).save(&data);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut step = u8::default();
+        let mut tdat = u16::default();
+        let mut data = [u8::default(); 2];
+        inp.load(&mut step)?.load(&mut tdat)?.load(&mut data)?;
+        *self = match step {
+            0 => Step::Fetch,
+            1 => Step::Read0 { tdat },
+            2 => Step::Read1 {
+                tdat,
+                data: data[0],
+            },
+            3 => Step::Push { data },
+            _ => return Err(state::Error::Invalid("Step")),
+        };
+        Ok(())
+    }
+}
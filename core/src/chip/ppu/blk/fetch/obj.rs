@@ -1,6 +1,7 @@
 use rugby_arch::Block;
+use rugby_arch::state::{self, Reader, State, Writer};
 
-use super::meta::{Layer, Row, Sprite};
+use super::meta::{Layer, Meta, Row, Sprite};
 use super::{Fifo, Ppu, Step};
 
 /// Sprite fetcher.
@@ -33,11 +34,22 @@ impl Fetcher {
             Step::Fetch => exec::fetch(ppu, obj),
             Step::Read0 { tdat } => exec::read0(ppu, tdat),
             Step::Read1 { tdat, data } => exec::read1(ppu, tdat, data),
-            Step::Push { data } => exec::push(self, data, obj),
+            Step::Push { data } => exec::push(ppu, self, data, obj),
         }
     }
 }
 
+impl State for Fetcher {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.fifo).save(&self.step);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.fifo)?.load(&mut self.step)?;
+        Ok(())
+    }
+}
+
 impl Block for Fetcher {
     fn reset(&mut self) {
         std::mem::take(&mut self.fifo);
@@ -50,7 +62,7 @@ pub(super) mod exec {
     use log::trace;
     use rugby_arch::reg::Register;
 
-    use super::{Fetcher, Ppu, Row, Sprite, Step};
+    use super::{Fetcher, Meta, Ppu, Row, Sprite, Step};
 
     /// Executes fetch tile step.
     pub fn fetch(ppu: &Ppu, obj: &Sprite) -> Step {
@@ -81,6 +93,10 @@ pub(super) mod exec {
         if obj.attr.yflip {
             tdat ^= 0b0000_1110;
         }
+        // Select VRAM bank
+        if obj.attr.bank && ppu.cgb_mode() {
+            tdat |= 0x2000;
+        }
 
         // Progress to next step
         Step::Read0 { tdat }
@@ -89,7 +105,7 @@ pub(super) mod exec {
     pub use super::super::bgw::exec::{read0, read1};
 
     /// Executes push to FIFO.
-    pub fn push(fetch: &mut Fetcher, data: [u8; 2], obj: &Sprite) -> Step {
+    pub fn push(ppu: &Ppu, fetch: &mut Fetcher, data: [u8; 2], obj: &Sprite) -> Step {
         // Decode pixel row from bytes
         let mut row = Row::from(data);
         // Perform horizontal flip
@@ -101,11 +117,20 @@ pub(super) mod exec {
         let skip = usize::from(8u8.saturating_sub(obj.xpos));
         row.shift(skip);
 
+        // Determine drawing priority
+        let mut meta = obj.meta();
+        if let (Meta::Obj { xpos, .. }, Some(cgb)) = (&mut meta, &ppu.cgb)
+            && cgb.oidx()
+        {
+            // Only the OAM index is considered
+            *xpos = 0;
+        }
+
         // Push to the FIFO
         //
         // NOTE: Some pixels may be discarded when FIFO is non-empty.
         trace!("pushed row of pixels: {row:?}");
-        fetch.fifo.push(row, obj.meta());
+        fetch.fifo.push(row, meta);
         // Restart from beginning
         Step::Fetch
     }
@@ -1,6 +1,8 @@
 use std::collections::VecDeque;
 
-use super::meta::{Meta, Pixel, Row};
+use rugby_arch::state::{self, Reader, State, Writer};
+
+use super::meta::{Color, Meta, Pixel, Row};
 
 /// Pixel FIFO.
 #[derive(Clone, Debug, Default)]
@@ -54,3 +56,16 @@ impl Fifo {
         self.0.len() >= 8
     }
 }
+
+impl State for Fifo {
+    fn save(&self, out: &mut Writer) {
+        out.seq(&self.0);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.0 = inp
+            .seq(|| Pixel::new(Color::default(), Meta::default()))?
+            .into();
+        Ok(())
+    }
+}
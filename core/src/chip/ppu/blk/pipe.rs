@@ -3,10 +3,11 @@ use std::collections::VecDeque;
 use log::trace;
 use rugby_arch::Block;
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 
 use super::Ppu;
 use super::fetch::{self, Step};
-use super::meta::{Color, Layer, Pixel, Sprite};
+use super::meta::{Color, Layer, Meta, Pixel, Sprite};
 
 /// Pixel pipeline.
 #[derive(Clone, Debug, Default)]
@@ -25,6 +26,32 @@ pub struct Pipeline {
     pub ipr: VecDeque<usize>,
 }
 
+impl State for Pipeline {
+    #[expect(clippy::cast_possible_truncation)]
+    fn save(&self, out: &mut Writer) {
+        // NOTE: Sprite indices never exceed the 40 OAM entries.
+        let ipr: Vec<u8> = self.ipr.iter().map(|&idx| idx as u8).collect();
+        out.save(&self.ready)
+            .save(&self.scx)
+            .save(&self.lx)
+            .save(&self.bgw)
+            .save(&self.obj)
+            .save(&ipr);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut ipr = Vec::<u8>::new();
+        inp.load(&mut self.ready)?
+            .load(&mut self.scx)?
+            .load(&mut self.lx)?
+            .load(&mut self.bgw)?
+            .load(&mut self.obj)?
+            .load(&mut ipr)?;
+        self.ipr = ipr.into_iter().map(usize::from).collect();
+        Ok(())
+    }
+}
+
 impl Pipeline {
     /// Performs a fetch for the next pixels to the appropriate FIFO.
     pub fn fetch(&mut self, ppu: &mut Ppu, objs: &[Sprite]) {
@@ -126,12 +153,23 @@ impl Pipeline {
         }
 
         // Overwrite if the background/window is disabled
-        if !ppu.reg.lcdc.borrow().bg_win_enable() {
+        //
+        // NOTE: In CGB mode, the background/window is instead always drawn,
+        //       with this bit removing its priority over sprites.
+        let enable = ppu.reg.lcdc.borrow().bg_win_enable();
+        if !enable && !ppu.cgb_mode() {
             bgwin.col = Color::C0;
         }
 
         // Pop from the sprite FIFO
-        let pixel = if let Some(sprite) = self.obj.fifo.pop() {
+        let pixel = if let Some(mut sprite) = self.obj.fifo.pop() {
+            // Remove background priority
+            if !enable && ppu.cgb_mode() {
+                for meta in [&mut bgwin.meta, &mut sprite.meta] {
+                    let (Meta::Bgw { prty, .. } | Meta::Obj { prty, .. }) = meta;
+                    *prty = false;
+                }
+            }
             Pixel::blend(bgwin, sprite) // blend the pixels together
         } else {
             bgwin // no sprite; use background/window pixel
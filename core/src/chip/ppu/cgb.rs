@@ -0,0 +1,283 @@
+//! Color graphics.
+//!
+//! Additional graphics hardware present only on the CGB, comprising a second
+//! bank of video RAM, color palette memory, and an RGB framebuffer.
+
+use rugby_arch::Shared;
+use rugby_arch::mem::{Memory, Ram};
+use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
+
+use super::meta::{Color, Rgb555};
+use super::{LCD, Vram};
+use crate::api::video::Frame;
+
+/// Color graphics hardware.
+#[derive(Debug)]
+pub struct Cgb {
+    /// Video RAM (bank 1).
+    ///
+    /// Holds background map attributes, and a second set of tile data.
+    pub vram: Shared<Vram>,
+    /// Color registers.
+    pub reg: File,
+    /// DMG compatibility mode.
+    ///
+    /// Latched from the cartridge header at boot. While set, the PPU renders as
+    /// a DMG, with monochrome shades translated through the first color
+    /// palettes.
+    pub dmg: bool,
+    /// Framebuffer.
+    pub(super) buf: Frame<Rgb555>,
+}
+
+impl Cgb {
+    /// Constructs a new `Cgb` using the provided video RAM bank.
+    #[must_use]
+    pub fn with(vram: &Shared<Vram>) -> Self {
+        Self {
+            vram: vram.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Gets the RGB framebuffer.
+    #[must_use]
+    pub fn frame(&self) -> &[Rgb555] {
+        &self.buf
+    }
+
+    /// Checks if objects are prioritized by OAM index.
+    #[must_use]
+    pub fn oidx(&self) -> bool {
+        !self.reg.opri.load()
+    }
+}
+
+impl Default for Cgb {
+    fn default() -> Self {
+        Self {
+            vram: Shared::new(Vram::from([u8::default(); 0x2000])),
+            reg: File::default(),
+            dmg: bool::default(),
+            buf: vec![Rgb555::default(); LCD.depth()].into_boxed_slice(),
+        }
+    }
+}
+
+impl State for Cgb {
+    // NOTE: Video RAM is owned by the motherboard, and is saved there.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.dmg).save(&self.buf);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.dmg)?
+            .load(&mut self.buf)?;
+        Ok(())
+    }
+}
+
+/// Color registers.
+///
+/// | Address | Size | Name | Description                   |
+/// |:-------:|------|------|-------------------------------|
+/// | `$FF68` | Byte | BCPS | BG palette specification      |
+/// | `$FF69` | Byte | BCPD | BG palette data               |
+/// | `$FF6A` | Byte | OCPS | OBJ palette specification     |
+/// | `$FF6B` | Byte | OCPD | OBJ palette data              |
+/// | `$FF6C` | Byte | OPRI | Object priority mode          |
+#[rustfmt::skip]
+#[derive(Clone, Debug, Default)]
+#[derive(Memory)]
+pub struct File {
+    /// BG color palettes.
+    #[mmap(0xff68..=0xff69, mask = 0x0001)]
+    pub bcp:  Shared<Cram>,
+    /// OBJ color palettes.
+    #[mmap(0xff6a..=0xff6b, mask = 0x0001)]
+    pub ocp:  Shared<Cram>,
+    /// Object priority mode.
+    #[mmap(0xff6c)]
+    pub opri: Shared<Opri>,
+}
+
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.bcp).save(&self.ocp).save(&self.opri);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.bcp)?
+            .load(&mut self.ocp)?
+            .load(&mut self.opri)?;
+        Ok(())
+    }
+}
+
+/// Color palette memory.
+///
+/// 64 byte RAM storing eight palettes of four [colors](Rgb555), accessed
+/// indirectly through a pair of registers:
+///
+/// | Offset | Name | Description                             |
+/// |:------:|------|-----------------------------------------|
+/// |   `0`  | xCPS | Specification: `0bI_AAAAAA`             |
+/// |   `1`  | xCPD | Data at address `A`                     |
+///
+/// When `I` is set, the address auto-increments after each data write.
+///
+/// See more details [here][cram].
+///
+/// [cram]: https://gbdev.io/pandocs/Palettes.html#lcd-color-palettes-cgb-only
+#[derive(Debug)]
+pub struct Cram {
+    /// Palette address.
+    addr: u8,
+    /// Auto-increment.
+    incr: bool,
+    /// Palette data.
+    data: Ram<[u8; 0x40]>,
+}
+
+impl Cram {
+    /// Looks up the color of a palette entry.
+    #[must_use]
+    pub fn color(&self, pal: u8, col: Color) -> Rgb555 {
+        let idx = 8 * usize::from(pal & 0b111) + 2 * col as usize;
+        let data = self.data.inner();
+        Rgb555::from(u16::from_le_bytes([data[idx], data[idx + 1]]))
+    }
+
+    /// Assigns the color of a palette entry.
+    pub fn set(&mut self, pal: u8, col: Color, rgb: Rgb555) {
+        let idx = 8 * usize::from(pal & 0b111) + 2 * col as usize;
+        let data = self.data.inner_mut();
+        data[idx..=idx + 1].copy_from_slice(&u16::from(rgb).to_le_bytes());
+    }
+}
+
+impl Default for Cram {
+    fn default() -> Self {
+        Self {
+            addr: u8::default(),
+            incr: bool::default(),
+            data: Ram::from([0xff; 0x40]),
+        }
+    }
+}
+
+impl Memory for Cram {
+    fn read(&self, addr: u16) -> rugby_arch::mem::Result<u8> {
+        match addr {
+            // Specification
+            0 => Ok(u8::from(self.incr) << 7 | 0x40 | self.addr),
+            // Data
+            _ => self.data.read(self.addr.into()),
+        }
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        if addr == 0 {
+            // Specification
+            self.addr = data & 0x3f;
+            self.incr = data & 0x80 != 0;
+        } else {
+            // Data
+            self.data.write(self.addr.into(), data)?;
+            if self.incr {
+                self.addr = (self.addr + 1) & 0x3f;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl State for Cram {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.addr).save(&self.incr).save(&self.data);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.addr)?
+            .load(&mut self.incr)?
+            .load(&mut self.data)?;
+        Ok(())
+    }
+}
+
+/// `OPRI`: Object priority mode.
+///
+/// When clear, overlapping objects are prioritized by their OAM index. When
+/// set, they are instead prioritized by X-coordinate, as on the DMG.
+#[derive(Debug, Default)]
+pub struct Opri(bool);
+
+impl Memory for Opri {
+    fn read(&self, _: u16) -> rugby_arch::mem::Result<u8> {
+        Ok(0xfe | u8::from(self.load()))
+    }
+
+    fn write(&mut self, _: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        self.store(data & 0x01 != 0);
+        Ok(())
+    }
+}
+
+impl Register for Opri {
+    type Value = bool;
+
+    fn load(&self) -> Self::Value {
+        self.0
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.0 = value;
+    }
+}
+
+impl State for Opri {
+    fn save(&self, out: &mut Writer) {
+        self.0.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.0.restore(inp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cram_increment_works() {
+        let mut cram = Cram::default();
+        // Select palette 1, with auto-increment
+        cram.write(0, 0x88).unwrap();
+        assert_eq!(cram.read(0).unwrap(), 0xc8);
+        // Write color 0
+        cram.write(1, 0x1f).unwrap();
+        cram.write(1, 0x00).unwrap();
+        assert_eq!(cram.read(0).unwrap(), 0xca);
+        assert_eq!(cram.color(1, Color::C0), Rgb555::new(0x1f, 0, 0));
+        // Wraps at the end of memory
+        cram.write(0, 0xbf).unwrap();
+        cram.write(1, 0x00).unwrap();
+        assert_eq!(cram.read(0).unwrap(), 0xc0);
+    }
+
+    #[test]
+    fn cram_color_works() {
+        let mut cram = Cram::default();
+        let rgb = Rgb555::new(0x01, 0x02, 0x03);
+        cram.set(7, Color::C3, rgb);
+        assert_eq!(cram.color(7, Color::C3), rgb);
+        // Read back through the data register
+        cram.write(0, 0x3e).unwrap();
+        assert_eq!(cram.read(1).unwrap(), 0x41);
+        cram.write(0, 0x3f).unwrap();
+        assert_eq!(cram.read(1).unwrap(), 0x0c);
+    }
+}
@@ -49,7 +49,7 @@ impl Debug {
             .map(|tnum| tdat[usize::from(Ppu::tidx(ppu, layer, tnum) >> 4)].clone());
 
         // Render tile data, maps
-        let meta = Meta::Bgw;
+        let meta = Meta::default();
         let tdat = Self::render(&tdat, ppu, &meta, 16); // 16x24 tiles
         let map1 = Self::render(&map1, ppu, &meta, 32); // 32x32 tiles
         let map2 = Self::render(&map2, ppu, &meta, 32); // 32x32 tiles
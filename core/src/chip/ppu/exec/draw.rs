@@ -1,9 +1,10 @@
 use log::{debug, trace};
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 
-use super::ppu::Color;
 use super::ppu::blk::pipe::Pipeline;
-use super::ppu::meta::{Layer, Palette, Pixel, Sprite};
+use super::ppu::meta::{Layer, Meta, Palette, Pixel, Sprite};
+use super::ppu::{Color, Rgb555};
 use super::scan::Scan;
 use super::{LCD, Mode, Ppu};
 
@@ -16,6 +17,18 @@ pub struct Draw {
     pub(super) objs: Vec<Sprite>,
 }
 
+impl State for Draw {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.pipe).seq(&self.objs);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.pipe)?;
+        self.objs = inp.seq(|| Sprite::new([0; 4]))?;
+        Ok(())
+    }
+}
+
 impl Draw {
     pub fn exec(mut self, ppu: &mut Ppu) -> Mode {
         // Execute fetch cycle
@@ -32,6 +45,11 @@ impl Draw {
             let pidx = (ly * LCD.wd) + lx; // calculate index
             ppu.etc.buf[usize::from(pidx)] = color;
             trace!("wrote pixel: {color:?} -> (row: {ly}, col: {lx})");
+            if let Some(rgb) = ppu.rgb(&pixel)
+                && let Some(cgb) = &mut ppu.cgb
+            {
+                cgb.buf[usize::from(pidx)] = rgb;
+            }
 
             // Move to next pixel
             self.pipe.lx += 1;
@@ -86,4 +104,29 @@ impl Ppu {
         );
         col
     }
+
+    /// Color a pixel using the current color palette.
+    ///
+    /// Returns `None` if there is no color hardware.
+    pub(in super::super) fn rgb(&self, pixel: &Pixel) -> Option<Rgb555> {
+        let cgb = self.cgb.as_ref()?;
+        let bcp = cgb.reg.bcp.borrow();
+        let ocp = cgb.reg.ocp.borrow();
+        let rgb = if cgb.dmg {
+            // Translate monochrome shades through the first palettes
+            let col = self.color(pixel);
+            match pixel.meta.pal() {
+                Palette::BgWin => bcp.color(0, col),
+                Palette::Obp0 => ocp.color(0, col),
+                Palette::Obp1 => ocp.color(1, col),
+            }
+        } else {
+            // Assign colors using the attributed palette
+            match pixel.meta {
+                Meta::Bgw { cpal, .. } => bcp.color(cpal, pixel.col),
+                Meta::Obj { cpal, .. } => ocp.color(cpal, pixel.col),
+            }
+        };
+        Some(rgb)
+    }
 }
@@ -1,5 +1,6 @@
 use ppu::{LCD, Ppu};
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 
 use self::draw::Draw;
 use self::hblank::HBlank;
@@ -32,6 +33,32 @@ impl Default for Mode {
     }
 }
 
+impl State for Mode {
+    fn save(&self, out: &mut Writer) {
+        self.value().save(out);
+        match self {
+            Mode::Scan(scan) => scan.save(out),
+            Mode::Draw(draw) => draw.save(out),
+            Mode::HBlank(_) | Mode::VBlank(_) => (),
+        }
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        *self = match u8::from_le_bytes(inp.array()?) {
+            0b10 => Mode::Scan(Scan::default()),
+            0b11 => Mode::Draw(Draw::default()),
+            0b00 => Mode::HBlank(HBlank),
+            0b01 => Mode::VBlank(VBlank),
+            _ => return Err(state::Error::Invalid("Mode")),
+        };
+        match self {
+            Mode::Scan(scan) => scan.restore(inp),
+            Mode::Draw(draw) => draw.restore(inp),
+            Mode::HBlank(_) | Mode::VBlank(_) => Ok(()),
+        }
+    }
+}
+
 impl Mode {
     /// Returns the internal mode value.
     #[must_use]
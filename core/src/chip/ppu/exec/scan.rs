@@ -1,6 +1,7 @@
 use log::{debug, trace};
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 
 use super::hblank::HBlank;
 use super::ppu::meta::Sprite;
@@ -16,6 +17,18 @@ pub struct Scan {
     pub(super) objs: Vec<Sprite>,
 }
 
+impl State for Scan {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.addr).seq(&self.objs);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.addr)?;
+        self.objs = inp.seq(|| Sprite::new([0; 4]))?;
+        Ok(())
+    }
+}
+
 impl Scan {
     pub fn exec(mut self, ppu: &mut Ppu) -> Mode {
         // Scanning a single entry takes 2 dots
@@ -37,7 +50,10 @@ impl Scan {
                         .unwrap_or_else(|_| panic!("failed to read from OAM at index: {addr}"))
                 });
                 // Parse sprite from bytes
-                let obj = Sprite::from(obj);
+                let obj = Sprite {
+                    idx: u8::try_from(self.addr / 4).unwrap(),
+                    ..Sprite::from(obj)
+                };
 
                 // Record sprites to be rendered that are visible this scanline
                 let ypos = obj.ypos;
@@ -1,3 +1,5 @@
+use rugby_arch::state::{self, Reader, State, Writer};
+
 use crate::api::video::Pixel;
 
 /// Color values.
@@ -27,3 +29,89 @@ impl From<u8> for Color {
 }
 
 impl Pixel for Color {}
+
+impl State for Color {
+    fn save(&self, out: &mut Writer) {
+        (*self as u8).save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        *self = match u8::from_le_bytes(inp.array()?) {
+            value @ 0b00..=0b11 => Self::from(value),
+            _ => return Err(state::Error::Invalid("Color")),
+        };
+        Ok(())
+    }
+}
+
+/// 15-bit RGB color.
+///
+/// Encoded as `0bxBBBBBGGGGGRRRRR`, matching the layout of color palette
+/// memory, with 5 bits per channel.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rgb555(u16);
+
+impl Rgb555 {
+    /// Constructs a new `Rgb555` from its 5-bit channels.
+    #[must_use]
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self((red as u16 & 0x1f) | (green as u16 & 0x1f) << 5 | (blue as u16 & 0x1f) << 10)
+    }
+
+    /// Gets the 5-bit red channel.
+    #[must_use]
+    pub const fn red(self) -> u8 {
+        (self.0 & 0x1f) as u8
+    }
+
+    /// Gets the 5-bit green channel.
+    #[must_use]
+    pub const fn green(self) -> u8 {
+        (self.0 >> 5 & 0x1f) as u8
+    }
+
+    /// Gets the 5-bit blue channel.
+    #[must_use]
+    pub const fn blue(self) -> u8 {
+        (self.0 >> 10 & 0x1f) as u8
+    }
+}
+
+impl From<u16> for Rgb555 {
+    fn from(value: u16) -> Self {
+        Self(value & 0x7fff)
+    }
+}
+
+impl From<Color> for Rgb555 {
+    /// Translates a monochrome shade to grayscale.
+    fn from(value: Color) -> Self {
+        match value {
+            Color::C0 => Self::new(0x1f, 0x1f, 0x1f),
+            Color::C1 => Self::new(0x14, 0x14, 0x14),
+            Color::C2 => Self::new(0x0a, 0x0a, 0x0a),
+            Color::C3 => Self::new(0x00, 0x00, 0x00),
+        }
+    }
+}
+
+impl From<Rgb555> for u16 {
+    fn from(value: Rgb555) -> Self {
+        value.0
+    }
+}
+
+impl Pixel for Rgb555 {}
+
+impl State for Rgb555 {
+    fn save(&self, out: &mut Writer) {
+        self.0.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut bits = u16::default();
+        inp.load(&mut bits)?;
+        *self = Self::from(bits);
+        Ok(())
+    }
+}
@@ -1,12 +1,14 @@
 //! Graphics metadata.
 
+use rugby_arch::state::{self, Reader, State, Writer};
+
 mod color;
 mod obj;
 mod pixel;
 mod tile;
 
-pub use self::color::Color;
-pub use self::obj::Sprite;
+pub use self::color::{Color, Rgb555};
+pub use self::obj::{Attributes, Sprite};
 pub use self::pixel::{Meta, Pixel};
 pub use self::tile::{Row, Tile};
 
@@ -52,3 +54,19 @@ impl Palette {
         [Self::Obp0, Self::Obp1][usize::from(flag)]
     }
 }
+
+impl State for Layer {
+    fn save(&self, out: &mut Writer) {
+        (*self as u8).save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        *self = match u8::from_le_bytes(inp.array()?) {
+            0 => Self::Background,
+            1 => Self::Window,
+            2 => Self::Sprite,
+            _ => return Err(state::Error::Invalid("Layer")),
+        };
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use rugby_arch::state::{self, Reader, State, Writer};
+
 use super::Palette;
 use super::pixel::Meta;
 
@@ -12,6 +14,8 @@ pub struct Sprite {
     pub tnum: u8,
     /// Byte 3: Attributes.
     pub attr: Attributes,
+    /// OAM index.
+    pub idx: u8,
 }
 
 impl Sprite {
@@ -28,6 +32,8 @@ impl Sprite {
             objp: Palette::objp(self.attr.objp),
             prty: self.attr.prty,
             xpos: self.xpos,
+            cpal: self.attr.cpal,
+            oidx: self.idx,
         }
     }
 }
@@ -39,18 +45,24 @@ impl From<[u8; 4]> for Sprite {
             xpos: data[1],
             tnum: data[2],
             attr: data[3].into(),
+            idx: u8::default(),
         }
     }
 }
 
 /// Sprite attributes.
 ///
-/// Attributes are encoded as `0bZYXP0000`, where:
+/// Attributes are encoded as `0bZYXPBCCC`, where:
+/// - `C` is the color palette. (CGB only.)
+/// - `B` is the VRAM bank. (CGB only.)
 /// - `P` is the object palette.
 /// - `X` is the x-flip flag.
 /// - `Y` is the y-flip flag.
 /// - `Z` is the priority flag.
-#[derive(Clone, Debug)]
+///
+/// On the CGB, background map attributes share this layout, with the object
+/// palette bit left unused.
+#[derive(Clone, Debug, Default)]
 #[expect(clippy::struct_excessive_bools)]
 pub struct Attributes {
     /// Priority flag.
@@ -70,6 +82,14 @@ pub struct Attributes {
     ///
     /// Selects between using `obp0` or `obp1`.
     pub objp: bool,
+    /// VRAM bank.
+    ///
+    /// Selects the bank from which tile data is fetched. (CGB only.)
+    pub bank: bool,
+    /// Color palette.
+    ///
+    /// Selects one of eight color palettes. (CGB only.)
+    pub cpal: u8,
 }
 
 impl Attributes {
@@ -88,6 +108,44 @@ impl From<u8> for Attributes {
             yflip: byte & (1 << 6) != 0,
             xflip: byte & (1 << 5) != 0,
             objp:  byte & (1 << 4) != 0,
+            bank:  byte & (1 << 3) != 0,
+            cpal:  byte & 0b111,
         }
     }
 }
+
+impl From<&Attributes> for u8 {
+    #[rustfmt::skip]
+    fn from(attr: &Attributes) -> Self {
+        (u8::from(attr.prty)  << 7)
+      | (u8::from(attr.yflip) << 6)
+      | (u8::from(attr.xflip) << 5)
+      | (u8::from(attr.objp)  << 4)
+      | (u8::from(attr.bank)  << 3)
+      | (attr.cpal & 0b111)
+    }
+}
+
+impl State for Sprite {
+    fn save(&self, out: &mut Writer) {
+        out.save(&[self.ypos, self.xpos, self.tnum, u8::from(&self.attr)])
+            .save(&self.idx);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        *self = Self::from(inp.array::<4>()?);
+        inp.load(&mut self.idx)?;
+        Ok(())
+    }
+}
+
+impl State for Attributes {
+    fn save(&self, out: &mut Writer) {
+        u8::from(self).save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        *self = Self::from(u8::from_le_bytes(inp.array()?));
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use rugby_arch::state::{self, Reader, State, Writer};
+
 use super::{Color, Palette};
 
 /// Pre-rendered pixel.
@@ -20,9 +22,9 @@ impl Pixel {
     #[must_use]
     pub fn blend(a: Self, b: Self) -> Self {
         match (&a.meta, &b.meta) {
-            (Meta::Bgw, Meta::Bgw) => select::color(a, b),
-            (Meta::Bgw, Meta::Obj { .. }) => select::blend(a, b),
-            (Meta::Obj { .. }, Meta::Bgw) => select::blend(b, a),
+            (Meta::Bgw { .. }, Meta::Bgw { .. }) => select::color(a, b),
+            (Meta::Bgw { .. }, Meta::Obj { .. }) => select::blend(a, b),
+            (Meta::Obj { .. }, Meta::Bgw { .. }) => select::blend(b, a),
             (Meta::Obj { .. }, Meta::Obj { .. }) => select::overlap(a, b),
         }
     }
@@ -44,21 +46,16 @@ mod select {
         //
         // 1. If the color number of the sprite pixel is 0, the background pixel
         //    is used.
-        if obj.col == Color::C0 {
-            bgw
-        }
-        // 2. If the BG-to-OBJ priority bit is 1 and the color number of the
-        //    background pixel is anything other than 0, the background pixel is
-        //    used.
-        else if bgw.col != Color::C0
-            && let Meta::Obj { prty: true, .. } = obj.meta
-        {
-            bgw
-        }
+        let transparent = obj.col == Color::C0;
+        // 2. If either BG-to-OBJ priority bit is 1 and the color number of
+        //    the background pixel is anything other than 0, the background
+        //    pixel is used. (Only the CGB has a priority bit for the
+        //    background.)
+        let prioritized = bgw.col != Color::C0
+            && (matches!(obj.meta, Meta::Obj { prty: true, .. })
+                || matches!(bgw.meta, Meta::Bgw { prty: true, .. }));
         // 3. If none of the above conditions apply, the sprite pixel is used.
-        else {
-            obj
-        }
+        if transparent || prioritized { bgw } else { obj }
         // <https://hacktix.github.io/GBEDG/ppu/#pixel-mixing>
     }
 
@@ -72,9 +69,17 @@ mod select {
         // Otherwise, use drawing priority such that:
         //
         // 1. Lower X-coordinates win
+        // 2. Lower OAM indices win
         match (&a.meta, &b.meta) {
-            (Meta::Obj { xpos: xa, .. }, Meta::Obj { xpos: xb, .. }) => {
-                if xa < xb {
+            (
+                Meta::Obj {
+                    xpos: xa, oidx: ia, ..
+                },
+                Meta::Obj {
+                    xpos: xb, oidx: ib, ..
+                },
+            ) => {
+                if (xa, ia) < (xb, ib) {
                     a
                 } else {
                     b
@@ -89,7 +94,12 @@ mod select {
 #[derive(Clone, Debug)]
 pub enum Meta {
     /// Background/window metadata.
-    Bgw,
+    Bgw {
+        /// Color palette.
+        cpal: u8,
+        /// Background priority.
+        prty: bool,
+    },
     /// Object metadata.
     Obj {
         /// Object palette.
@@ -97,7 +107,13 @@ pub enum Meta {
         /// Background priority.
         prty: bool,
         /// X-coordinate.
+        ///
+        /// Cleared when objects are prioritized by OAM index alone.
         xpos: u8,
+        /// Color palette.
+        cpal: u8,
+        /// OAM index.
+        oidx: u8,
     },
 }
 
@@ -106,8 +122,69 @@ impl Meta {
     #[must_use]
     pub fn pal(&self) -> Palette {
         match self {
-            Meta::Bgw => Palette::BgWin,
+            Meta::Bgw { .. } => Palette::BgWin,
             Meta::Obj { objp, .. } => *objp,
         }
     }
+
+    /// Retrieve the color palette.
+    #[must_use]
+    pub fn cpal(&self) -> u8 {
+        match self {
+            Meta::Bgw { cpal, .. } | Meta::Obj { cpal, .. } => *cpal,
+        }
+    }
+}
+
+impl Default for Meta {
+    fn default() -> Self {
+        Self::Bgw {
+            cpal: u8::default(),
+            prty: bool::default(),
+        }
+    }
+}
+
+impl State for Pixel {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.col).save(&self.meta);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.col)?.load(&mut self.meta)?;
+        Ok(())
+    }
+}
+
+impl State for Meta {
+    fn save(&self, out: &mut Writer) {
+        match self {
+            Meta::Bgw { cpal, prty } => out.save(&[0, 0, u8::from(*prty), 0, *cpal, 0]),
+            Meta::Obj {
+                objp,
+                prty,
+                xpos,
+                cpal,
+                oidx,
+            } => out.save(&[1, *objp as u8, u8::from(*prty), *xpos, *cpal, *oidx]),
+        };
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        *self = match inp.array::<6>()? {
+            [0, _, prty @ 0..=1, _, cpal, _] => Meta::Bgw {
+                cpal,
+                prty: prty != 0,
+            },
+            [1, objp @ 1..=2, prty @ 0..=1, xpos, cpal, oidx] => Meta::Obj {
+                objp: Palette::objp(objp == 2),
+                prty: prty != 0,
+                xpos,
+                cpal,
+                oidx,
+            },
+            _ => return Err(state::Error::Invalid("Meta")),
+        };
+        Ok(())
+    }
 }
@@ -2,6 +2,7 @@
 
 use rugby_arch::mem::{Memory, Ram};
 use rugby_arch::reg::{Port, Register};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use self::exec::hblank::HBlank;
@@ -13,13 +14,14 @@ use crate::dmg::pcb::Vram;
 mod blk;
 mod exec;
 
+pub mod cgb;
 #[cfg(feature = "debug")]
 pub mod dbg;
 pub mod meta;
 pub mod reg;
 
 pub use self::exec::Mode;
-pub use self::meta::Color;
+pub use self::meta::{Color, Rgb555};
 
 /// Frame frame rate.
 ///
@@ -133,6 +135,10 @@ pub struct Ppu {
     pub etc: Internal,
     /// Interrupt line.
     pub irq: irq::Line,
+    /// Color graphics.
+    ///
+    /// Only present on the CGB.
+    pub cgb: Option<cgb::Cgb>,
 }
 
 /// Graphics internals.
@@ -180,6 +186,29 @@ impl Default for Internal {
     }
 }
 
+impl State for Internal {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.buf)
+            .save(&self.dot)
+            .save(&self.line)
+            .save(&self.int)
+            .save(&self.ywin)
+            .save(&self.ytrg)
+            .save(&self.mode);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.buf)?
+            .load(&mut self.dot)?
+            .load(&mut self.line)?
+            .load(&mut self.int)?
+            .load(&mut self.ywin)?
+            .load(&mut self.ytrg)?
+            .load(&mut self.mode)?;
+        Ok(())
+    }
+}
+
 impl Ppu {
     /// Gets the current execution cycle.
     #[must_use]
@@ -210,6 +239,27 @@ impl Ppu {
         self.etc.ytrg = false;
         self.etc.mode = Mode::default();
     }
+
+    /// Checks if the PPU is rendering in CGB mode.
+    ///
+    /// This is false for the DMG, as well as for the CGB while in DMG
+    /// compatibility mode.
+    #[must_use]
+    pub fn cgb_mode(&self) -> bool {
+        self.cgb.as_ref().is_some_and(|cgb| !cgb.dmg)
+    }
+
+    /// Reads a byte of video RAM.
+    ///
+    /// Bit 13 of the address selects the VRAM bank, with bank 1 only present on
+    /// the CGB.
+    pub(crate) fn vram(&self, addr: u16) -> u8 {
+        match (addr & 0x2000 != 0, &self.cgb) {
+            (true, Some(cgb)) => cgb.vram.read(addr & 0x1fff),
+            _ => self.mem.vram.read(addr & 0x1fff),
+        }
+        .unwrap()
+    }
 }
 
 impl Api for Ppu {
@@ -254,6 +304,27 @@ impl Block for Ppu {
     }
 }
 
+impl State for Ppu {
+    // NOTE: Video RAM is owned by the motherboard, and is saved there.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.mem.oam).save(&self.etc);
+        // Color graphics are fixed by the model, so are saved in place
+        if let Some(cgb) = &self.cgb {
+            out.save(cgb);
+        }
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?
+            .load(&mut self.mem.oam)?
+            .load(&mut self.etc)?;
+        if let Some(cgb) = &mut self.cgb {
+            inp.load(cgb)?;
+        }
+        Ok(())
+    }
+}
+
 #[rustfmt::skip]
 impl Port<u8> for Ppu {
     type Select = Select;
@@ -379,3 +450,35 @@ pub struct File {
 impl Block for File {
     fn reset(&mut self) {}
 }
+
+impl State for File {
+    // NOTE: The DMA register is owned by the DMA unit, and is saved there.
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.lcdc)
+            .save(&self.stat)
+            .save(&self.scy)
+            .save(&self.scx)
+            .save(&self.ly)
+            .save(&self.lyc)
+            .save(&self.bgp)
+            .save(&self.obp0)
+            .save(&self.obp1)
+            .save(&self.wy)
+            .save(&self.wx);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.lcdc)?
+            .load(&mut self.stat)?
+            .load(&mut self.scy)?
+            .load(&mut self.scx)?
+            .load(&mut self.ly)?
+            .load(&mut self.lyc)?
+            .load(&mut self.bgp)?
+            .load(&mut self.obp0)?
+            .load(&mut self.obp1)?
+            .load(&mut self.wy)?
+            .load(&mut self.wx)?;
+        Ok(())
+    }
+}
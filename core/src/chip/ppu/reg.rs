@@ -5,6 +5,7 @@
 use bitfield_struct::bitfield;
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 
 /// `LCDC`: LCD control register.
 ///
@@ -226,6 +227,16 @@ impl Register for Ly {
     }
 }
 
+impl State for Ly {
+    fn save(&self, out: &mut Writer) {
+        self.0.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.0.restore(inp)
+    }
+}
+
 /// `LYC`: LY compare.
 pub type Lyc = u8;
 
@@ -234,3 +245,5 @@ pub type Wy = u8;
 
 /// `WX`: Window X position.
 pub type Wx = u8;
+
+bitfield_state!(Lcdc, Stat, Pal);
@@ -6,10 +6,11 @@ use std::io::{BufRead, Write};
 use log::{debug, trace};
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::{Port, Register};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::irq::{self, Interrupt};
-use crate::api::cable::Cable as Api;
+use crate::api::cable::{Cable as Api, Status};
 
 /// Serial register select.
 ///
@@ -64,6 +65,29 @@ impl Internal {
     }
 }
 
+impl State for Serial {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.etc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?.load(&mut self.etc)?;
+        Ok(())
+    }
+}
+
+impl State for Internal {
+    // NOTE: The transfer queues belong to the connected cable, rather than the
+    //       port, so are left untouched.
+    fn save(&self, out: &mut Writer) {
+        self.ip.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.ip.restore(inp)
+    }
+}
+
 impl Serial {
     /// Perform a tick of the external clock.
     pub fn tick(&mut self) {
@@ -107,6 +131,19 @@ impl Api for Serial {
         // Return `rx`, since the internal receiver is the external transmitter.
         &mut self.etc.rx
     }
+
+    fn status(&self) -> Status {
+        let sc = self.reg.sc.borrow();
+        match (sc.ena(), sc.clk()) {
+            (false, _) => Status::Idle,
+            (true, true) => Status::Master,
+            (true, false) => Status::Slave(self.reg.sb.load()),
+        }
+    }
+
+    fn tick(&mut self) {
+        Serial::tick(self);
+    }
 }
 
 impl Block for Serial {
@@ -193,5 +230,16 @@ impl Block for File {
     }
 }
 
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.sb).save(&self.sc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.sb)?.load(&mut self.sc)?;
+        Ok(())
+    }
+}
+
 /// Serial register models.
 pub mod reg;
@@ -4,6 +4,7 @@ use bitfield_struct::bitfield;
 use log::{debug, warn};
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 
 /// Serial data.
 pub type Sb = u8;
@@ -83,3 +84,16 @@ impl Register for Sc {
         debug!("started tx");
     }
 }
+
+bitfield_state!(ScBits);
+
+impl State for Sc {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.bit);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?.load(&mut self.bit)?;
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@
 use log::{debug, trace};
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::{Port, Register};
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::irq::{self, Interrupt};
@@ -48,6 +49,16 @@ impl Internal {
     }
 }
 
+impl State for Internal {
+    fn save(&self, out: &mut Writer) {
+        self.and.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.and.restore(inp)
+    }
+}
+
 impl Timer {
     /// Constructs a new `Timer`.
     #[must_use]
@@ -129,6 +140,17 @@ impl Block for Timer {
     }
 }
 
+impl State for Timer {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.reg).save(&self.etc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?.load(&mut self.etc)?;
+        Ok(())
+    }
+}
+
 impl Port<u8> for Timer {
     type Select = Select;
 
@@ -185,6 +207,23 @@ impl Block for File {
     }
 }
 
+impl State for File {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.div)
+            .save(&self.tima)
+            .save(&self.tma)
+            .save(&self.tac);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.div)?
+            .load(&mut self.tima)?
+            .load(&mut self.tma)?
+            .load(&mut self.tac)?;
+        Ok(())
+    }
+}
+
 /// Timer register models.
 pub mod reg;
 #[cfg(test)]
@@ -4,6 +4,7 @@ use bitfield_struct::bitfield;
 use log::debug;
 use rugby_arch::mem::Memory;
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 
 /// Divider register.
 #[derive(Debug, Default)]
@@ -55,6 +56,16 @@ impl Register for Div {
     }
 }
 
+impl State for Div {
+    fn save(&self, out: &mut Writer) {
+        self.0.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.0.restore(inp)
+    }
+}
+
 /// Timer counter.
 #[derive(Debug, Default)]
 pub struct Tima {
@@ -148,6 +159,31 @@ impl Register for Tima {
     }
 }
 
+impl State for Tima {
+    fn save(&self, out: &mut Writer) {
+        let (rel, cnt) = match self.rel {
+            Reload::None => (0, 0),
+            Reload::Wait(cnt) => (1, cnt),
+            Reload::Load => (2, 0),
+            Reload::Done(cnt) => (3, cnt),
+        };
+        out.save(&self.reg).save(&[rel, cnt]).save(&self.sup);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.reg)?;
+        self.rel = match inp.array()? {
+            [0, _] => Reload::None,
+            [1, cnt] => Reload::Wait(cnt),
+            [2, _] => Reload::Load,
+            [3, cnt] => Reload::Done(cnt),
+            _ => return Err(state::Error::Invalid("Reload")),
+        };
+        inp.load(&mut self.sup)?;
+        Ok(())
+    }
+}
+
 /// Timer modulo.
 pub type Tma = u8;
 
@@ -213,3 +249,5 @@ impl Register for Tac {
         *self = Self::from_bits(value & 0b111);
     }
 }
+
+bitfield_state!(Tac);
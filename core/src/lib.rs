@@ -15,6 +15,9 @@
 //! - [DMG-CPU-B](model::dmg::rev::B).
 //! - [DMG-CPU-C](model::dmg::rev::C).
 //!
+//! The [_Game Boy Color_](model::cgb::GameBoyColor) is modelled separately, as
+//! it has no revision-specific behaviour.
+//!
 //! [`rugby`]: https://docs.rs/rugby
 
 #![warn(clippy::pedantic)]
@@ -32,5 +35,5 @@ pub mod api;
 pub mod cart;
 pub mod chip;
 
-pub use crate::model::dmg;
+pub use crate::model::{cgb, dmg};
 pub use crate::rev::Revision;
@@ -0,0 +1,199 @@
+//! Memory map.
+//!
+//! The Game Boy Color's memory architecture is divided across the same three
+//! buses as the [DMG](crate::dmg::bus), with the addition of switchable banks
+//! for video and work RAM:
+//!
+//! - [Internal](Ibus): Embedded within the CPU CGB.
+//! - [External](Ebus): Accessible to on-board components.
+//! - [Graphics](Vbus): Connected only to VRAM.
+//!
+//! See more details [here][mmap].
+//!
+//! [mmap]: https://gbdev.io/pandocs/Memory_Map.html
+
+use rugby_arch::Shared;
+use rugby_arch::mem::Memory;
+use rugby_arch::reg::Register;
+
+use super::pcb::{Vram, Wram};
+use super::reg::{Bank, Speed};
+use super::soc::{apu, cpu, dma, hdma, irq, joy, ppu, sio, tma};
+use crate::cart;
+
+pub mod view;
+
+/// Internal bus.
+///
+/// Embedded within the CPU CGB, usable only by the CPU.
+///
+/// |     Address     |  Size  | Device | Description      |
+/// |:---------------:|-------:|--------|------------------|
+/// | `$FE00..=$FE9F` |  160 B | `ppu`  | Object memory    |
+/// | `$FF00..=$FF7F` |  128 B | `io`   | I/O registers    |
+/// | `$FF30..=$FF3F` |   16 B | `apu`  | Wave RAM         |
+/// | `$FF80..=$FFFE` |  127 B | `cpu`  | High RAM         |
+/// | `$FFFF..=$FFFF` |    1 B | `io`   | Interrupt enable |
+#[derive(Debug)]
+#[derive(Memory)]
+pub struct Ibus {
+    /// Graphics memory.
+    #[mmap(0xfe00..=0xfe9f)]
+    pub ppu: dma::Gate,
+    /// Audio memory.
+    #[mmap(0xff30..=0xff3f)]
+    pub apu: apu::Bank,
+    /// I/O registers.
+    #[mmap(0xff00..=0xff7f)]
+    #[mmap(0xffff)]
+    pub io: File,
+    /// Processor memory.
+    #[mmap(0xff80..=0xfffe)]
+    pub cpu: cpu::Bank,
+}
+
+/// External bus.
+///
+/// Accessible to on-board components.
+///
+/// |     Address     |  Size  | Device | Description   |
+/// |:---------------:|-------:|--------|---------------|
+/// | `$0000..=$7FFF` | 32 KiB | `cart` | Cartridge ROM |
+/// | `$A000..=$BFFF` |  8 KiB | `cart` | External RAM  |
+/// | `$C000..=$FFFF` |  8 KiB | `wram` | Work/Echo RAM |
+#[derive(Debug)]
+#[derive(Memory)]
+pub struct Ebus {
+    /// Game cartridge.
+    #[mmap(0x0000..=0x7fff)]
+    #[mmap(0xa000..=0xbfff)]
+    pub cart: cart::Slot,
+    /// Work RAM.
+    #[mmap(0xc000..=0xffff, mask = 0x1fff)]
+    pub wram: Wbank,
+}
+
+/// Banked work RAM.
+///
+/// The lower 4 KiB always maps bank 0, while the upper 4 KiB maps the bank
+/// selected by `SVBK`, with a selection of 0 treated as bank 1.
+///
+/// |     Address     |  Size  | Description  |
+/// |:---------------:|-------:|--------------|
+/// | `$0000..=$0FFF` |  4 KiB | Bank 0       |
+/// | `$1000..=$1FFF` |  4 KiB | Banks 1-7    |
+#[derive(Debug)]
+pub struct Wbank {
+    /// Work RAM.
+    pub mem: Shared<Wram>,
+    /// Bank select.
+    pub svbk: Shared<Bank>,
+}
+
+impl Wbank {
+    /// Translates an address into work RAM.
+    fn adjust(&self, addr: u16) -> u16 {
+        if addr < 0x1000 {
+            addr
+        } else {
+            let bank = u16::from(self.svbk.load().max(1));
+            bank << 12 | (addr & 0x0fff)
+        }
+    }
+}
+
+impl Memory for Wbank {
+    fn read(&self, addr: u16) -> rugby_arch::mem::Result<u8> {
+        self.mem.read(self.adjust(addr))
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        self.mem.write(self.adjust(addr), data)
+    }
+}
+
+/// Video bus.
+///
+/// Connected only to VRAM, with the bank selected by `VBK`.
+///
+/// |     Address     |  Size  | Device | Description |
+/// |:---------------:|-------:|--------|-------------|
+/// | `$8000..=$9FFF` |  8 KiB | `vram` | Video RAM   |
+#[derive(Debug)]
+pub struct Vbus {
+    /// Video RAM.
+    pub vram: [Shared<Vram>; 2],
+    /// Bank select.
+    pub vbk: Shared<Bank>,
+}
+
+impl Memory for Vbus {
+    fn read(&self, addr: u16) -> rugby_arch::mem::Result<u8> {
+        let bank = usize::from(self.vbk.load());
+        self.vram[bank].read(addr & 0x1fff)
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        let bank = usize::from(self.vbk.load());
+        self.vram[bank].write(addr & 0x1fff, data)
+    }
+}
+
+/// I/O registers.
+///
+/// |     Address     |  Size  | Device | Description      |
+/// |:---------------:|-------:|--------|------------------|
+/// | `$FF00..=$FF00` |    1 B | `joy`  | Joypad           |
+/// | `$FF01..=$FF02` |    2 B | `sio`  | Serial           |
+/// | `$FF04..=$FF07` |    4 B | `tma`  | Timer            |
+/// | `$FF0F..=$FF0F` |    1 B | `irq`  | Interrupt flag   |
+/// | `$FF10..=$FF26` |   23 B | `apu`  | Audio            |
+/// | `$FF40..=$FF4B` |   12 B | `ppu`  | Graphics         |
+/// | `$FF46..=$FF46` |    1 B | `dma`  | OAM DMA          |
+/// | `$FF4D..=$FF4D` |    1 B | `key1` | Speed switch     |
+/// | `$FF4F..=$FF4F` |    1 B | `vbk`  | VRAM bank        |
+/// | `$FF51..=$FF55` |    5 B | `hdma` | VRAM DMA         |
+/// | `$FF68..=$FF6C` |    5 B | `cgb`  | Color graphics   |
+/// | `$FF70..=$FF70` |    1 B | `svbk` | WRAM bank        |
+/// | `$FFFF..=$FFFF` |    1 B | `irq`  | Interrupt enable |
+#[derive(Debug)]
+#[derive(Memory)]
+pub struct File {
+    /// Joypad.
+    #[mmap(0xff00)]
+    pub joy: Shared<joy::Control>,
+    /// Serial.
+    #[mmap(0xff01..=0xff02)]
+    pub sio: sio::File,
+    /// Timer.
+    #[mmap(0xff04..=0xff07)]
+    pub tma: tma::File,
+    /// Interrupts.
+    #[mmap(0xff0f)]
+    #[mmap(0xffff)]
+    pub irq: irq::File,
+    /// Audio.
+    #[mmap(0xff10..=0xff26)]
+    pub apu: apu::File,
+    /// OAM DMA.
+    #[mmap(0xff46)]
+    pub dma: Shared<dma::Control>,
+    /// Graphics.
+    #[mmap(0xff40..=0xff4b)]
+    pub ppu: ppu::File,
+    /// Speed switch.
+    #[mmap(0xff4d)]
+    pub key1: Shared<Speed>,
+    /// VRAM bank.
+    #[mmap(0xff4f)]
+    pub vbk: Shared<Bank>,
+    /// VRAM DMA.
+    #[mmap(0xff51..=0xff55, mask = 0x0007)]
+    pub hdma: Shared<hdma::Control>,
+    /// Color graphics.
+    #[mmap(0xff68..=0xff6c)]
+    pub cgb: ppu::cgb::File,
+    /// WRAM bank.
+    #[mmap(0xff70)]
+    pub svbk: Shared<Bank>,
+}
@@ -0,0 +1,10 @@
+//! Debugging the [CGB](super).
+
+use super::GameBoyColor;
+use super::soc::ppu;
+
+/// Collect debug information from the PPU.
+#[must_use]
+pub fn ppu(emu: &GameBoyColor) -> ppu::dbg::Debug {
+    ppu::dbg::info(&emu.main.soc.ppu)
+}
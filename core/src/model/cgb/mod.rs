@@ -1,5 +1,396 @@
 //! _Game Boy Color_.
 
+use std::io::{BufRead, Write};
+
+use log::warn;
+use rugby_arch::reg::{Port, Register};
+use rugby_arch::state::{self, Reader, Writer};
+use rugby_arch::{Block, Shared};
+
+use self::pcb::{Motherboard, Vram, Wram};
+use self::soc::cpu::Cpu;
+use self::soc::joy::Joypad;
+use self::soc::ppu::{Color, Ppu, Rgb555};
+use crate::api::audio::{Audio, Capture, Chiptune, Mixer, Sample};
+use crate::api::cable::{Cable, Status};
+use crate::api::camera::{Camera, ImageSource};
+use crate::api::cheat::{Cheat, Code};
+use crate::api::infrared::Infrared;
+use crate::api::input::{Event, Input};
+use crate::api::motion::Motion;
+use crate::api::rumble::Rumble;
+use crate::api::snapshot::{self, Snapshot};
+use crate::api::video::{Aspect, Video};
+use crate::cart::Cartridge;
+
+pub mod bus;
+#[cfg(feature = "debug")]
+pub mod dbg;
+pub mod pcb;
+pub mod reg;
+pub mod soc;
+
+/// Clock frequency.
+///
+/// Crystal oscillator frequency of 4 KiHz, doubled in double speed mode.
+#[expect(clippy::doc_markdown)]
+pub const CLOCK: u32 = 4_194_304;
+
 /// _Game Boy Color_.
-#[expect(unused)]
-pub struct GameBoyColor;
+#[derive(Debug)]
+pub struct GameBoyColor {
+    /// CGB-CPU-01 Motherboard.
+    main: Motherboard,
+}
+
+impl Default for GameBoyColor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameBoyColor {
+    /// Constructs a new `GameBoyColor`.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut this = Self {
+            main: Motherboard::default(),
+        };
+        this.boot();
+        this
+    }
+
+    /// Simulate the bootup sequence.
+    ///
+    /// This prepares the `GameBoyColor` to run the contents of a game
+    /// cartridge.
+    #[rustfmt::skip]
+    fn boot(&mut self) {
+        let cpu = &mut self.main.soc.cpu;
+
+        // Initialize registers
+        #[expect(clippy::items_after_statements)]
+        type Select = <Cpu as Port<u16>>::Select;
+        cpu.store(Select::AF, 0x1180_u16);
+        cpu.store(Select::BC, 0x0000_u16);
+        cpu.store(Select::DE, 0xff56_u16);
+        cpu.store(Select::HL, 0x000d_u16);
+        cpu.store(Select::SP, 0xfffe_u16);
+
+        // Perform bootup sequence
+        cpu.blk.bus.write(0xff40, 0x91); // enable display
+        cpu.exec(0xfb);          // enable interrupts
+        cpu.goto(0x0100);        // transfer program control
+
+        // Select compatibility mode
+        self.latch();
+    }
+
+    /// Latches the rendering mode from the inserted cartridge.
+    ///
+    /// Cartridges without CGB support run in DMG compatibility mode, using a
+    /// monochrome palette and DMG object priority.
+    fn latch(&mut self) {
+        let dmg = self.cart().is_some_and(|cart| !cart.header().compat.cgb);
+        let Some(cgb) = &mut self.main.soc.ppu.cgb else {
+            unreachable!("CGB PPU should have color graphics");
+        };
+        cgb.dmg = dmg;
+        if dmg {
+            // Prioritize objects by X-coordinate
+            cgb.reg.opri.store(true);
+            // Initialize monochrome palettes
+            //
+            // Grayscale stands in for the colorization the boot ROM would
+            // otherwise select.
+            for col in [Color::C0, Color::C1, Color::C2, Color::C3] {
+                let rgb = Rgb555::from(col);
+                cgb.reg.bcp.borrow_mut().set(0, col, rgb);
+                cgb.reg.ocp.borrow_mut().set(0, col, rgb);
+                cgb.reg.ocp.borrow_mut().set(1, col, rgb);
+            }
+        }
+    }
+
+    /// Gets the current frame as monochrome shades.
+    ///
+    /// Shades are those which would be displayed by a DMG, before any colors
+    /// are applied.
+    #[must_use]
+    pub fn shades(&self) -> &[Color] {
+        self.main.soc.ppu.frame()
+    }
+
+    /// Checks if running in DMG compatibility mode.
+    #[must_use]
+    pub fn compat(&self) -> bool {
+        !self.main.soc.ppu.cgb_mode()
+    }
+
+    /// Gets the inserted game cartridge, if any.
+    #[must_use]
+    pub fn cart(&self) -> Option<Cartridge> {
+        self.main.cart.get()
+    }
+
+    /// Inserts a game cartridge.
+    ///
+    /// If a cartridge is already inserted, it will first be
+    /// [ejected](Self::eject).
+    pub fn insert(&mut self, cart: Cartridge) {
+        // Disconnect previous cartridge
+        if let Some(cart) = self.eject() {
+            warn!("ejected previous cartridge: {}", cart.header());
+        }
+        // Insert supplied cartridge
+        self.main.cart.insert(cart);
+        // Select compatibility mode
+        self.latch();
+    }
+
+    /// Ejects the inserted game cartridge, if any.
+    pub fn eject(&mut self) -> Option<Cartridge> {
+        self.main.cart.eject()
+    }
+
+    /// Gets a handle to the video RAM.
+    #[must_use]
+    pub fn vram(&self) -> [Shared<Vram>; 2] {
+        self.main.vram.clone()
+    }
+
+    /// Gets a handle to the work RAM.
+    #[must_use]
+    pub fn wram(&self) -> Shared<Wram> {
+        self.main.wram.clone()
+    }
+}
+
+#[cfg(feature = "debug")]
+impl GameBoyColor {
+    /// Borrows the internal emulator.
+    #[must_use]
+    pub fn inner(&self) -> &Motherboard {
+        &self.main
+    }
+
+    /// Mutably borrows the internal emulator.
+    pub fn inner_mut(&mut self) -> &mut Motherboard {
+        &mut self.main
+    }
+}
+
+impl Block for GameBoyColor {
+    fn ready(&self) -> bool {
+        self.main.ready()
+    }
+
+    fn cycle(&mut self) {
+        self.main.cycle();
+    }
+
+    fn reset(&mut self) {
+        self.main.reset();
+        self.boot();
+        self.main.cart.reset();
+    }
+}
+
+impl Audio for GameBoyColor {
+    fn sample(&self) -> Chiptune {
+        self.main.soc.apu.sample()
+    }
+
+    fn set_rate(&mut self, rate: u32) {
+        self.main.soc.apu.set_rate(rate);
+    }
+
+    fn pull(&mut self, out: &mut Vec<Sample>) {
+        self.main.soc.apu.pull(out);
+    }
+
+    fn set_mixer(&mut self, mix: Mixer) {
+        self.main.soc.apu.set_mixer(mix);
+    }
+
+    fn set_capture(&mut self, rate: u32) {
+        self.main.soc.apu.set_capture(rate);
+    }
+
+    fn capture(&mut self, out: &mut Capture) {
+        self.main.soc.apu.capture(out);
+    }
+}
+
+impl Cable for GameBoyColor {
+    fn rx(&mut self) -> &mut dyn BufRead {
+        self.main.soc.sio.rx()
+    }
+
+    fn tx(&mut self) -> &mut dyn Write {
+        self.main.soc.sio.tx()
+    }
+
+    fn status(&self) -> Status {
+        self.main.soc.sio.status()
+    }
+
+    fn tick(&mut self) {
+        self.main.soc.sio.tick();
+    }
+}
+
+impl Cheat for GameBoyColor {
+    fn cheats(&self) -> Vec<Code> {
+        self.main.cart.cheats()
+    }
+
+    fn add_cheat(&mut self, code: Code) {
+        self.main.cart.add_cheat(code);
+    }
+
+    fn clear_cheats(&mut self) {
+        self.main.cart.clear_cheats();
+    }
+}
+
+impl Input for GameBoyColor {
+    type Button = <Joypad as Input>::Button;
+
+    fn recv(&mut self, events: impl IntoIterator<Item = Event<Self::Button>>) {
+        self.main.soc.joy.recv(events);
+    }
+}
+
+impl Rumble for GameBoyColor {
+    fn rumble(&self) -> f32 {
+        self.main.cart.rumble()
+    }
+}
+
+impl Infrared for GameBoyColor {
+    fn emit(&self) -> bool {
+        self.main.cart.infrared()
+    }
+
+    fn detect(&mut self, light: bool) {
+        self.main.cart.set_infrared(light);
+    }
+}
+
+impl Motion for GameBoyColor {
+    fn tilt(&mut self, x: f32, y: f32) {
+        self.main.cart.tilt(x, y);
+    }
+}
+
+impl Camera for GameBoyColor {
+    fn set_source(&mut self, src: Box<dyn ImageSource>) {
+        self.main.cart.set_camera(src);
+    }
+}
+
+impl GameBoyColor {
+    /// Snapshot signature.
+    const MAGIC: [u8; 8] = *b"RUGBYSAV";
+
+    /// Snapshot format version.
+    ///
+    /// Must be incremented whenever the encoding changes.
+    const VERSION: u16 = 4;
+
+    /// Snapshot model identifier.
+    const MODEL: &str = "CGB";
+
+    /// Restores the motherboard from an encoded snapshot body.
+    fn restore(&mut self, mut inp: Reader) -> state::Result<()> {
+        inp.load(&mut self.main)?;
+        inp.finish()
+    }
+}
+
+impl Snapshot for GameBoyColor {
+    fn save_state(&self) -> Vec<u8> {
+        let mut out = Writer::new();
+        // Write the header
+        out.put(&Self::MAGIC);
+        out.save(&Self::VERSION)
+            .section(b"INFO", |out| {
+                let cart = self.cart().map(|cart| cart.header().check.gchk);
+                out.seq(Self::MODEL.as_bytes()).save(&cart);
+            })
+            // Write the machine
+            .save(&self.main);
+        out.finish()
+    }
+
+    fn load_state(&mut self, buf: &[u8]) -> snapshot::Result<()> {
+        let mut inp = Reader::new(buf);
+        // Check the signature
+        if inp.take(Self::MAGIC.len()).ok() != Some(&Self::MAGIC) {
+            return Err(snapshot::Error::Signature);
+        }
+        // Check the version
+        let mut version = u16::default();
+        inp.load(&mut version)?;
+        if version != Self::VERSION {
+            return Err(snapshot::Error::Version {
+                found: version,
+                expected: Self::VERSION,
+            });
+        }
+        // Check the machine
+        let mut model = Vec::<u8>::new();
+        let mut cart = Option::<u16>::None;
+        inp.section(b"INFO", |inp| {
+            inp.load(&mut model)?.load(&mut cart)?;
+            Ok(())
+        })?;
+        let model = String::from_utf8_lossy(&model);
+        if model != Self::MODEL {
+            return Err(snapshot::Error::Model {
+                found: model.into_owned(),
+                expected: Self::MODEL.to_string(),
+            });
+        }
+        if cart != self.cart().map(|cart| cart.header().check.gchk) {
+            return Err(snapshot::Error::Cartridge);
+        }
+        // Restore the machine
+        //
+        // NOTE: As restoring is performed in place, a backup is used to roll
+        //       back partially restored states.
+        let backup = {
+            let mut out = Writer::new();
+            out.save(&self.main);
+            out.finish()
+        };
+        self.restore(inp).inspect_err(|_| {
+            self.restore(Reader::new(&backup))
+                .expect("backup should always restore");
+        })?;
+        Ok(())
+    }
+}
+
+impl Video for GameBoyColor {
+    const SIZE: Aspect = Ppu::SIZE;
+
+    type Pixel = Rgb555;
+
+    fn vsync(&self) -> bool {
+        self.main.soc.ppu.vsync()
+    }
+
+    fn frame(&self) -> &[Self::Pixel] {
+        self.main
+            .soc
+            .ppu
+            .cgb
+            .as_ref()
+            .map_or(&[], |cgb| cgb.frame())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,223 @@
+//! CGB-CPU-01 motherboard.
+
+use log::warn;
+use rugby_arch::mem::Ram;
+use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
+use rugby_arch::{Block, Shared};
+
+use super::soc::SoC;
+use super::soc::ppu::Mode;
+use crate::api::cheat::Code;
+use crate::api::video::Video;
+use crate::cart;
+pub use crate::model::dmg::pcb::Vram;
+
+/// Work RAM.
+///
+/// 32 KiB RAM used as general-purpose transient memory, divided into eight
+/// banks of 4 KiB.
+pub type Wram = Ram<Box<[u8]>>;
+
+/// CGB-CPU-01 PCB.
+#[derive(Debug)]
+pub struct Motherboard {
+    /// Crystal oscillator.
+    pub clk: u128,
+    /// Video RAM.
+    ///
+    /// Both banks reside in the same package.
+    pub vram: [Shared<Vram>; 2],
+    /// Work RAM.
+    pub wram: Shared<Wram>,
+    /// Cartridge slot.
+    pub cart: cart::Slot,
+    /// System-on-chip.
+    pub soc: SoC,
+}
+
+impl Default for Motherboard {
+    fn default() -> Self {
+        // Crystal oscillator
+        let clk = u128::default();
+        // Video RAM
+        let vram = [(); 2].map(|()| Shared::new(Vram::from([u8::default(); 0x2000])));
+        // Work RAM
+        let wram = Shared::new(Wram::from(vec![u8::default(); 0x8000].into_boxed_slice()));
+        // Cartridge slot
+        let cart = cart::Slot::new();
+        // System-on-chip
+        let soc = SoC::new(&vram, &wram, &cart);
+
+        // Finish construction
+        Self {
+            clk,
+            vram,
+            wram,
+            cart,
+            soc,
+        }
+    }
+}
+
+impl Motherboard {
+    /// Constructs a new `Motherboard`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl State for Motherboard {
+    fn save(&self, out: &mut Writer) {
+        out.section(b"MAIN", |out| {
+            out.save(&self.clk);
+        })
+        .section(b"VRAM", |out| {
+            out.save(&self.vram[0]).save(&self.vram[1]);
+        })
+        .section(b"WRAM", |out| {
+            out.save(&self.wram);
+        })
+        .section(b"CART", |out| {
+            out.save(&self.cart);
+        })
+        .save(&self.soc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.section(b"MAIN", |inp| inp.load(&mut self.clk).map(drop))?
+            .section(b"VRAM", |inp| {
+                let [vram0, vram1] = &mut self.vram;
+                inp.load(vram0)?.load(vram1).map(drop)
+            })?
+            .section(b"WRAM", |inp| inp.load(&mut self.wram).map(drop))?
+            .section(b"CART", |inp| inp.load(&mut self.cart).map(drop))?
+            .load(&mut self.soc)?;
+        Ok(())
+    }
+}
+
+impl Block for Motherboard {
+    fn ready(&self) -> bool {
+        self.soc.ready()
+    }
+
+    fn cycle(&mut self) {
+        // Wake halted CPU on pending interrupt
+        if self.soc.cpu.halted() && self.soc.irq.line.pending() {
+            self.soc.cpu.wake();
+        }
+        // Switch speed when stopped while armed
+        //
+        // The CPU is woken immediately, but remains paused until the switch
+        // completes.
+        if self.soc.cpu.stopped() && self.soc.key1.borrow().armed() {
+            self.soc.key1.borrow_mut().switch();
+            self.soc.cpu.wake();
+        }
+        // Wake stopped CPU on joypad input
+        if self.soc.cpu.stopped() && self.soc.joy.reg.load() & 0x0f != 0x0f {
+            self.soc.cpu.wake();
+        }
+        // Halt the oscillator while stopped
+        if self.soc.cpu.stopped() {
+            return;
+        }
+
+        // In double speed mode, the CPU and its peripherals run twice as fast
+        // relative to the PPU and APU.
+        let fast = self.soc.key1.borrow().fast();
+        let div: u128 = if fast { 2 } else { 4 };
+
+        // APU: 4 MiHz
+        //
+        // NOTE: The frame sequencer compensates for the faster divider.
+        self.soc.apu.seq.fast = fast;
+        if self.soc.apu.ready() {
+            self.soc.apu.cycle();
+        }
+        // Speed switch: 1 MiHz (2 MiHz)
+        //
+        // While switching, the divider is held reset.
+        let switching = self.soc.key1.borrow().switching();
+        if switching && self.clk.is_multiple_of(div) {
+            self.soc.key1.borrow_mut().settle();
+            self.soc.tma.reg.div.take();
+        }
+        // CPU: 1 MiHz (2 MiHz)
+        //
+        // NOTE: The CPU is paused during VRAM DMA and speed switches.
+        if self.soc.cpu.ready()
+            && !self.soc.hdma.ready()
+            && !switching
+            && self.clk.is_multiple_of(div)
+        {
+            // Track the ROM bank in which call frames are entered
+            //
+            // Only needed while frames are observed by the debugger.
+            #[cfg(feature = "debug")]
+            if self.soc.cpu.stack().watched() {
+                let bank = self.cart.bank().unwrap_or(1);
+                let bank = u16::try_from(bank).unwrap_or(u16::MAX);
+                self.soc.cpu.stack_mut().map(bank);
+            }
+            self.soc.cpu.cycle();
+        }
+        // DMA: 1 MiHz (2 MiHz)
+        if self.soc.dma.ready() && self.clk.is_multiple_of(div) {
+            self.soc.dma.cycle();
+        }
+        // HDMA: 2 MiHz
+        if self.soc.hdma.ready() && self.clk.is_multiple_of(2) {
+            self.soc.hdma.cycle();
+        }
+        // PPU: 4 MiHz
+        if self.soc.ppu.ready() {
+            self.soc.ppu.cycle();
+        } else {
+            // When the LCD is disabled, the PPU holds its reset state.
+            self.soc.ppu.disable();
+        }
+        // HDMA: signal horizontal blank
+        let hblank = self.soc.ppu.ready() && matches!(self.soc.ppu.mode(), Mode::HBlank(_));
+        self.soc.hdma.hblank(hblank);
+        // Cheats: once per frame
+        //
+        // Writes bypass the processor's probes, as the cheat device is not
+        // the processor.
+        if self.soc.ppu.vsync() {
+            for code in self.cart.codes().iter() {
+                if let Code::Shark(code) = code {
+                    self.soc.cpu.blk.bus.poke(code.addr, code.data);
+                }
+            }
+        }
+        // Cartridge: 32 KiHz
+        if self.cart.ready() && self.clk.is_multiple_of(128) {
+            self.cart.cycle();
+        }
+        // Serial: 8192 Hz (16384 Hz)
+        if self.soc.sio.ready() && self.clk.is_multiple_of(128 * div) {
+            self.soc.sio.cycle();
+        }
+        // Timer: 4 MiHz (8 MiHz)
+        if self.soc.tma.ready() {
+            self.soc.tma.cycle();
+            if fast {
+                self.soc.tma.cycle();
+            }
+        }
+
+        // Update executed cycle count
+        let (clock, carry) = self.clk.overflowing_add(1);
+        if carry {
+            warn!("internal cycle counter overflowed; resetting");
+        }
+        self.clk = clock;
+    }
+
+    fn reset(&mut self) {
+        self.soc.reset();
+    }
+}
@@ -0,0 +1,199 @@
+//! CGB registers.
+
+use rugby_arch::Block;
+use rugby_arch::mem::Memory;
+use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
+
+/// `KEY1`: Speed switch.
+///
+/// | Bit | Name   | Description                 |
+/// |:---:|--------|-----------------------------|
+/// |  7  | Speed  | Current speed (read-only)   |
+/// |  0  | Switch | Speed switch armed          |
+///
+/// A speed switch is performed by executing `STOP` while armed. The CPU then
+/// pauses for 2050 M-cycles while the clock settles, during which `DIV` is
+/// reset and held.
+///
+/// See more details [here][key1].
+///
+/// [key1]: https://gbdev.io/pandocs/CGB_Registers.html#ff4d--key1-spd-cgb-mode-only-prepare-speed-switch
+#[derive(Debug, Default)]
+pub struct Speed {
+    /// Double speed mode.
+    fast: bool,
+    /// Speed switch armed.
+    armed: bool,
+    /// Speed switch pause.
+    ///
+    /// Remaining M-cycles for which the CPU is paused following a switch.
+    wait: u16,
+}
+
+impl Speed {
+    /// Duration of a speed switch pause, in M-cycles.
+    const PAUSE: u16 = 2050;
+
+    /// Checks if double speed mode is enabled.
+    #[must_use]
+    pub fn fast(&self) -> bool {
+        self.fast
+    }
+
+    /// Checks if a speed switch is armed.
+    #[must_use]
+    pub fn armed(&self) -> bool {
+        self.armed
+    }
+
+    /// Checks if a speed switch is in progress.
+    ///
+    /// While switching, the CPU remains paused.
+    #[must_use]
+    pub fn switching(&self) -> bool {
+        self.wait > 0
+    }
+
+    /// Performs an armed speed switch.
+    ///
+    /// Begins the pause during which the clock settles.
+    pub fn switch(&mut self) {
+        self.fast = !self.fast;
+        self.armed = false;
+        self.wait = Self::PAUSE;
+    }
+
+    /// Advances a speed switch in progress by a single M-cycle.
+    pub fn settle(&mut self) {
+        self.wait = self.wait.saturating_sub(1);
+    }
+}
+
+impl Block for Speed {
+    fn reset(&mut self) {
+        std::mem::take(self);
+    }
+}
+
+impl Memory for Speed {
+    fn read(&self, _: u16) -> rugby_arch::mem::Result<u8> {
+        Ok(self.load())
+    }
+
+    fn write(&mut self, _: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Speed {
+    type Value = u8;
+
+    fn load(&self) -> Self::Value {
+        u8::from(self.fast) << 7 | 0x7e | u8::from(self.armed)
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        // NOTE: Only the switch bit is writable.
+        self.armed = value & 0x01 != 0;
+    }
+}
+
+impl State for Speed {
+    fn save(&self, out: &mut Writer) {
+        out.save(&self.fast).save(&self.armed).save(&self.wait);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.load(&mut self.fast)?
+            .load(&mut self.armed)?
+            .load(&mut self.wait)?;
+        Ok(())
+    }
+}
+
+/// Memory bank select.
+///
+/// Used for both `VBK` (video RAM) and `SVBK` (work RAM). Unused bits read
+/// as set.
+#[derive(Debug)]
+pub struct Bank {
+    /// Selectable bits.
+    mask: u8,
+    /// Selected bank.
+    sel: u8,
+}
+
+impl Bank {
+    /// Constructs a new `Bank` with the provided selectable bits.
+    #[must_use]
+    pub fn new(mask: u8) -> Self {
+        Self { mask, sel: 0 }
+    }
+}
+
+impl Block for Bank {
+    fn reset(&mut self) {
+        self.sel = 0;
+    }
+}
+
+impl Memory for Bank {
+    fn read(&self, _: u16) -> rugby_arch::mem::Result<u8> {
+        Ok(!self.mask | self.load())
+    }
+
+    fn write(&mut self, _: u16, data: u8) -> rugby_arch::mem::Result<()> {
+        self.store(data);
+        Ok(())
+    }
+}
+
+impl Register for Bank {
+    type Value = u8;
+
+    fn load(&self) -> Self::Value {
+        self.sel
+    }
+
+    fn store(&mut self, value: Self::Value) {
+        self.sel = value & self.mask;
+    }
+}
+
+impl State for Bank {
+    fn save(&self, out: &mut Writer) {
+        self.sel.save(out);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        self.sel.restore(inp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speed_works() {
+        let mut key1 = Speed::default();
+        assert_eq!(key1.read(0).unwrap(), 0x7e);
+        // Arm a switch
+        key1.write(0, 0xff).unwrap();
+        assert_eq!(key1.read(0).unwrap(), 0x7f);
+        // Perform the switch
+        key1.switch();
+        assert!(key1.fast());
+        assert_eq!(key1.read(0).unwrap(), 0xfe);
+    }
+
+    #[test]
+    fn bank_works() {
+        let mut svbk = Bank::new(0b111);
+        svbk.write(0, 0xfd).unwrap();
+        assert_eq!(svbk.load(), 0b101);
+        assert_eq!(svbk.read(0).unwrap(), 0xfd);
+    }
+}
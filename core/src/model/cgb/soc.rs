@@ -0,0 +1,297 @@
+//! CPU CGB.
+
+use rugby_arch::state::{self, Reader, State, Writer};
+use rugby_arch::{Block, Shared};
+
+use super::bus;
+use super::pcb::{Vram, Wram};
+use super::reg::{Bank, Speed};
+use crate::api::audio::{Capacitor, Mixer};
+use crate::cart;
+pub use crate::chip::{apu, cpu, dma, hdma, irq, joy, ppu, sio, tma};
+
+/// CPU CGB.
+#[derive(Debug)]
+pub struct SoC {
+    /// Audio processing unit.
+    pub apu: apu::Apu,
+    /// Central processing unit.
+    pub cpu: cpu::Cpu,
+    /// Direct memory access unit.
+    pub dma: dma::Dma,
+    /// VRAM direct memory access unit.
+    pub hdma: hdma::Hdma,
+    /// Interrupt controller.
+    pub irq: irq::Irq,
+    /// Joypad controller.
+    pub joy: joy::Joypad,
+    /// Picture processing unit
+    pub ppu: ppu::Ppu,
+    /// Serial communications port.
+    pub sio: sio::Serial,
+    /// Hardware timer.
+    pub tma: tma::Timer,
+    /// Speed switch.
+    pub key1: Shared<Speed>,
+    /// VRAM bank select.
+    pub vbk: Shared<Bank>,
+    /// WRAM bank select.
+    pub svbk: Shared<Bank>,
+}
+
+impl SoC {
+    /// Constructs a new `SoC`.
+    ///
+    /// # Note
+    ///
+    /// Construction largely mirrors the [DMG](crate::dmg::soc::SoC), with
+    /// banked memory routed through the `VBK` and `SVBK` registers.
+    #[must_use]
+    #[expect(clippy::too_many_lines)]
+    pub fn new(vram: &[Shared<Vram>; 2], wram: &Shared<Wram>, cart: &cart::Slot) -> Self {
+        // Interrupt controller
+        let irq = irq::Irq::default();
+        // Hardware timer
+        let tma = tma::Timer {
+            reg: tma::File::default(),
+            etc: tma::Internal::default(),
+            irq: irq.line.clone(),
+        };
+        // Bank registers
+        let key1 = Shared::new(Speed::default());
+        let vbk = Shared::new(Bank::new(0b001));
+        let svbk = Shared::new(Bank::new(0b111));
+        // Memory buses
+        //
+        // Each bus driver holds its own view.
+        let vbus = || bus::Vbus {
+            vram: vram.clone(),
+            vbk: vbk.clone(),
+        };
+        let ebus = || bus::Ebus {
+            cart: cart.clone(),
+            wram: bus::Wbank {
+                mem: wram.clone(),
+                svbk: svbk.clone(),
+            },
+        };
+        // Object attribute memory
+        //
+        // Created before the PPU and DMA, which each hold a handle.
+        let oam = Shared::new(ppu::Oam::from([u8::default(); 0x00a0]));
+
+        // Audio processing unit
+        let apu = {
+            let mem = apu::Bank::default();
+            let reg = apu::File::default();
+            apu::Apu {
+                ch1: apu::ch1::Channel {
+                    out: f32::default(),
+                    reg: apu::ch1::File::with(&reg),
+                    etc: apu::ch1::Internal::default(),
+                },
+                ch2: apu::ch2::Channel {
+                    out: f32::default(),
+                    reg: apu::ch2::File::with(&reg),
+                    etc: apu::ch2::Internal::default(),
+                },
+                ch3: apu::ch3::Channel {
+                    out: f32::default(),
+                    reg: apu::ch3::File::with(&reg),
+                    mem: mem.clone(),
+                    etc: apu::ch3::Internal::default(),
+                },
+                ch4: apu::ch4::Channel {
+                    out: f32::default(),
+                    reg: apu::ch4::File::with(&reg),
+                    etc: apu::ch4::Internal::default(),
+                },
+                reg,
+                mem,
+                seq: apu::Sequencer {
+                    bit: bool::default(),
+                    fast: bool::default(),
+                    clk: u8::default(),
+                    div: tma.reg.div.clone(),
+                },
+                etc: apu::Internal::default(),
+                out: apu::blip::Buffer::new(Capacitor::CGB),
+                rec: apu::blip::Recorder::new(Capacitor::CGB),
+                mix: Mixer::default(),
+            }
+        };
+        // Direct memory access unit
+        let dma = dma::Dma {
+            bus: dma::View::Cgb(bus::view::Dma {
+                vbus: vbus(),
+                ebus: ebus(),
+            }),
+            mem: oam.clone(),
+            reg: Shared::new(dma::Control::default()),
+        };
+        // VRAM direct memory access unit
+        let hdma = hdma::Hdma {
+            bus: Box::new(bus::view::Dma {
+                vbus: vbus(),
+                ebus: ebus(),
+            }),
+            reg: Shared::new(hdma::Control::default()),
+        };
+        // Joypad controller
+        let joy = joy::Joypad {
+            reg: Shared::new(joy::Control::default()),
+            irq: irq.line.clone(),
+        };
+        // Color graphics
+        let [vram0, vram1] = vram;
+        let cgb = ppu::cgb::Cgb::with(vram1);
+        let cgb_reg = cgb.reg.clone();
+        // Picture processing unit
+        let ppu = ppu::Ppu {
+            mem: ppu::Bank {
+                vram: vram0.clone(),
+                oam,
+            },
+            reg: ppu::File {
+                dma: dma.reg.clone(),
+                ..Default::default()
+            },
+            etc: ppu::Internal::default(),
+            irq: irq.line.clone(),
+            cgb: Some(cgb),
+        };
+        // Serial communications port
+        let sio = sio::Serial {
+            reg: sio::File::default(),
+            etc: sio::Internal::default(),
+            irq: irq.line.clone(),
+        };
+        // Central processing unit
+        let cpu = {
+            let mem = cpu::Bank::default();
+            let bus = bus::view::Cpu {
+                ibus: bus::Ibus {
+                    ppu: dma::Gate {
+                        mem: ppu.mem.clone(),
+                        reg: dma.reg.clone(),
+                    },
+                    apu: apu.mem.clone(),
+                    io: bus::File {
+                        joy: joy.reg.clone(),
+                        sio: sio.reg.clone(),
+                        tma: tma.reg.clone(),
+                        irq: irq.reg.clone(),
+                        apu: apu.reg.clone(),
+                        dma: dma.reg.clone(),
+                        ppu: ppu.reg.clone(),
+                        key1: key1.clone(),
+                        vbk: vbk.clone(),
+                        hdma: hdma.reg.clone(),
+                        cgb: cgb_reg,
+                        svbk: svbk.clone(),
+                    },
+                    cpu: mem.clone(),
+                },
+                vbus: vbus(),
+                ebus: ebus(),
+            };
+            cpu::Cpu {
+                blk: cpu::blk::Hardware::new(cpu::blk::Bus::new(bus)),
+                reg: cpu::File::default(),
+                mem,
+                etc: cpu::Internal::default(),
+                irq: irq.line.clone(),
+            }
+        };
+
+        // Finish construction
+        Self {
+            apu,
+            cpu,
+            dma,
+            hdma,
+            irq,
+            joy,
+            ppu,
+            sio,
+            tma,
+            key1,
+            vbk,
+            svbk,
+        }
+    }
+}
+
+impl State for SoC {
+    fn save(&self, out: &mut Writer) {
+        out.section(b"APU ", |out| {
+            out.save(&self.apu);
+        })
+        .section(b"CPU ", |out| {
+            out.save(&self.cpu);
+        })
+        .section(b"DMA ", |out| {
+            out.save(&self.dma);
+        })
+        .section(b"HDMA", |out| {
+            out.save(&self.hdma);
+        })
+        .section(b"IRQ ", |out| {
+            out.save(&self.irq);
+        })
+        .section(b"JOY ", |out| {
+            out.save(&self.joy);
+        })
+        .section(b"PPU ", |out| {
+            out.save(&self.ppu);
+        })
+        .section(b"SIO ", |out| {
+            out.save(&self.sio);
+        })
+        .section(b"TMA ", |out| {
+            out.save(&self.tma);
+        })
+        .section(b"BANK", |out| {
+            out.save(&self.key1).save(&self.vbk).save(&self.svbk);
+        });
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.section(b"APU ", |inp| inp.load(&mut self.apu).map(drop))?
+            .section(b"CPU ", |inp| inp.load(&mut self.cpu).map(drop))?
+            .section(b"DMA ", |inp| inp.load(&mut self.dma).map(drop))?
+            .section(b"HDMA", |inp| inp.load(&mut self.hdma).map(drop))?
+            .section(b"IRQ ", |inp| inp.load(&mut self.irq).map(drop))?
+            .section(b"JOY ", |inp| inp.load(&mut self.joy).map(drop))?
+            .section(b"PPU ", |inp| inp.load(&mut self.ppu).map(drop))?
+            .section(b"SIO ", |inp| inp.load(&mut self.sio).map(drop))?
+            .section(b"TMA ", |inp| inp.load(&mut self.tma).map(drop))?
+            .section(b"BANK", |inp| {
+                inp.load(&mut self.key1)?
+                    .load(&mut self.vbk)?
+                    .load(&mut self.svbk)
+                    .map(drop)
+            })?;
+        Ok(())
+    }
+}
+
+impl Block for SoC {
+    fn ready(&self) -> bool {
+        self.cpu.ready()
+    }
+
+    fn reset(&mut self) {
+        self.apu.reset();
+        self.cpu.reset();
+        self.dma.reset();
+        self.hdma.reset();
+        self.joy.reset();
+        self.ppu.reset();
+        self.sio.reset();
+        self.tma.reset();
+        self.key1.reset();
+        self.vbk.reset();
+        self.svbk.reset();
+    }
+}
@@ -0,0 +1,247 @@
+use rugby_arch::mem::Memory;
+use rugby_arch::reg::Port;
+
+use super::*;
+use crate::cart::head;
+
+/// Sample cart ROM.
+const GAME: &[u8; 0x8000] = include_bytes!("../../../../roms/games/2048/2048.gb");
+
+/// Patches the sample cart ROM to run the provided program in CGB mode.
+fn rom(prog: &[u8]) -> Box<[u8]> {
+    let mut rom: Box<[u8]> = Box::new(*GAME);
+    // Jump to the program
+    rom[0x100..0x104].copy_from_slice(&[0x00, 0xc3, 0x50, 0x01]);
+    rom[0x150..0x150 + prog.len()].copy_from_slice(prog);
+    // Mark as CGB compatible
+    rom[0x143] = 0x80;
+    // Fix checksums
+    rom[0x14d] = head::hchk(&rom);
+    let [hi, lo] = head::gchk(&rom).to_be_bytes();
+    rom[0x14e] = hi;
+    rom[0x14f] = lo;
+    rom
+}
+
+fn setup(prog: &[u8]) -> GameBoyColor {
+    // Instantiate a `Cartridge`
+    let cart = Cartridge::new(&rom(prog)).unwrap();
+    // Create a `GameBoyColor` instance
+    let mut emu = GameBoyColor::new();
+    // Load the cartridge into the emulator
+    emu.insert(cart);
+
+    emu
+}
+
+#[test]
+fn boot_works() {
+    let emu = setup(&[0x18, 0xfe]); // JR -2
+    // Identify as a CGB
+    let af: u16 = emu.main.soc.cpu.load(<Cpu as Port<u16>>::Select::AF);
+    assert_eq!(af >> 8, 0x11);
+    // Run in CGB mode
+    assert!(!emu.compat());
+}
+
+#[test]
+fn compat_works() {
+    let mut emu = GameBoyColor::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+    assert!(emu.compat());
+    // Monochrome palettes are initialized
+    let cgb = emu.main.soc.ppu.cgb.as_ref().unwrap();
+    assert_eq!(
+        cgb.reg.bcp.borrow().color(0, Color::C0),
+        Rgb555::from(Color::C0)
+    );
+    assert_eq!(
+        cgb.reg.ocp.borrow().color(1, Color::C3),
+        Rgb555::from(Color::C3)
+    );
+    assert!(cgb.reg.opri.load());
+}
+
+#[test]
+fn vram_bank_works() {
+    let mut emu = setup(&[0x18, 0xfe]); // JR -2
+    let bus = &mut emu.main.soc.cpu.blk.bus;
+
+    // Bank 0
+    bus.write(0x8000, 0xaa);
+    assert_eq!(bus.read(0xff4f), 0xfe);
+    // Bank 1
+    bus.write(0xff4f, 0x01);
+    assert_eq!(bus.read(0xff4f), 0xff);
+    assert_eq!(bus.read(0x8000), 0x00);
+    bus.write(0x9fff, 0x55);
+    assert_eq!(emu.main.vram[0].read(0x0000).unwrap(), 0xaa);
+    assert_eq!(emu.main.vram[1].read(0x1fff).unwrap(), 0x55);
+}
+
+#[test]
+fn wram_bank_works() {
+    let mut emu = setup(&[0x18, 0xfe]); // JR -2
+    let bus = &mut emu.main.soc.cpu.blk.bus;
+
+    // Bank 0 treated as bank 1
+    bus.write(0xd000, 0x11);
+    bus.write(0xff70, 0x01);
+    assert_eq!(bus.read(0xd000), 0x11);
+    // Bank 7
+    bus.write(0xff70, 0xff);
+    assert_eq!(bus.read(0xff70), 0xff);
+    bus.write(0xddff, 0x77);
+    // Echo RAM is also banked
+    assert_eq!(bus.read(0xfdff), 0x77);
+    // Fixed bank 0
+    bus.write(0xc000, 0x22);
+    let wram = emu.main.wram.clone();
+    assert_eq!(wram.read(0x0000).unwrap(), 0x22);
+    assert_eq!(wram.read(0x1000).unwrap(), 0x11);
+    assert_eq!(wram.read(0x7dff).unwrap(), 0x77);
+}
+
+#[test]
+fn palette_works() {
+    let mut emu = setup(&[0x18, 0xfe]); // JR -2
+    let bus = &mut emu.main.soc.cpu.blk.bus;
+
+    // Write BG palette 2, color 1
+    bus.write(0xff68, 0x92);
+    bus.write(0xff69, 0xe0);
+    bus.write(0xff69, 0x03);
+    assert_eq!(bus.read(0xff68), 0xd4);
+    // Write OBJ palette 7, color 3
+    bus.write(0xff6a, 0xbe);
+    bus.write(0xff6b, 0x1f);
+    bus.write(0xff6b, 0x00);
+    let cgb = emu.main.soc.ppu.cgb.as_ref().unwrap();
+    assert_eq!(
+        cgb.reg.bcp.borrow().color(2, Color::C1),
+        Rgb555::new(0x00, 0x1f, 0x00)
+    );
+    assert_eq!(
+        cgb.reg.ocp.borrow().color(7, Color::C3),
+        Rgb555::new(0x1f, 0x00, 0x00)
+    );
+}
+
+#[test]
+fn speed_switch_works() {
+    #[rustfmt::skip]
+    let mut emu = setup(&[
+        0x3e, 0x01, // LD A, 0x01
+        0xe0, 0x4d, // LDH (0x4d), A
+        0x10, 0x00, // STOP
+        0xe0, 0x80, // LDH (0x80), A
+        0x18, 0xfe, // JR -2
+    ]);
+
+    assert_eq!(emu.main.soc.cpu.blk.bus.read(0xff4d), 0x7e);
+    while !emu.main.soc.key1.borrow().fast() {
+        emu.cycle();
+    }
+    // Switched to double speed
+    assert!(!emu.main.soc.cpu.stopped());
+    assert_eq!(emu.main.soc.cpu.blk.bus.read(0xff4d), 0xfe);
+    // CPU is paused while the clock settles, holding DIV reset
+    (0..2 * 2_000).for_each(|_| emu.cycle());
+    assert!(emu.main.soc.key1.borrow().switching());
+    assert_eq!(emu.main.soc.cpu.blk.bus.read(0xff04), 0x00);
+    assert_ne!(emu.main.soc.cpu.blk.bus.read(0xff80), 0x01);
+    // ... then resumes
+    (0..2 * 100).for_each(|_| emu.cycle());
+    assert!(!emu.main.soc.key1.borrow().switching());
+    assert_eq!(emu.main.soc.cpu.blk.bus.read(0xff80), 0x01);
+}
+
+#[test]
+fn gdma_works() {
+    let mut emu = setup(&[0x18, 0xfe]); // JR -2
+    let bus = &mut emu.main.soc.cpu.blk.bus;
+
+    // Fill source data
+    (0..0x20).for_each(|idx| bus.write(0xc000 | u16::from(idx), idx));
+    // Copy 2 blocks into bank 1
+    bus.write(0xff4f, 0x01);
+    bus.write(0xff51, 0xc0);
+    bus.write(0xff52, 0x00);
+    bus.write(0xff53, 0x01);
+    bus.write(0xff54, 0x00);
+    bus.write(0xff55, 0x01);
+    // Run until complete
+    let mut cycles: u32 = 0;
+    while emu.main.soc.hdma.ready() {
+        emu.cycle();
+        cycles += 1;
+    }
+    // Copies a byte every other cycle
+    assert!(cycles.abs_diff(0x40) <= 1);
+    assert_eq!(emu.main.soc.cpu.blk.bus.read(0xff55), 0xff);
+    (0..0x20).for_each(|idx| {
+        assert_eq!(emu.main.vram[1].read(0x0100 | u16::from(idx)).unwrap(), idx);
+    });
+}
+
+#[test]
+fn snapshot_round_trip_works() {
+    let mut emu = GameBoyColor::new();
+    emu.insert(Cartridge::new(GAME).unwrap());
+
+    // Run until mid-frame
+    (0..123_456).for_each(|_| emu.cycle());
+    // Take a snapshot
+    let state = emu.save_state();
+    // Run a few frames, recording the result
+    (0..250_000).for_each(|_| emu.cycle());
+    let frame = emu.frame().to_vec();
+    let after = emu.save_state();
+
+    // Restore the snapshot
+    emu.load_state(&state).unwrap();
+    assert_eq!(emu.save_state(), state);
+    // Run again, checking the result is identical
+    (0..250_000).for_each(|_| emu.cycle());
+    assert_eq!(emu.frame(), frame);
+    assert_eq!(emu.save_state(), after);
+    // Frame is colorized
+    assert!(frame.iter().any(|&pix| pix != Rgb555::default()));
+}
+
+#[test]
+fn double_speed_length_works() {
+    /// Measures the cycles until a triggered length counter expires.
+    fn expire(fast: bool) -> u32 {
+        let mut emu = setup(&[0x18, 0xfe]); // JR -2
+        if fast {
+            let mut key1 = emu.main.soc.key1.borrow_mut();
+            key1.store(0x01);
+            key1.switch();
+        }
+        let bus = &mut emu.main.soc.cpu.blk.bus;
+        // Enable the APU
+        bus.write(0xff26, 0x80);
+        // Enable the DAC, then trigger with a length of 32
+        bus.write(0xff12, 0xf0);
+        bus.write(0xff11, 0x20);
+        bus.write(0xff14, 0xc0);
+        // Run until the channel is disabled
+        let mut cycles: u32 = 0;
+        loop {
+            emu.cycle();
+            cycles += 1;
+            if emu.main.soc.cpu.blk.bus.read(0xff26) & 0x01 == 0 {
+                break cycles;
+            }
+            assert!(cycles < 1_000_000, "length counter never expired");
+        }
+    }
+
+    // Expires after 32 ticks at 256 Hz
+    let slow = expire(false);
+    let fast = expire(true);
+    assert!(slow.abs_diff(0x8_0000) <= 0x4000, "{slow}");
+    // Both speeds expire within a single sequencer step
+    assert!(slow.abs_diff(fast) <= 0x4000, "{slow} != {fast}");
+}
@@ -5,6 +5,7 @@ use std::fmt::Debug;
 use log::{debug, trace};
 use rugby_arch::mem::{Error, Memory, Result};
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 /// Boot ROM.
@@ -62,6 +63,29 @@ impl Block for Slot {
     }
 }
 
+impl State for Slot {
+    // NOTE: Only the disable register is saved. The boot ROM itself is
+    //       considered part of the machine's configuration.
+    fn save(&self, out: &mut Writer) {
+        let reg = self.0.borrow().as_ref().map(|chip| chip.reg.borrow().0);
+        out.save(&reg);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        let mut reg = Option::<bool>::None;
+        inp.load(&mut reg)?;
+        match (self.0.borrow_mut().as_mut(), reg) {
+            // Restore the disable register
+            (Some(chip), reg) => chip.reg.borrow_mut().0 = reg.unwrap_or(true),
+            // Cannot resume booting without a boot ROM
+            (None, Some(false)) => return Err(state::Error::Invalid("Boot")),
+            // Otherwise, nothing to restore
+            (None, _) => (),
+        }
+        Ok(())
+    }
+}
+
 impl Memory for Slot {
     fn read(&self, addr: u16) -> Result<u8> {
         self.0
@@ -1,8 +1,16 @@
 //! Bus driver views.
 
+#[cfg(feature = "debug")]
+use rugby_arch::Shared;
 use rugby_arch::mem::Memory;
+#[cfg(feature = "debug")]
+use rugby_arch::mem::Result;
 
 use super::{Ebus, Ibus, Vbus};
+#[cfg(feature = "debug")]
+use crate::dmg::dbg::prof::Profile;
+#[cfg(feature = "debug")]
+use crate::dmg::dbg::watch::{Access, Event, Watch};
 
 /// CPU memory view.
 ///
@@ -53,3 +61,66 @@ pub struct Dma {
     #[mmap(0x0000..=0xffff)]
     pub ebus: Ebus,
 }
+
+/// Probed memory view.
+///
+/// Wraps another view, reporting accesses made through it to a [watch
+/// hook](Watch). Writes are reported with the value previously held by the
+/// address, and the value held following the write.
+///
+/// Reads are additionally reported to an execution [profiler](Profile).
+#[cfg(feature = "debug")]
+#[derive(Debug)]
+pub struct Probe<M> {
+    /// Probed view.
+    pub view: M,
+    /// Watch hook.
+    pub hook: Shared<Watch>,
+    /// Execution profiler.
+    pub prof: Shared<Profile>,
+}
+
+#[cfg(feature = "debug")]
+impl<M: Memory> Memory for Probe<M> {
+    fn read(&self, addr: u16) -> Result<u8> {
+        let data = self.view.read(addr)?;
+        // Skip the watch hook when nothing is watched
+        if !self.hook.borrow().is_empty() {
+            self.hook.borrow_mut().record(Event {
+                kind: Access::Read,
+                addr,
+                old: data,
+                new: data,
+            });
+        }
+        // Skip the profiler when disabled
+        if self.prof.borrow().enabled() {
+            self.prof.borrow_mut().read(addr);
+        }
+        Ok(data)
+    }
+
+    fn write(&mut self, addr: u16, data: u8) -> Result<()> {
+        // Skip the watch hook when nothing is watched
+        if self.hook.borrow().is_empty() {
+            return self.view.write(addr, data);
+        }
+        // Only sample the previous value when watched, as reads of the view
+        // are otherwise unnecessary
+        let old = self
+            .hook
+            .borrow()
+            .watches(Access::Write, addr)
+            .then(|| self.view.read(addr).unwrap_or(0xff));
+        self.view.write(addr, data)?;
+        if let Some(old) = old {
+            self.hook.borrow_mut().record(Event {
+                kind: Access::Write,
+                addr,
+                old,
+                new: self.view.read(addr).unwrap_or(data),
+            });
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 //! Debugging the [DMG-01](super).
 
+pub mod prof;
+pub mod sym;
 pub mod trace;
+pub mod watch;
 
 use super::GameBoy;
 use super::soc::ppu;
@@ -0,0 +1,312 @@
+//! Execution profiling.
+//!
+//! Attributes each M-cycle to the instruction executing during it, and records
+//! how each byte of the cartridge ROM has been accessed. Results may be
+//! exported as:
+//!
+//! - [Folded stacks][folded], as consumed by flamegraph tooling.
+//! - A code/data log ([CDL]), with a byte of [flags](self::cdl) per ROM byte.
+//!
+//! [folded]: https://github.com/brendangregg/FlameGraph#2-fold-stacks
+//! [CDL]:    https://fceux.com/web/help/CodeDataLogger.html
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+use rugby_arch::reg::Port;
+
+use super::sym::{Symbol, Symbols};
+use crate::chip::cpu::{Cpu, Select16};
+
+/// Code/data log flags.
+pub mod cdl {
+    /// Byte was executed as part of an instruction.
+    pub const CODE: u8 = 0x01;
+    /// Byte was read as data.
+    pub const DATA: u8 = 0x02;
+}
+
+/// Size of a ROM bank.
+const BANK: usize = 0x4000;
+
+/// Execution profile.
+///
+/// Once enabled, is sampled by the console once per M-cycle.
+///
+/// # Note
+///
+/// ROM reads are attributed to the bank currently mapped into the switchable
+/// region, whereas the fixed region (`$0000..=$3FFF`) is always attributed to
+/// bank zero. Reads of a mapped boot ROM are ignored.
+#[derive(Debug, Default)]
+pub struct Profile {
+    /// Profiling enabled.
+    enable: bool,
+    /// Executing instruction.
+    insn: Option<Symbol>,
+    /// ROM reads since the previous sample.
+    read: Vec<u16>,
+    /// Call tree.
+    ///
+    /// Each path from a root visits the outermost call site, followed by the
+    /// callee of each frame.
+    tree: Vec<Node>,
+    /// Call tree nodes, by parent and location.
+    kids: HashMap<(Option<usize>, Symbol), usize>,
+    /// Call tree path of the executing instruction.
+    path: Vec<(Symbol, usize)>,
+    /// Cycles by call tree node and instruction.
+    cycles: HashMap<(Option<usize>, Symbol), u64>,
+    /// ROM access flags, by offset.
+    cover: Vec<u8>,
+}
+
+/// Call tree node.
+#[derive(Debug)]
+struct Node {
+    /// Parent node, if any.
+    ///
+    /// Only call sites, which are roots, are without a parent.
+    parent: Option<usize>,
+    /// Node location.
+    sym: Symbol,
+}
+
+impl Profile {
+    /// Enables profiling.
+    pub fn enable(&mut self) {
+        self.enable = true;
+    }
+
+    /// Disables profiling.
+    ///
+    /// Collected results are retained.
+    pub fn disable(&mut self) {
+        self.enable = false;
+        self.insn = None;
+        self.read.clear();
+    }
+
+    /// Checks if profiling is enabled.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.enable
+    }
+
+    /// Discards all collected results.
+    pub fn clear(&mut self) {
+        self.tree.clear();
+        self.kids.clear();
+        self.path.clear();
+        self.cycles.clear();
+        self.cover.clear();
+    }
+
+    /// Records a CPU read of the cartridge ROM.
+    pub(crate) fn read(&mut self, addr: u16) {
+        if self.enable && addr <= 0x7fff {
+            self.read.push(addr);
+        }
+    }
+
+    /// Samples the processor following an M-cycle.
+    ///
+    /// Reads of the fixed boot ROM are excluded while `boot` is mapped.
+    pub(crate) fn sample(&mut self, cpu: &Cpu, bank: usize, boot: bool) {
+        let bank = u16::try_from(bank).unwrap_or(u16::MAX);
+        let pc: u16 = cpu.load(Select16::PC);
+
+        // Classify ROM reads
+        //
+        // As the program counter is incremented with each byte of an
+        // instruction fetched, reads just behind it must belong to one.
+        for addr in self.read.drain(..) {
+            if boot && addr <= 0x00ff {
+                continue;
+            }
+            let flag = if addr == pc.wrapping_sub(1) {
+                cdl::CODE
+            } else {
+                cdl::DATA
+            };
+            let offset = self::offset(bank, addr);
+            if self.cover.len() <= offset {
+                self.cover.resize(offset + 1, 0);
+            }
+            self.cover[offset] |= flag;
+        }
+
+        // Count cycle against the executing instruction
+        if let Some(insn) = self.insn {
+            let node = self.path.last().map(|&(_, node)| node);
+            *self.cycles.entry((node, insn)).or_default() += 1;
+        }
+        // Update executing instruction
+        if !cpu.busy() {
+            // Opcode has already been fetched
+            self.insn = Some(Symbol::new(bank, pc.wrapping_sub(1)));
+            // Follow the call stack as the instruction begins, such that cycles
+            // of calls and returns are counted within their caller and callee
+            self.follow(cpu);
+        }
+    }
+
+    /// Follows the processor's call stack through the call tree.
+    ///
+    /// Each frame is located within the bank mapped as it was entered.
+    fn follow(&mut self, cpu: &Cpu) {
+        let frames = cpu.stack().frames();
+        let path = frames
+            .first()
+            .map(|frame| (frame.bank, frame.site))
+            .into_iter()
+            .chain(frames.iter().map(|frame| (frame.bank, frame.addr)))
+            .map(|(bank, addr)| Symbol::new(bank, addr));
+        // Retain the unchanged portion of the path
+        let same = self
+            .path
+            .iter()
+            .zip(path.clone())
+            .take_while(|&(&(old, _), new)| old == new)
+            .count();
+        self.path.truncate(same);
+        // Descend through the remainder
+        for sym in path.skip(same) {
+            let parent = self.path.last().map(|&(_, node)| node);
+            let node = *self.kids.entry((parent, sym)).or_insert_with(|| {
+                self.tree.push(Node { parent, sym });
+                self.tree.len() - 1
+            });
+            self.path.push((sym, node));
+        }
+    }
+
+    /// Gets the cycles spent executing each instruction.
+    #[must_use]
+    pub fn cycles(&self) -> HashMap<Symbol, u64> {
+        let mut cycles = HashMap::new();
+        for (&(_, insn), count) in &self.cycles {
+            *cycles.entry(insn).or_default() += count;
+        }
+        cycles
+    }
+
+    /// Gets the cycles spent executing from each ROM bank.
+    ///
+    /// Cycles spent executing outside the ROM are not included.
+    #[must_use]
+    pub fn banks(&self) -> BTreeMap<u16, u64> {
+        let mut banks = BTreeMap::new();
+        for (&(_, insn), count) in &self.cycles {
+            if insn.addr <= 0x7fff {
+                *banks.entry(insn.bank).or_default() += count;
+            }
+        }
+        banks
+    }
+
+    /// Gets the cycles spent executing within each labelled function.
+    ///
+    /// Instructions are attributed to the nearest preceding label, with any
+    /// which are unlabelled excluded.
+    #[must_use]
+    pub fn functions<'a>(&self, syms: &'a Symbols) -> BTreeMap<&'a str, u64> {
+        let mut funcs = BTreeMap::new();
+        for (&(_, insn), count) in &self.cycles {
+            if let Some((label, _)) = syms.locate(insn.bank.into(), insn.addr) {
+                *funcs.entry(label).or_default() += count;
+            }
+        }
+        funcs
+    }
+
+    /// Gets the ROM access flags, indexed by offset within the ROM.
+    ///
+    /// Flags are only present up until the final accessed offset, and must be
+    /// padded to match the size of the ROM.
+    #[must_use]
+    pub fn coverage(&self) -> &[u8] {
+        &self.cover
+    }
+
+    /// Writes cycles by call stack as folded stacks.
+    ///
+    /// Each stack begins with the function containing the outermost call site,
+    /// followed by the callee of each frame, and ends with the function
+    /// containing the executing instruction. Unlabelled callees are named by
+    /// their address, whereas other unlabelled functions are omitted. Stacks
+    /// which would otherwise be empty are named by the executing instruction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stacks could not be written.
+    pub fn folded(&self, syms: &Symbols, out: &mut impl Write) -> io::Result<()> {
+        let func = |sym: Symbol| {
+            syms.locate(sym.bank.into(), sym.addr)
+                .map(|(label, _)| label)
+        };
+        // Name each call tree node's stack, alongside its innermost function
+        //
+        // As nodes always follow their parent, these can be named in order.
+        let mut names = Vec::<(String, Option<String>)>::with_capacity(self.tree.len());
+        for node in &self.tree {
+            let Some(parent) = node.parent else {
+                // Call sites are named by their function
+                let name = func(node.sym).map(str::to_string);
+                names.push((name.clone().unwrap_or_default(), name));
+                continue;
+            };
+            // Callees are named by their label
+            let name = syms
+                .label(node.sym.bank.into(), node.sym.addr)
+                .map_or_else(|| self::name(node.sym), str::to_string);
+            let (stack, _) = &names[parent];
+            let stack = if stack.is_empty() {
+                name.clone()
+            } else {
+                format!("{stack};{name}")
+            };
+            names.push((stack, Some(name)));
+        }
+        // Aggregate stacks by name
+        let mut folded = BTreeMap::<String, u64>::new();
+        for (&(node, insn), count) in &self.cycles {
+            let (stack, last) = node
+                .map(|node| &names[node])
+                .map_or(("", None), |(stack, last)| {
+                    (stack.as_str(), last.as_deref())
+                });
+            let stack = match func(insn) {
+                Some(func) if last == Some(func) => stack.to_string(),
+                Some(func) if stack.is_empty() => func.to_string(),
+                Some(func) => format!("{stack};{func}"),
+                None if stack.is_empty() => self::name(insn),
+                None => stack.to_string(),
+            };
+            *folded.entry(stack).or_default() += count;
+        }
+        // Write aggregated stacks
+        for (stack, count) in folded {
+            writeln!(out, "{stack} {count}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the ROM offset of an address.
+fn offset(bank: u16, addr: u16) -> usize {
+    if Symbol::banked(addr) {
+        usize::from(bank) * BANK + usize::from(addr) - BANK
+    } else {
+        usize::from(addr)
+    }
+}
+
+/// Names an unlabelled location by its address.
+fn name(sym: Symbol) -> String {
+    if Symbol::banked(sym.addr) {
+        format!("${:02x}:{:04x}", sym.bank, sym.addr)
+    } else {
+        format!("${:04x}", sym.addr)
+    }
+}
@@ -0,0 +1,249 @@
+//! Debug symbols.
+//!
+//! Parses symbol files emitted by common toolchains, resolving between labels
+//! and (banked) addresses. The following formats are supported:
+//!
+//! - [RGBDS] (`.sym`): lines of `BB:AAAA Label`.
+//! - [GBDK] (`.noi`): lines of `DEF Label 0xBBAAAA`.
+//!
+//! [RGBDS]: https://rgbds.gbdev.io/sym/
+//! [GBDK]: https://gbdk-2020.github.io/gbdk-2020/docs/api/docs_toolchain.html
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+/// Symbol location.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol {
+    /// Memory bank.
+    ///
+    /// Only meaningful within the switchable ROM region (`$4000..=$7FFF`),
+    /// and is zero elsewhere.
+    pub bank: u16,
+    /// Memory address.
+    pub addr: u16,
+}
+
+impl Symbol {
+    /// Constructs a new `Symbol`.
+    ///
+    /// Banks outside the switchable ROM region are discarded, as banks for
+    /// other regions are not tracked.
+    #[must_use]
+    pub fn new(bank: u16, addr: u16) -> Self {
+        let bank = if Self::banked(addr) { bank } else { 0 };
+        Self { bank, addr }
+    }
+
+    /// Checks if an address lies within the switchable ROM region.
+    #[must_use]
+    pub fn banked(addr: u16) -> bool {
+        (0x4000..=0x7fff).contains(&addr)
+    }
+}
+
+/// Symbol table.
+#[derive(Clone, Debug, Default)]
+pub struct Symbols {
+    /// Locations by label.
+    name: HashMap<String, Symbol>,
+    /// Labels by location.
+    addr: BTreeMap<Symbol, String>,
+}
+
+impl Symbols {
+    /// Constructs a new, empty `Symbols`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks if the table has no symbols.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty()
+    }
+
+    /// Gets the number of symbols in the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.name.len()
+    }
+
+    /// Inserts a labelled symbol.
+    ///
+    /// Where multiple labels share a location, the first is used when
+    /// resolving the location's label.
+    pub fn insert(&mut self, label: String, sym: Symbol) {
+        self.addr.entry(sym).or_insert_with(|| label.clone());
+        self.name.insert(label, sym);
+    }
+
+    /// Resolves a label to its location.
+    #[must_use]
+    pub fn resolve(&self, label: &str) -> Option<Symbol> {
+        self.name.get(label).copied()
+    }
+
+    /// Gets the label at a location, where `bank` is the currently mapped
+    /// switchable ROM bank.
+    #[must_use]
+    pub fn label(&self, bank: usize, addr: u16) -> Option<&str> {
+        let sym = Symbol::new(u16::try_from(bank).ok()?, addr);
+        self.addr.get(&sym).map(String::as_str)
+    }
+
+    /// Locates the nearest label at or preceding a location, where `bank` is
+    /// the currently mapped switchable ROM bank.
+    ///
+    /// Returns the label along with the offset of the address from it. Labels
+    /// are only considered within the same memory region.
+    #[must_use]
+    pub fn locate(&self, bank: usize, addr: u16) -> Option<(&str, u16)> {
+        let sym = Symbol::new(u16::try_from(bank).ok()?, addr);
+        let start = Symbol::new(sym.bank, self::region(addr));
+        self.addr
+            .range(start..=sym)
+            .next_back()
+            .map(|(found, label)| (label.as_str(), addr - found.addr))
+    }
+}
+
+impl FromStr for Symbols {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut syms = Self::new();
+        for (idx, line) in s.lines().enumerate() {
+            // Strip comments
+            let line = line.split(';').next().unwrap_or_default();
+            let mut words = line.split_whitespace();
+            let Some(word) = words.next() else {
+                continue;
+            };
+            let err = || Error::Syntax(idx + 1);
+            let (label, sym) = if word == "DEF" {
+                // GBDK: `DEF Label 0xBBAAAA`
+                let label = words.next().ok_or_else(err)?;
+                let value = words.next().ok_or_else(err)?;
+                let value = value
+                    .strip_prefix("0x")
+                    .or_else(|| value.strip_prefix("0X"))
+                    .ok_or_else(err)?;
+                let value = u32::from_str_radix(value, 16).map_err(|_| err())?;
+                let bank = u16::try_from(value >> 16).map_err(|_| err())?;
+                #[expect(clippy::cast_possible_truncation)]
+                let addr = value as u16;
+                (label, Symbol::new(bank, addr))
+            } else if let Some((bank, addr)) = word.split_once(':') {
+                // RGBDS: `BB:AAAA Label`
+                let label = words.next().ok_or_else(err)?;
+                let bank = u16::from_str_radix(bank, 16).map_err(|_| err())?;
+                let addr = u16::from_str_radix(addr, 16).map_err(|_| err())?;
+                (label, Symbol::new(bank, addr))
+            } else {
+                // Ignore unrecognized directives
+                continue;
+            };
+            syms.insert(label.to_string(), sym);
+        }
+        Ok(syms)
+    }
+}
+
+/// Gets the start of the memory region containing an address.
+fn region(addr: u16) -> u16 {
+    match addr {
+        0x0000..=0x3fff => 0x0000,
+        0x4000..=0x7fff => 0x4000,
+        0x8000..=0x9fff => 0x8000,
+        0xa000..=0xbfff => 0xa000,
+        0xc000..=0xdfff => 0xc000,
+        0xe000..=0xff7f => 0xe000,
+        0xff80..=0xffff => 0xff80,
+    }
+}
+
+/// An error caused by parsing a symbol file.
+#[derive(Debug)]
+#[derive(thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Malformed line.
+    #[error("malformed symbol on line {0}")]
+    Syntax(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rgbds_works() {
+        let syms: Symbols = "\
+            ; File generated by rgblink\n\
+            00:0150 Main\n\
+            00:0153 Main.loop\n\
+            03:4000 Banked\n\
+            00:c000 wCount\n\
+            00:ff80 hFlag ; trailing comment\n"
+            .parse()
+            .unwrap();
+        assert_eq!(syms.len(), 5);
+        assert_eq!(syms.resolve("Main.loop"), Some(Symbol::new(0, 0x0153)));
+        assert_eq!(syms.resolve("Banked"), Some(Symbol::new(3, 0x4000)));
+        assert_eq!(syms.resolve("Missing"), None);
+    }
+
+    #[test]
+    fn parse_gbdk_works() {
+        let syms: Symbols = "\
+            LOAD main.ihx\n\
+            DEF _main 0x200\n\
+            DEF _banked 0x24010\n\
+            DEF _ram 0xc0a0\n"
+            .parse()
+            .unwrap();
+        assert_eq!(syms.len(), 3);
+        assert_eq!(syms.resolve("_main"), Some(Symbol::new(0, 0x0200)));
+        assert_eq!(syms.resolve("_banked"), Some(Symbol::new(2, 0x4010)));
+        assert_eq!(syms.resolve("_ram"), Some(Symbol::new(0, 0xc0a0)));
+    }
+
+    #[test]
+    fn parse_rejects_malformed() {
+        assert!(matches!(
+            "00:0150 Main\nzz:0150 Oops\n".parse::<Symbols>(),
+            Err(Error::Syntax(2))
+        ));
+        assert!(matches!(
+            "DEF _main 200\n".parse::<Symbols>(),
+            Err(Error::Syntax(1))
+        ));
+    }
+
+    #[test]
+    fn lookup_is_bank_aware() {
+        let syms: Symbols = "\
+            00:0150 Main\n\
+            01:4000 One\n\
+            02:4000 Two\n\
+            02:4100 Two.next\n\
+            00:c000 wVar\n"
+            .parse()
+            .unwrap();
+        // Exact labels depend on the mapped bank
+        assert_eq!(syms.label(1, 0x4000), Some("One"));
+        assert_eq!(syms.label(2, 0x4000), Some("Two"));
+        assert_eq!(syms.label(3, 0x4000), None);
+        // Unbanked regions ignore the mapped bank
+        assert_eq!(syms.label(7, 0x0150), Some("Main"));
+        assert_eq!(syms.label(7, 0xc000), Some("wVar"));
+        // Nearest labels remain within their bank and region
+        assert_eq!(syms.locate(2, 0x4123), Some(("Two.next", 0x23)));
+        assert_eq!(syms.locate(1, 0x4123), Some(("One", 0x123)));
+        assert_eq!(syms.locate(3, 0x4123), None);
+        assert_eq!(syms.locate(1, 0x3fff), Some(("Main", 0x3eaf)));
+        assert_eq!(syms.locate(1, 0x0100), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! Memory watchpoints.
+
+use std::ops::RangeInclusive;
+
+/// Memory access kind.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Access {
+    /// Memory read.
+    Read,
+    /// Memory write.
+    Write,
+}
+
+/// Watched memory access.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    /// Access kind.
+    pub kind: Access,
+    /// Accessed address.
+    pub addr: u16,
+    /// Value prior to the access.
+    pub old: u8,
+    /// Value following the access.
+    pub new: u8,
+}
+
+/// Memory watch hook.
+///
+/// Records processor accesses to watched addresses, to be consumed by a
+/// debugger between cycles.
+#[derive(Debug, Default)]
+pub struct Watch {
+    /// Watched reads.
+    read: Vec<RangeInclusive<u16>>,
+    /// Watched writes.
+    write: Vec<RangeInclusive<u16>>,
+    /// Recorded events.
+    log: Vec<Event>,
+}
+
+impl Watch {
+    /// Watches a range of addresses for the specified access.
+    pub fn watch(&mut self, kind: Access, addrs: RangeInclusive<u16>) {
+        match kind {
+            Access::Read => self.read.push(addrs),
+            Access::Write => self.write.push(addrs),
+        }
+    }
+
+    /// Stops watching all addresses.
+    pub fn clear(&mut self) {
+        self.read.clear();
+        self.write.clear();
+    }
+
+    /// Checks if no addresses are watched.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.read.is_empty() && self.write.is_empty()
+    }
+
+    /// Checks if an address is watched for the specified access.
+    #[must_use]
+    pub fn watches(&self, kind: Access, addr: u16) -> bool {
+        match kind {
+            Access::Read => &self.read,
+            Access::Write => &self.write,
+        }
+        .iter()
+        .any(|addrs| addrs.contains(&addr))
+    }
+
+    /// Records an access event, if watched.
+    pub fn record(&mut self, event: Event) {
+        if self.watches(event.kind, event.addr) {
+            self.log.push(event);
+        }
+    }
+
+    /// Takes all recorded access events.
+    pub fn take(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.log)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_works() {
+        let mut watch = Watch::default();
+        watch.watch(Access::Write, 0xc000..=0xc0ff);
+        // Unwatched accesses are dropped
+        let read = Event {
+            kind: Access::Read,
+            addr: 0xc000,
+            old: 0x00,
+            new: 0x00,
+        };
+        watch.record(read);
+        let write = Event {
+            kind: Access::Write,
+            addr: 0xc100,
+            old: 0x00,
+            new: 0x01,
+        };
+        watch.record(write);
+        assert!(watch.take().is_empty());
+        // Watched accesses are recorded
+        let write = Event {
+            addr: 0xc0ff,
+            ..write
+        };
+        watch.record(write);
+        assert_eq!(watch.take(), [write]);
+        // Cleared watches are dropped
+        watch.clear();
+        assert!(watch.is_empty());
+        watch.record(write);
+        assert!(watch.take().is_empty());
+    }
+}
@@ -4,17 +4,25 @@ use std::io::{BufRead, Write};
 use std::marker::PhantomData;
 
 use log::warn;
-use rugby_arch::Block;
 use rugby_arch::reg::Port;
+use rugby_arch::state::{self, Reader, Writer};
+use rugby_arch::{Block, Shared};
 
-use self::pcb::Motherboard;
+use self::pcb::{Motherboard, Vram, Wram};
+use self::soc::apu::blip;
 use self::soc::cpu::Cpu;
 use self::soc::joy::Joypad;
-use self::soc::ppu::Ppu;
-use crate::api::audio::{Audio, Chiptune};
-use crate::api::cable::Cable;
+use self::soc::ppu::{Ppu, Rgb555};
+use crate::api::audio::{Audio, Capacitor, Capture, Chiptune, Mixer, Sample};
+use crate::api::cable::{Cable, Status};
+use crate::api::camera::{Camera, ImageSource};
+use crate::api::cheat::{Cheat, Code};
+use crate::api::infrared::Infrared;
 use crate::api::input::{Event, Input};
-use crate::api::video::{Aspect, Video};
+use crate::api::motion::Motion;
+use crate::api::rumble::Rumble;
+use crate::api::snapshot::{self, Snapshot};
+use crate::api::video::{Aspect, Frame, Video};
 use crate::rev::Revision;
 
 pub mod bus;
@@ -35,22 +43,47 @@ use crate::cart::Cartridge;
 pub const CLOCK: u32 = 4_194_304;
 
 /// _Game Boy_.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct GameBoy<R: Revision = rev::C> {
     /// DMG-01 Motherboard.
     main: Motherboard,
+    /// Grayscale framebuffer.
+    rgb: Frame<Rgb555>,
     /// Revision marker.
     _rev: PhantomData<R>,
 }
 
+impl<R: Revision> Default for GameBoy<R>
+where
+    GameBoy<R>: Instance,
+{
+    fn default() -> Self {
+        let mut main = Motherboard::default();
+        // Install the revision's output capacitor
+        main.soc.apu.out = blip::Buffer::new(Self::CAPACITOR);
+        main.soc.apu.rec = blip::Recorder::new(Self::CAPACITOR);
+
+        Self {
+            main,
+            rgb: vec![Rgb555::default(); Ppu::SIZE.depth()].into_boxed_slice(),
+            _rev: PhantomData,
+        }
+    }
+}
+
 /// Revision-specific hardware model.
 trait Instance {
+    /// Output high-pass capacitor.
+    const CAPACITOR: Capacitor;
+
     /// Simulate the bootup sequence.
     fn boot(&mut self);
 }
 
 #[rustfmt::skip]
 impl Instance for GameBoy<rev::Zero> {
+    const CAPACITOR: Capacitor = Capacitor::DMG0;
+
     fn boot(&mut self) {
         let cpu = &mut self.main.soc.cpu;
 
@@ -73,6 +106,8 @@ impl Instance for GameBoy<rev::Zero> {
 
 #[rustfmt::skip]
 impl Instance for GameBoy<rev::A> {
+    const CAPACITOR: Capacitor = Capacitor::DMGA;
+
     fn boot(&mut self) {
         let cpu = &mut self.main.soc.cpu;
 
@@ -108,7 +143,11 @@ impl<R: Revision> GameBoy<R> {
 
     /// Constructs a new `GameBoy`, initialized with the provided boot ROM.
     #[must_use]
-    pub fn with(boot: boot::Boot) -> Self {
+    #[expect(private_bounds)]
+    pub fn with(boot: boot::Boot) -> Self
+    where
+        Self: Instance,
+    {
         let mut this = Self::default();
 
         // Initialize boot ROM
@@ -153,6 +192,36 @@ impl<R: Revision> GameBoy<R> {
     pub fn eject(&mut self) -> Option<Cartridge> {
         self.main.cart.eject()
     }
+
+    /// Gets the last complete frame as grayscale colors.
+    ///
+    /// Shades are translated as they would be displayed by a CGB running in
+    /// DMG compatibility mode. Unlike [`Video::frame`], this is only updated
+    /// on [vsync](Video::vsync), so never yields an incomplete frame.
+    #[must_use]
+    pub fn colors(&self) -> &[Rgb555] {
+        &self.rgb
+    }
+
+    /// Translates the framebuffer to grayscale.
+    fn paint(&mut self) {
+        let frame = self.main.soc.ppu.frame();
+        for (rgb, &col) in self.rgb.iter_mut().zip(frame) {
+            *rgb = Rgb555::from(col);
+        }
+    }
+
+    /// Gets a handle to the video RAM.
+    #[must_use]
+    pub fn vram(&self) -> Shared<Vram> {
+        self.main.vram.clone()
+    }
+
+    /// Gets a handle to the work RAM.
+    #[must_use]
+    pub fn wram(&self) -> Shared<Wram> {
+        self.main.wram.clone()
+    }
 }
 
 #[cfg(feature = "debug")]
@@ -179,6 +248,9 @@ where
 
     fn cycle(&mut self) {
         self.main.cycle();
+        if self.vsync() {
+            self.paint();
+        }
     }
 
     fn reset(&mut self) {
@@ -189,6 +261,7 @@ where
             self.boot();
         }
         self.main.cart.reset();
+        self.paint();
     }
 }
 
@@ -199,6 +272,26 @@ where
     fn sample(&self) -> Chiptune {
         self.main.soc.apu.sample()
     }
+
+    fn set_rate(&mut self, rate: u32) {
+        self.main.soc.apu.set_rate(rate);
+    }
+
+    fn pull(&mut self, out: &mut Vec<Sample>) {
+        self.main.soc.apu.pull(out);
+    }
+
+    fn set_mixer(&mut self, mix: Mixer) {
+        self.main.soc.apu.set_mixer(mix);
+    }
+
+    fn set_capture(&mut self, rate: u32) {
+        self.main.soc.apu.set_capture(rate);
+    }
+
+    fn capture(&mut self, out: &mut Capture) {
+        self.main.soc.apu.capture(out);
+    }
 }
 
 impl<R: Revision> Cable for GameBoy<R>
@@ -212,6 +305,31 @@ where
     fn tx(&mut self) -> &mut dyn Write {
         self.main.soc.sio.tx()
     }
+
+    fn status(&self) -> Status {
+        self.main.soc.sio.status()
+    }
+
+    fn tick(&mut self) {
+        self.main.soc.sio.tick();
+    }
+}
+
+impl<R: Revision> Cheat for GameBoy<R>
+where
+    GameBoy<R>: Instance,
+{
+    fn cheats(&self) -> Vec<Code> {
+        self.main.cart.cheats()
+    }
+
+    fn add_cheat(&mut self, code: Code) {
+        self.main.cart.add_cheat(code);
+    }
+
+    fn clear_cheats(&mut self) {
+        self.main.cart.clear_cheats();
+    }
 }
 
 impl<R: Revision> Input for GameBoy<R>
@@ -225,6 +343,129 @@ where
     }
 }
 
+impl<R: Revision> Rumble for GameBoy<R>
+where
+    GameBoy<R>: Instance,
+{
+    fn rumble(&self) -> f32 {
+        self.main.cart.rumble()
+    }
+}
+
+impl<R: Revision> Infrared for GameBoy<R>
+where
+    GameBoy<R>: Instance,
+{
+    fn emit(&self) -> bool {
+        self.main.cart.infrared()
+    }
+
+    fn detect(&mut self, light: bool) {
+        self.main.cart.set_infrared(light);
+    }
+}
+
+impl<R: Revision> Motion for GameBoy<R>
+where
+    GameBoy<R>: Instance,
+{
+    fn tilt(&mut self, x: f32, y: f32) {
+        self.main.cart.tilt(x, y);
+    }
+}
+
+impl<R: Revision> Camera for GameBoy<R>
+where
+    GameBoy<R>: Instance,
+{
+    fn set_source(&mut self, src: Box<dyn ImageSource>) {
+        self.main.cart.set_camera(src);
+    }
+}
+
+impl<R: Revision> GameBoy<R> {
+    /// Snapshot signature.
+    const MAGIC: [u8; 8] = *b"RUGBYSAV";
+
+    /// Snapshot format version.
+    ///
+    /// Must be incremented whenever the encoding changes.
+    const VERSION: u16 = 4;
+
+    /// Restores the motherboard from an encoded snapshot body.
+    fn restore(&mut self, mut inp: Reader) -> state::Result<()> {
+        inp.load(&mut self.main)?;
+        self.paint();
+        inp.finish()
+    }
+}
+
+impl<R: Revision> Snapshot for GameBoy<R> {
+    fn save_state(&self) -> Vec<u8> {
+        let mut out = Writer::new();
+        // Write the header
+        out.put(&Self::MAGIC);
+        out.save(&Self::VERSION)
+            .section(b"INFO", |out| {
+                let model = R::default().to_string();
+                let cart = self.cart().map(|cart| cart.header().check.gchk);
+                out.seq(model.as_bytes()).save(&cart);
+            })
+            // Write the machine
+            .save(&self.main);
+        out.finish()
+    }
+
+    fn load_state(&mut self, buf: &[u8]) -> snapshot::Result<()> {
+        let mut inp = Reader::new(buf);
+        // Check the signature
+        if inp.take(Self::MAGIC.len()).ok() != Some(&Self::MAGIC) {
+            return Err(snapshot::Error::Signature);
+        }
+        // Check the version
+        let mut version = u16::default();
+        inp.load(&mut version)?;
+        if version != Self::VERSION {
+            return Err(snapshot::Error::Version {
+                found: version,
+                expected: Self::VERSION,
+            });
+        }
+        // Check the machine
+        let mut model = Vec::<u8>::new();
+        let mut cart = Option::<u16>::None;
+        inp.section(b"INFO", |inp| {
+            inp.load(&mut model)?.load(&mut cart)?;
+            Ok(())
+        })?;
+        let model = String::from_utf8_lossy(&model);
+        let expected = R::default().to_string();
+        if model != expected {
+            return Err(snapshot::Error::Model {
+                found: model.into_owned(),
+                expected,
+            });
+        }
+        if cart != self.cart().map(|cart| cart.header().check.gchk) {
+            return Err(snapshot::Error::Cartridge);
+        }
+        // Restore the machine
+        //
+        // NOTE: As restoring is performed in place, a backup is used to roll
+        //       back partially restored states.
+        let backup = {
+            let mut out = Writer::new();
+            out.save(&self.main);
+            out.finish()
+        };
+        self.restore(inp).inspect_err(|_| {
+            self.restore(Reader::new(&backup))
+                .expect("backup should always restore");
+        })?;
+        Ok(())
+    }
+}
+
 impl<R: Revision> Video for GameBoy<R>
 where
     GameBoy<R>: Instance,
@@ -3,9 +3,12 @@
 use log::warn;
 use rugby_arch::mem::Ram;
 use rugby_arch::reg::Register;
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
 use super::soc::SoC;
+use crate::api::cheat::Code;
+use crate::api::video::Video;
 use crate::cart;
 
 /// Sharp LH5164N (64K SRAM).
@@ -77,6 +80,33 @@ impl Motherboard {
     }
 }
 
+impl State for Motherboard {
+    fn save(&self, out: &mut Writer) {
+        out.section(b"MAIN", |out| {
+            out.save(&self.clk);
+        })
+        .section(b"VRAM", |out| {
+            out.save(&self.vram);
+        })
+        .section(b"WRAM", |out| {
+            out.save(&self.wram);
+        })
+        .section(b"CART", |out| {
+            out.save(&self.cart);
+        })
+        .save(&self.soc);
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.section(b"MAIN", |inp| inp.load(&mut self.clk).map(drop))?
+            .section(b"VRAM", |inp| inp.load(&mut self.vram).map(drop))?
+            .section(b"WRAM", |inp| inp.load(&mut self.wram).map(drop))?
+            .section(b"CART", |inp| inp.load(&mut self.cart).map(drop))?
+            .load(&mut self.soc)?;
+        Ok(())
+    }
+}
+
 impl Block for Motherboard {
     fn ready(&self) -> bool {
         self.soc.ready()
@@ -102,8 +132,27 @@ impl Block for Motherboard {
         }
         // CPU: 1 MiHz
         if self.soc.cpu.ready() && self.clk.is_multiple_of(4) {
+            // Track the ROM bank in which call frames are entered
+            //
+            // Only needed while frames are observed by the debugger or
+            // profiler.
+            #[cfg(feature = "debug")]
+            if self.soc.cpu.stack().watched() || self.soc.prof.borrow().enabled() {
+                let bank = self.cart.bank().unwrap_or(1);
+                let bank = u16::try_from(bank).unwrap_or(u16::MAX);
+                self.soc.cpu.stack_mut().map(bank);
+            }
             self.soc.cpu.cycle();
         }
+        // Profiler: 1 MiHz
+        #[cfg(feature = "debug")]
+        if self.clk.is_multiple_of(4) {
+            let mut prof = self.soc.prof.borrow_mut();
+            if prof.enabled() {
+                let bank = self.cart.bank().unwrap_or(1);
+                prof.sample(&self.soc.cpu, bank, self.soc.boot.ready());
+            }
+        }
         // DMA: 1 MiHz
         if self.soc.dma.ready() && self.clk.is_multiple_of(4) {
             self.soc.dma.cycle();
@@ -115,6 +164,21 @@ impl Block for Motherboard {
             // When the LCD is disabled, the PPU holds its reset state.
             self.soc.ppu.disable();
         }
+        // Cheats: once per frame
+        //
+        // Writes bypass the processor's probes, as the cheat device is not
+        // the processor.
+        if self.soc.ppu.vsync() {
+            for code in self.cart.codes().iter() {
+                if let Code::Shark(code) = code {
+                    self.soc.cpu.blk.bus.poke(code.addr, code.data);
+                }
+            }
+        }
+        // Cartridge: 32 KiHz
+        if self.cart.ready() && self.clk.is_multiple_of(128) {
+            self.cart.cycle();
+        }
         // Serial: 8192 Hz
         if self.soc.sio.ready() && self.clk.is_multiple_of(512) {
             self.soc.sio.cycle();
@@ -1,9 +1,13 @@
 //! Sharp LR35902.
 
+use rugby_arch::state::{self, Reader, State, Writer};
 use rugby_arch::{Block, Shared};
 
+#[cfg(feature = "debug")]
+use super::dbg;
 use super::pcb::{Vram, Wram};
 use super::{boot, bus};
+use crate::api::audio::{Capacitor, Mixer};
 use crate::cart;
 pub use crate::chip::{apu, cpu, dma, irq, joy, ppu, sio, tma};
 
@@ -28,6 +32,12 @@ pub struct SoC {
     pub sio: sio::Serial,
     /// Hardware timer.
     pub tma: tma::Timer,
+    /// Memory watch hook.
+    #[cfg(feature = "debug")]
+    pub watch: Shared<dbg::watch::Watch>,
+    /// Execution profiler.
+    #[cfg(feature = "debug")]
+    pub prof: Shared<dbg::prof::Profile>,
 }
 
 impl SoC {
@@ -86,21 +96,25 @@ impl SoC {
                 mem,
                 seq: apu::Sequencer {
                     bit: bool::default(),
+                    fast: bool::default(),
                     clk: u8::default(),
                     div: tma.reg.div.clone(),
                 },
                 etc: apu::Internal::default(),
+                out: apu::blip::Buffer::new(Capacitor::default()),
+                rec: apu::blip::Recorder::new(Capacitor::default()),
+                mix: Mixer::default(),
             }
         };
         // Direct memory access unit
         let dma = dma::Dma {
-            bus: bus::view::Dma {
+            bus: dma::View::Dmg(bus::view::Dma {
                 vbus: bus::Vbus { vram: vram.clone() },
                 ebus: bus::Ebus {
                     cart: cart.clone(),
                     wram: wram.clone(),
                 },
-            },
+            }),
             mem: oam.clone(),
             reg: Shared::new(dma::Control::default()),
         };
@@ -121,6 +135,7 @@ impl SoC {
             },
             etc: ppu::Internal::default(),
             irq: irq.line.clone(),
+            cgb: None,
         };
         // Serial communications port
         let sio = sio::Serial {
@@ -128,6 +143,12 @@ impl SoC {
             etc: sio::Internal::default(),
             irq: irq.line.clone(),
         };
+        // Memory watch hook
+        #[cfg(feature = "debug")]
+        let watch = Shared::new(dbg::watch::Watch::default());
+        // Execution profiler
+        #[cfg(feature = "debug")]
+        let prof = Shared::new(dbg::prof::Profile::default());
         // Central processing unit
         let cpu = {
             let mem = cpu::Bank::default();
@@ -157,6 +178,12 @@ impl SoC {
                     wram: wram.clone(),
                 },
             };
+            #[cfg(feature = "debug")]
+            let bus = bus::view::Probe {
+                view: bus,
+                hook: watch.clone(),
+                prof: prof.clone(),
+            };
             cpu::Cpu {
                 blk: cpu::blk::Hardware::new(cpu::blk::Bus::new(bus)),
                 reg: cpu::File::default(),
@@ -177,10 +204,59 @@ impl SoC {
             ppu,
             sio,
             tma,
+            #[cfg(feature = "debug")]
+            watch,
+            #[cfg(feature = "debug")]
+            prof,
         }
     }
 }
 
+impl State for SoC {
+    fn save(&self, out: &mut Writer) {
+        out.section(b"APU ", |out| {
+            out.save(&self.apu);
+        })
+        .section(b"BOOT", |out| {
+            out.save(&self.boot);
+        })
+        .section(b"CPU ", |out| {
+            out.save(&self.cpu);
+        })
+        .section(b"DMA ", |out| {
+            out.save(&self.dma);
+        })
+        .section(b"IRQ ", |out| {
+            out.save(&self.irq);
+        })
+        .section(b"JOY ", |out| {
+            out.save(&self.joy);
+        })
+        .section(b"PPU ", |out| {
+            out.save(&self.ppu);
+        })
+        .section(b"SIO ", |out| {
+            out.save(&self.sio);
+        })
+        .section(b"TMA ", |out| {
+            out.save(&self.tma);
+        });
+    }
+
+    fn restore(&mut self, inp: &mut Reader) -> state::Result<()> {
+        inp.section(b"APU ", |inp| inp.load(&mut self.apu).map(drop))?
+            .section(b"BOOT", |inp| inp.load(&mut self.boot).map(drop))?
+            .section(b"CPU ", |inp| inp.load(&mut self.cpu).map(drop))?
+            .section(b"DMA ", |inp| inp.load(&mut self.dma).map(drop))?
+            .section(b"IRQ ", |inp| inp.load(&mut self.irq).map(drop))?
+            .section(b"JOY ", |inp| inp.load(&mut self.joy).map(drop))?
+            .section(b"PPU ", |inp| inp.load(&mut self.ppu).map(drop))?
+            .section(b"SIO ", |inp| inp.load(&mut self.sio).map(drop))?
+            .section(b"TMA ", |inp| inp.load(&mut self.tma).map(drop))?;
+        Ok(())
+    }
+}
+
 impl Block for SoC {
     fn ready(&self) -> bool {
         self.cpu.ready()
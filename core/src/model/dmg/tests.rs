@@ -6,6 +6,7 @@ use self::soc::irq::Irq;
 use self::soc::ppu::Ppu;
 use self::soc::tma::Timer;
 use super::*;
+use crate::api::cheat::Genie;
 use crate::cart::Cartridge;
 use crate::cart::chip::Mbc;
 
@@ -192,3 +193,289 @@ fn bus_unmapped_works() {
         }
     }
 }
+
+#[test]
+fn snapshot_round_trip_works() {
+    let mut emu = setup();
+
+    // Run until mid-frame
+    (0..123_456).for_each(|_| emu.cycle());
+    // Take a snapshot
+    let state = emu.save_state();
+    // Run a few frames, recording the result
+    (0..250_000).for_each(|_| emu.cycle());
+    let frame = emu.frame().to_vec();
+    let after = emu.save_state();
+
+    // Restore the snapshot
+    emu.load_state(&state).unwrap();
+    assert_eq!(emu.save_state(), state);
+    // Run again, checking the result is identical
+    (0..250_000).for_each(|_| emu.cycle());
+    assert_eq!(emu.frame(), frame);
+    assert_eq!(emu.save_state(), after);
+}
+
+#[test]
+fn snapshot_invalid_fails() {
+    let mut emu = setup();
+    (0..10_000).for_each(|_| emu.cycle());
+    let state = emu.save_state();
+
+    // Bad signature
+    assert!(matches!(
+        emu.load_state(b"NOTASAVE"),
+        Err(snapshot::Error::Signature)
+    ));
+    // Truncated body
+    assert!(matches!(
+        emu.load_state(&state[..state.len() - 1]),
+        Err(snapshot::Error::Decode(_))
+    ));
+    // Failed loads leave the emulator untouched
+    assert_eq!(emu.save_state(), state);
+
+    // Mismatched cartridge
+    let mut other: GameBoy = GameBoy::with(Boot::from(*BOOT));
+    other.insert(
+        Cartridge::new(include_bytes!("../../../../roms/test/acid2/dmg-acid2.gb")).unwrap(),
+    );
+    assert!(matches!(
+        other.load_state(&state),
+        Err(snapshot::Error::Cartridge)
+    ));
+}
+
+#[test]
+fn cheat_genie_works() {
+    let mut emu = setup();
+    let orig = GAME[0x0150];
+
+    // Patch a ROM byte
+    emu.add_cheat(Code::Genie(Genie::new(0x0150, !orig, None)));
+    assert_eq!(emu.main.soc.cpu.blk.bus.read(0x0150), !orig);
+    // Only patch when the compare byte matches
+    emu.clear_cheats();
+    emu.add_cheat(Code::Genie(Genie::new(0x0150, !orig, Some(!orig))));
+    assert_eq!(emu.main.soc.cpu.blk.bus.read(0x0150), orig);
+}
+
+#[test]
+fn cheat_shark_works() {
+    let mut emu = setup();
+
+    // Write a RAM byte once per frame
+    emu.add_cheat("0142DFCF".parse().unwrap());
+    assert_eq!(emu.cheats().len(), 1);
+    for _ in 0..2 {
+        emu.main.soc.cpu.blk.bus.write(0xcfdf, 0x00);
+        // Run until the next vertical sync
+        //
+        // NOTE: The boot ROM keeps the LCD disabled for several frames.
+        for _ in 0..100 * 70_224 {
+            emu.cycle();
+            if emu.vsync() {
+                break;
+            }
+        }
+        assert!(emu.vsync());
+        assert_eq!(emu.main.soc.cpu.blk.bus.read(0xcfdf), 0x42);
+    }
+}
+
+#[test]
+fn colors_works() {
+    // Run a program of NOPs
+    let mut emu = GameBoy::<rev::C>::new();
+    emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+    // Run until the next vertical sync
+    for _ in 0..70_224 {
+        emu.cycle();
+        if emu.vsync() {
+            break;
+        }
+    }
+    assert!(emu.vsync());
+
+    // Check the frame was translated to grayscale
+    assert_eq!(emu.colors().len(), emu.frame().len());
+    assert!(
+        emu.colors()
+            .iter()
+            .zip(emu.frame())
+            .all(|(&rgb, &col)| rgb == Rgb555::from(col))
+    );
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn cheat_shark_unobserved() {
+    use self::dbg::watch::Access;
+
+    // Run a program of NOPs
+    let mut emu = GameBoy::<rev::C>::new();
+    emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+    // Watch the cheat's address
+    emu.main
+        .soc
+        .watch
+        .borrow_mut()
+        .watch(Access::Write, 0xcfdf..=0xcfdf);
+    emu.add_cheat("0142DFCF".parse().unwrap());
+    // Run until the next vertical sync
+    for _ in 0..70_224 {
+        emu.cycle();
+        if emu.vsync() {
+            break;
+        }
+    }
+    assert!(emu.vsync());
+
+    // Check the write was applied unobserved
+    assert_eq!(emu.main.soc.cpu.blk.bus.read(0xcfdf), 0x42);
+    assert!(emu.main.soc.watch.borrow_mut().take().is_empty());
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn watch_works() {
+    use self::dbg::watch::{Access, Event};
+
+    let mut emu = setup();
+    let bus = &mut emu.main.soc.cpu.blk.bus;
+    bus.write(0xc000, 0x12);
+
+    // Watch a WRAM variable
+    emu.main
+        .soc
+        .watch
+        .borrow_mut()
+        .watch(Access::Write, 0xc000..=0xc000);
+    emu.main
+        .soc
+        .watch
+        .borrow_mut()
+        .watch(Access::Read, 0xc000..=0xc000);
+    let bus = &mut emu.main.soc.cpu.blk.bus;
+    bus.write(0xc001, 0x00);
+    bus.write(0xc000, 0x34);
+    assert_eq!(bus.read(0xc000), 0x34);
+    assert_eq!(
+        emu.main.soc.watch.borrow_mut().take(),
+        [
+            Event {
+                kind: Access::Write,
+                addr: 0xc000,
+                old: 0x12,
+                new: 0x34,
+            },
+            Event {
+                kind: Access::Read,
+                addr: 0xc000,
+                old: 0x34,
+                new: 0x34,
+            },
+        ]
+    );
+    // Echo RAM is decoded separately
+    emu.main.soc.cpu.blk.bus.write(0xe000, 0x56);
+    assert!(emu.main.soc.watch.borrow_mut().take().is_empty());
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn profile_works() {
+    use self::dbg::prof::cdl;
+    use self::dbg::sym::{Symbol, Symbols};
+
+    // Run a program which calls a subroutine then spins
+    let mut rom = vec![0; 0x8000];
+    rom[0x0100..0x0105].copy_from_slice(&[0xcd, 0x00, 0x02, 0x18, 0xfe]); // CALL $0200; JR @
+    rom[0x0200..0x0204].copy_from_slice(&[0xfa, 0x00, 0x03, 0xc9]); // LD A, ($0300); RET
+    let mut emu = GameBoy::<rev::C>::new();
+    emu.insert(Cartridge::unchecked(&rom).unwrap());
+    emu.main.soc.prof.borrow_mut().enable();
+    for _ in 0..4 * 32 {
+        emu.cycle();
+    }
+    let prof = emu.main.soc.prof.borrow();
+
+    // Check cycles
+    let cycles = |addr| prof.cycles().get(&Symbol::new(1, addr)).copied();
+    assert_eq!(cycles(0x0100), Some(6));
+    assert_eq!(cycles(0x0200), Some(4));
+    assert_eq!(cycles(0x0203), Some(4));
+    // Check coverage
+    let cover = prof.coverage();
+    assert!(cover[0x0100..0x0105].iter().all(|&flag| flag == cdl::CODE));
+    assert!(cover[0x0200..0x0204].iter().all(|&flag| flag == cdl::CODE));
+    assert_eq!(cover[0x0300], cdl::DATA);
+    // Check stacks
+    let syms: Symbols = "00:0100 Main\n00:0200 Load\n".parse().unwrap();
+    let mut out = Vec::new();
+    prof.folded(&syms, &mut out).unwrap();
+    assert_eq!(out, b"Main 23\nMain;Load 8\n");
+    assert_eq!(prof.functions(&syms).values().sum::<u64>(), 31);
+    assert_eq!(prof.banks().get(&0), Some(&31));
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn stack_watch_works() {
+    // Run a program which calls a subroutine then spins
+    let mut rom = vec![0; 0x8000];
+    rom[0x0100..0x0105].copy_from_slice(&[0xcd, 0x00, 0x02, 0x18, 0xfe]); // CALL $0200; JR @
+    let mut emu = GameBoy::<rev::C>::new();
+    emu.insert(Cartridge::unchecked(&rom).unwrap());
+    for _ in 0..4 * 8 {
+        emu.cycle();
+    }
+    // Unobserved frames are entered without tracking the bank
+    assert_eq!(emu.main.soc.cpu.stack().frames()[0].bank, 0);
+
+    // Observed frames are entered within the mapped bank
+    emu.reset();
+    emu.main.soc.cpu.stack_mut().clear();
+    emu.main.soc.cpu.stack_mut().watch(true);
+    for _ in 0..4 * 8 {
+        emu.cycle();
+    }
+    assert_eq!(emu.main.soc.cpu.stack().frames()[0].bank, 1);
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn profile_banked_works() {
+    use self::dbg::sym::Symbols;
+
+    // Run a program which calls into bank 1, which switches to bank 2 before
+    // returning
+    let mut rom = vec![0; 0x10000];
+    rom[0x0147] = 0x01; // MBC1
+    rom[0x0148] = 0x01; // 64 KiB
+    rom[0x0100..0x0105].copy_from_slice(&[0xcd, 0x00, 0x40, 0x18, 0xfe]); // CALL $4000; JR @
+    rom[0x4000..0x4005].copy_from_slice(&[0x3e, 0x02, 0xea, 0x00, 0x20]); // LD A, 2; LD ($2000), A
+    rom[0x8005] = 0xc9; // RET
+    let mut emu = GameBoy::<rev::C>::new();
+    emu.insert(Cartridge::unchecked(&rom).unwrap());
+    emu.main.soc.prof.borrow_mut().enable();
+    for _ in 0..4 * 32 {
+        emu.cycle();
+    }
+    let prof = emu.main.soc.prof.borrow();
+
+    // Check stacks remain within the callee's bank
+    let syms: Symbols = "00:0100 Main\n01:4000 Far\n".parse().unwrap();
+    let mut out = Vec::new();
+    prof.folded(&syms, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(
+        out.lines().any(|line| line.starts_with("Main;Far ")),
+        "{out}"
+    );
+    assert!(
+        out.lines()
+            .all(|line| line.starts_with("Main ") || line.starts_with("Main;Far ")),
+        "{out}"
+    );
+}
@@ -1,6 +1,7 @@
 //! Audio options.
 
 use merge::Merge;
+use rugby_core::api::audio;
 
 /// Audio options.
 #[derive(Debug, Default, Merge)]
@@ -33,4 +34,45 @@ pub struct Audio {
     #[merge(strategy = merge::num::overwrite_zero)]
     #[expect(clippy::doc_markdown)]
     pub rate: u32,
+    /// Audio mixer.
+    ///
+    /// Selects how audio channels are mixed for output. The analog mixer
+    /// models the console's output circuitry, including DAC pops and the
+    /// decaying offset of its high-pass capacitor.
+    #[cfg_attr(feature = "clap", arg(long = "mixer", value_name = "MODE", value_enum))]
+    #[cfg_attr(feature = "facet", facet(rename = "mixer"))]
+    #[cfg_attr(feature = "serde", serde(rename = "mixer"))]
+    #[merge(strategy = merge::option::overwrite_none)]
+    pub mix: Option<Mixer>,
+}
+
+/// Audio mixer selection.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(
+    feature = "facet",
+    derive(facet::Facet),
+    facet(rename_all = "kebab-case")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "kebab-case")
+)]
+#[repr(C)]
+pub enum Mixer {
+    /// Ideal digital mixing.
+    #[default]
+    Digital,
+    /// Analog output stage.
+    Analog,
+}
+
+impl From<Mixer> for audio::Mixer {
+    fn from(value: Mixer) -> Self {
+        match value {
+            Mixer::Digital => audio::Mixer::Digital,
+            Mixer::Analog => audio::Mixer::Analog,
+        }
+    }
 }
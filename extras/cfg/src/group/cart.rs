@@ -1,5 +1,7 @@
 //! Cartridge options.
 
+use std::path::PathBuf;
+
 use merge::Merge;
 
 pub use crate::types::When;
@@ -50,4 +52,26 @@ pub struct Cart {
     )]
     #[merge(strategy = merge::option::overwrite_none)]
     pub save: Option<When>,
+
+    /// MBC1 multicart wiring.
+    ///
+    /// This option can be used to override the heuristic detection of MBC1
+    /// multicart (MBC1M) compilations, which wire the cartridge's ROM banks
+    /// differently.
+    #[cfg_attr(feature = "clap", arg(long, value_name = "WHEN", value_enum))]
+    #[merge(strategy = merge::option::overwrite_none)]
+    pub multicart: Option<When>,
+
+    /// Camera image source.
+    ///
+    /// Path to a PNG image to be used as the input to the Game Boy Camera's
+    /// sensor. If omitted, a procedurally generated test pattern is used
+    /// instead.
+    #[cfg_attr(feature = "clap", arg(
+        long,
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+    ))]
+    #[merge(strategy = merge::option::overwrite_none)]
+    pub camera: Option<PathBuf>,
 }
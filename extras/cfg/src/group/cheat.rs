@@ -0,0 +1,31 @@
+//! Cheat options.
+
+use merge::Merge;
+
+/// Cheat options.
+#[derive(Debug, Default, Merge)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+#[cfg_attr(
+    feature = "facet",
+    derive(facet::Facet),
+    facet(default, deny_unknown_fields)
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default, deny_unknown_fields)
+)]
+#[cfg_attr(
+    all(feature = "facet", feature = "serde"),
+    expect(clippy::unsafe_derive_deserialize)
+)]
+#[cfg_attr(feature = "clap", command(next_help_heading = "Cheats"))]
+pub struct Cheat {
+    /// Cheat codes.
+    ///
+    /// Activates a Game Genie (e.g. "00A-17B-C49") or GameShark (e.g.
+    /// "010238CD") cheat code. May be specified multiple times.
+    #[cfg_attr(feature = "clap", arg(long = "cheat", value_name = "CODE"))]
+    #[merge(strategy = merge::vec::overwrite_empty)]
+    pub codes: Vec<String>,
+}
@@ -4,14 +4,16 @@ mod audio;
 mod boot;
 mod cable;
 mod cart;
+mod cheat;
 mod input;
 mod model;
 mod video;
 
-pub use self::audio::Audio;
+pub use self::audio::{Audio, Mixer};
 pub use self::boot::Boot;
 pub use self::cable::Cable;
 pub use self::cart::Cart;
+pub use self::cheat::Cheat;
 pub use self::input::Input;
 pub use self::model::Model;
 pub use self::video::{Palette, Video};
@@ -30,7 +30,7 @@ pub mod fmt;
 pub mod group;
 pub mod types;
 
-pub use self::group::{Audio, Boot, Cable, Cart, Input, Palette, Video};
+pub use self::group::{Audio, Boot, Cable, Cart, Cheat, Input, Palette, Video};
 
 /// Emulator configuration.
 ///
@@ -90,6 +90,10 @@ pub struct Config {
     #[cfg_attr(feature = "clap", command(flatten))]
     pub cart: group::Cart,
 
+    /// Cheat options.
+    #[cfg_attr(feature = "clap", command(flatten))]
+    pub cheat: group::Cheat,
+
     /// Hardware model options.
     #[cfg_attr(feature = "clap", command(flatten))]
     pub model: group::Model,
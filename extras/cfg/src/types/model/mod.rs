@@ -11,6 +11,9 @@ pub enum Model {
     /// DMG options.
     #[display("dmg{0}")]
     Dmg(dmg::Dmg),
+    /// CGB.
+    #[display("cgb")]
+    Cgb,
 }
 
 impl Default for Model {
@@ -70,7 +73,8 @@ mod imp {
                             ),
                             |val, help| val.help(help.clone()),
                         )
-                    }),
+                    })
+                    .chain([PossibleValue::new(Model::Cgb.to_string()).help("Game Boy Color")]),
             ))
         }
     }
@@ -2,28 +2,105 @@
 
 use std::fs::File;
 use std::ops::Not;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use itertools::Itertools;
 use orng::Orange;
 use rugby_arch::Block;
 use rugby_arch::reg::Port;
+use rugby_core::api::audio::Audio;
+use rugby_core::api::audio::rec::Recording;
 use rugby_core::api::cable::Cable;
+use rugby_core::api::cheat::Cheat as _;
 use rugby_core::api::video::Video;
+use rugby_core::chip::cpu::insn::dis::{self, Disasm};
 use rugby_core::chip::ppu::LCD;
 
-use super::lang::{Keyword, Select, Serial, Value};
-use super::{Debugger, Error, GameBoy, Result, Tick};
-use crate::Breakpoint;
+use super::lang::{Address, Cheat, Expr, Keyword, Select, Serial, Value, Watch};
+use super::prompt::Script;
+use super::{Debugger, Error, GameBoy, NEST, RATE, Result, Tick};
+use crate::{Breakpoint, Point};
+
+pub fn alias(gbd: &mut Debugger, def: Option<(String, String)>) -> Result<()> {
+    if let Some((name, body)) = def {
+        // Define the alias
+        gbd.alias.insert(name, body);
+    } else if gbd.alias.is_empty() {
+        // Print empty message
+        advise::info!("no aliases defined");
+    } else {
+        // Print each alias
+        for (name, body) in &gbd.alias {
+            advise::info!("{name}: {body}");
+        }
+    }
+
+    Ok(())
+}
+
+pub fn assert(gbd: &mut Debugger, emu: &GameBoy, expr: &Expr) -> Result<()> {
+    // Evaluate the expression, counting errors as failures
+    let value = expr.eval(emu).inspect_err(|_| gbd.fail += 1)?;
+    // Check the assertion holds
+    if value == 0 {
+        gbd.fail += 1;
+        return Err(Error::Assertion(expr.to_string()));
+    }
+
+    Ok(())
+}
+
+pub fn backtrace(gbd: &Debugger, emu: &GameBoy) -> Result<()> {
+    let bank = super::bank(emu);
+    // Describe a location by its nearest label
+    let place = |bank: usize, addr: u16| {
+        gbd.syms
+            .locate(bank, addr)
+            .map(|(label, off)| match off {
+                0 => format!(" in {label}"),
+                off => format!(" in {label}+{off}"),
+            })
+            .unwrap_or_default()
+    };
+    // Print the current location
+    advise::info!("#0  ${:04x}{}", gbd.insn, place(bank, gbd.insn));
+    // Print each call site, innermost first
+    let frames = emu.inner().soc.cpu.stack().frames();
+    for (idx, frame) in frames.iter().rev().enumerate() {
+        advise::info!(
+            "#{:<2} ${:04x}{} ({} ${:04x})",
+            idx + 1,
+            frame.site,
+            place(frame.bank.into(), frame.site),
+            frame.kind,
+            frame.addr,
+        );
+    }
 
-pub fn r#break(gbd: &mut Debugger, addr: u16) -> Result<()> {
+    Ok(())
+}
+
+pub fn r#break(gbd: &mut Debugger, addr: &Address, cond: Option<Expr>) -> Result<()> {
+    // Resolve the breakpoint's location
+    let (addr, bank) = gbd.resolve(addr)?;
+    let at = Point::Break(bank, addr);
     // Check if the breakpoint already exists
-    if let Some((point, _, Some(_))) = gbd.bpts.get_full_mut(&addr) {
-        // Inform of existing breakpoint
-        advise::warn!("breakpoint {point} already exists at ${addr:04x}");
+    if let Some((point, _, Some(bpt))) = gbd.bpts.get_full_mut(&at) {
+        if cond.is_some() {
+            // Update the existing condition
+            bpt.cond = cond;
+            advise::info!("{}", bpt.display(point, at));
+        } else {
+            // Inform of existing breakpoint
+            advise::warn!("{} already exists", at.display(point));
+        }
     } else {
         // Create a new breakpoint
-        let (point, _) = gbd.bpts.insert_full(addr, Some(Breakpoint::default()));
+        let bpt = Breakpoint {
+            cond,
+            ..Default::default()
+        };
+        let (point, _) = gbd.bpts.insert_full(at, Some(bpt));
         advise::info!("breakpoint {point} created");
     }
 
@@ -71,6 +148,33 @@ pub fn capture(emu: &mut GameBoy, path: &Path, force: bool) -> Result<()> {
     Ok(())
 }
 
+pub fn cheat(emu: &mut GameBoy, mode: Cheat) -> Result<()> {
+    match mode {
+        Cheat::List => {
+            // Print active cheats
+            let codes = emu.cheats();
+            if codes.is_empty() {
+                advise::info!("no cheats active");
+            }
+            for (index, code) in codes.iter().enumerate() {
+                advise::info!("cheat {index}: {code}");
+            }
+        }
+        Cheat::Clear => {
+            // Deactivate all cheats
+            emu.clear_cheats();
+            advise::info!("cheats cleared");
+        }
+        Cheat::Add(code) => {
+            // Activate the cheat
+            advise::info!("cheat {code} activated");
+            emu.add_cheat(code);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn r#continue(gbd: &mut Debugger) -> Result<()> {
     gbd.step = None; // reset step count
     gbd.resume(); // resume console
@@ -80,36 +184,84 @@ pub fn r#continue(gbd: &mut Debugger) -> Result<()> {
 
 pub fn delete(gbd: &mut Debugger, point: usize) -> Result<()> {
     // Find the specified breakpoint
-    let Some((&addr, bpt @ Some(_))) = gbd.bpts.get_index_mut(point) else {
+    let Some((&at, bpt @ Some(_))) = gbd.bpts.get_index_mut(point) else {
         return Err(Error::Breakpoint);
     };
     // Mark it as deleted
     *bpt = None;
-    advise::info!("breakpoint {point} @ ${addr:04x} deleted");
+    advise::info!("{} deleted", at.display(point));
 
     Ok(())
 }
 
 pub fn disable(gbd: &mut Debugger, point: usize) -> Result<()> {
     // Find the specified breakpoint
-    let Some((&addr, Some(bpt))) = gbd.bpts.get_index_mut(point) else {
+    let Some((&at, Some(bpt))) = gbd.bpts.get_index_mut(point) else {
         return Err(Error::Breakpoint);
     };
     // Disable it
     bpt.disable = true;
-    advise::info!("breakpoint {point} @ ${addr:04x} disabled");
+    advise::info!("{} disabled", at.display(point));
+
+    Ok(())
+}
+
+pub fn disasm(gbd: &Debugger, emu: &GameBoy, addr: Option<&Address>) -> Result<()> {
+    /// Number of instructions to disassemble.
+    const COUNT: usize = 10;
+
+    // Disassemble from the PC by default
+    let start = addr
+        .map(|addr| self::locate(gbd, emu, addr))
+        .transpose()?
+        .unwrap_or(gbd.pc);
+    let bus = emu.inner().soc.cpu.blk.bus.peek();
+    self::listing(
+        gbd,
+        emu,
+        dis::disassemble(bus, start..=u16::MAX).take(COUNT),
+    );
+
+    Ok(())
+}
+
+#[expect(clippy::needless_pass_by_value)]
+pub fn disasm_range(gbd: &Debugger, emu: &GameBoy, range: Orange<u16>) -> Result<()> {
+    let Orange { start, end } = range;
+    // Split ranges which wrap around memory
+    let ranges = if start <= end {
+        [Some(start..=end), None]
+    } else {
+        [Some(start..=u16::MAX), Some(u16::MIN..=end)]
+    };
+    let bus = emu.inner().soc.cpu.blk.bus.peek();
+    let insns = ranges
+        .into_iter()
+        .flatten()
+        .flat_map(|range| dis::disassemble(bus, range));
+    self::listing(gbd, emu, insns);
 
     Ok(())
 }
 
 pub fn enable(gbd: &mut Debugger, point: usize) -> Result<()> {
     // Find the specified breakpoint
-    let Some((&addr, Some(bpt))) = gbd.bpts.get_index_mut(point) else {
+    let Some((&at, Some(bpt))) = gbd.bpts.get_index_mut(point) else {
         return Err(Error::Breakpoint);
     };
     // Enable it
     bpt.disable = false;
-    advise::info!("breakpoint {point} @ ${addr:04x} enabled");
+    advise::info!("{} enabled", at.display(point));
+
+    Ok(())
+}
+
+pub fn finish(gbd: &mut Debugger) -> Result<()> {
+    // Step until the current frame is left
+    let depth = gbd.depth.checked_sub(1).ok_or(Error::Frame)?;
+    gbd.frame = Some(depth);
+    gbd.step = Some(0);
+    gbd.resume(); // resume console
 
     Ok(())
 }
@@ -125,7 +277,9 @@ pub fn freq(gbd: &mut Debugger, mode: Option<Tick>) -> Result<()> {
     Ok(())
 }
 
-pub fn goto(emu: &mut GameBoy, addr: u16) -> Result<()> {
+pub fn goto(gbd: &Debugger, emu: &mut GameBoy, addr: &Address) -> Result<()> {
+    // Resolve specified address
+    let addr = self::locate(gbd, emu, addr)?;
     // Jump to specified address
     emu.inner_mut().soc.cpu.goto(addr);
 
@@ -144,7 +298,9 @@ pub fn help(what: Option<Keyword>) -> Result<()> {
     Ok(())
 }
 
-pub fn jump(gbd: &mut Debugger, emu: &mut GameBoy, addr: u16) -> Result<()> {
+pub fn jump(gbd: &mut Debugger, emu: &mut GameBoy, addr: &Address) -> Result<()> {
+    // Resolve specified address
+    let addr = self::locate(gbd, emu, addr)?;
     // Jump to specified address
     emu.inner_mut().soc.cpu.goto(addr);
     // Continue execution
@@ -155,12 +311,12 @@ pub fn jump(gbd: &mut Debugger, emu: &mut GameBoy, addr: u16) -> Result<()> {
 
 pub fn ignore(gbd: &mut Debugger, point: usize, many: usize) -> Result<()> {
     // Find the specified breakpoint
-    let Some((&addr, Some(bpt))) = gbd.bpts.get_index_mut(point) else {
+    let Some((&at, Some(bpt))) = gbd.bpts.get_index_mut(point) else {
         return Err(Error::Breakpoint);
     };
     // Update ignore count
     bpt.ignore = many;
-    advise::info!("{}", bpt.display(point, addr));
+    advise::info!("{}", bpt.display(point, at));
 
     Ok(())
 }
@@ -175,23 +331,30 @@ pub fn info(gbd: &Debugger, what: Option<Keyword>) -> Result<()> {
 
     // Handle keyword
     match kword {
-        // Print breakpoints
-        Keyword::Break => {
+        // Print breakpoints, watchpoints
+        Keyword::Break | Keyword::Watch => {
+            let watch = matches!(kword, Keyword::Watch);
             let bpts: Vec<_> = gbd
                 .bpts
                 .iter()
                 // Add breakpoint indices
                 .enumerate()
                 // Filter out deleted breakpoints
-                .filter_map(|(point, (&addr, bpt))| bpt.as_ref().map(|bpt| (point, addr, bpt)))
+                .filter_map(|(point, (&at, bpt))| bpt.as_ref().map(|bpt| (point, at, bpt)))
+                // Filter out non-watchpoints (if requested)
+                .filter(|(_, at, _)| !watch || matches!(at, Point::Watch(..)))
                 .collect();
             if bpts.is_empty() {
                 // Print empty message
-                advise::info!("no breakpoints set");
+                if watch {
+                    advise::info!("no watchpoints set");
+                } else {
+                    advise::info!("no breakpoints set");
+                }
             } else {
                 // Print each breakpoint
-                for (point, addr, bpt) in bpts {
-                    advise::info!("{}", bpt.display(point, addr));
+                for (point, at, bpt) in bpts {
+                    advise::info!("{}", bpt.display(point, at));
                 }
             }
         }
@@ -202,12 +365,29 @@ pub fn info(gbd: &Debugger, what: Option<Keyword>) -> Result<()> {
 }
 
 pub fn list(gbd: &Debugger, emu: &GameBoy) -> Result<()> {
-    let insn = emu.inner().soc.cpu.insn();
-    advise::info!(
-        "${addr:04x}: {opcode:02X} ; {insn}",
-        addr = gbd.pc,
-        opcode = insn.opcode()
-    );
+    /// Number of instructions to list before the PC.
+    const BEFORE: usize = 3;
+    /// Number of instructions to list after the PC.
+    const AFTER: usize = 4;
+
+    let bus = emu.inner().soc.cpu.blk.bus.peek();
+    // Find the preceding instructions
+    //
+    // Decoding backwards is ambiguous, so try starting from the furthest
+    // address which decodes into the PC.
+    let before = (1..=3 * BEFORE)
+        .rev()
+        .filter_map(|back| gbd.pc.checked_sub(u16::try_from(back).ok()?))
+        .find_map(|start| {
+            let insns: Vec<_> = dis::disassemble(bus, start..=gbd.pc - 1).collect();
+            (insns.last()?.next() == gbd.pc).then_some(insns)
+        })
+        .unwrap_or_default();
+    let skip = before.len().saturating_sub(BEFORE);
+    // Find the following instructions
+    let after = dis::disassemble(bus, gbd.pc..=u16::MAX).take(1 + AFTER);
+    // Print the listing
+    self::listing(gbd, emu, before.into_iter().skip(skip).chain(after));
 
     Ok(())
 }
@@ -268,13 +448,45 @@ pub fn load(emu: &GameBoy, loc: Select) -> Result<()> {
     Ok(())
 }
 
+pub fn next(gbd: &mut Debugger, many: Option<usize>) -> Result<()> {
+    // Step within the current frame
+    gbd.frame = Some(gbd.depth);
+    gbd.step = many.or(Some(0)); // set step count
+    gbd.resume(); // resume console
+
+    Ok(())
+}
+
+pub fn print(emu: &GameBoy, expr: &Expr) -> Result<()> {
+    // Evaluate the expression
+    let value = expr.eval(emu)?;
+    // Print the result
+    if value < 0 {
+        advise::info!("{expr} = {value}");
+    } else {
+        advise::info!("{expr} = {value} (${value:x})");
+    }
+
+    Ok(())
+}
+
 pub fn quit() -> Result<()> {
     Err(Error::Quit)
 }
 
-pub fn read(emu: &mut GameBoy, addr: u16) -> Result<()> {
+pub fn read(gbd: &Debugger, emu: &mut GameBoy, addr: &Address) -> Result<()> {
+    // Resolve specified address
+    let addr = self::locate(gbd, emu, addr)?;
     // Perform the read
-    let byte = emu.inner().soc.cpu.blk.bus.read(addr);
+    let byte = emu
+        .inner()
+        .soc
+        .cpu
+        .blk
+        .bus
+        .peek()
+        .read(addr)
+        .unwrap_or(0xff);
     advise::info!("${addr:04x}: {byte:02x}");
 
     Ok(())
@@ -286,7 +498,16 @@ pub fn read_range(emu: &mut GameBoy, range: Orange<u16>) -> Result<()> {
     let iter = range.into_iter();
     // Load all reads
     let data: Vec<_> = iter
-        .map(|addr| emu.inner().soc.cpu.blk.bus.read(addr))
+        .map(|addr| {
+            emu.inner()
+                .soc
+                .cpu
+                .blk
+                .bus
+                .peek()
+                .read(addr)
+                .unwrap_or(0xff)
+        })
         .collect();
     // Display results
     advise::info!("read {size}:", size = bfmt::Size::from(data.len()));
@@ -298,6 +519,39 @@ pub fn read_range(emu: &mut GameBoy, range: Orange<u16>) -> Result<()> {
     Ok(())
 }
 
+pub fn record(
+    gbd: &mut Debugger,
+    emu: &mut GameBoy,
+    path: Option<PathBuf>,
+    stems: bool,
+) -> Result<()> {
+    if let Some(path) = path {
+        // Ensure audio capture is available
+        if gbd.rec.is_some() || emu.inner().soc.apu.rec.enabled() {
+            return Err(Error::Capture);
+        }
+        // Start recording
+        let rate = gbd.rate.unwrap_or(RATE);
+        gbd.rec = Some(Recording::new(&path, rate, stems)?);
+        emu.set_capture(rate);
+        advise::info!("recording to file: `{}`", path.display());
+    } else {
+        // Stop recording
+        if gbd.rec.is_none() {
+            return Err(Error::Record);
+        }
+        let res = gbd.record(emu);
+        let rec = gbd.rec.take().ok_or(Error::Record)?;
+        emu.set_capture(0);
+        res?;
+        for path in rec.finish()? {
+            advise::info!("wrote to file: `{}`", path.display());
+        }
+    }
+
+    Ok(())
+}
+
 pub fn reset(gbd: &mut Debugger, emu: &mut GameBoy) -> Result<()> {
     // Reset the console
     emu.reset();
@@ -308,6 +562,20 @@ pub fn reset(gbd: &mut Debugger, emu: &mut GameBoy) -> Result<()> {
     Ok(())
 }
 
+pub fn reverse_continue(gbd: &mut Debugger, emu: &mut GameBoy) -> Result<()> {
+    // Rewind to the previous breakpoint
+    gbd.rewind(emu, None, 1)?;
+    // Print the new context
+    rewound(gbd, emu)
+}
+
+pub fn reverse_step(gbd: &mut Debugger, emu: &mut GameBoy, many: Option<usize>) -> Result<()> {
+    // Rewind the step count
+    gbd.rewind(emu, Some(0), many.unwrap_or(1))?;
+    // Print the new context
+    rewound(gbd, emu)
+}
+
 pub fn serial(emu: &mut GameBoy, mode: Serial) -> Result<()> {
     let sio = &mut emu.inner_mut().soc.sio;
     match mode {
@@ -351,6 +619,18 @@ pub fn serial(emu: &mut GameBoy, mode: Serial) -> Result<()> {
     Ok(())
 }
 
+pub fn source(gbd: &mut Debugger, path: &Path) -> Result<()> {
+    // Limit nesting of sourced scripts
+    if gbd.srcs.len() >= NEST {
+        return Err(Error::Recursion(path.display().to_string()));
+    }
+    // Read commands from the script until exhausted
+    let src = Script::open(path)?;
+    gbd.srcs.push(src);
+
+    Ok(())
+}
+
 pub fn step(gbd: &mut Debugger, many: Option<usize>) -> Result<()> {
     gbd.step = many.or(Some(0)); // set step count
     gbd.resume(); // resume console
@@ -359,9 +639,17 @@ pub fn step(gbd: &mut Debugger, many: Option<usize>) -> Result<()> {
 }
 
 #[expect(clippy::needless_pass_by_value)]
-pub fn stores(emu: &mut GameBoy, locs: Vec<Select>, value: Value) -> Result<()> {
-    locs.into_iter()
-        .try_for_each(|loc| store(emu, loc, value.clone()))
+pub fn stores(emu: &mut GameBoy, locs: Vec<Select>, expr: Expr) -> Result<()> {
+    // Evaluate the value once for all stores
+    let int = expr.eval(emu)?;
+    locs.into_iter().try_for_each(|loc| {
+        // Fit the value to the register
+        let value = match loc {
+            Select::Word(_) => Value::Word(self::word(int)?),
+            _ => Value::Byte(self::byte(int)?),
+        };
+        store(emu, loc, value)
+    })
 }
 
 #[expect(clippy::needless_pass_by_value)]
@@ -431,7 +719,26 @@ pub fn store(emu: &mut GameBoy, loc: Select, value: Value) -> Result<()> {
     Ok(())
 }
 
-pub fn write(emu: &mut GameBoy, addr: u16, byte: u8) -> Result<()> {
+#[expect(clippy::needless_pass_by_value)]
+pub fn watch(gbd: &mut Debugger, range: Orange<u16>, mode: Watch) -> Result<()> {
+    let Orange { start, end } = range;
+    let at = Point::Watch(mode, start, end);
+    // Check if the watchpoint already exists
+    if let Some((point, _, Some(_))) = gbd.bpts.get_full_mut(&at) {
+        // Inform of existing watchpoint
+        advise::warn!("{} already exists", at.display(point));
+    } else {
+        // Create a new watchpoint
+        let (point, _) = gbd.bpts.insert_full(at, Some(Breakpoint::default()));
+        advise::info!("watchpoint {point} created");
+    }
+
+    Ok(())
+}
+
+pub fn write(emu: &mut GameBoy, addr: u16, expr: &Expr) -> Result<()> {
+    // Evaluate the byte
+    let byte = self::byte(expr.eval(emu)?)?;
     let cpu = &mut emu.inner_mut().soc.cpu;
     // Perform the write
     cpu.blk.bus.write(addr, byte);
@@ -439,13 +746,15 @@ pub fn write(emu: &mut GameBoy, addr: u16, byte: u8) -> Result<()> {
     if data != byte {
         advise::warn!("ignored write ${addr:04x} <- {byte:02x} (retained: {data:02x})");
     }
-    // Read the written value
-    read(emu, addr)?;
+    // Print the written value
+    advise::info!("${addr:04x}: {data:02x}");
 
     Ok(())
 }
 
-pub fn write_range(emu: &mut GameBoy, range: Orange<u16>, byte: u8) -> Result<()> {
+pub fn write_range(emu: &mut GameBoy, range: Orange<u16>, expr: &Expr) -> Result<()> {
+    // Evaluate the byte
+    let byte = self::byte(expr.eval(emu)?)?;
     let cpu = &mut emu.inner_mut().soc.cpu;
     // Create iterator from range
     let Orange { start, end } = range.clone();
@@ -473,3 +782,62 @@ pub fn write_range(emu: &mut GameBoy, range: Orange<u16>, byte: u8) -> Result<()
 
     Ok(())
 }
+
+/// Reports the context after rewinding execution.
+fn rewound(gbd: &mut Debugger, emu: &GameBoy) -> Result<()> {
+    // Report triggered watchpoints
+    for hit in gbd.hits.drain(..) {
+        advise::info!("{hit}");
+    }
+    // List the current instructions
+    list(gbd, emu)
+}
+
+/// Resolves an address operand, warning if its bank is not mapped.
+fn locate(gbd: &Debugger, emu: &GameBoy, addr: &Address) -> Result<u16> {
+    let (found, bank) = gbd.resolve(addr)?;
+    if let Some(bank) = bank {
+        let mapped = crate::bank(emu);
+        if usize::from(bank) != mapped {
+            advise::warn!("{addr} is in bank {bank}, but bank {mapped} is mapped");
+        }
+    }
+
+    Ok(found)
+}
+
+/// Prints disassembled instructions, preceded by any labels.
+fn listing(gbd: &Debugger, emu: &GameBoy, insns: impl Iterator<Item = Disasm>) {
+    let bank = crate::bank(emu);
+    for insn in insns {
+        if let Some(label) = gbd.syms.label(bank, insn.addr()) {
+            advise::info!("{label}:");
+        }
+        advise::info!("{}", self::insn(gbd, bank, &insn));
+    }
+}
+
+/// Formats a disassembled instruction, marking the PC.
+fn insn(gbd: &Debugger, bank: usize, insn: &Disasm) -> String {
+    format!(
+        "{mark} ${addr:04x}: {bytes:<8} ; {insn}",
+        mark = if insn.addr() == gbd.pc { "=>" } else { "  " },
+        addr = insn.addr(),
+        bytes = format!("{:02X}", insn.bytes().iter().format(" ")),
+        insn = insn.display(|addr| gbd.syms.label(bank, addr).map(String::from)),
+    )
+}
+
+/// Fits an evaluated integer to a byte, either signed or unsigned.
+fn byte(int: i64) -> Result<u8> {
+    u8::try_from(int)
+        .or_else(|_| i8::try_from(int).map(i8::cast_unsigned))
+        .map_err(|_| Error::Value)
+}
+
+/// Fits an evaluated integer to a word, either signed or unsigned.
+fn word(int: i64) -> Result<u16> {
+    u16::try_from(int)
+        .or_else(|_| i16::try_from(int).map(i16::cast_unsigned))
+        .map_err(|_| Error::Value)
+}
@@ -0,0 +1,144 @@
+//! Execution history.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+use rugby_core::chip::joy::Button;
+use rugby_core::chip::ppu::FRAME;
+
+/// Cycles between recorded snapshots.
+const INTERVAL: usize = FRAME as usize;
+
+/// Recorded execution history.
+///
+/// Holds periodic snapshots of the console within a bounded size, discarding
+/// the oldest once full. Alongside these, changes to the joypad's pressed keys
+/// are logged, such that they can be re-applied when re-executing.
+///
+/// By default, the size is zero, and nothing is recorded.
+#[derive(Debug, Default)]
+pub struct History {
+    /// Maximum size of snapshots, in bytes.
+    size: usize,
+    /// Total size of recorded snapshots, in bytes.
+    used: usize,
+    /// Recorded snapshots.
+    snaps: VecDeque<Snapshot>,
+    /// Recorded inputs.
+    input: BTreeMap<usize, HashSet<Button>>,
+    /// Last recorded keys.
+    held: HashSet<Button>,
+}
+
+impl History {
+    /// Constructs a new `History` bounded to the specified size, in bytes.
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            ..Default::default()
+        }
+    }
+
+    /// Checks if history is being recorded.
+    pub fn enabled(&self) -> bool {
+        self.size > 0
+    }
+
+    /// Checks if a snapshot is due at the specified cycle.
+    pub fn due(&self, cycle: usize) -> bool {
+        self.enabled()
+            && self
+                .snaps
+                .back()
+                .is_none_or(|snap| cycle >= snap.cycle + INTERVAL)
+    }
+
+    /// Records a snapshot taken at the specified cycle.
+    ///
+    /// The most recent snapshot is always kept, even if it alone exceeds the
+    /// size of the history.
+    pub fn push(&mut self, cycle: usize, state: Vec<u8>) {
+        // Evict the oldest snapshots
+        while self.used + state.len() > self.size
+            && let Some(snap) = self.snaps.pop_front()
+        {
+            self.used -= snap.state.len();
+        }
+        self.used += state.len();
+        self.snaps.push_back(Snapshot { cycle, state });
+        // Evict inputs preceding the oldest snapshot
+        if let Some(snap) = self.snaps.front() {
+            self.input = self.input.split_off(&snap.cycle);
+        }
+    }
+
+    /// Records the keys pressed at the specified cycle.
+    ///
+    /// Only changes to the pressed keys are logged.
+    pub fn press(&mut self, cycle: usize, keys: &HashSet<Button>) {
+        if !self.enabled() || *keys == self.held {
+            return;
+        }
+        self.held.clone_from(keys);
+        self.input.insert(cycle, keys.clone());
+    }
+
+    /// Gets the keys pressed at the specified cycle, if they changed.
+    pub fn keys(&self, cycle: usize) -> Option<&HashSet<Button>> {
+        self.input.get(&cycle)
+    }
+
+    /// Discards snapshots taken after the specified cycle.
+    pub fn truncate(&mut self, cycle: usize) {
+        while self.snaps.back().is_some_and(|snap| snap.cycle > cycle) {
+            if let Some(snap) = self.snaps.pop_back() {
+                self.used -= snap.state.len();
+            }
+        }
+        self.input.split_off(&(cycle + 1));
+    }
+
+    /// Discards all snapshots.
+    pub fn clear(&mut self) {
+        self.snaps.clear();
+        self.used = 0;
+        self.input.clear();
+        self.held.clear();
+    }
+
+    /// Iterates over snapshots, from most to least recent.
+    pub fn iter(&self) -> impl Iterator<Item = &Snapshot> {
+        self.snaps.iter().rev()
+    }
+}
+
+/// Console snapshot.
+#[derive(Debug)]
+pub struct Snapshot {
+    /// Debugger cycle when taken.
+    pub cycle: usize,
+    /// Saved console state.
+    pub state: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_bounded() {
+        let mut hist = History::new(0x300);
+        for idx in 0..4 {
+            hist.push(idx * INTERVAL, vec![0; 0x100]);
+        }
+        // Oldest snapshots are evicted once full
+        let cycles: Vec<_> = hist.iter().map(|snap| snap.cycle).collect();
+        assert_eq!(cycles, [3 * INTERVAL, 2 * INTERVAL, INTERVAL]);
+        // Rewound snapshots free their size
+        hist.truncate(INTERVAL);
+        hist.push(2 * INTERVAL, vec![0; 0x200]);
+        assert_eq!(hist.iter().count(), 2);
+        // Oversized snapshots are kept alone
+        hist.push(3 * INTERVAL, vec![0; 0x400]);
+        assert_eq!(hist.iter().count(), 1);
+    }
+}
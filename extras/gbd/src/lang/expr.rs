@@ -0,0 +1,255 @@
+//! Debugger expressions.
+
+use std::fmt::Display;
+
+use rugby_arch::reg::Port;
+use rugby_core::dmg::GameBoy;
+
+use super::Select;
+
+/// Debugger expression.
+///
+/// Evaluated as a signed 64-bit integer against the state of the console.
+/// Boolean results are represented as `1` (true) or `0` (false), with any
+/// non-zero value considered true.
+#[derive(Clone, Debug)]
+pub enum Expr {
+    /// Integer literal.
+    Int(i64),
+    /// Register value.
+    Reg(Select),
+    /// Memory dereference.
+    Mem(Box<Expr>),
+    /// Unary operation.
+    Unary(Unary, Box<Expr>),
+    /// Binary operation.
+    Binary(Binary, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Evaluates the expression.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the expression divides by zero, or dereferences an address
+    /// outside the address space.
+    pub fn eval(&self, emu: &GameBoy) -> Result<i64> {
+        Ok(match self {
+            Expr::Int(int) => *int,
+            Expr::Reg(reg) => self::load(emu, reg).into(),
+            Expr::Mem(addr) => {
+                let addr = addr.eval(emu)?;
+                let addr = u16::try_from(addr).map_err(|_| Error::Address(addr))?;
+                // Avoid observing the dereference as a processor access
+                let bus = emu.inner().soc.cpu.blk.bus.peek();
+                bus.read(addr).unwrap_or(0xff).into()
+            }
+            Expr::Unary(op, rhs) => op.eval(rhs.eval(emu)?),
+            Expr::Binary(op, lhs, rhs) => {
+                // Short-circuit logical operators
+                let lhs = lhs.eval(emu)?;
+                match op {
+                    Binary::LogAnd if lhs == 0 => return Ok(0),
+                    Binary::LogOr if lhs != 0 => return Ok(1),
+                    _ => (),
+                }
+                op.eval(lhs, rhs.eval(emu)?)?
+            }
+        })
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Int(int) if *int < 0 => write!(f, "{int}"),
+            Expr::Int(int) => write!(f, "${int:02x}"),
+            Expr::Reg(reg) => write!(f, "{reg}"),
+            Expr::Mem(addr) => write!(f, "[{addr}]"),
+            Expr::Unary(op, rhs) => match **rhs {
+                Expr::Binary(..) => write!(f, "{op}({rhs})"),
+                _ => write!(f, "{op}{rhs}"),
+            },
+            Expr::Binary(op, lhs, rhs) => {
+                // Parenthesize nested operations
+                for (idx, arg) in [lhs, rhs].into_iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, " {op} ")?;
+                    }
+                    match **arg {
+                        Expr::Binary(..) => write!(f, "({arg})")?,
+                        _ => write!(f, "{arg}")?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Unary operator.
+#[derive(Copy, Clone, Debug)]
+pub enum Unary {
+    /// Arithmetic negation.
+    Neg,
+    /// Logical negation.
+    Not,
+    /// Bitwise complement.
+    Cpl,
+}
+
+impl Unary {
+    /// Applies the operator.
+    fn eval(self, rhs: i64) -> i64 {
+        match self {
+            Unary::Neg => rhs.wrapping_neg(),
+            Unary::Not => (rhs == 0).into(),
+            Unary::Cpl => !rhs,
+        }
+    }
+}
+
+#[rustfmt::skip]
+impl Display for Unary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Neg => "-",
+                Self::Not => "!",
+                Self::Cpl => "~",
+            }
+        )
+    }
+}
+
+/// Binary operator.
+#[derive(Copy, Clone, Debug)]
+pub enum Binary {
+    /// Logical or.
+    LogOr,
+    /// Logical and.
+    LogAnd,
+    /// Equal to.
+    Eq,
+    /// Not equal to.
+    Ne,
+    /// Less than.
+    Lt,
+    /// Less than or equal to.
+    Le,
+    /// Greater than.
+    Gt,
+    /// Greater than or equal to.
+    Ge,
+    /// Bitwise or.
+    BitOr,
+    /// Bitwise exclusive or.
+    BitXor,
+    /// Bitwise and.
+    BitAnd,
+    /// Left shift.
+    Shl,
+    /// Right shift.
+    Shr,
+    /// Addition.
+    Add,
+    /// Subtraction.
+    Sub,
+    /// Multiplication.
+    Mul,
+    /// Division.
+    Div,
+    /// Remainder.
+    Rem,
+}
+
+impl Binary {
+    /// Applies the operator.
+    #[rustfmt::skip]
+    fn eval(self, lhs: i64, rhs: i64) -> Result<i64> {
+        // Shifts beyond the width of the value are saturated
+        let shift = u32::try_from(rhs).unwrap_or(u32::MAX).min(i64::BITS - 1);
+        Ok(match self {
+            Binary::LogOr  => (lhs != 0 || rhs != 0).into(),
+            Binary::LogAnd => (lhs != 0 && rhs != 0).into(),
+            Binary::Eq     => (lhs == rhs).into(),
+            Binary::Ne     => (lhs != rhs).into(),
+            Binary::Lt     => (lhs < rhs).into(),
+            Binary::Le     => (lhs <= rhs).into(),
+            Binary::Gt     => (lhs > rhs).into(),
+            Binary::Ge     => (lhs >= rhs).into(),
+            Binary::BitOr  => lhs | rhs,
+            Binary::BitXor => lhs ^ rhs,
+            Binary::BitAnd => lhs & rhs,
+            Binary::Shl    => lhs << shift,
+            Binary::Shr    => lhs >> shift,
+            Binary::Add    => lhs.wrapping_add(rhs),
+            Binary::Sub    => lhs.wrapping_sub(rhs),
+            Binary::Mul    => lhs.wrapping_mul(rhs),
+            Binary::Div    => lhs.checked_div(rhs).ok_or(Error::DivZero)?,
+            Binary::Rem    => lhs.checked_rem(rhs).ok_or(Error::DivZero)?,
+        })
+    }
+}
+
+#[rustfmt::skip]
+impl Display for Binary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::LogOr  => "||",
+                Self::LogAnd => "&&",
+                Self::Eq     => "==",
+                Self::Ne     => "!=",
+                Self::Lt     => "<",
+                Self::Le     => "<=",
+                Self::Gt     => ">",
+                Self::Ge     => ">=",
+                Self::BitOr  => "|",
+                Self::BitXor => "^",
+                Self::BitAnd => "&",
+                Self::Shl    => "<<",
+                Self::Shr    => ">>",
+                Self::Add    => "+",
+                Self::Sub    => "-",
+                Self::Mul    => "*",
+                Self::Div    => "/",
+                Self::Rem    => "%",
+            }
+        )
+    }
+}
+
+/// Loads the value of a register.
+fn load(emu: &GameBoy, reg: &Select) -> u16 {
+    let soc = &emu.inner().soc;
+    match reg {
+        Select::Apu(reg) => Port::<u8>::load(&soc.apu, *reg).into(),
+        Select::Byte(reg) => Port::<u8>::load(&soc.cpu, *reg).into(),
+        Select::Word(reg) => Port::<u16>::load(&soc.cpu, *reg),
+        Select::Irq(reg) => Port::<u8>::load(&soc.irq, *reg).into(),
+        Select::Ppu(reg) => Port::<u8>::load(&soc.ppu, *reg).into(),
+        Select::Serial(reg) => Port::<u8>::load(&soc.sio, *reg).into(),
+        Select::Timer(reg) => Port::<u8>::load(&soc.tma, *reg).into(),
+    }
+}
+
+/// A convenient type alias for [`Result`](std::result::Result).
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// An error caused by evaluating an expression.
+#[derive(Debug)]
+#[derive(thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Address is outside the address space.
+    #[error("address out of range: {0}")]
+    Address(i64),
+    /// Division by zero.
+    #[error("division by zero")]
+    DivZero,
+}
@@ -1,14 +1,18 @@
 use std::collections::VecDeque;
+use std::fmt::Display;
 use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
 
 use orng::Orange;
+use rugby_core::api::cheat::Code;
 use rugby_core::chip::{apu, cpu, irq, ppu, sio, tma};
 
 use super::Tick;
 
+pub mod expr;
 mod parse;
 
+pub use self::expr::Expr;
 pub use self::parse::Error;
 
 #[derive(Clone, Debug)]
@@ -49,22 +53,36 @@ impl IntoIterator for Program {
 /// Debugger commands.
 #[derive(Clone, Debug)]
 pub enum Command {
+    /// Define or list command [aliases][`Keyword::Alias`].
+    Alias(Option<(String, String)>),
+    /// [Assert][`Keyword::Assert`] that an expression holds.
+    Assert(Expr),
+    /// Print a [backtrace][`Keyword::Backtrace`] of the call stack.
+    Backtrace,
     /// Set a [breakpoint][`Keyword::Break`].
-    Break(u16),
+    Break(Address, Option<Expr>),
     /// [Capture][`Keyword::Capture`] a screenshot.
     Capture(PathBuf, bool),
+    /// Manage [cheat][`Keyword::Cheat`] codes.
+    Cheat(Cheat),
     /// [Continue][`Keyword::Continue`] execution.
     Continue,
     /// [Delete][`Keyword::Delete`] a breakpoint.
     Delete(usize),
     /// [Disable][`Keyword::Disable`] a breakpoint.
     Disable(usize),
+    /// [Disassemble][`Keyword::Disasm`] instructions at an address.
+    Disasm(Option<Address>),
+    /// [Disassemble][`Keyword::Disasm`] instructions in an address range.
+    DisasmRange(Orange<u16>),
     /// [Enable][`Keyword::Enable`] a breakpoint.
     Enable(usize),
+    /// [Finish][`Keyword::Finish`] the current call frame.
+    Finish,
     /// Change the step [unit][`Keyword::Freq`].
     Freq(Option<Tick>),
     /// [Goto][`Keyword::Goto`] an address.
-    Goto(u16),
+    Goto(Address),
     /// Print [help][`Keyword::Help`].
     Help(Option<Keyword>),
     /// [Ignore][`Keyword::Ignore`] a breakpoint.
@@ -72,31 +90,45 @@ pub enum Command {
     /// Print [info][`Keyword::Info`] debugger info.
     Info(Option<Keyword>),
     /// [Jump][`Keyword::Jump`] and [continue][`Keyword::Continue`].
-    Jump(u16),
-    /// [List][`Keyword::List`] the current instruction.
+    Jump(Address),
+    /// [List][`Keyword::List`] instructions around the current instruction.
     List,
     /// [Load][`Keyword::Load`] from a register.
     Load(Vec<Select>),
     /// Change the [log][`Keyword::Log`] level.
     Log(Option<String>),
+    /// Execute a single step, stepping over calls with [next][`Keyword::Next`].
+    Next(Option<usize>),
+    /// [Print][`Keyword::Print`] an expression.
+    Print(Expr),
     /// [Quit][`Keyword::Quit`] the program.
     Quit,
     /// [Read][`Keyword::Read`] from an address.
-    Read(u16),
+    Read(Address),
     /// [Read][`Keyword::Read`] from an address range.
     ReadRange(Orange<u16>),
+    /// Start or stop [recording][`Keyword::Record`] audio.
+    Record(Option<PathBuf>, bool),
     /// [Reset][`Keyword::Reset`] the console.
     Reset,
+    /// [Continue][`Keyword::RevContinue`] execution backwards.
+    RevContinue,
+    /// Rewind a single [step][`Keyword::RevStep`].
+    RevStep(Option<usize>),
     /// Perform [serial][`Keyword::Serial`] I/O.
     Serial(Serial),
+    /// [Source][`Keyword::Source`] commands from a file.
+    Source(PathBuf),
     /// Execute a single [step][`Keyword::Step`].
     Step(Option<usize>),
     /// [Store][`Keyword::Store`] to a register.
-    Store(Vec<Select>, Value),
+    Store(Vec<Select>, Expr),
+    /// Set a [watchpoint][`Keyword::Watch`].
+    Watch(Orange<u16>, Watch),
     /// [Write][`Keyword::Write`] to an address.
-    Write(u16, u8),
+    Write(u16, Expr),
     /// [Write][`Keyword::Write`] to an address range.
-    WriteRange(Orange<u16>, u8),
+    WriteRange(Orange<u16>, Expr),
 }
 
 /// Debugger keywords.
@@ -108,42 +140,125 @@ pub enum Keyword {
      * Game Boy Debugger.
      *
      * COMMANDS:
+     * * `alias`                 : Define a command alias.
+     * * `assert`                : Assert that an expression holds.
+     * * `awatch`,    `aw`       : Set an access watchpoint.
+     * * `backtrace`, `bt`       : Print the call stack.
      * * `break`,     `br`,   `b`: Set a breakpoint.
      * * `capture`,   `ps`       : Capture a screenshot.
+     * * `cheat`,     `ch`       : Manage cheat codes.
      * * `continue`,  `cont`, `c`: Continue execution.
      * * `delete`,    `del`      : Delete a breakpoint.
      * * `disable`,   `dis`,  `d`: Disable a breakpoint.
+     * * `disassemble`,      `da`: Disassemble instructions.
      * * `enable`,    `en`,   `e`: Enable a breakpoint.
+     * * `finish`,    `fin`      : Step out of the current call.
      * * `frequency`, `freq`, `f`: Change the step unit.
      * * `goto`,      `go`,   `g`: Goto an address.
      * * `help`,              `h`: Print help.
      * * `ignore`,    `ig`       : Ignore a breakpoint.
      * * `info`,              `i`: Print debugger info.
      * * `jump`,      `jp`,   `j`: Jump and continue.
-     * * `list`,      `ls`,   `l`: List the current instruction.
+     * * `list`,      `ls`,   `l`: List instructions around the PC.
      * * `load`,      `ld`       : Load from a register.
      * * `log`,       `lo`       : Change the logging level.
+     * * `next`,              `n`: Step over calls.
+     * * `print`,             `p`: Print an expression.
      * * `quit`,              `q`: Quit the program.
      * * `read`,      `rd`,   `r`: Read from an address.
+     * * `record`,    `rec`      : Start or stop recording audio.
      * * `reset`,     `res`      : Reset the console.
+     * * `reverse-continue`, `rc`: Continue execution backwards.
+     * * `reverse-step`,     `rs`: Rewind a single step.
+     * * `rwatch`,    `rw`       : Set a read watchpoint.
      * * `serial`,    `sx`       : Perform serial I/O.
+     * * `source`                : Execute commands from a file.
      * * `step`,              `s`: Execute a single step.
      * * `store`,     `sr`       : Store to a register.
+     * * `watch`,     `wa`       : Set a write watchpoint.
      * * `write`,     `wr`,   `w`: Write to an address.
      *
      * Use `help` for more information about how to use a command.
      */
     All,
     /**
-     * `break <ADDRESS>`
+     * `alias [NAME PROGRAM]`
+     *
+     * Define an alias for a program of commands.
+     *
+     * Once defined, `NAME` may be used in place of a command, expanding to the
+     * program. Any arguments following the alias are appended to it. The
+     * definition extends to the end of the line, such that it may contain
+     * several commands separated by `;`. Redefining an alias replaces it.
+     *
+     * When no arguments are provided, defined aliases are listed.
+     *
+     * For example, `alias ni step; list` defines a command `ni` which steps
+     * then lists the instructions around the PC.
+     */
+    Alias,
+    /**
+     * `assert <EXPR>`
+     *
+     * Assert that an expression holds.
+     *
+     * The assertion fails if the expression evaluates as false (zero), or
+     * cannot be evaluated. Failed assertions are reported, and counted such
+     * that non-interactive sessions may report failure upon exit.
+     *
+     * For example, `assert a == $01` asserts that the A register holds 1.
+     *
+     * See also: `print`
+     */
+    Assert,
+    /**
+     * `awatch <ADDRESS | RANGE>`
+     *
+     * Set a watchpoint on reads from or writes to the specified address or
+     * range.
+     *
+     * Aliases: `aw`
+     *
+     * See also: `watch`
+     */
+    AWatch,
+    /**
+     * `backtrace`
+     *
+     * Print the call stack.
+     *
+     * Lists the current instruction, followed by the site of each active call,
+     * restart, or interrupt dispatch, innermost first.
+     *
+     * Frames are tracked as calls are entered and returned from, so the stack
+     * is only known since the console was last reset or restored (such as
+     * after reverse execution).
+     *
+     * Aliases: `bt`
+     *
+     * See also: `finish`, `next`
+     */
+    Backtrace,
+    /**
+     * `break <ADDRESS> [if <EXPR>]`
      *
      * Set a breakpoint at the specified location.
      *
+     * When a condition is provided, the breakpoint will only trigger when the
+     * expression evaluates as true (non-zero). Setting a condition on an
+     * existing breakpoint replaces its condition.
+     *
+     * The address may instead be given as a label from the loaded symbol file.
+     * Labels within the switchable ROM region (`$4000..=$7FFF`) are bound to
+     * their bank, such that the breakpoint only triggers while it is mapped.
+     *
      * Note that due to the SM83 CPU supporting multi-byte instructions, there
      * is a chance that the specified breakpoint will not occur upon an
      * instruction boundary. When this occurs, the breakpoint will NOT trigger.
      *
      * Aliases: `br`, `b`
+     *
+     * See also: `print`
      */
     Break,
     /**
@@ -160,6 +275,21 @@ pub enum Keyword {
      * Aliases: `ps`
      */
     Capture,
+    /**
+     * `cheat[!] [CODE]`
+     *
+     * List, activate, or clear cheat codes.
+     *
+     * When `CODE` is present, it is activated. Otherwise, the active cheat
+     * codes are listed. To deactivate all cheat codes, pass the `!` argument.
+     *
+     * Supported codes are one of the following:
+     * * Game Genie: Patches a ROM byte, e.g. `00A-17B` or `00A-17B-C49`
+     * * GameShark:  Writes a RAM byte each frame, e.g. `010238CD`
+     *
+     * Aliases: `ch`
+     */
+    Cheat,
     /**
      * `continue`
      *
@@ -193,6 +323,28 @@ pub enum Keyword {
      * Aliases: `dis`, `d`
      */
     Disable,
+    /**
+     * `disassemble [ADDRESS | RANGE]`
+     *
+     * Disassemble and print the instructions at the specified address or
+     * range.
+     *
+     * When provided an address (or nothing, implying the current value of the
+     * PC), a fixed number of instructions are printed. When provided a range,
+     * all instructions beginning within the range are printed.
+     *
+     * Symbols are used both to resolve a label as the address and to annotate
+     * the listing, with labels replacing known addresses.
+     *
+     * Note that instructions are decoded sequentially from the start address,
+     * so the listing will be misaligned if it does not begin on an instruction
+     * boundary.
+     *
+     * Aliases: `disasm`, `da`
+     *
+     * See also: `list`, `read`
+     */
+    Disasm,
     /**
      * `enable <BREAKPOINT>`
      *
@@ -202,6 +354,19 @@ pub enum Keyword {
      * Aliases: `en`, `e`
      */
     Enable,
+    /**
+     * `finish`
+     *
+     * Step out of the current call frame.
+     *
+     * Execution continues until the innermost call, restart, or interrupt
+     * handler returns, stopping at the following step.
+     *
+     * Aliases: `fin`
+     *
+     * See also: `backtrace`, `next`
+     */
+    Finish,
     /**
      * `frequency [TICK]`
      *
@@ -228,6 +393,9 @@ pub enum Keyword {
      *
      * Set the PC to the specified address without continuing execution.
      *
+     * The address may be given as a label, which warns if it lies within a
+     * ROM bank that is not currently mapped.
+     *
      * Note that if the current instruction has already been fetched, it will
      * complete execution at the specified address. This has the consequence of
      * potentially reading incorrect data if the executing instruction performs
@@ -262,7 +430,9 @@ pub enum Keyword {
      *
      * Print info about the debugger's state.
      *
-     * Currently only supports breakpoints, with the `break` keyword.
+     * Currently supports breakpoints, with the `break` keyword, and
+     * watchpoints, with the `watch` keyword. Watchpoints are also listed
+     * alongside breakpoints.
      *
      * Aliases: `i`
      */
@@ -272,6 +442,8 @@ pub enum Keyword {
      *
      * Set the PC to the specified address and continue execution.
      *
+     * As with `goto`, the address may be given as a label.
+     *
      * Note that if the current instruction has already been fetched, it will
      * complete execution at the specified address. This has the consequence of
      * potentially reading incorrect data if the executing instruction performs
@@ -283,14 +455,16 @@ pub enum Keyword {
     /**
      * `list`
      *
-     * Print the instruction currently being executed.
+     * Print the instructions surrounding the current value of the PC.
      *
-     * If no instruction is being executed, decode and print the instruction at
-     * the current value of the PC.
+     * The current instruction is marked with an arrow. Preceding instructions
+     * are found by decoding backwards, which may be inaccurate when data is
+     * interleaved with code. Any labels from the loaded symbol file are printed
+     * above the instructions they mark.
      *
      * Aliases: `ls`, `l`
      *
-     * See also: `goto`
+     * See also: `disassemble`, `goto`
      */
     List,
     /**
@@ -325,6 +499,44 @@ pub enum Keyword {
      * Aliases: `lo`
      */
     Log,
+    /**
+     * `next [COUNT]`
+     *
+     * Perform a (or many) steps of the debugger, stepping over calls.
+     *
+     * Unlike `step`, any time spent within called subroutines or interrupt
+     * handlers is not counted, such that execution stops within the current
+     * call frame (or upon returning from it). Breakpoints within calls will
+     * still trigger.
+     *
+     * Aliases: `n`
+     *
+     * See also: `finish`, `freq`, `step`
+     */
+    Next,
+    /**
+     * `print <EXPR>`
+     *
+     * Evaluate an expression and print its value.
+     *
+     * Expressions are composed of the following (C semantics):
+     * * Integers: e.g. `42`, `$2a`, `0x2a`, `%101010`
+     * * Registers: any valid to `load`, e.g. `a`, `hl`, `ly`
+     * * Memory: the byte at an address, e.g. `[hl]`, `[$c000 + b]`
+     * * Unary operators: `-`, `!`, `~`
+     * * Binary operators: see below
+     *
+     * Binary operators are listed from lowest to highest precedence:
+     * `||`; `&&`; `==`, `!=`, `<`, `<=`, `>`, `>=`; `|`; `^`; `&`; `<<`, `>>`;
+     * `+`, `-`; `*`, `/`, `%`.
+     *
+     * Parentheses may be used to group sub-expressions. Values are evaluated as
+     * signed 64-bit integers, with comparisons producing either 1 (true) or 0
+     * (false).
+     *
+     * Aliases: `p`
+     */
+    Print,
     /**
      * `quit`
      *
@@ -336,7 +548,8 @@ pub enum Keyword {
     /**
      * `read <ADDRESS | RANGE>`
      *
-     * Read the byte(s) at the specified address or range and print.
+     * Read the byte(s) at the specified address or range and print. A single
+     * address may also be given as a label.
      *
      * Ranges can be specified as one of the following (Rust semantics):
      * * `A..B`: Exclusive on the right
@@ -351,6 +564,26 @@ pub enum Keyword {
      * See also: `write`
      */
     Read,
+    /**
+     * `record[+] [PATH]`
+     *
+     * Start or stop recording audio.
+     *
+     * When `PATH` is present, a recording is started, otherwise the active
+     * recording is stopped. Recordings are saved as WAV formatted audio at the
+     * configured sample rate. The filename may be modified to include the
+     * "wav" extension as needed.
+     *
+     * To also record each channel's contribution to the mix separately, pass
+     * the `+` argument. These stems are saved alongside the recording, named
+     * by channel (e.g. "out.ch1.wav").
+     *
+     * Only one recording may be active at a time, including any started from
+     * the command-line.
+     *
+     * Aliases: `rec`
+     */
+    Record,
     /**
      * `reset`
      *
@@ -359,6 +592,46 @@ pub enum Keyword {
      * Aliases: `res`
      */
     Reset,
+    /**
+     * `reverse-continue`
+     *
+     * Continue program execution backwards.
+     *
+     * Execution is rewound to the most recent point at which a breakpoint or
+     * watchpoint would have triggered.
+     *
+     * Rewinding works by restoring a periodic snapshot of the console, then
+     * deterministically re-executing from it. As such, execution can only be
+     * rewound as far back as the recorded history, and any input received in
+     * the meantime is not replayed.
+     *
+     * Aliases: `rc`
+     *
+     * See also: `continue`, `reverse-step`
+     */
+    RevContinue,
+    /**
+     * `reverse-step [COUNT]`
+     *
+     * Rewind a (or many) steps of the debugger at the specified frequency.
+     *
+     * This is subject to the same limitations as `reverse-continue`.
+     *
+     * Aliases: `rs`
+     *
+     * See also: `freq`, `step`
+     */
+    RevStep,
+    /**
+     * `rwatch <ADDRESS | RANGE>`
+     *
+     * Set a watchpoint on reads from the specified address or range.
+     *
+     * Aliases: `rw`
+     *
+     * See also: `watch`
+     */
+    RWatch,
     /**
      * `serial[!] [DATA]`
      *
@@ -375,6 +648,16 @@ pub enum Keyword {
      * Aliases: `sx`
      */
     Serial,
+    /**
+     * `source <PATH>`
+     *
+     * Execute commands from a file.
+     *
+     * Each line of the file is executed as though it were entered at the
+     * prompt, before returning to the prompt once the file is exhausted. Blank
+     * lines and lines starting with `#` are skipped.
+     */
+    Source,
     /**
      * `step [COUNT]`
      *
@@ -386,10 +669,13 @@ pub enum Keyword {
      */
     Step,
     /**
-     * `store <REGISTER...> <VALUE>`
+     * `store <REGISTER...> <EXPR>`
      *
      * Store a value to the specified register(s) and print.
      *
+     * The value is evaluated as an expression before it is stored, and must
+     * fit within the register either as a signed or unsigned integer.
+     *
      * If specified using the special `sb` or `sw` alias, the specified resister
      * must be either byte or word size respectively.
      *
@@ -408,12 +694,34 @@ pub enum Keyword {
      */
     Store,
     /**
-     * `write <ADDRESS | RANGE> <BYTE>`
+     * `watch <ADDRESS | RANGE>`
+     *
+     * Set a watchpoint on writes to the specified address or range.
+     *
+     * When triggered, execution is paused after the offending instruction's
+     * access, reporting the address, its old and new value, and the PC of the
+     * instruction. Only accesses made by the CPU are watched, decoded by their
+     * address as seen by the CPU (e.g. writes to echo RAM will not trigger a
+     * watchpoint on the mirrored work RAM).
+     *
+     * Ranges are specified the same as with `read`.
+     *
+     * Watchpoints share indices with breakpoints, and are managed using the
+     * same commands (e.g. `delete`, `disable`, `ignore`).
+     *
+     * Aliases: `wa`
+     *
+     * See also: `awatch`, `rwatch`
+     */
+    Watch,
+    /**
+     * `write <ADDRESS | RANGE> <EXPR>`
      *
      * Write a byte to the specified address or range.
      *
-     * When writing to a range, the byte is repeated for each address within the
-     * range.
+     * The byte is evaluated as an expression before it is written, and must fit
+     * within a byte either as a signed or unsigned integer. When writing to a
+     * range, the byte is repeated for each address within the range.
      *
      * Ranges can be specified as one of the following (Rust semantics):
      * * `A..B`: Exclusive on the right
@@ -430,6 +738,24 @@ pub enum Keyword {
     Write,
 }
 
+/// Address operand.
+#[derive(Clone, Debug)]
+pub enum Address {
+    /// Numeric address.
+    Int(u16),
+    /// Symbol label.
+    Label(String),
+}
+
+impl Display for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Address::Int(addr) => write!(f, "${addr:04x}"),
+            Address::Label(label) => write!(f, "{label}"),
+        }
+    }
+}
+
 /// Register select.
 #[derive(Clone, Debug)]
 pub enum Select {
@@ -442,15 +768,59 @@ pub enum Select {
     Timer(tma::Select),
 }
 
+impl Display for Select {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Select::Apu(reg) => format!("{reg:?}"),
+            Select::Byte(reg) => format!("{reg:?}"),
+            Select::Word(reg) => format!("{reg:?}"),
+            Select::Irq(reg) => format!("{reg:?}"),
+            Select::Ppu(reg) => format!("{reg:?}"),
+            Select::Serial(reg) => format!("{reg:?}"),
+            Select::Timer(reg) => format!("{reg:?}"),
+        };
+        write!(f, "{}", name.to_lowercase())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Value {
     Byte(u8),
     Word(u16),
 }
 
+#[derive(Clone, Debug)]
+pub enum Cheat {
+    List,
+    Clear,
+    Add(Code),
+}
+
 #[derive(Clone, Debug)]
 pub enum Serial {
     Peek,
     Recv,
     Send(Vec<u8>),
 }
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Watch {
+    Read,
+    Write,
+    Access,
+}
+
+#[rustfmt::skip]
+impl Display for Watch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Read   => "read",
+                Self::Write  => "write",
+                Self::Access => "access",
+            }
+        )
+    }
+}
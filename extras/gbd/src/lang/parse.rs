@@ -4,8 +4,9 @@ use std::num::ParseIntError;
 use std::str::FromStr;
 
 use pest::Parser;
+use rugby_core::api::cheat;
 
-use super::{Command, Keyword, Program, Select, Serial, Tick, Value};
+use super::{Address, Cheat, Command, Expr, Keyword, Program, Select, Serial, Tick, Watch};
 
 mod imp;
 
@@ -47,30 +48,45 @@ impl Display for Rule {
 
         match self {
             // Keywords
-            KBreak    => write!(f, "{Break}"),
-            KCapture  => write!(f, "{Capture}"),
-            KContinue => write!(f, "{Continue}"),
-            KDelete   => write!(f, "{Delete}"),
-            KDisable  => write!(f, "{Disable}"),
-            KEnable   => write!(f, "{Enable}"),
-            KFreq     => write!(f, "{Freq}"),
-            KGoto     => write!(f, "{Goto}"),
-            KHelp     => write!(f, "{Help}"),
-            KIgnore   => write!(f, "{Ignore}"),
-            KInfo     => write!(f, "{Info}"),
-            KJump     => write!(f, "{Jump}"),
-            KList     => write!(f, "{List}"),
-            KLoad     => write!(f, "{Load}"),
-            KLog      => write!(f, "{Log}"),
-            KQuit     => write!(f, "{Quit}"),
-            KRead     => write!(f, "{Read}"),
-            KReset    => write!(f, "{Reset}"),
-            KSerial   => write!(f, "{Serial}"),
-            KStep     => write!(f, "{Step}"),
-            KStore    => write!(f, "{Store}"),
-            KWrite    => write!(f, "{Write}"),
+            KAlias     => write!(f, "{Alias}"),
+            KAssert    => write!(f, "{Assert}"),
+            KAWatch    => write!(f, "{AWatch}"),
+            KBacktrace => write!(f, "{Backtrace}"),
+            KBreak     => write!(f, "{Break}"),
+            KCapture   => write!(f, "{Capture}"),
+            KCheat     => write!(f, "{Cheat}"),
+            KContinue  => write!(f, "{Continue}"),
+            KDelete    => write!(f, "{Delete}"),
+            KDisable   => write!(f, "{Disable}"),
+            KDisasm    => write!(f, "{Disasm}"),
+            KEnable    => write!(f, "{Enable}"),
+            KFinish    => write!(f, "{Finish}"),
+            KFreq      => write!(f, "{Freq}"),
+            KGoto      => write!(f, "{Goto}"),
+            KHelp      => write!(f, "{Help}"),
+            KIgnore    => write!(f, "{Ignore}"),
+            KInfo      => write!(f, "{Info}"),
+            KJump      => write!(f, "{Jump}"),
+            KList      => write!(f, "{List}"),
+            KLoad      => write!(f, "{Load}"),
+            KLog       => write!(f, "{Log}"),
+            KNext      => write!(f, "{Next}"),
+            KPrint     => write!(f, "{Print}"),
+            KQuit      => write!(f, "{Quit}"),
+            KRead      => write!(f, "{Read}"),
+            KRecord    => write!(f, "{Record}"),
+            KReset     => write!(f, "{Reset}"),
+            KRevCont   => write!(f, "{RevCont}"),
+            KRevStep   => write!(f, "{RevStep}"),
+            KRWatch    => write!(f, "{RWatch}"),
+            KSerial    => write!(f, "{Serial}"),
+            KSource    => write!(f, "{Source}"),
+            KStep      => write!(f, "{Step}"),
+            KStore     => write!(f, "{Store}"),
+            KWatch     => write!(f, "{Watch}"),
+            KWrite     => write!(f, "{Write}"),
             // Locations
-            SerialX   => write!(f, "{Serial}"),
+            SerialX    => write!(f, "{Serial}"),
             _ => write!(f, "{self:?}"),
         }
     }
@@ -91,6 +107,9 @@ pub enum Error {
     /// An internal error is considered a bug.
     #[error("an internal error occurred")]
     Internal(#[from] imp::Error),
+    /// Cheat code parsing error.
+    #[error(transparent)]
+    ParseCheat(#[from] cheat::Error),
     /// Integer parsing error.
     #[error(transparent)]
     ParseInt(#[from] ParseIntError),
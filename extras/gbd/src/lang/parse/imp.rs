@@ -2,17 +2,46 @@ use std::num::ParseIntError;
 use std::ops::RangeInclusive;
 use std::panic;
 use std::path::PathBuf;
+use std::sync::LazyLock;
 
 use log::trace;
 use num::traits::{WrappingAdd, WrappingSub};
 use num::{Bounded, Integer};
 use orng::Orange;
 use pest::iterators::Pair;
+use pest::pratt_parser::{Assoc, Op, PrattParser};
 use rugby_core::chip::{apu, cpu, irq, ppu, sio, tma};
 
-use super::{Command, Keyword, Result, Rule, Select, Serial, Tick, Value};
+use super::{Address, Cheat, Command, Expr, Keyword, Result, Rule, Select, Serial, Tick, Watch};
+use crate::lang::expr::{Binary, Unary};
+
+/// Expression operator precedence.
+#[rustfmt::skip]
+static PRATT: LazyLock<PrattParser<Rule>> = LazyLock::new(|| {
+    PrattParser::new()
+        .op(Op::infix(Rule::LogOr, Assoc::Left))
+        .op(Op::infix(Rule::LogAnd, Assoc::Left))
+        .op(Op::infix(Rule::Eq, Assoc::Left)
+          | Op::infix(Rule::Ne, Assoc::Left)
+          | Op::infix(Rule::Lt, Assoc::Left)
+          | Op::infix(Rule::Le, Assoc::Left)
+          | Op::infix(Rule::Gt, Assoc::Left)
+          | Op::infix(Rule::Ge, Assoc::Left))
+        .op(Op::infix(Rule::BitOr, Assoc::Left))
+        .op(Op::infix(Rule::BitXor, Assoc::Left))
+        .op(Op::infix(Rule::BitAnd, Assoc::Left))
+        .op(Op::infix(Rule::Shl, Assoc::Left)
+          | Op::infix(Rule::Shr, Assoc::Left))
+        .op(Op::infix(Rule::Add, Assoc::Left)
+          | Op::infix(Rule::Sub, Assoc::Left))
+        .op(Op::infix(Rule::Mul, Assoc::Left)
+          | Op::infix(Rule::Quo, Assoc::Left)
+          | Op::infix(Rule::Rem, Assoc::Left))
+        .op(Op::prefix(Rule::Neg)
+          | Op::prefix(Rule::Not)
+          | Op::prefix(Rule::Cpl))
+});
 
-#[expect(clippy::cast_sign_loss)]
 #[expect(clippy::too_many_lines)]
 pub fn command(input: Pair<Rule>) -> Result<Command> {
     // Extract keyword and args
@@ -27,9 +56,49 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
 
     // Parse individual command
     let cmd = match rule {
+        Rule::Alias => {
+            let def = match args.next() {
+                Some(name) => {
+                    let body = args.next().exception()?;
+                    Some((name.as_str().to_string(), body.as_str().trim().to_string()))
+                }
+                None => None,
+            };
+            Command::Alias(def)
+        }
+        Rule::Assert => {
+            let expr = args.next().exception().and_then(self::expr)?;
+            Command::Assert(expr)
+        }
+        Rule::AWatch | Rule::RWatch | Rule::Watch => {
+            let mode = match rule {
+                Rule::AWatch => Watch::Access,
+                Rule::RWatch => Watch::Read,
+                Rule::Watch => Watch::Write,
+                rule => return rule.exception(),
+            };
+            let what = args.next().exception()?;
+            // Match on address (range)
+            let range = match what.as_rule() {
+                Rule::UInt => {
+                    let addr = self::integer(what)?;
+                    Orange::from(addr..=addr)
+                }
+                Rule::RangeBounds => {
+                    let mut pairs = what.into_inner();
+                    // Match on range bounds
+                    let pair = pairs.next().exception()?;
+                    self::range(pair)?
+                }
+                rule => return rule.exception(),
+            };
+            Command::Watch(range, mode)
+        }
+        Rule::Backtrace => Command::Backtrace,
         Rule::Break => {
-            let addr = args.next().exception().and_then(self::integer)?;
-            Command::Break(addr)
+            let addr = args.next().exception().and_then(self::address)?;
+            let cond = args.next().map(self::expr).transpose()?;
+            Command::Break(addr, cond)
         }
         Rule::Capture => {
             let force = args
@@ -47,6 +116,16 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
                 .exception()?;
             Command::Capture(path, force)
         }
+        Rule::Cheat => {
+            let pair = args.next().exception()?;
+            let mode = match pair.as_rule() {
+                Rule::Codes => Cheat::List,
+                Rule::Clear => Cheat::Clear,
+                Rule::Code => Cheat::Add(pair.as_str().parse()?),
+                rule => return rule.exception(),
+            };
+            Command::Cheat(mode)
+        }
         Rule::Continue => Command::Continue,
         Rule::Delete => {
             let index = args.next().exception().and_then(self::integer)?;
@@ -56,10 +135,29 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
             let index = args.next().exception().and_then(self::integer)?;
             Command::Disable(index)
         }
+        Rule::Disasm => match args.next() {
+            None => Command::Disasm(None),
+            // Match on address (range)
+            Some(what) => match what.as_rule() {
+                Rule::UInt | Rule::Label => {
+                    let addr = self::address(what)?;
+                    Command::Disasm(Some(addr))
+                }
+                Rule::RangeBounds => {
+                    let mut pairs = what.into_inner();
+                    // Match on range bounds
+                    let pair = pairs.next().exception()?;
+                    let range = self::range(pair)?;
+                    Command::DisasmRange(range)
+                }
+                rule => return rule.exception(),
+            },
+        },
         Rule::Enable => {
             let index = args.next().exception().and_then(self::integer)?;
             Command::Enable(index)
         }
+        Rule::Finish => Command::Finish,
         Rule::Freq => {
             #[rustfmt::skip]
             let mode = args
@@ -78,7 +176,7 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
             Command::Freq(mode)
         }
         Rule::Goto => {
-            let addr = args.next().exception().and_then(self::integer)?;
+            let addr = args.next().exception().and_then(self::address)?;
             Command::Goto(addr)
         }
         Rule::Help => {
@@ -95,7 +193,7 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
             Command::Info(what)
         }
         Rule::Jump => {
-            let addr = args.next().exception().and_then(self::integer)?;
+            let addr = args.next().exception().and_then(self::address)?;
             Command::Jump(addr)
         }
         Rule::List => Command::List,
@@ -107,13 +205,21 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
             let filter = args.next().map(|pair| pair.as_span().as_str().to_string());
             Command::Log(filter)
         }
+        Rule::Next => {
+            let many = args.next().map(self::integer).transpose()?;
+            Command::Next(many)
+        }
+        Rule::Print => {
+            let expr = args.next().exception().and_then(self::expr)?;
+            Command::Print(expr)
+        }
         Rule::Quit => Command::Quit,
         Rule::Read => {
             let what = args.next().exception()?;
             // Match on address (range)
             match what.as_rule() {
-                Rule::UInt => {
-                    let addr = self::integer(what)?;
+                Rule::UInt | Rule::Label => {
+                    let addr = self::address(what)?;
                     Command::Read(addr)
                 }
                 Rule::RangeBounds => {
@@ -126,7 +232,27 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
                 rule => return rule.exception(),
             }
         }
+        Rule::Record => {
+            let stems = args
+                .peek()
+                .filter(|pair| pair.as_rule() == Rule::Stems)
+                .inspect(|_| {
+                    args.next(); // consume only if found
+                })
+                .is_some();
+            let path = args
+                .next()
+                .map(|pair| pair.as_span().as_str().trim())
+                .filter(|path| !path.is_empty())
+                .map(|path| PathBuf::from(path).with_extension("wav"));
+            Command::Record(path, stems)
+        }
         Rule::Reset => Command::Reset,
+        Rule::RevCont => Command::RevContinue,
+        Rule::RevStep => {
+            let many = args.next().map(self::integer).transpose()?;
+            Command::RevStep(many)
+        }
         Rule::Serial => {
             let pair = args.next().exception()?;
             let mode = match pair.as_rule() {
@@ -161,42 +287,30 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
             };
             Command::Serial(mode)
         }
+        Rule::Source => {
+            let path = args
+                .next()
+                .map(|pair| PathBuf::from(pair.as_span().as_str().trim()))
+                .exception()?;
+            Command::Source(path)
+        }
         Rule::Step => {
             let many = args.next().map(self::integer).transpose()?;
             Command::Step(many)
         }
         Rule::Store => {
             let mut args = args.rev(); // must extract the value first
-            let value = args.next().exception()?;
+            let value = args.next().exception().and_then(self::expr)?;
             let locs = args
                 .rev() // undo previous reverse to perform stores in order
                 .map(self::location)
                 .collect::<Result<Vec<_>>>()?;
-            let value = match locs.first().exception()? {
-                Select::Apu(_)
-                    | Select::Byte(_)
-                    | Select::Irq(_)
-                    | Select::Ppu(_)
-                    | Select::Serial(_)
-                    | Select::Timer(_)
-                    => Value::Byte(
-                    self::integer(value.clone()) // attempt both `u8` and `i8`
-                        .or_else(|_| self::integer::<i8>(value).map(|int| int as u8))?,
-                ),
-                Select::Word(_) => Value::Word(
-                    self::integer(value.clone()) // attempt both `u16` and `i16`
-                        .or_else(|_| self::integer::<i16>(value).map(|int| int as u16))?,
-                ),
-            };
             Command::Store(locs, value)
         }
         Rule::Write => {
             let what = args.next().exception()?;
             // Match on data byte
-            let pair = args.next().exception()?;
-            let byte =
-                self::integer(pair.clone()) // attempt both `u8` and `i8`
-                    .or_else(|_| self::integer::<i8>(pair).map(|int| int as u8))?;
+            let byte = args.next().exception().and_then(self::expr)?;
             // Match on address (range)
             match what.as_rule() {
                 Rule::UInt => {
@@ -219,6 +333,62 @@ pub fn command(input: Pair<Rule>) -> Result<Command> {
     Ok(cmd)
 }
 
+pub fn expr(pair: Pair<Rule>) -> Result<Expr> {
+    PRATT
+        .map_primary(|pair| match pair.as_rule() {
+            Rule::UInt => self::integer(pair).map(Expr::Int),
+            Rule::Deref => {
+                let addr = pair.into_inner().next().exception().and_then(self::expr)?;
+                Ok(Expr::Mem(Box::new(addr)))
+            }
+            Rule::Expr => self::expr(pair),
+            _ => self::location(pair).map(Expr::Reg),
+        })
+        .map_prefix(|op, rhs| {
+            let op = match op.as_rule() {
+                Rule::Neg => Unary::Neg,
+                Rule::Not => Unary::Not,
+                Rule::Cpl => Unary::Cpl,
+                rule => return rule.exception(),
+            };
+            Ok(Expr::Unary(op, Box::new(rhs?)))
+        })
+        .map_infix(|lhs, op, rhs| {
+            #[rustfmt::skip]
+            let op = match op.as_rule() {
+                Rule::LogOr  => Binary::LogOr,
+                Rule::LogAnd => Binary::LogAnd,
+                Rule::Eq     => Binary::Eq,
+                Rule::Ne     => Binary::Ne,
+                Rule::Lt     => Binary::Lt,
+                Rule::Le     => Binary::Le,
+                Rule::Gt     => Binary::Gt,
+                Rule::Ge     => Binary::Ge,
+                Rule::BitOr  => Binary::BitOr,
+                Rule::BitXor => Binary::BitXor,
+                Rule::BitAnd => Binary::BitAnd,
+                Rule::Shl    => Binary::Shl,
+                Rule::Shr    => Binary::Shr,
+                Rule::Add    => Binary::Add,
+                Rule::Sub    => Binary::Sub,
+                Rule::Mul    => Binary::Mul,
+                Rule::Quo    => Binary::Div,
+                Rule::Rem    => Binary::Rem,
+                rule => return rule.exception(),
+            };
+            Ok(Expr::Binary(op, Box::new(lhs?), Box::new(rhs?)))
+        })
+        .parse(pair.into_inner())
+}
+
+pub fn address(pair: Pair<Rule>) -> Result<Address> {
+    match pair.as_rule() {
+        Rule::UInt => self::integer(pair).map(Address::Int),
+        Rule::Label => Ok(Address::Label(pair.as_str().to_string())),
+        rule => rule.exception(),
+    }
+}
+
 pub fn integer<I>(pair: Pair<Rule>) -> Result<I>
 where
     I: Integer<FromStrRadixErr = ParseIntError>,
@@ -342,28 +512,43 @@ where
 pub fn keyword(pair: Pair<Rule>) -> Result<Keyword> {
     // Extract the keyword rule
     Ok(match pair.as_rule() {
-        Rule::KBreak    => Keyword::Break,
-        Rule::KCapture  => Keyword::Capture,
-        Rule::KContinue => Keyword::Continue,
-        Rule::KDelete   => Keyword::Delete,
-        Rule::KDisable  => Keyword::Disable,
-        Rule::KEnable   => Keyword::Enable,
-        Rule::KFreq     => Keyword::Freq,
-        Rule::KGoto     => Keyword::Goto,
-        Rule::KHelp     => Keyword::Help,
-        Rule::KIgnore   => Keyword::Ignore,
-        Rule::KInfo     => Keyword::Info,
-        Rule::KJump     => Keyword::Jump,
-        Rule::KList     => Keyword::List,
-        Rule::KLoad     => Keyword::Load,
-        Rule::KLog      => Keyword::Log,
-        Rule::KQuit     => Keyword::Quit,
-        Rule::KRead     => Keyword::Read,
-        Rule::KReset    => Keyword::Reset,
-        Rule::KSerial   => Keyword::Serial,
-        Rule::KStep     => Keyword::Step,
-        Rule::KStore    => Keyword::Store,
-        Rule::KWrite    => Keyword::Write,
+        Rule::KAlias     => Keyword::Alias,
+        Rule::KAssert    => Keyword::Assert,
+        Rule::KAWatch    => Keyword::AWatch,
+        Rule::KBacktrace => Keyword::Backtrace,
+        Rule::KBreak     => Keyword::Break,
+        Rule::KCapture   => Keyword::Capture,
+        Rule::KCheat     => Keyword::Cheat,
+        Rule::KContinue  => Keyword::Continue,
+        Rule::KDelete    => Keyword::Delete,
+        Rule::KDisable   => Keyword::Disable,
+        Rule::KDisasm    => Keyword::Disasm,
+        Rule::KEnable    => Keyword::Enable,
+        Rule::KFinish    => Keyword::Finish,
+        Rule::KFreq      => Keyword::Freq,
+        Rule::KGoto      => Keyword::Goto,
+        Rule::KHelp      => Keyword::Help,
+        Rule::KIgnore    => Keyword::Ignore,
+        Rule::KInfo      => Keyword::Info,
+        Rule::KJump      => Keyword::Jump,
+        Rule::KList      => Keyword::List,
+        Rule::KLoad      => Keyword::Load,
+        Rule::KLog       => Keyword::Log,
+        Rule::KNext      => Keyword::Next,
+        Rule::KPrint     => Keyword::Print,
+        Rule::KQuit      => Keyword::Quit,
+        Rule::KRead      => Keyword::Read,
+        Rule::KRecord    => Keyword::Record,
+        Rule::KReset     => Keyword::Reset,
+        Rule::KRevCont   => Keyword::RevContinue,
+        Rule::KRevStep   => Keyword::RevStep,
+        Rule::KRWatch    => Keyword::RWatch,
+        Rule::KSerial    => Keyword::Serial,
+        Rule::KSource    => Keyword::Source,
+        Rule::KStep      => Keyword::Step,
+        Rule::KStore     => Keyword::Store,
+        Rule::KWatch     => Keyword::Watch,
+        Rule::KWrite     => Keyword::Write,
         rule => return rule.exception(),
     })
 }
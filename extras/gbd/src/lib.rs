@@ -17,42 +17,82 @@
 // Allowed lints: clippy
 #![allow(clippy::result_large_err)]
 
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Debug, Display, Write};
 
 use indexmap::IndexMap;
 use log::debug;
 use rugby_arch::Block;
 use rugby_arch::reg::Port;
+use rugby_core::api::audio::rec::Recording;
+use rugby_core::api::audio::{Audio, Capture};
+use rugby_core::api::input::{self, Input as _};
+use rugby_core::api::snapshot::{self, Snapshot};
+use rugby_core::chip::joy::Button;
 use rugby_core::chip::{cpu, ppu};
 use rugby_core::dmg::GameBoy;
+use rugby_core::dmg::dbg::sym::{Symbol, Symbols};
+use rugby_core::dmg::dbg::watch::{Access, Event};
 
-use self::lang::Program;
-use self::prompt::Prompt;
+use self::hist::History;
+use self::lang::{Address, Expr, Program, Watch};
+use self::prompt::{Prompt, Script};
+use self::rsp::Remote;
 
 mod exec;
+mod hist;
 mod lang;
 
 pub mod prompt;
+pub mod rsp;
 
 pub use self::lang::{Command, Keyword};
 
+/// Interval, in cycles, at which the remote debugger is polled.
+const POLL: usize = 0x1000;
+
+/// Maximum nesting depth of aliases and sourced scripts.
+const NEST: usize = 16;
+
+/// Default audio recording sample rate.
+const RATE: u32 = 48_000;
+
+/// Interval, in cycles, at which recorded audio is written.
+///
+/// Approximately 16 milliseconds, well within what the console buffers.
+const INTERVAL: usize = 0x10000;
+
 /// Interactive debugger object.
 #[derive(Debug, Default)]
 pub struct Debugger {
     // Application
     cycle: usize,
     line: Option<Box<dyn Prompt>>,
+    srcs: Vec<Script>,
     log: Option<Box<dyn Filter>>,
+    rsp: Option<Remote>,
+    syms: Symbols,
+    rate: Option<u32>,
     // Console
     pc: u16,
+    insn: u16,
+    bpt: Option<Point>,
+    depth: usize,
     state: State,
     // Internal
     play: bool,
     freq: Tick,
     step: Option<usize>,
+    frame: Option<usize>,
     prog: Option<Program>,
     prev: Option<Program>,
-    bpts: IndexMap<u16, Option<Breakpoint>>,
+    alias: BTreeMap<String, String>,
+    fail: usize,
+    bpts: IndexMap<Point, Option<Breakpoint>>,
+    hits: Vec<Hit>,
+    hist: History,
+    rec: Option<Recording>,
+    cap: Capture,
 }
 
 impl Debugger {
@@ -76,6 +116,46 @@ impl Debugger {
         self.line = Some(Box::new(line));
     }
 
+    /// Sets the remote debugger.
+    ///
+    /// Used in place of the prompt to serve an external debugger. As the
+    /// remote steps by instruction, this also changes the step unit.
+    pub fn remote(&mut self, rsp: Remote) {
+        self.rsp = Some(rsp);
+        self.freq = Tick::Insn;
+    }
+
+    /// Sets the size of the execution history, in bytes.
+    ///
+    /// Used to enable reverse execution, bounding how far execution may be
+    /// rewound, as snapshots are recorded once per frame. By default, the size
+    /// is zero, and nothing is recorded.
+    pub fn history(&mut self, size: usize) {
+        self.hist = History::new(size);
+    }
+
+    /// Sets the audio sample rate.
+    ///
+    /// Used by audio recordings, which otherwise default to 48 kHz.
+    pub fn sample_rate(&mut self, rate: u32) {
+        self.rate = Some(rate);
+    }
+
+    /// Returns the number of failed assertions.
+    ///
+    /// Used to report the outcome of a non-interactive session.
+    #[must_use]
+    pub fn failures(&self) -> usize {
+        self.fail
+    }
+
+    /// Sets the symbol table.
+    ///
+    /// Used to resolve labels within commands and output.
+    pub fn symbols(&mut self, syms: Symbols) {
+        self.syms = syms;
+    }
+
     /// Enables the debugger.
     pub fn enable(&mut self) {
         self.step = Some(0);
@@ -98,16 +178,104 @@ impl Debugger {
     }
 
     /// Synchronizes the debugger with the console.
-    pub fn sync(&mut self, emu: &GameBoy) {
+    pub fn sync(&mut self, emu: &mut GameBoy) {
+        // Observe call frames while attached
+        emu.inner_mut().soc.cpu.stack_mut().watch(true);
+        // Record execution history
+        if self.hist.due(self.cycle) {
+            self.hist.push(self.cycle, emu.save_state());
+        }
+        self.hist
+            .press(self.cycle, emu.inner().soc.joy.reg.borrow().keys());
+        // Write recorded audio
+        if self.rec.is_some()
+            && self.cycle.is_multiple_of(INTERVAL)
+            && let Err(err) = self.record(emu)
+        {
+            advise::error!("could not record audio: {err}");
+            // Stop the failed recording
+            self.rec = None;
+            emu.set_capture(0);
+        }
+        // Poll the remote debugger for interrupts
+        if let Some(rsp) = self.rsp.as_mut()
+            && self.play
+            && self.cycle.is_multiple_of(POLL)
+        {
+            match rsp.poll() {
+                Ok(true) => self.step = Some(0),
+                Ok(false) => (),
+                Err(err) => advise::error!("could not poll remote debugger: {err}"),
+            }
+        }
+        // Update console state
+        self.update(emu);
+    }
+
+    /// Writes audio captured by the console to the recording, if any.
+    fn record(&mut self, emu: &mut GameBoy) -> std::io::Result<()> {
+        let Some(rec) = self.rec.as_mut() else {
+            return Ok(());
+        };
+        emu.capture(&mut self.cap);
+        let res = rec.write(&self.cap);
+        self.cap.clear();
+        res
+    }
+
+    /// Updates the debugger's view of the console.
+    fn update(&mut self, emu: &GameBoy) {
         let cpu = &emu.inner().soc.cpu;
         let ppu = &emu.inner().soc.ppu;
 
+        // Check watched accesses
+        //
+        // Must occur before the in-flight instruction is updated, as accesses
+        // belong to the instruction which was executing during the last cycle.
+        let events = emu.inner().soc.watch.borrow_mut().take();
+        for event in events {
+            self.watch(event);
+        }
         // Update program counter
         self.pc = cpu.load(cpu::Select16::PC);
+        if !cpu.busy() {
+            // Opcode has already been fetched
+            self.insn = self.pc.wrapping_sub(1);
+        }
+        // Update call stack depth
+        self.depth = cpu.stack().depth();
         self.state = State {
             cpu: !cpu.busy(),
             ppu: (ppu.dot(), ppu.load(ppu::Select::Ly)),
         };
+        // Find an enabled breakpoint at this instruction whose condition holds
+        //
+        // Banked breakpoints require their bank to be mapped, and are
+        // preferred to those in any bank.
+        if self.edge() {
+            let bank = u16::try_from(self::bank(emu))
+                .ok()
+                .filter(|_| Symbol::banked(self.pc));
+            let points = [Point::Break(bank, self.pc), Point::Break(None, self.pc)];
+            self.bpt = points.into_iter().find(|at| {
+                self.bpts
+                    .get(at)
+                    .and_then(Option::as_ref)
+                    .filter(|bpt| !bpt.disable)
+                    .is_some_and(|bpt| {
+                        bpt.cond.as_ref().is_none_or(|cond| {
+                            cond.eval(emu).map_or_else(
+                                |err| {
+                                    // Break on failed evaluation
+                                    advise::error!("could not evaluate condition: {err}");
+                                    true
+                                },
+                                |value| value != 0,
+                            )
+                        })
+                    })
+            });
+        }
     }
 
     /// Informs the user of the current emulation context.
@@ -116,6 +284,10 @@ impl Debugger {
     ///
     /// Cannot panic.
     pub fn inform(&self, emu: &GameBoy) {
+        // Report triggered watchpoints
+        for hit in &self.hits {
+            advise::info!("{hit}");
+        }
         // Give context if recently paused
         if self.play {
             exec::list(self, emu).unwrap();
@@ -130,8 +302,22 @@ impl Debugger {
     pub fn run(&mut self, emu: &mut GameBoy) -> Result<()> {
         // Provide information to user before prompting for command
         self.inform(emu);
+        self.hits.clear();
         // Prompt and execute commands until emulation resumed
         self.pause();
+        self.frame = None;
+        if let Some(mut rsp) = self.rsp.take() {
+            // Serve the remote debugger instead, if attached
+            match rsp.serve(self, emu) {
+                Ok(true) => self.rsp = Some(rsp),
+                Ok(false) => (),
+                err @ Err(Error::Quit) => return err.map(|_| ()),
+                Err(err) => {
+                    advise::error!("remote debugger failed: {err}");
+                    exec::r#continue(self)?;
+                }
+            }
+        }
         'gbd: while self.paused() {
             let res = 'res: {
                 // Attempt to fetch command
@@ -180,6 +366,8 @@ impl Debugger {
                 Err(err) => advise::error!("{err}"),
             }
         }
+        // Arm watchpoints before resuming
+        self.arm(emu);
 
         Ok(())
     }
@@ -190,16 +378,32 @@ impl Debugger {
     ///
     /// Errors if the prompt failed.
     pub fn readline(&mut self) -> Result<()> {
-        // Extract the prompt handle
-        let line = self.line.as_mut().ok_or(Error::CfgPrompt)?;
-
         // Present the prompt; get input
         let fmt = format!("(#{} @ ${:04x})> ", self.cycle, self.pc);
-        let input = match line.prompt(&fmt) {
-            Err(prompt::Error::Quit) => return Err(Error::Quit),
-            res => res?,
+        let input = loop {
+            // Extract the prompt handle, preferring sourced scripts
+            let line = match self.srcs.last_mut() {
+                Some(src) => src as &mut dyn Prompt,
+                None => self.line.as_deref_mut().ok_or(Error::CfgPrompt)?,
+            };
+            match line.prompt(&fmt) {
+                Ok(input) => break input,
+                // Sourced script exhausted; resume with enclosing input
+                Err(prompt::Error::Quit) if !self.srcs.is_empty() => {
+                    self.srcs.pop();
+                }
+                Err(prompt::Error::Quit) => return Err(Error::Quit),
+                Err(err) => {
+                    // Abandon failed script
+                    self.srcs.pop();
+                    return Err(err.into());
+                }
+            }
         };
 
+        // Expand aliases
+        let input = self.expand(&input, 0)?;
+
         // Parse input
         let prog: Program = input.trim().parse()?;
         debug!("parsed program: `{prog:?}`");
@@ -219,6 +423,31 @@ impl Debugger {
         }
     }
 
+    /// Expands command aliases within a line of input.
+    fn expand(&self, input: &str, depth: usize) -> Result<String> {
+        // Alias definitions are kept verbatim
+        let (name, _) = input
+            .trim()
+            .split_once(char::is_whitespace)
+            .unwrap_or((input.trim(), ""));
+        if name.eq_ignore_ascii_case("alias") {
+            return Ok(input.to_string());
+        }
+        // Expand each command in turn
+        self::split(input)
+            .map(|cmd| {
+                let cmd = cmd.trim();
+                let (name, args) = cmd.split_once(char::is_whitespace).unwrap_or((cmd, ""));
+                match self.alias.get(name) {
+                    Some(_) if depth >= NEST => Err(Error::Recursion(name.to_string())),
+                    Some(body) => self.expand(format!("{body} {args}").trim(), depth + 1),
+                    None => Ok(cmd.to_string()),
+                }
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(|cmds| cmds.join("; "))
+    }
+
     /// Fetches the next command of the debugger program.
     pub fn fetch(&mut self) -> Option<Command> {
         self.prog.as_mut()?.pop_front()
@@ -240,33 +469,264 @@ impl Debugger {
 
         // Perform the command
         match cmd {
-            Break(addr)             => exec::r#break(self, addr),
+            Alias(def)              => exec::alias(self, def),
+            Assert(expr)            => exec::assert(self, emu, &expr),
+            Backtrace               => exec::backtrace(self, emu),
+            Break(addr, cond)       => exec::r#break(self, &addr, cond),
             Capture(path, force)    => exec::capture(emu, &path, force),
+            Cheat(mode)             => exec::cheat(emu, mode),
             Continue                => exec::r#continue(self, ),
             Delete(point)           => exec::delete(self, point),
             Disable(point)          => exec::disable(self, point),
+            Disasm(addr)            => exec::disasm(self, emu, addr.as_ref()),
+            DisasmRange(range)      => exec::disasm_range(self, emu, range),
             Enable(point)           => exec::enable(self, point),
+            Finish                  => exec::finish(self),
             Freq(mode)              => exec::freq(self, mode),
-            Goto(addr)              => exec::goto(emu, addr),
+            Goto(addr)              => exec::goto(self, emu, &addr),
             Help(what)              => exec::help(what),
             Ignore(point, many)     => exec::ignore(self, point, many),
             Info(what)              => exec::info(self, what),
-            Jump(addr)              => exec::jump(self, emu, addr),
+            Jump(addr)              => exec::jump(self, emu, &addr),
             List                    => exec::list(self, emu),
             Load(loc)               => exec::loads(emu, loc),
             Log(filter)             => exec::log(self, filter),
+            Next(many)              => exec::next(self, many),
+            Print(expr)             => exec::print(emu, &expr),
             Quit                    => exec::quit(),
-            Read(addr)              => exec::read(emu, addr),
+            Read(addr)              => exec::read(self, emu, &addr),
             ReadRange(range)        => exec::read_range(emu, range),
+            Record(path, stems)     => exec::record(self, emu, path, stems),
             Reset                   => exec::reset(self, emu),
+            RevContinue             => exec::reverse_continue(self, emu),
+            RevStep(many)           => exec::reverse_step(self, emu, many),
             Serial(mode)            => exec::serial(emu, mode),
+            Source(path)            => exec::source(self, &path),
             Step(many)              => exec::step(self, many),
             Store(loc, value)       => exec::stores(emu, loc, value),
-            Write(addr, byte)       => exec::write(emu, addr, byte),
-            WriteRange(range, byte) => exec::write_range(emu, range, byte),
+            Watch(range, mode)      => exec::watch(self, range, mode),
+            Write(addr, expr)       => exec::write(emu, addr, &expr),
+            WriteRange(range, expr) => exec::write_range(emu, range, &expr),
         }
     }
 
+    /// Resolves an address operand, looking up labels in the symbol table.
+    ///
+    /// Returns the address along with its bank, if it lies within the
+    /// switchable ROM region.
+    fn resolve(&self, addr: &Address) -> Result<(u16, Option<u16>)> {
+        match addr {
+            Address::Int(addr) => Ok((*addr, None)),
+            Address::Label(label) => {
+                let Symbol { bank, addr } = self
+                    .syms
+                    .resolve(label)
+                    .ok_or_else(|| Error::Symbol(label.clone()))?;
+                Ok((addr, Symbol::banked(addr).then_some(bank)))
+            }
+        }
+    }
+
+    /// Rewinds execution to a previous stopping point.
+    ///
+    /// Stopping points are cycles at which the debugger would have become
+    /// ready, using the supplied step count. Execution is rewound to the
+    /// `many`-th most recent of these, found by re-executing from recorded
+    /// snapshots.
+    ///
+    /// If no such point is found, the console is left unchanged.
+    fn rewind(&mut self, emu: &mut GameBoy, step: Option<usize>, many: usize) -> Result<()> {
+        // Reverse execution requires recorded history
+        if !self.hist.enabled() {
+            return Err(Error::CfgHistory);
+        }
+        let now = self.cycle;
+        let save = emu.save_state();
+        // Write audio recorded until now
+        self.record(emu)?;
+        // Preserve state mutated during re-execution
+        let bpts = self.bpts.clone();
+        let prev = std::mem::replace(&mut self.step, step);
+        let hist = std::mem::take(&mut self.hist);
+        self.arm(emu);
+        // Find the stopping point
+        let res = self.seek(emu, &hist, now, many);
+        // Restore preserved state
+        self.bpts = bpts;
+        self.step = prev;
+        self.hist = hist;
+        // Discard audio synthesized while re-executing
+        if self.rec.is_some() {
+            emu.capture(&mut self.cap);
+            self.cap.clear();
+        }
+        match res {
+            Ok(Some(cycle)) => {
+                // Discard rewound history
+                self.hist.truncate(cycle);
+                Ok(())
+            }
+            res => {
+                // Return to the present
+                emu.load_state(&save)?;
+                self.cycle = now;
+                self.update(emu);
+                self.hits.clear();
+                res.and(Err(Error::History))
+            }
+        }
+    }
+
+    /// Seeks to the `many`-th most recent stopping point before a cycle.
+    ///
+    /// Returns the cycle of the stopping point, if found.
+    fn seek(
+        &mut self,
+        emu: &mut GameBoy,
+        hist: &History,
+        mut until: usize,
+        mut many: usize,
+    ) -> Result<Option<usize>> {
+        // Search each interval between snapshots, starting with the most
+        // recent, such that every cycle is re-executed at most twice
+        for snap in hist.iter() {
+            // Find stopping points within this interval
+            let mut stops = Vec::new();
+            self.replay(emu, hist, snap, until, |gbd| {
+                if gbd.ready() {
+                    stops.push(gbd.cycle);
+                }
+            })?;
+            // Re-execute until the stopping point, if found
+            if let Some(idx) = stops.len().checked_sub(many) {
+                let cycle = stops[idx];
+                self.replay(emu, hist, snap, cycle, |_| ())?;
+                return Ok(Some(cycle));
+            }
+            // Continue with the preceding interval
+            many -= stops.len();
+            until = snap.cycle;
+        }
+
+        Ok(None)
+    }
+
+    /// Re-executes from a snapshot until a cycle, visiting each prior cycle.
+    ///
+    /// Recorded inputs are re-applied as they were originally received.
+    fn replay(
+        &mut self,
+        emu: &mut GameBoy,
+        hist: &History,
+        snap: &hist::Snapshot,
+        until: usize,
+        mut visit: impl FnMut(&Self),
+    ) -> Result<()> {
+        emu.load_state(&snap.state)?;
+        self.cycle = snap.cycle;
+        // Discard stale accesses
+        emu.inner().soc.watch.borrow_mut().take();
+        loop {
+            self.update(emu);
+            if self.cycle >= until {
+                break;
+            }
+            visit(self);
+            self.hits.clear();
+            // Advance the console
+            self.cycle += 1;
+            emu.cycle();
+            // Re-apply recorded inputs
+            if let Some(keys) = hist.keys(self.cycle) {
+                Self::press(emu, keys);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Presses exactly the specified keys, releasing all others.
+    fn press(emu: &mut GameBoy, keys: &HashSet<Button>) {
+        let held = emu.inner().soc.joy.reg.borrow().keys().clone();
+        let ups = held.difference(keys).map(|&input| input::Event {
+            input,
+            state: input::State::Up,
+        });
+        let dns = keys.difference(&held).map(|&input| input::Event {
+            input,
+            state: input::State::Dn,
+        });
+        emu.recv(ups.chain(dns).collect::<Vec<_>>());
+    }
+
+    /// Arms the console's watch hook with all enabled watchpoints.
+    ///
+    /// Any accesses recorded in the meantime (i.e. those performed by debugger
+    /// commands) are discarded.
+    fn arm(&self, emu: &GameBoy) {
+        let mut hook = emu.inner().soc.watch.borrow_mut();
+        hook.clear();
+        // Watch enabled watchpoints
+        let wpts = self
+            .bpts
+            .iter()
+            .filter(|(_, bpt)| bpt.as_ref().is_some_and(|bpt| !bpt.disable))
+            .filter_map(|(&point, _)| match point {
+                Point::Watch(mode, start, end) => Some((mode, start, end)),
+                Point::Break(..) => None,
+            });
+        for (mode, start, end) in wpts {
+            // Split wrapping ranges
+            let addrs = if start <= end {
+                [Some(start..=end), None]
+            } else {
+                [Some(start..=u16::MAX), Some(u16::MIN..=end)]
+            };
+            for addrs in addrs.into_iter().flatten() {
+                if mode != Watch::Write {
+                    hook.watch(Access::Read, addrs.clone());
+                }
+                if mode != Watch::Read {
+                    hook.watch(Access::Write, addrs);
+                }
+            }
+        }
+        // Discard debugger accesses
+        hook.take();
+    }
+
+    /// Checks a watched access against all watchpoints.
+    fn watch(&mut self, event: Event) {
+        for (point, (at, bpt)) in self.bpts.iter_mut().enumerate() {
+            // Find enabled watchpoints triggered by this access
+            let Some(bpt) = bpt.as_mut().filter(|bpt| !bpt.disable) else {
+                continue;
+            };
+            if !at.triggers(event) {
+                continue;
+            }
+            // Handle ignored watchpoints
+            if bpt.ignore > 0 {
+                bpt.ignore -= 1;
+                continue;
+            }
+            // Record the hit
+            self.hits.push(Hit {
+                point,
+                pc: self.insn,
+                event,
+            });
+        }
+    }
+
+    /// Checks if the console is within the stepped call frame.
+    ///
+    /// Cycles spent within deeper frames (i.e. called subroutines and
+    /// interrupt handlers) are stepped over entirely.
+    fn within(&self) -> bool {
+        self.frame.is_none_or(|depth| self.depth <= depth)
+    }
+
     /// Returns whether the current cycle is an active edge cycle.
     ///
     /// Depending on the [`Debugger`]'s frequency setting, the definition of an
@@ -294,16 +754,20 @@ impl Block for Debugger {
     fn ready(&self) -> bool {
         // Is this an edge cycle?
         let edge = self.edge();
+        // Is this cycle within the stepped frame?
+        let frame = self.within();
         // Is this cycle being stepped over?
-        let step = self.step != Some(0);
+        let step = self.step != Some(0) || !frame;
         // Are we at a breakpoint?
         let bpt = self
-            .bpts
-            .get(&self.pc)
+            .bpt
+            .and_then(|at| self.bpts.get(&at))
             .and_then(Option::as_ref)
             .is_some_and(|bpt| !bpt.disable && bpt.ignore == 0);
+        // Was a watchpoint triggered?
+        let wpt = !self.hits.is_empty();
         // Should we enable the debugger?
-        edge && (!step || bpt)
+        (edge && (!step || bpt)) || wpt
     }
 
     fn cycle(&mut self) {
@@ -313,13 +777,21 @@ impl Block for Debugger {
         if !self.edge() {
             return;
         }
-        // Handle stepped over cycles
-        if let Some(step) = &mut self.step {
+        // Handle stepped over cycles (only counting those within the stepped
+        // frame)
+        if self.within()
+            && let Some(step) = &mut self.step
+        {
             // Decrement step count
             *step = step.saturating_sub(1);
         }
-        // Handle ignored breakpoints
-        if let Some(bpt) = self.bpts.get_mut(&self.pc).and_then(Option::as_mut) {
+        // Handle ignored breakpoints (only counting crossings which meet the
+        // breakpoint's condition)
+        if let Some(bpt) = self
+            .bpt
+            .and_then(|at| self.bpts.get_mut(&at))
+            .and_then(Option::as_mut)
+        {
             // Decrement ignore count
             bpt.ignore = bpt.ignore.saturating_sub(1);
         }
@@ -328,6 +800,8 @@ impl Block for Debugger {
     fn reset(&mut self) {
         // Application
         std::mem::take(&mut self.cycle);
+        // Internal
+        self.hist.clear();
     }
 }
 
@@ -336,17 +810,26 @@ impl Block for Debugger {
 struct Breakpoint {
     disable: bool,
     ignore: usize,
+    cond: Option<Expr>,
 }
 
 impl Breakpoint {
-    fn display(&self, point: usize, addr: u16) -> impl Display + use<> {
-        let &Self { disable, ignore } = self;
+    fn display(&self, point: usize, at: Point) -> impl Display + use<> {
+        let Self {
+            disable,
+            ignore,
+            ref cond,
+        } = *self;
 
         // Prepare format string
         let mut f = String::new();
 
-        // Format the point, addr
-        write!(f, "breakpoint {point} @ ${addr:04x}").unwrap();
+        // Format the point, location
+        write!(f, "{}", at.display(point)).unwrap();
+        // Format the condition
+        if let Some(cond) = cond {
+            write!(f, " if {cond}").unwrap();
+        }
         // Format characteristics
         if disable {
             write!(f, ": disabled").unwrap();
@@ -358,6 +841,112 @@ impl Breakpoint {
     }
 }
 
+/// Splits input into commands.
+///
+/// Commands are separated by `;`, except within quoted strings.
+fn split(input: &str) -> impl Iterator<Item = &str> {
+    let mut quote = false;
+    input.split(move |ch| {
+        if ch == '"' {
+            quote = !quote;
+        }
+        ch == ';' && !quote
+    })
+}
+
+/// Gets the ROM bank mapped into the switchable region.
+fn bank(emu: &GameBoy) -> usize {
+    emu.cart().map_or(1, |cart| cart.bank())
+}
+
+/// Debugging breakpoint location.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+enum Point {
+    /// Instruction address, within a ROM bank if specified.
+    Break(Option<u16>, u16),
+    /// Memory access within an (inclusive, wrapping) address range.
+    Watch(Watch, u16, u16),
+}
+
+impl Point {
+    fn display(self, point: usize) -> impl Display + use<> {
+        // Prepare format string
+        let mut f = String::new();
+
+        // Format the point, location
+        match self {
+            Point::Break(bank, addr) => {
+                write!(f, "breakpoint {point} @ ${addr:04x}").unwrap();
+                if let Some(bank) = bank {
+                    write!(f, " (bank {bank})").unwrap();
+                }
+            }
+            Point::Watch(mode, start, end) => {
+                write!(f, "watchpoint {point} @ ${start:04x}").unwrap();
+                if start != end {
+                    write!(f, "..=${end:04x}").unwrap();
+                }
+                write!(f, " ({mode})").unwrap();
+            }
+        }
+
+        f
+    }
+
+    /// Checks if a memory access triggers this point.
+    fn triggers(self, event: Event) -> bool {
+        let Point::Watch(mode, start, end) = self else {
+            return false;
+        };
+        // Check the access kind
+        let kind = match mode {
+            Watch::Read => event.kind == Access::Read,
+            Watch::Write => event.kind == Access::Write,
+            Watch::Access => true,
+        };
+        // Check the address
+        let addr = if start <= end {
+            (start..=end).contains(&event.addr)
+        } else {
+            event.addr >= start || event.addr <= end
+        };
+        kind && addr
+    }
+}
+
+/// Triggered watchpoint.
+#[derive(Clone, Debug)]
+struct Hit {
+    /// Watchpoint index.
+    point: usize,
+    /// Offending instruction address.
+    pc: u16,
+    /// Watched access.
+    event: Event,
+}
+
+impl Display for Hit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { point, pc, event } = self;
+        let Event {
+            kind,
+            addr,
+            old,
+            new,
+        } = event;
+        match kind {
+            Access::Read => write!(
+                f,
+                "watchpoint {point}: ${pc:04x} read ${addr:04x} (value: {new:02x})"
+            ),
+            Access::Write => write!(
+                f,
+                "watchpoint {point}: ${pc:04x} wrote ${addr:04x} (old: {old:02x}, new: {new:02x})"
+            ),
+        }
+    }
+}
+
 /// Debugger progress unit.
 #[derive(Copy, Clone, Debug, Default)]
 pub enum Tick {
@@ -432,18 +1021,36 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 #[derive(thiserror::Error)]
 #[non_exhaustive]
 pub enum Error {
+    /// Assertion did not hold.
+    #[error("assertion failed: `{0}`")]
+    Assertion(String),
     /// Requested breakpoint could not be found.
     #[error("breakpoint not found")]
     Breakpoint,
+    /// Audio capture is already in use.
+    #[error("audio capture already in use")]
+    Capture,
     /// Prompt has not been configured.
     #[error("prompt not configured")]
     CfgPrompt,
     /// Logger has not been configured.
     #[error("logger not configured")]
     CfgLogger,
+    /// History has not been configured.
+    #[error("history not configured")]
+    CfgHistory,
     /// Prompt returned empty string.
     #[error("no input provided")]
     Empty,
+    /// Expression evaluation error.
+    #[error(transparent)]
+    Expr(#[from] lang::expr::Error),
+    /// Not within a call frame.
+    #[error("not within a call frame")]
+    Frame,
+    /// Execution history has been exhausted.
+    #[error("not found within recorded history")]
+    History,
     /// Image encoding error.
     #[error(transparent)]
     Image(#[from] png::EncodingError),
@@ -459,6 +1066,18 @@ pub enum Error {
     /// Quit requested by user.
     #[error("quit requested by user")]
     Quit,
+    /// Audio is not being recorded.
+    #[error("not recording audio")]
+    Record,
+    /// Aliases or sourced scripts were nested too deeply.
+    #[error("exceeded maximum nesting depth: `{0}`")]
+    Recursion(String),
+    /// Snapshot could not be restored.
+    #[error(transparent)]
+    Snapshot(#[from] snapshot::Error),
+    /// Requested symbol could not be found.
+    #[error("symbol not found: `{0}`")]
+    Symbol(String),
     /// Attempted an unsupported operation.
     #[error("operation not supported")]
     Unsupported,
@@ -466,3 +1085,299 @@ pub enum Error {
     #[error("unexpected value")]
     Value,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use rugby_core::cart::Cartridge;
+    use rugby_core::chip::ppu::FRAME;
+
+    use super::*;
+
+    #[test]
+    fn script_works() {
+        // Write a script to source
+        let path = std::env::temp_dir().join(format!("gbd-{}.gbd", std::process::id()));
+        std::fs::write(&path, "assert a == $00\n").unwrap();
+        // Run a script on a program of NOPs
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+        let mut gbd = Debugger::new();
+        gbd.prompt(Script::new(Cursor::new(format!(
+            "# Define aliases
+                 alias sa store a
+                 alias loop loop
+
+                 sa $42; assert a == $42
+                 assert a == $01
+                 loop
+                 source {path}",
+            path = path.display(),
+        ))));
+        gbd.enable();
+        gbd.sync(&mut emu);
+        assert!(gbd.ready());
+        assert!(matches!(gbd.run(&mut emu), Err(Error::Quit)));
+        std::fs::remove_file(path).unwrap();
+        // Check assertions
+        assert_eq!(gbd.failures(), 2);
+        assert_eq!(gbd.alias.len(), 2);
+    }
+
+    #[test]
+    fn expand_works() {
+        let mut gbd = Debugger::new();
+        gbd.alias
+            .insert("hi".to_string(), r#"serial "a;b""#.to_string());
+        // Quoted separators are preserved within aliases
+        assert_eq!(
+            gbd.expand("hi; serial \";\"", 0).unwrap(),
+            r#"serial "a;b"; serial ";""#,
+        );
+        // Expanded commands can be parsed
+        let prog: Program = gbd.expand("hi", 0).unwrap().parse().unwrap();
+        assert_eq!(prog.len(), 1);
+    }
+
+    #[test]
+    fn frames_work() {
+        // Run a program of nested calls
+        let mut rom = vec![0; 0x8000];
+        rom[0x0100..0x0103].copy_from_slice(&[0xcd, 0x00, 0x02]); // CALL $0200
+        rom[0x0200..0x0204].copy_from_slice(&[0xcd, 0x00, 0x03, 0xc9]); // CALL $0300; RET
+        rom[0x0300] = 0xc9; // RET
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&rom).unwrap());
+        let mut gbd = Debugger::new();
+        gbd.freq = Tick::Insn;
+        gbd.enable();
+        gbd.sync(&mut emu);
+        // Execute commands, resuming until the debugger is ready
+        let mut exec = |gbd: &mut Debugger, cmd| {
+            gbd.exec(&mut emu, cmd)?;
+            loop {
+                gbd.cycle();
+                emu.cycle();
+                gbd.sync(&mut emu);
+                if gbd.ready() {
+                    break;
+                }
+            }
+            gbd.pause();
+            gbd.frame = None;
+            Ok::<_, Error>((gbd.insn, gbd.depth))
+        };
+
+        // Step into a call
+        assert_eq!(exec(&mut gbd, Command::Step(None)).unwrap(), (0x0100, 0));
+        assert_eq!(exec(&mut gbd, Command::Step(None)).unwrap(), (0x0200, 1));
+        // Step over a call
+        assert_eq!(exec(&mut gbd, Command::Next(None)).unwrap(), (0x0203, 1));
+        // Step out of a call
+        assert_eq!(exec(&mut gbd, Command::Finish).unwrap(), (0x0103, 0));
+        assert!(matches!(exec(&mut gbd, Command::Finish), Err(Error::Frame)));
+    }
+
+    #[test]
+    fn cond_unobserved() {
+        // Run a program of NOPs
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+        emu.inner().soc.prof.borrow_mut().enable();
+        let mut gbd = Debugger::new();
+        gbd.freq = Tick::Insn;
+        gbd.sync(&mut emu);
+        // Watch an address only read by a breakpoint condition
+        let prog: Program = "rwatch $0200; break $0104 if [$0200] == $01"
+            .parse()
+            .unwrap();
+        for cmd in prog {
+            gbd.exec(&mut emu, cmd).unwrap();
+        }
+        gbd.arm(&emu);
+        for _ in 0..0x100 {
+            gbd.cycle();
+            emu.cycle();
+            gbd.sync(&mut emu);
+        }
+
+        // Check the condition was evaluated unobserved
+        assert!(gbd.hits.is_empty());
+        let cover = emu.inner().soc.prof.borrow().coverage().to_vec();
+        assert_eq!(cover.get(0x0200).copied().unwrap_or_default(), 0);
+    }
+
+    #[test]
+    fn banked_break_works() {
+        // Run a program which calls into banks 1 and 2
+        let mut rom = vec![0; 0x10000];
+        rom[0x0147] = 0x01; // MBC1
+        rom[0x0148] = 0x01; // 64 KiB
+        rom[0x0100..0x0103].copy_from_slice(&[0xcd, 0x00, 0x40]); // CALL $4000
+        rom[0x0103..0x0108].copy_from_slice(&[0x3e, 0x02, 0xea, 0x00, 0x20]); // LD A, 2; LD ($2000), A
+        rom[0x0108..0x010d].copy_from_slice(&[0xcd, 0x00, 0x40, 0x18, 0xfe]); // CALL $4000; JR @
+        rom[0x4001] = 0xc9; // NOP; RET
+        rom[0x8001] = 0xc9; // NOP; RET
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&rom).unwrap());
+        let mut gbd = Debugger::new();
+        gbd.syms = "01:4001 One\n02:4001 Two\n".parse().unwrap();
+        gbd.freq = Tick::Insn;
+        gbd.sync(&mut emu);
+
+        // Break at the same address in each bank
+        let prog: Program = "break One; break Two; disable 0".parse().unwrap();
+        for cmd in prog {
+            gbd.exec(&mut emu, cmd).unwrap();
+        }
+        assert_eq!(gbd.bpts.len(), 2);
+        // Continue until the breakpoint in bank 2
+        gbd.exec(&mut emu, Command::Continue).unwrap();
+        loop {
+            gbd.cycle();
+            emu.cycle();
+            gbd.sync(&mut emu);
+            if gbd.ready() {
+                break;
+            }
+        }
+        assert_eq!((gbd.pc, self::bank(&emu)), (0x4001, 2));
+        assert_eq!(gbd.bpt, Some(Point::Break(Some(2), 0x4001)));
+    }
+
+    #[test]
+    fn record_works() {
+        // Run a program of NOPs
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+        let mut gbd = Debugger::new();
+        gbd.sync(&mut emu);
+        // Start recording with stems
+        let path = std::env::temp_dir().join(format!("gbd-{}.wav", std::process::id()));
+        gbd.exec(&mut emu, Command::Record(Some(path.clone()), true))
+            .unwrap();
+        assert!(matches!(
+            gbd.exec(&mut emu, Command::Record(Some(path.clone()), false)),
+            Err(Error::Capture)
+        ));
+        for _ in 0..FRAME {
+            gbd.cycle();
+            emu.cycle();
+            gbd.sync(&mut emu);
+        }
+        // Stop recording
+        gbd.exec(&mut emu, Command::Record(None, false)).unwrap();
+        assert!(matches!(
+            gbd.exec(&mut emu, Command::Record(None, false)),
+            Err(Error::Record)
+        ));
+
+        // Check recordings
+        let stems = (1..=4).map(|ch| path.with_extension(format!("ch{ch}.wav")));
+        for path in std::iter::once(path.clone()).chain(stems) {
+            let data = std::fs::read(&path).unwrap();
+            std::fs::remove_file(path).unwrap();
+            let size = u32::from_le_bytes(data[40..44].try_into().unwrap());
+            assert_eq!(&data[0..4], b"RIFF");
+            assert_eq!(size as usize, data.len() - 44);
+            assert!(size > 0);
+        }
+    }
+
+    #[test]
+    fn rewind_works() {
+        // Run a program of NOPs
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+        let mut gbd = Debugger::new();
+        gbd.history(0x100_0000);
+        let until = 3 * FRAME as usize;
+        let mut edges = Vec::new();
+        loop {
+            gbd.sync(&mut emu);
+            if gbd.cycle == until {
+                break;
+            }
+            if gbd.edge() {
+                edges.push((gbd.cycle, gbd.pc));
+            }
+            gbd.cycle();
+            emu.cycle();
+        }
+
+        // Step back a single instruction
+        gbd.rewind(&mut emu, Some(0), 1).unwrap();
+        let (cycle, pc) = edges.pop().unwrap();
+        assert_eq!((gbd.cycle, gbd.pc), (cycle, pc));
+        // Step back across snapshots
+        let many = FRAME as usize / 2;
+        gbd.rewind(&mut emu, Some(0), many).unwrap();
+        let (cycle, pc) = edges[edges.len() - many];
+        assert_eq!((gbd.cycle, gbd.pc), (cycle, pc));
+        // Fail to step beyond recorded history
+        assert!(matches!(
+            gbd.rewind(&mut emu, Some(0), edges.len()),
+            Err(Error::History)
+        ));
+        assert_eq!((gbd.cycle, gbd.pc), (cycle, pc));
+    }
+
+    #[test]
+    fn rewind_disabled() {
+        // Run a program of NOPs without recording history
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+        let mut gbd = Debugger::new();
+        for _ in 0..FRAME {
+            gbd.sync(&mut emu);
+            gbd.cycle();
+            emu.cycle();
+        }
+
+        // Check nothing was recorded
+        assert_eq!(gbd.hist.iter().count(), 0);
+        assert!(matches!(
+            gbd.rewind(&mut emu, Some(0), 1),
+            Err(Error::CfgHistory)
+        ));
+    }
+
+    #[test]
+    fn rewind_input_works() {
+        // Run a program of NOPs, pressing a key between snapshots
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+        let mut gbd = Debugger::new();
+        gbd.history(0x100_0000);
+        let press = FRAME as usize + FRAME as usize / 2;
+        let until = 2 * FRAME as usize - 100;
+        let mut edges = 0;
+        loop {
+            gbd.sync(&mut emu);
+            if gbd.cycle == until {
+                break;
+            }
+            if gbd.cycle >= press && gbd.edge() {
+                edges += 1;
+            }
+            gbd.cycle();
+            emu.cycle();
+            if gbd.cycle == press {
+                emu.recv([input::Event {
+                    input: Button::A,
+                    state: input::State::Dn,
+                }]);
+            }
+        }
+        let keys = |emu: &GameBoy| emu.inner().soc.joy.reg.borrow().keys().clone();
+
+        // Step back after the press
+        gbd.rewind(&mut emu, Some(0), 1).unwrap();
+        assert_eq!(keys(&emu), HashSet::from([Button::A]));
+        // Step back before the press
+        gbd.rewind(&mut emu, Some(0), edges).unwrap();
+        assert!(gbd.cycle < press);
+        assert!(keys(&emu).is_empty());
+    }
+}
@@ -2,6 +2,9 @@
 
 use std::error::Error as StdError;
 use std::fmt::Debug;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
 
 /// Behaviour for prompting a user for input.
 pub trait Prompt: Debug + Send {
@@ -15,6 +18,61 @@ pub trait Prompt: Debug + Send {
     fn prompt(&mut self, msg: &str) -> Result<String, Error>;
 }
 
+/// Non-interactive prompt, reading input from a script.
+///
+/// Lines are read in order, skipping those which are blank or start with `#`.
+/// Each is echoed after the prompt message, producing a transcript of the
+/// session. Once the script is exhausted, [`Error::Quit`] is returned.
+#[derive(Debug)]
+pub struct Script<R = BufReader<File>> {
+    /// Script reader.
+    read: R,
+}
+
+impl Script {
+    /// Opens a script file.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the file could not be opened.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        File::open(path).map(BufReader::new).map(Self::new)
+    }
+}
+
+impl<R: BufRead> Script<R> {
+    /// Constructs a new `Script` from a reader.
+    pub fn new(read: R) -> Self {
+        Self { read }
+    }
+}
+
+impl<R: BufRead + Debug + Send> Prompt for Script<R> {
+    fn prompt(&mut self, msg: &str) -> Result<String, Error> {
+        let mut line = String::new();
+        loop {
+            // Read the next line
+            line.clear();
+            let read = self
+                .read
+                .read_line(&mut line)
+                .map_err(|err| Box::new(err) as Box<dyn StdError>)?;
+            if read == 0 {
+                // Script exhausted
+                return Err(Error::Quit);
+            }
+            // Skip blank lines and comments
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // Echo script input
+            advise::info!("{msg}{line}");
+            return Ok(line.to_string());
+        }
+    }
+}
+
 /// A convenient type alias for [`Result`](std::result::Result).
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
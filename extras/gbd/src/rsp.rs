@@ -0,0 +1,507 @@
+//! GDB remote serial protocol.
+//!
+//! Provides a [`Remote`] transport, allowing an external debugger which speaks
+//! the [GDB RSP][rsp] to attach to a [`Debugger`] over TCP.
+//!
+//! Registers are numbered in the following order: the 8-bit registers `A`,
+//! `F`, `B`, `C`, `D`, `E`, `H`, `L`, followed by the 16-bit registers `SP`
+//! and `PC` (little-endian).
+//!
+//! [rsp]: https://sourceware.org/gdb/current/onlinedocs/gdb.html/Remote-Protocol.html
+
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+use log::{debug, trace};
+use rugby_arch::reg::Port;
+use rugby_core::chip::cpu::{Select8, Select16};
+use rugby_core::dmg::GameBoy;
+
+use crate::lang::Address;
+use crate::{Debugger, Error, Point, Result, exec};
+
+/// Interrupt request.
+const INTR: u8 = 0x03;
+
+/// Stop reply, reporting a trap.
+const TRAP: &str = "S05";
+
+/// Error reply.
+const FAIL: &str = "E01";
+
+/// Byte registers, in protocol order.
+const REGS: [Select8; 8] = [
+    Select8::A,
+    Select8::F,
+    Select8::B,
+    Select8::C,
+    Select8::D,
+    Select8::E,
+    Select8::H,
+    Select8::L,
+];
+
+/// Word registers, in protocol order.
+const WORDS: [Select16; 2] = [Select16::SP, Select16::PC];
+
+/// Remote debugger connection.
+#[derive(Debug)]
+pub struct Remote {
+    /// Connection reader.
+    rx: BufReader<TcpStream>,
+    /// Connection writer.
+    tx: TcpStream,
+    /// Acknowledge packets.
+    ack: bool,
+    /// Stop reply is owed.
+    stop: bool,
+}
+
+impl Remote {
+    /// Listens for a remote debugger, blocking until one connects.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the listener could not be bound, or the connection failed.
+    pub fn listen(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let (conn, peer) = TcpListener::bind(addr)?.accept()?;
+        debug!("remote debugger connected: {peer}");
+        Self::new(conn)
+    }
+
+    /// Constructs a new `Remote` over an established connection.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the connection could not be configured.
+    pub fn new(conn: TcpStream) -> io::Result<Self> {
+        conn.set_nodelay(true)?;
+        Ok(Self {
+            rx: BufReader::new(conn.try_clone()?),
+            tx: conn,
+            ack: true,
+            stop: false,
+        })
+    }
+
+    /// Polls for an interrupt request without blocking.
+    pub(crate) fn poll(&mut self) -> io::Result<bool> {
+        // Check for pending input
+        if self.rx.buffer().is_empty() {
+            self.rx.get_ref().set_nonblocking(true)?;
+            let res = self.rx.fill_buf().map(<[u8]>::len);
+            self.rx.get_ref().set_nonblocking(false)?;
+            match res {
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(false),
+                res => res?,
+            };
+        }
+        // Consume the interrupt
+        let intr = self.rx.buffer().first() == Some(&INTR);
+        if intr {
+            self.rx.consume(1);
+        }
+
+        Ok(intr)
+    }
+
+    /// Serves requests until execution is resumed.
+    ///
+    /// Returns whether the remote debugger remains attached.
+    pub(crate) fn serve(&mut self, gbd: &mut Debugger, emu: &mut GameBoy) -> Result<bool> {
+        // Report that execution has stopped
+        if std::mem::take(&mut self.stop) {
+            self.send(TRAP)?;
+        }
+        // Handle packets while paused
+        while gbd.paused() {
+            let Some(pkt) = self.recv()? else {
+                advise::warn!("remote debugger disconnected");
+                exec::r#continue(gbd)?;
+                return Ok(false);
+            };
+            match Self::handle(gbd, emu, &pkt)? {
+                Reply::Packet(data) => self.send(&data)?,
+                Reply::Resume => self.stop = true,
+                Reply::Detach => {
+                    self.send("OK")?;
+                    exec::r#continue(gbd)?;
+                    return Ok(false);
+                }
+            }
+            // Disable acknowledgements once negotiated
+            if pkt == "QStartNoAckMode" {
+                self.ack = false;
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Handles a single request.
+    fn handle(gbd: &mut Debugger, emu: &mut GameBoy, pkt: &str) -> Result<Reply> {
+        let (kind, args) = pkt.split_at(pkt.len().min(1));
+        let data = match kind {
+            // Stop reason
+            "?" => Some(TRAP.to_string()),
+            // Registers
+            "g" => Some(self::load(emu)),
+            "G" => self::store(emu, args).map(|()| "OK".to_string()),
+            "p" => usize::from_str_radix(args, 16)
+                .ok()
+                .and_then(|reg| self::read(emu, reg)),
+            "P" => args
+                .split_once('=')
+                .and_then(|(reg, data)| {
+                    let reg = usize::from_str_radix(reg, 16).ok()?;
+                    self::write(emu, reg, &self::decode(data)?)
+                })
+                .map(|()| "OK".to_string()),
+            // Memory
+            "m" => self::range(args).map(|(addr, len)| self::peek(emu, addr, len)),
+            "M" => args.split_once(':').and_then(|(range, data)| {
+                let (addr, len) = self::range(range)?;
+                let data = self::decode(data).filter(|data| data.len() == len)?;
+                self::poke(emu, addr, &data);
+                Some("OK".to_string())
+            }),
+            // Breakpoints
+            "Z" | "z" => match self::point(args) {
+                Some(addr) => {
+                    if kind == "Z" {
+                        exec::r#break(gbd, &Address::Int(addr), None)?;
+                    } else if let Some(point) = gbd
+                        .bpts
+                        .get_index_of(&Point::Break(None, addr))
+                        .filter(|&point| gbd.bpts[point].is_some())
+                    {
+                        exec::delete(gbd, point)?;
+                    }
+                    Some("OK".to_string())
+                }
+                // Only (software and hardware) breakpoints are supported
+                None => Some(String::new()),
+            },
+            // Execution
+            "c" | "s" => {
+                // Resume from the specified address
+                if !args.is_empty() {
+                    let Ok(addr) = u16::from_str_radix(args, 16) else {
+                        return Ok(Reply::Packet(FAIL.to_string()));
+                    };
+                    emu.inner_mut().soc.cpu.goto(addr);
+                }
+                if kind == "c" {
+                    exec::r#continue(gbd)?;
+                } else {
+                    exec::step(gbd, None)?;
+                }
+                return Ok(Reply::Resume);
+            }
+            "D" => return Ok(Reply::Detach),
+            "k" => return Err(Error::Quit),
+            // Queries
+            "q" if args.starts_with("Supported") => {
+                Some("PacketSize=1000;QStartNoAckMode+".to_string())
+            }
+            "q" if args == "Attached" => Some("1".to_string()),
+            "Q" if args == "StartNoAckMode" => Some("OK".to_string()),
+            "H" => Some("OK".to_string()),
+            // Unsupported
+            _ => Some(String::new()),
+        };
+
+        Ok(Reply::Packet(data.unwrap_or_else(|| FAIL.to_string())))
+    }
+
+    /// Receives a packet.
+    ///
+    /// Returns `None` once the connection is closed.
+    fn recv(&mut self) -> io::Result<Option<String>> {
+        loop {
+            // Find the start of a packet
+            let mut skip = Vec::new();
+            self.rx.read_until(b'$', &mut skip)?;
+            if skip.pop() != Some(b'$') {
+                return Ok(None);
+            }
+            // Read the packet data
+            let mut data = Vec::new();
+            self.rx.read_until(b'#', &mut data)?;
+            if data.pop() != Some(b'#') {
+                return Ok(None);
+            }
+            // Verify the checksum
+            let mut sum = [0; 2];
+            self.rx.read_exact(&mut sum)?;
+            let valid = std::str::from_utf8(&sum)
+                .ok()
+                .and_then(|sum| u8::from_str_radix(sum, 16).ok())
+                == Some(self::checksum(&data));
+            if self.ack {
+                self.tx.write_all(if valid { b"+" } else { b"-" })?;
+            }
+            if valid {
+                let pkt = String::from_utf8_lossy(&self::unescape(&data)).into_owned();
+                trace!("remote: recv `{pkt}`");
+                return Ok(Some(pkt));
+            }
+        }
+    }
+
+    /// Sends a packet.
+    fn send(&mut self, data: &str) -> io::Result<()> {
+        trace!("remote: send `{data}`");
+        let pkt = format!("${data}#{:02x}", self::checksum(data.as_bytes()));
+        loop {
+            self.tx.write_all(pkt.as_bytes())?;
+            if !self.ack {
+                return Ok(());
+            }
+            // Await acknowledgement, retransmitting as needed
+            let mut byte = [0];
+            loop {
+                self.rx.read_exact(&mut byte)?;
+                match byte[0] {
+                    b'+' => return Ok(()),
+                    b'-' => break,
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+/// Request outcome.
+#[derive(Debug)]
+enum Reply {
+    /// Reply with a packet.
+    Packet(String),
+    /// Resume execution, replying once stopped.
+    Resume,
+    /// Detach the remote debugger.
+    Detach,
+}
+
+/// Computes a packet checksum.
+fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0, |sum, &byte| sum.wrapping_add(byte))
+}
+
+/// Removes escapes from packet data.
+fn unescape(data: &[u8]) -> Vec<u8> {
+    let mut iter = data.iter().copied();
+    std::iter::from_fn(|| match iter.next()? {
+        b'}' => iter.next().map(|byte| byte ^ 0x20),
+        byte => Some(byte),
+    })
+    .collect()
+}
+
+/// Decodes a hex string into bytes.
+fn decode(data: &str) -> Option<Vec<u8>> {
+    (0..data.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(data.get(idx..idx + 2)?, 16).ok())
+        .collect()
+}
+
+/// Parses an `ADDR,LEN` memory range.
+fn range(args: &str) -> Option<(u16, usize)> {
+    let (addr, len) = args.split_once(',')?;
+    Some((
+        u16::from_str_radix(addr, 16).ok()?,
+        usize::from_str_radix(len, 16).ok()?,
+    ))
+}
+
+/// Parses a `TYPE,ADDR,KIND` breakpoint, supporting only types 0 and 1.
+fn point(args: &str) -> Option<u16> {
+    let mut args = args.split(',');
+    matches!(args.next()?, "0" | "1")
+        .then(|| u16::from_str_radix(args.next()?, 16).ok())
+        .flatten()
+}
+
+/// Loads all registers.
+fn load(emu: &GameBoy) -> String {
+    (0..REGS.len() + WORDS.len())
+        .filter_map(|reg| self::read(emu, reg))
+        .collect()
+}
+
+/// Stores all registers.
+fn store(emu: &mut GameBoy, args: &str) -> Option<()> {
+    let data = self::decode(args).filter(|data| data.len() == REGS.len() + 2 * WORDS.len())?;
+    let (bytes, words) = data.split_at(REGS.len());
+    for (reg, byte) in bytes.iter().enumerate() {
+        self::write(emu, reg, &[*byte])?;
+    }
+    for (reg, word) in words.chunks(2).enumerate() {
+        self::write(emu, REGS.len() + reg, word)?;
+    }
+    Some(())
+}
+
+/// Reads a single register.
+fn read(emu: &GameBoy, reg: usize) -> Option<String> {
+    let cpu = &emu.inner().soc.cpu;
+    let mut out = String::new();
+    if let Some(&reg) = REGS.get(reg) {
+        let byte: u8 = cpu.load(reg);
+        write!(out, "{byte:02x}").unwrap();
+    } else {
+        let word: u16 = cpu.load(*WORDS.get(reg - REGS.len())?);
+        write!(out, "{:04x}", word.swap_bytes()).unwrap();
+    }
+    Some(out)
+}
+
+/// Writes a single register.
+fn write(emu: &mut GameBoy, reg: usize, data: &[u8]) -> Option<()> {
+    let cpu = &mut emu.inner_mut().soc.cpu;
+    match (reg.checked_sub(REGS.len()).map(|reg| WORDS.get(reg)), data) {
+        (None, &[byte]) => cpu.store(REGS[reg], byte),
+        (Some(Some(Select16::PC)), &[lo, hi]) => cpu.goto(u16::from_le_bytes([lo, hi])),
+        (Some(Some(&reg)), &[lo, hi]) => cpu.store(reg, u16::from_le_bytes([lo, hi])),
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Reads a range of memory.
+fn peek(emu: &GameBoy, addr: u16, len: usize) -> String {
+    let bus = emu.inner().soc.cpu.blk.bus.peek();
+    let mut out = String::new();
+    for addr in (0..len).map_while(|idx| Some(addr.wrapping_add(u16::try_from(idx).ok()?))) {
+        write!(out, "{:02x}", bus.read(addr).unwrap_or(0xff)).unwrap();
+    }
+    out
+}
+
+/// Writes a range of memory.
+fn poke(emu: &mut GameBoy, addr: u16, data: &[u8]) {
+    let bus = &mut emu.inner_mut().soc.cpu.blk.bus;
+    for (addr, &byte) in (addr..=u16::MAX).chain(0..addr).zip(data) {
+        bus.poke(addr, byte);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use rugby_arch::Block;
+    use rugby_core::cart::Cartridge;
+
+    use super::*;
+
+    /// Scripted remote debugger client.
+    struct Client(BufReader<TcpStream>);
+
+    impl Client {
+        /// Sends a packet, returning the reply.
+        fn send(&mut self, data: &str) -> String {
+            self.post(data);
+            self.recv()
+        }
+
+        /// Receives a packet.
+        fn recv(&mut self) -> String {
+            let mut pkt = Vec::new();
+            self.0.read_until(b'$', &mut pkt).unwrap();
+            pkt.clear();
+            self.0.read_until(b'#', &mut pkt).unwrap();
+            pkt.pop();
+            let mut sum = [0; 2];
+            self.0.read_exact(&mut sum).unwrap();
+            assert_eq!(sum, format!("{:02x}", checksum(&pkt)).as_bytes());
+            self.0.get_mut().write_all(b"+").unwrap();
+            String::from_utf8(pkt).unwrap()
+        }
+
+        /// Sends a packet without awaiting a reply.
+        fn post(&mut self, data: &str) {
+            let pkt = format!("${data}#{:02x}", checksum(data.as_bytes()));
+            self.0.get_mut().write_all(pkt.as_bytes()).unwrap();
+            let mut ack = [0];
+            self.0.read_exact(&mut ack).unwrap();
+            assert_eq!(ack, *b"+");
+        }
+    }
+
+    #[test]
+    fn remote_works() {
+        let host = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = host.local_addr().unwrap();
+        // Script the remote debugger
+        let client = thread::spawn(move || {
+            let mut rsp = Client(BufReader::new(TcpStream::connect(addr).unwrap()));
+            // Query target state
+            assert_eq!(rsp.send("?"), "S05");
+            assert!(rsp.send("qSupported:swbreak+").starts_with("PacketSize="));
+            assert_eq!(rsp.send("g").len(), 2 * (REGS.len() + 2 * WORDS.len()));
+            // Access registers
+            assert_eq!(rsp.send("P0=42"), "OK");
+            assert_eq!(rsp.send("p0"), "42");
+            assert_eq!(rsp.send("P8=fcff"), "OK");
+            assert_eq!(rsp.send("p8"), "fcff");
+            assert_eq!(rsp.send("pa"), FAIL);
+            // Access memory
+            assert_eq!(rsp.send("Mc000,2:abcd"), "OK");
+            assert_eq!(rsp.send("mc000,2"), "abcd");
+            // Continue to a breakpoint
+            assert_eq!(rsp.send("Z0,160,1"), "OK");
+            assert_eq!(rsp.send("c"), "S05");
+            assert_eq!(rsp.send("p9"), "6001");
+            // Step a single instruction
+            assert_eq!(rsp.send("s"), "S05");
+            assert_eq!(rsp.send("p9"), "6101");
+            // Interrupt execution
+            assert_eq!(rsp.send("z0,160,1"), "OK");
+            rsp.post("c");
+            rsp.0.get_mut().write_all(&[INTR]).unwrap();
+            assert_eq!(rsp.recv(), "S05");
+            // Kill the target
+            rsp.post("k");
+        });
+        let (conn, _) = host.accept().unwrap();
+
+        // Run a program of NOPs
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+        let mut gbd = Debugger::new();
+        gbd.remote(Remote::new(conn).unwrap());
+        gbd.enable();
+        loop {
+            gbd.sync(&mut emu);
+            if gbd.ready() {
+                match gbd.run(&mut emu) {
+                    Err(Error::Quit) => break,
+                    res => res.unwrap(),
+                }
+            }
+            gbd.cycle();
+            emu.cycle();
+        }
+        client.join().unwrap();
+    }
+
+    #[test]
+    fn poke_unobserved() {
+        use rugby_core::dmg::dbg::watch::Access;
+
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::unchecked(&vec![0; 0x8000]).unwrap());
+        // Watch the written addresses
+        let watch = emu.inner().soc.watch.clone();
+        watch.borrow_mut().watch(Access::Write, 0xc000..=0xc001);
+        // Write memory on behalf of the remote debugger
+        poke(&mut emu, 0xc000, &[0xab, 0xcd]);
+
+        // Check the write was applied unobserved
+        assert_eq!(peek(&emu, 0xc000, 2), "abcd");
+        assert!(watch.borrow_mut().take().is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::sync::OnceLock;
 
+use super::dev::retro_set_rumble_state_t;
 use super::{unsigned, void};
 // documentation uses
 #[allow(unused_imports)]
@@ -152,3 +153,10 @@ pub type retro_input_state_t =
 
 /// Input state callback.
 pub(crate) static INPUT_STATE: OnceLock<retro_input_state_t> = OnceLock::new();
+
+/// Rumble state callback.
+///
+/// # See
+///
+/// - [`RETRO_ENVIRONMENT_GET_RUMBLE_INTERFACE`]
+pub(crate) static RUMBLE: OnceLock<retro_set_rumble_state_t> = OnceLock::new();
@@ -10,7 +10,7 @@
 //! to a real input device without having to worry about the correct use of
 //! arbitrary (real) controller layouts.
 
-use super::unsigned;
+use super::{int, unsigned};
 // documentation uses
 #[allow(unused_imports)]
 use crate::*;
@@ -358,3 +358,50 @@ pub const RETRO_DEVICE_ID_POINTER_PRESSED: unsigned       = 2;
 pub const RETRO_DEVICE_ID_POINTER_COUNT: unsigned         = 3;
 /// Indicates if pointer is off the screen or near the edge
 pub const RETRO_DEVICE_ID_POINTER_IS_OFFSCREEN: unsigned  = 15;
+
+/* Rumble */
+
+/// Rumble motor effects.
+///
+/// # See
+///
+/// - [`retro_set_rumble_state_t`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum retro_rumble_effect {
+   RETRO_RUMBLE_STRONG = 0,
+   RETRO_RUMBLE_WEAK   = 1,
+
+   /// Ensure `sizeof(enum) == sizeof(int)`
+   RETRO_RUMBLE_DUMMY  = int::MAX,
+}
+
+/// Sets the rumble state of a controller.
+///
+/// # Parameters
+///
+/// - `port`: The port of the controller to rumble.
+/// - `effect`: The motor to set.
+/// - `strength`: The strength of the effect, where `0` disables the motor.
+///
+/// # Returns
+///
+/// `true` if the rumble state was honored.
+///
+/// # See
+///
+/// - [`retro_rumble_interface`]
+pub type retro_set_rumble_state_t =
+    extern "C" fn(port: unsigned, effect: retro_rumble_effect, strength: u16) -> bool;
+
+/// An interface for controlling a controller's rumble motors.
+///
+/// # See
+///
+/// - [`RETRO_ENVIRONMENT_GET_RUMBLE_INTERFACE`]
+#[derive(Copy, Clone, Debug, Default)]
+#[repr(C)]
+pub struct retro_rumble_interface {
+   /// Sets the rumble state of a controller.
+   pub set_rumble_state: Option<retro_set_rumble_state_t>,
+}
@@ -22,13 +22,16 @@
 
 use std::ffi::CStr;
 use std::ops::{Deref, DerefMut};
-use std::ptr;
+use std::ptr::{self, NonNull};
 
 use constcat::concat;
 use log::{error, info, warn};
 use parking_lot::Mutex;
 use rugby::api::audio::{Audio, Sample};
+use rugby::api::cheat::{Cheat, Code};
 use rugby::api::input::{Input, State};
+use rugby::api::rumble::Rumble;
+use rugby::api::snapshot::Snapshot;
 use rugby::api::video::Video;
 use rugby::arch::Block;
 use rugby::core::cart::Cartridge;
@@ -70,12 +73,10 @@ use self::mem::*;
 #[allow(unused_imports)]
 use self::pix::*;
 
-/// Audio sample divider.
+/// Audio sample rate.
 ///
-/// As an optimization, audio can be linearly downsampled by this value to
-/// decrease the amount of calls to the audio subsystem, hereby improving
-/// performance.
-const AUDIO: u32 = 32;
+/// Audio is synthesized by the emulator directly at this rate.
+const AUDIO: u32 = 48_000;
 
 /// Shim for accessing the emulation core.
 #[derive(Debug, Default)]
@@ -84,7 +85,9 @@ struct Emulator(GameBoy);
 impl Emulator {
     /// Constructs a new `Emulator`.
     pub fn new() -> Self {
-        Self(GameBoy::new())
+        let mut emu = GameBoy::new();
+        emu.set_rate(AUDIO);
+        Self(emu)
     }
 }
 
@@ -109,6 +112,119 @@ unsafe impl Sync for Emulator {}
 /// Singleton emulation core.
 static EMULATOR: Mutex<Option<Emulator>> = Mutex::new(None);
 
+/// Save state headroom.
+///
+/// Some parts of the emulator's state (such as the PPU's pixel FIFOs) vary in
+/// length throughout a frame. This much space is reserved on top of a measured
+/// save state to account for them.
+const STATE_SLACK: usize = 0x400;
+
+/// Save state size bound.
+///
+/// Measured once per loaded game, as the frontend expects the size reported by
+/// [`retro_serialize_size`] to never grow.
+static STATE_SIZE: Mutex<Option<usize>> = Mutex::new(None);
+
+/// Cheat codes.
+///
+/// Indexed as set by the frontend, with disabled cheats left empty.
+static CHEATS: Mutex<Vec<Vec<Code>>> = Mutex::new(Vec::new());
+
+/// Parses a cheat's codes.
+///
+/// # Note
+///
+/// Frontends may combine several codes into a single cheat, separated by
+/// either `+` or whitespace.
+fn cheat(code: &str) -> Option<Vec<Code>> {
+    code.split('+')
+        .flat_map(str::split_whitespace)
+        .map(|code| {
+            code.parse()
+                .inspect_err(|err| error!("invalid cheat code `{code}`: {err}"))
+                .ok()
+        })
+        .collect()
+}
+
+/// Applies the frontend's cheats to the emulator.
+fn recheat(emu: &mut GameBoy, cheats: &[Vec<Code>]) {
+    emu.clear_cheats();
+    for code in cheats.iter().flatten() {
+        emu.add_cheat(code.clone());
+    }
+}
+
+/// Serializes the emulator into a buffer.
+///
+/// # Note
+///
+/// As frontends provide buffers which may exceed the size of the state, it is
+/// prefixed by its length.
+fn serialize(emu: &GameBoy, buf: &mut [u8]) -> bool {
+    // Take a snapshot
+    let state = emu.save_state();
+    let head = u64::try_from(state.len()).expect("state size should fit in 64 bits");
+    let head = head.to_le_bytes();
+    // Ensure it fits
+    let size = head.len() + state.len();
+    if buf.len() < size {
+        error!("state buffer too small: {} < {size}", buf.len());
+        return false;
+    }
+    // Write the state
+    let (lhs, rhs) = buf.split_at_mut(head.len());
+    lhs.copy_from_slice(&head);
+    rhs[..state.len()].copy_from_slice(&state);
+    rhs[state.len()..].fill(0);
+    true
+}
+
+/// Locates a region of emulator memory.
+///
+/// # Note
+///
+/// Emulator memory is heap-allocated and reference counted, so the returned
+/// region remains valid for as long as its owning hardware is connected.
+fn memory(emu: &GameBoy, id: unsigned) -> Option<NonNull<[u8]>> {
+    match id & mem::RETRO_MEMORY_MASK {
+        mem::RETRO_MEMORY_SAVE_RAM => emu
+            .cart()
+            .map(|cart| NonNull::from(&mut *cart.sram()))
+            .filter(|ram| !ram.is_empty()),
+        mem::RETRO_MEMORY_SYSTEM_RAM => Some(NonNull::from(
+            emu.wram().borrow_mut().inner_mut().as_mut_slice(),
+        )),
+        mem::RETRO_MEMORY_VIDEO_RAM => Some(NonNull::from(
+            emu.vram().borrow_mut().inner_mut().as_mut_slice(),
+        )),
+        mem::RETRO_MEMORY_RTC => emu
+            .cart()
+            .and_then(|cart| cart.rtc().map(|mut rtc| NonNull::from(&mut *rtc))),
+        _ => None,
+    }
+}
+
+/// Unserializes the emulator from a buffer.
+fn unserialize(emu: &mut GameBoy, buf: &[u8]) -> bool {
+    // Read the state
+    let Some((head, rest)) = buf.split_first_chunk::<8>() else {
+        error!("state buffer too small: {}", buf.len());
+        return false;
+    };
+    let Some(state) = usize::try_from(u64::from_le_bytes(*head))
+        .ok()
+        .and_then(|len| rest.get(..len))
+    else {
+        error!("state buffer truncated");
+        return false;
+    };
+    // Restore the snapshot
+    emu.load_state(state)
+        .inspect_err(|err| error!("failed to load state: {err}"))
+        .is_ok()
+}
+
 /// The major version of the `libretro` API and ABI.
 ///
 /// Cores may support multiple versions, or they may reject cores with
@@ -303,7 +419,7 @@ const AV_INFO: retro_system_av_info = retro_system_av_info {
     },
     timing: retro_system_timing {
         fps: 4_194_304. / (ppu::FRAME as double),
-        sample_rate: 4_194_304. / (AUDIO as double),
+        sample_rate: AUDIO as double,
     },
 };
 
@@ -502,6 +618,15 @@ pub extern "C" fn retro_init() {
         std::ptr::from_ref::<int>(&(retro_pixel_format::RETRO_PIXEL_FORMAT_XRGB8888 as int))
             .cast::<void>(),
     );
+    // Acquire rumble interface
+    let mut rumble = retro_rumble_interface::default();
+    if env(
+        env::RETRO_ENVIRONMENT_GET_RUMBLE_INTERFACE,
+        std::ptr::from_mut(&mut rumble).cast::<void>(),
+    ) && let Some(cb) = rumble.set_rumble_state
+    {
+        let _ = def::RUMBLE.set(cb);
+    }
 }
 
 /// Called by the frontend when deinitializing a `libretro` core.
@@ -709,27 +834,20 @@ pub extern "C" fn retro_run() {
         }
     }
 
-    // Buffer audio samples
-    let mut audio = Vec::with_capacity(0x2000);
-
     // Emulate single frame
-    let mut cycle = 0;
-    let frame = loop {
+    loop {
         // Tick emulator
         emu.cycle();
-        // Sample audio frames
-        if cycle % AUDIO == 0 {
-            let Sample { lt, rt } = emu.sample().mix();
-            audio.push(lt);
-            audio.push(rt);
-        }
         // Finish at vertical sync
         if emu.vsync() {
-            break emu.frame();
+            break;
         }
-        // Increment cycle count
-        cycle += 1;
-    };
+    }
+
+    // Buffer audio samples
+    let mut audio = Vec::with_capacity(0x400);
+    emu.pull(&mut audio);
+    let frame = emu.frame();
 
     // Play audio samples
     if let Some(play) = def::AUDIO_SAMPLE_BATCH.get() {
@@ -737,12 +855,21 @@ pub extern "C" fn retro_run() {
         #[allow(clippy::cast_possible_truncation)]
         let audio = audio
             .into_iter()
+            .flat_map(|Sample { lt, rt }| [lt, rt])
             .map(|sample| (sample.clamp(-1., 1.) * f32::from(i16::MAX)) as i16)
             .collect::<Vec<_>>();
         // Audio sample callback
         play(audio.as_ptr(), audio.len() / 2);
     }
 
+    // Drive rumble motor
+    if let Some(rumble) = def::RUMBLE.get() {
+        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_sign_loss)]
+        let strength = (emu.rumble().clamp(0., 1.) * f32::from(u16::MAX)) as u16;
+        rumble(0, retro_rumble_effect::RETRO_RUMBLE_STRONG, strength);
+    }
+
     // Draw video frame
     if let Some(draw) = def::VIDEO_REFRESH.get() {
         // Apply palette to frame
@@ -776,7 +903,14 @@ pub extern "C" fn retro_run() {
 /// - [`retro_serialize`]
 #[unsafe(no_mangle)]
 pub extern "C" fn retro_serialize_size() -> usize {
-    todo!()
+    // Acquire emulator instance
+    let guard = EMULATOR.lock();
+    let emu = guard.as_deref().expect("was not initialized");
+
+    // Measure state size
+    *STATE_SIZE
+        .lock()
+        .get_or_insert_with(|| size_of::<u64>() + emu.save_state().len() + STATE_SLACK)
 }
 
 /// Serializes the internal state.
@@ -795,10 +929,22 @@ pub extern "C" fn retro_serialize_size() -> usize {
 ///
 /// - [`retro_serialize_size`]
 /// - [`retro_unserialize`]
-#[allow(unused_variables)]
 #[unsafe(no_mangle)]
 pub extern "C" fn retro_serialize(data: *mut void, size: usize) -> bool {
-    todo!()
+    // Access state buffer
+    let buf = if data.is_null() {
+        return false;
+    } else {
+        // SAFETY: pointer is guaranteed non-null
+        unsafe { std::slice::from_raw_parts_mut(data.cast::<u8>(), size) }
+    };
+
+    // Acquire emulator instance
+    let guard = EMULATOR.lock();
+    let emu = guard.as_deref().expect("was not initialized");
+
+    // Serialize emulator
+    serialize(emu, buf)
 }
 
 /// Unserialize the given state data, and load it into the internal state.
@@ -810,10 +956,22 @@ pub extern "C" fn retro_serialize(data: *mut void, size: usize) -> bool {
 /// # See
 ///
 /// - [`retro_serialize`]
-#[allow(unused_variables)]
 #[unsafe(no_mangle)]
 pub extern "C" fn retro_unserialize(data: *const void, size: usize) -> bool {
-    todo!()
+    // Access state buffer
+    let buf = if data.is_null() {
+        return false;
+    } else {
+        // SAFETY: pointer is guaranteed non-null
+        unsafe { std::slice::from_raw_parts(data.cast::<u8>(), size) }
+    };
+
+    // Acquire emulator instance
+    let mut guard = EMULATOR.lock();
+    let emu = guard.as_deref_mut().expect("was not initialized");
+
+    // Unserialize emulator
+    unserialize(emu, buf)
 }
 
 /// Reset all the active cheats to their default disabled state.
@@ -821,10 +979,15 @@ pub extern "C" fn retro_unserialize(data: *const void, size: usize) -> bool {
 /// # See
 ///
 /// - [`retro_cheat_set`]
-#[allow(unused_variables)]
 #[unsafe(no_mangle)]
 pub extern "C" fn retro_cheat_reset() {
-    todo!()
+    // Acquire emulator instance
+    let mut guard = EMULATOR.lock();
+    let emu = guard.as_deref_mut().expect("was not initialized");
+
+    // Disable all cheats
+    CHEATS.lock().clear();
+    emu.clear_cheats();
 }
 
 /// Enable or disable a cheat.
@@ -838,10 +1001,37 @@ pub extern "C" fn retro_cheat_reset() {
 /// # See
 ///
 /// - [`retro_cheat_reset`]
-#[allow(unused_variables)]
 #[unsafe(no_mangle)]
 pub extern "C" fn retro_cheat_set(index: unsigned, enabled: bool, code: *const char) {
-    todo!()
+    // Access cheat code
+    let code = if code.is_null() {
+        Some("")
+    } else {
+        // SAFETY: pointer is guaranteed non-null
+        let code = unsafe { CStr::from_ptr(code) };
+        code.to_str()
+            .inspect_err(|err| error!("invalid cheat code: {err}"))
+            .ok()
+    };
+    // Invalid codes leave the slot cleared
+    let codes = if enabled {
+        code.and_then(cheat).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    // Acquire emulator instance
+    let mut guard = EMULATOR.lock();
+    let emu = guard.as_deref_mut().expect("was not initialized");
+
+    // Update the cheat
+    let mut cheats = CHEATS.lock();
+    let index = index as usize;
+    if cheats.len() <= index {
+        cheats.resize_with(index + 1, Vec::new);
+    }
+    cheats[index] = codes;
+    recheat(emu, &cheats);
 }
 
 /// Information about a game.
@@ -951,6 +1141,8 @@ pub extern "C" fn retro_load_game(game: *const retro_game_info) -> bool {
 
     // Insert game cartridge
     emu.insert(cart);
+    // Invalidate state size
+    STATE_SIZE.lock().take();
 
     // Report success
     true
@@ -1014,6 +1206,8 @@ pub extern "C" fn retro_unload_game() {
     // Eject game cartridge
     emu.eject()
         .inspect(|cart| info!("ejected game: {}", cart.title()));
+    // Invalidate state size
+    STATE_SIZE.lock().take();
 }
 
 /// Gets the region of the actively loaded content as either
@@ -1057,10 +1251,14 @@ pub extern "C" fn retro_get_region() -> unsigned {
 /// - [`RETRO_MEMORY_RTC`]
 /// - [`RETRO_MEMORY_SYSTEM_RAM`]
 /// - [`RETRO_MEMORY_VIDEO_RAM`]
-#[allow(unused_variables)]
 #[unsafe(no_mangle)]
 pub extern "C" fn retro_get_memory_data(id: unsigned) -> *mut void {
-    ptr::null_mut() // TODO
+    // Acquire emulator instance
+    let guard = EMULATOR.lock();
+    let emu = guard.as_deref().expect("was not initialized");
+
+    // Locate memory region
+    memory(emu, id).map_or(ptr::null_mut(), |mem| mem.cast::<void>().as_ptr())
 }
 
 /// Gets the size of the given region of memory.
@@ -1081,8 +1279,135 @@ pub extern "C" fn retro_get_memory_data(id: unsigned) -> *mut void {
 /// - [`RETRO_MEMORY_RTC`]
 /// - [`RETRO_MEMORY_SYSTEM_RAM`]
 /// - [`RETRO_MEMORY_VIDEO_RAM`]
-#[allow(unused_variables)]
 #[unsafe(no_mangle)]
 pub extern "C" fn retro_get_memory_size(id: unsigned) -> usize {
-    0 // TODO
+    // Acquire emulator instance
+    let guard = EMULATOR.lock();
+    let emu = guard.as_deref().expect("was not initialized");
+
+    // Measure memory region
+    memory(emu, id).map_or(0, NonNull::len)
+}
+
+#[cfg(test)]
+mod tests {
+    use rugby::arch::mem::Memory;
+
+    use super::*;
+
+    /// Sample cart ROM.
+    const GAME: &[u8] = include_bytes!("../../../roms/games/2048/2048.gb");
+
+    fn setup() -> GameBoy {
+        let mut emu = GameBoy::new();
+        emu.insert(Cartridge::new(GAME).unwrap());
+        emu
+    }
+
+    #[test]
+    fn serialize_round_trip_works() {
+        let mut emu = setup();
+        let mut buf = vec![0; size_of::<u64>() + emu.save_state().len() + STATE_SLACK];
+
+        // Serialize mid-frame
+        (0..54_321).for_each(|_| emu.cycle());
+        assert!(serialize(&emu, &mut buf));
+        // Run a few frames, recording the result
+        (0..250_000).for_each(|_| emu.cycle());
+        let frame = emu.frame().to_vec();
+
+        // Unserialize, then check emulation continues identically
+        assert!(unserialize(&mut emu, &buf));
+        (0..250_000).for_each(|_| emu.cycle());
+        assert_eq!(emu.frame(), frame);
+    }
+
+    #[test]
+    fn serialize_size_bounds_works() {
+        let mut emu = setup();
+        let size = emu.save_state().len() + STATE_SLACK;
+
+        // Check the size bound holds throughout a frame
+        for _ in 0..70_224 {
+            emu.cycle();
+            assert!(emu.save_state().len() <= size);
+        }
+    }
+
+    #[test]
+    fn serialize_small_buffer_fails() {
+        let emu = setup();
+        let mut buf = vec![0; emu.save_state().len()];
+
+        // Buffer is missing space for the length prefix
+        assert!(!serialize(&emu, &mut buf));
+    }
+
+    #[test]
+    fn cheat_parse_works() {
+        // Single code
+        assert_eq!(cheat("01FF00C0").unwrap().len(), 1);
+        // Combined codes
+        assert_eq!(cheat("01FF00C0+00A-17B").unwrap().len(), 2);
+        assert_eq!(cheat("01FF00C0 00A-17B\n3E0-1DF-4C1").unwrap().len(), 3);
+        // Invalid code
+        assert!(cheat("01FF00C0+XYZ").is_none());
+    }
+
+    #[test]
+    fn cheat_recheat_works() {
+        let mut emu = setup();
+        let mut cheats = vec![cheat("01FF00C0").unwrap(), Vec::new()];
+
+        // Apply all enabled cheats
+        cheats[1] = cheat("00A-17B+3E0-1DF-4C1").unwrap();
+        recheat(&mut emu, &cheats);
+        assert_eq!(emu.cheats().len(), 3);
+        // Replace disabled cheats
+        cheats[0].clear();
+        recheat(&mut emu, &cheats);
+        assert_eq!(emu.cheats(), cheats[1]);
+    }
+
+    #[test]
+    fn memory_regions_works() {
+        let emu = setup();
+
+        // Save RAM
+        let sram = memory(&emu, RETRO_MEMORY_SAVE_RAM).unwrap();
+        assert_eq!(sram.len(), emu.cart().unwrap().sram().len());
+        emu.cart().unwrap().sram()[0x0042] = 0x24;
+        // SAFETY: the emulator is still alive
+        assert_eq!(unsafe { sram.as_ref() }[0x0042], 0x24);
+        // Real-time clock (the sample cart has none)
+        assert!(memory(&emu, RETRO_MEMORY_RTC).is_none());
+        // System RAM
+        let wram = memory(&emu, RETRO_MEMORY_SYSTEM_RAM).unwrap();
+        assert_eq!(wram.len(), 0x2000);
+        emu.wram().borrow_mut().write(0x0123, 0xaa).unwrap();
+        // SAFETY: the emulator is still alive
+        assert_eq!(unsafe { wram.as_ref() }[0x0123], 0xaa);
+        // Video RAM
+        let vram = memory(&emu, RETRO_MEMORY_VIDEO_RAM).unwrap();
+        assert_eq!(vram.len(), 0x2000);
+        emu.vram().borrow_mut().write(0x1234, 0x55).unwrap();
+        // SAFETY: the emulator is still alive
+        assert_eq!(unsafe { vram.as_ref() }[0x1234], 0x55);
+        // Unknown
+        assert!(memory(&emu, 0xff).is_none());
+    }
+
+    #[test]
+    fn unserialize_invalid_fails() {
+        let mut emu = setup();
+
+        // Empty buffer
+        assert!(!unserialize(&mut emu, &[]));
+        // Truncated buffer
+        assert!(!unserialize(&mut emu, &u64::MAX.to_le_bytes()));
+        // Invalid state
+        let mut buf = 8u64.to_le_bytes().to_vec();
+        buf.extend_from_slice(b"NOTASAVE");
+        assert!(!unserialize(&mut emu, &buf));
+    }
 }
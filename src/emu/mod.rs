@@ -5,11 +5,19 @@ use std::io::{BufRead, Write};
 use crate::arch::Block;
 #[cfg(feature = "cfg")]
 use crate::cfg::types::model::dmg::Rev;
-use crate::core::api::audio::{Audio, Chiptune};
-use crate::core::api::cable::Cable;
+use crate::core::api::audio::{Audio, Capture, Chiptune, Mixer, Sample};
+use crate::core::api::cable::{Cable, Status};
+use crate::core::api::camera::{Camera, ImageSource};
+use crate::core::api::cheat::{Cheat, Code};
+use crate::core::api::infrared::Infrared;
 use crate::core::api::input::{Event, Input};
+use crate::core::api::motion::Motion;
+use crate::core::api::rumble::Rumble;
+use crate::core::api::snapshot::{Result, Snapshot};
 use crate::core::api::video::{Aspect, Video};
 use crate::core::cart::Cartridge;
+use crate::core::cgb;
+use crate::core::chip::ppu::{Color, Rgb555};
 use crate::core::dmg::{self, rev};
 
 /// Game Boy handheld game console.
@@ -24,6 +32,8 @@ pub enum GameBoy {
     DmgB(dmg::GameBoy<rev::B>),
     /// DMG-CPU C.
     DmgC(dmg::GameBoy<rev::C>),
+    /// CPU CGB.
+    Cgb(cgb::GameBoyColor),
 }
 
 impl Default for GameBoy {
@@ -49,6 +59,7 @@ impl Block for GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.ready(),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.ready(),
+            Self::Cgb(cgb) => cgb.ready(),
         }
     }
 
@@ -56,6 +67,7 @@ impl Block for GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.cycle(),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.cycle(),
+            Self::Cgb(cgb) => cgb.cycle(),
         }
     }
 
@@ -63,6 +75,7 @@ impl Block for GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.reset(),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.reset(),
+            Self::Cgb(cgb) => cgb.reset(),
         }
     }
 }
@@ -74,6 +87,7 @@ impl GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.cart(),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.cart(),
+            Self::Cgb(cgb) => cgb.cart(),
         }
     }
 
@@ -82,6 +96,7 @@ impl GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.insert(cart),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.insert(cart),
+            Self::Cgb(cgb) => cgb.insert(cart),
         }
     }
 
@@ -91,6 +106,7 @@ impl GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.eject(),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.eject(),
+            Self::Cgb(cgb) => cgb.eject(),
         }
     }
 }
@@ -100,6 +116,47 @@ impl Audio for GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.sample(),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.sample(),
+            Self::Cgb(cgb) => cgb.sample(),
+        }
+    }
+
+    fn set_rate(&mut self, rate: u32) {
+        match self {
+            Self::Dmg0(dmg) => dmg.set_rate(rate),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.set_rate(rate),
+            Self::Cgb(cgb) => cgb.set_rate(rate),
+        }
+    }
+
+    fn pull(&mut self, out: &mut Vec<Sample>) {
+        match self {
+            Self::Dmg0(dmg) => dmg.pull(out),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.pull(out),
+            Self::Cgb(cgb) => cgb.pull(out),
+        }
+    }
+
+    fn set_mixer(&mut self, mix: Mixer) {
+        match self {
+            Self::Dmg0(dmg) => dmg.set_mixer(mix),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.set_mixer(mix),
+            Self::Cgb(cgb) => cgb.set_mixer(mix),
+        }
+    }
+
+    fn set_capture(&mut self, rate: u32) {
+        match self {
+            Self::Dmg0(dmg) => dmg.set_capture(rate),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.set_capture(rate),
+            Self::Cgb(cgb) => cgb.set_capture(rate),
+        }
+    }
+
+    fn capture(&mut self, out: &mut Capture) {
+        match self {
+            Self::Dmg0(dmg) => dmg.capture(out),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.capture(out),
+            Self::Cgb(cgb) => cgb.capture(out),
         }
     }
 }
@@ -109,6 +166,7 @@ impl Cable for GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.rx(),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.rx(),
+            Self::Cgb(cgb) => cgb.rx(),
         }
     }
 
@@ -116,6 +174,49 @@ impl Cable for GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.tx(),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.tx(),
+            Self::Cgb(cgb) => cgb.tx(),
+        }
+    }
+
+    fn status(&self) -> Status {
+        match self {
+            Self::Dmg0(dmg) => dmg.status(),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.status(),
+            Self::Cgb(cgb) => cgb.status(),
+        }
+    }
+
+    fn tick(&mut self) {
+        match self {
+            Self::Dmg0(dmg) => dmg.tick(),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.tick(),
+            Self::Cgb(cgb) => cgb.tick(),
+        }
+    }
+}
+
+impl Cheat for GameBoy {
+    fn cheats(&self) -> Vec<Code> {
+        match self {
+            Self::Dmg0(dmg) => dmg.cheats(),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.cheats(),
+            Self::Cgb(cgb) => cgb.cheats(),
+        }
+    }
+
+    fn add_cheat(&mut self, code: Code) {
+        match self {
+            Self::Dmg0(dmg) => dmg.add_cheat(code),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.add_cheat(code),
+            Self::Cgb(cgb) => cgb.add_cheat(code),
+        }
+    }
+
+    fn clear_cheats(&mut self) {
+        match self {
+            Self::Dmg0(dmg) => dmg.clear_cheats(),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.clear_cheats(),
+            Self::Cgb(cgb) => cgb.clear_cheats(),
         }
     }
 }
@@ -127,6 +228,88 @@ impl Input for GameBoy {
         match self {
             Self::Dmg0(dmg) => dmg.recv(events),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.recv(events),
+            Self::Cgb(cgb) => cgb.recv(events),
+        }
+    }
+}
+
+impl Rumble for GameBoy {
+    fn rumble(&self) -> f32 {
+        match self {
+            Self::Dmg0(dmg) => dmg.rumble(),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.rumble(),
+            Self::Cgb(cgb) => cgb.rumble(),
+        }
+    }
+}
+
+impl Infrared for GameBoy {
+    fn emit(&self) -> bool {
+        match self {
+            Self::Dmg0(dmg) => dmg.emit(),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.emit(),
+            Self::Cgb(cgb) => cgb.emit(),
+        }
+    }
+
+    fn detect(&mut self, light: bool) {
+        match self {
+            Self::Dmg0(dmg) => dmg.detect(light),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.detect(light),
+            Self::Cgb(cgb) => cgb.detect(light),
+        }
+    }
+}
+
+impl Motion for GameBoy {
+    fn tilt(&mut self, x: f32, y: f32) {
+        match self {
+            Self::Dmg0(dmg) => dmg.tilt(x, y),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.tilt(x, y),
+            Self::Cgb(cgb) => cgb.tilt(x, y),
+        }
+    }
+}
+
+impl Camera for GameBoy {
+    fn set_source(&mut self, src: Box<dyn ImageSource>) {
+        match self {
+            Self::Dmg0(dmg) => dmg.set_source(src),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.set_source(src),
+            Self::Cgb(cgb) => cgb.set_source(src),
+        }
+    }
+}
+
+impl Snapshot for GameBoy {
+    fn save_state(&self) -> Vec<u8> {
+        match self {
+            Self::Dmg0(dmg) => dmg.save_state(),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.save_state(),
+            Self::Cgb(cgb) => cgb.save_state(),
+        }
+    }
+
+    fn load_state(&mut self, buf: &[u8]) -> Result<()> {
+        match self {
+            Self::Dmg0(dmg) => dmg.load_state(buf),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.load_state(buf),
+            Self::Cgb(cgb) => cgb.load_state(buf),
+        }
+    }
+}
+
+impl GameBoy {
+    /// Gets the current video framebuffer in its native format.
+    ///
+    /// As pixel formats differ between models, the framebuffer is tagged by
+    /// its format. Use [`Video::frame`] for a common RGB format instead.
+    #[must_use]
+    pub fn screen(&self) -> Frame<'_> {
+        match self {
+            Self::Dmg0(dmg) => Frame::Dmg(dmg.frame()),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => Frame::Dmg(dmg.frame()),
+            Self::Cgb(cgb) => Frame::Cgb(cgb.frame()),
         }
     }
 }
@@ -134,19 +317,34 @@ impl Input for GameBoy {
 impl Video for GameBoy {
     const SIZE: Aspect = dmg::GameBoy::<rev::C>::SIZE;
 
-    type Pixel = <dmg::GameBoy<rev::C> as Video>::Pixel;
+    type Pixel = Rgb555;
 
     fn vsync(&self) -> bool {
         match self {
             Self::Dmg0(dmg) => dmg.vsync(),
             Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.vsync(),
+            Self::Cgb(cgb) => cgb.vsync(),
         }
     }
 
+    /// Gets the current video framebuffer.
+    ///
+    /// Monochrome shades are translated to grayscale, such that both models
+    /// share an RGB format.
     fn frame(&self) -> &[Self::Pixel] {
         match self {
-            Self::Dmg0(dmg) => dmg.frame(),
-            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.frame(),
+            Self::Dmg0(dmg) => dmg.colors(),
+            Self::DmgA(dmg) | Self::DmgB(dmg) | Self::DmgC(dmg) => dmg.colors(),
+            Self::Cgb(cgb) => cgb.frame(),
         }
     }
 }
+
+/// Video framebuffer.
+#[derive(Clone, Copy, Debug)]
+pub enum Frame<'a> {
+    /// Monochrome shades.
+    Dmg(&'a [Color]),
+    /// RGB555 colors.
+    Cgb(&'a [Rgb555]),
+}
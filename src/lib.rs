@@ -4,10 +4,10 @@
 //! models:
 //!
 //! - [`DMG`](crate::core::dmg): _Game Boy_
+//! - [`CGB`](crate::core::cgb): _Game Boy Color_
 //!
 //! In addition, the following models are planned:
 //!
-//! - `CGB`: _Game Boy Color_
 //! - `AGB`: _Game Boy Advance_ (compat only)[^1]
 //!
 //! [^1]: Planned support covers Game Boy (Color) compatibility only. Game Boy
@@ -58,6 +58,7 @@ pub use crate::core::api;
 pub use crate::emu::GameBoy;
 
 pub mod emu;
+pub mod link;
 
 /// Name of this crate.
 pub const NAME: &str = env!("CARGO_CRATE_NAME");
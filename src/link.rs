@@ -0,0 +1,230 @@
+//! Link cable transport.
+//!
+//! Connects the serial ports of two emulators over a UDP socket, allowing them
+//! to communicate as if joined by a link cable.
+//!
+//! # Protocol
+//!
+//! Each datagram is a two byte packet, holding a tag followed by its data:
+//!
+//! | Tag  | Name  | Description                                          |
+//! |:----:|-------|------------------------------------------------------|
+//! | `$0` | Idle  | Slave is no longer awaiting a transfer.              |
+//! | `$1` | Ready | Slave is awaiting a transfer, holding the data.      |
+//! | `$2` | Data  | Master has transferred out the data.                 |
+//!
+//! As the slave's data must be shifted in while the master shifts its own data
+//! out, the slave announces its data ahead of time. Should the master begin a
+//! transfer before the slave is ready, the master is stalled until the slave
+//! catches up, hiding any latency between the two. Once the master completes,
+//! the slave is clocked through its own transfer.
+//!
+//! If the peer fails to respond in time, it is considered to be disconnected,
+//! with transfers completing as if no cable were connected until the peer
+//! responds once again.
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use log::{debug, trace, warn};
+
+use crate::api::cable::{Cable, Status};
+
+/// Duration after which an unresponsive peer is disconnected.
+pub const TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Number of syncs between polls of the socket.
+const POLL: u32 = 256;
+
+/// Link cable transport.
+#[derive(Debug)]
+pub struct Link {
+    /// Connected socket.
+    sock: UdpSocket,
+    /// Last observed status.
+    last: Status,
+    /// Peer's announced data.
+    peer: Option<u8>,
+    /// Master has been fed the peer's data.
+    fed: bool,
+    /// Stalled since.
+    wait: Option<Instant>,
+    /// Peer is disconnected.
+    lost: bool,
+    /// Sync counter.
+    idx: u32,
+}
+
+impl Link {
+    /// Constructs a new `Link` from a connected socket.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket could not be made non-blocking.
+    pub fn new(sock: UdpSocket) -> io::Result<Self> {
+        sock.set_nonblocking(true)?;
+        Ok(Self {
+            sock,
+            last: Status::Idle,
+            peer: None,
+            fed: false,
+            wait: None,
+            lost: false,
+            idx: 0,
+        })
+    }
+
+    /// Opens a new `Link` between a host and peer address.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket could not be bound or connected.
+    pub fn open(host: SocketAddr, peer: SocketAddr) -> io::Result<Self> {
+        let sock = UdpSocket::bind(host)?;
+        sock.connect(peer)?;
+        debug!("opened link: {host} -> {peer}");
+        Self::new(sock)
+    }
+
+    /// Synchronizes the emulator with its peer.
+    ///
+    /// Should be called before each cycle of the emulator.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` while the emulator is stalled awaiting its peer, in which
+    /// case it must not be cycled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket could not be read from or written to.
+    pub fn sync(&mut self, emu: &mut impl Cable) -> io::Result<bool> {
+        // Poll the socket
+        self.idx = self.idx.wrapping_add(1);
+        if self.wait.is_some() || self.idx.is_multiple_of(POLL) {
+            self.poll(emu)?;
+        }
+        // Handle status changes
+        let status = emu.status();
+        if status != self.last {
+            match (self.last, status) {
+                // Master completed its transfer
+                (Status::Master, _) => {
+                    let mut data = Vec::new();
+                    emu.rx().read_to_end(&mut data)?;
+                    if let Some(&data) = data.last() {
+                        self.send(Packet::Data(data))?;
+                    }
+                    self.fed = false;
+                }
+                // Slave stopped awaiting a transfer
+                (Status::Slave(_), Status::Idle) => self.send(Packet::Idle)?,
+                _ => (),
+            }
+            // Slave started awaiting a transfer
+            if let Status::Slave(data) = status {
+                self.send(Packet::Ready(data))?;
+            }
+            self.last = status;
+        }
+        // Feed the master its peer's data
+        if status == Status::Master && !self.fed {
+            if let Some(data) = self.peer.take() {
+                emu.tx().write_all(&[data])?;
+            } else if !self.lost {
+                // Stall until the peer is ready
+                let wait = *self.wait.get_or_insert_with(Instant::now);
+                if wait.elapsed() < TIMEOUT {
+                    return Ok(true);
+                }
+                warn!("link peer timed out");
+                self.lost = true;
+            }
+            self.fed = true;
+            self.wait = None;
+        }
+        Ok(false)
+    }
+
+    /// Polls the socket for received packets.
+    fn poll(&mut self, emu: &mut impl Cable) -> io::Result<()> {
+        let mut buf = [0; 2];
+        loop {
+            let pkt = match self.sock.recv(&mut buf) {
+                Ok(2) => Packet::decode(buf),
+                Ok(_) => None,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                // Unreachable peers are reported on some platforms
+                Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => return Ok(()),
+                Err(err) => return Err(err),
+            };
+            let Some(pkt) = pkt else {
+                debug!("ignored invalid packet: {buf:02x?}");
+                continue;
+            };
+            trace!("recv: {pkt:?}");
+            if self.lost {
+                debug!("link peer reconnected");
+                self.lost = false;
+            }
+            match pkt {
+                Packet::Idle => self.peer = None,
+                Packet::Ready(data) => self.peer = Some(data),
+                Packet::Data(data) => {
+                    if !matches!(emu.status(), Status::Slave(_)) {
+                        debug!("dropped transfer: {data:#04x}");
+                        continue;
+                    }
+                    // Clock the slave through its transfer
+                    emu.tx().write_all(&[data])?;
+                    (0..8).for_each(|_| emu.tick());
+                    // Discard the slave's data, which was already announced
+                    io::copy(emu.rx(), &mut io::sink())?;
+                }
+            }
+        }
+    }
+
+    /// Sends a packet to the peer.
+    fn send(&self, pkt: Packet) -> io::Result<()> {
+        trace!("send: {pkt:?}");
+        match self.sock.send(&pkt.encode()) {
+            // Unreachable peers are reported on some platforms
+            Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => Ok(()),
+            res => res.map(drop),
+        }
+    }
+}
+
+/// Link cable packet.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Packet {
+    /// Slave is no longer awaiting a transfer.
+    Idle,
+    /// Slave is awaiting a transfer.
+    Ready(u8),
+    /// Master has transferred out data.
+    Data(u8),
+}
+
+impl Packet {
+    /// Encodes a packet.
+    fn encode(self) -> [u8; 2] {
+        match self {
+            Packet::Idle => [0, 0],
+            Packet::Ready(data) => [1, data],
+            Packet::Data(data) => [2, data],
+        }
+    }
+
+    /// Decodes a packet.
+    fn decode([tag, data]: [u8; 2]) -> Option<Self> {
+        match tag {
+            0 => Some(Packet::Idle),
+            1 => Some(Packet::Ready(data)),
+            2 => Some(Packet::Data(data)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::net::UdpSocket;
+
+use rugby::arch::Block;
+use rugby::core::cart::Cartridge;
+use rugby::core::dmg::GameBoy;
+use rugby::link::Link;
+
+/// Number of cycles after which the test is considered to have failed due to a
+/// timeout error.
+const TIMEOUT: usize = 1_000_000;
+
+/// HRAM address where received data is stored.
+const DONE: u16 = 0xff80;
+
+/// Assembles a ROM which performs a single serial transfer.
+///
+/// After an optional delay, the ROM transfers out `sb` using the clock selected
+/// by `sc`, then stores the received data to HRAM.
+fn rom(sb: u8, sc: u8, delay: bool) -> Vec<u8> {
+    let mut rom = vec![0; 0x8000];
+    // jp $0150
+    rom[0x100..0x103].copy_from_slice(&[0xc3, 0x50, 0x01]);
+    let mut code = vec![
+        0xf3, // di
+        0x3e, 0x00, // ld a, $00
+        0xe0, 0x80, // ldh [$80], a
+    ];
+    if delay {
+        code.extend([
+            0x01, 0x00, 0x10, // ld bc, $1000
+            0x0b, // dec bc
+            0x78, // ld a, b
+            0xb1, // or c
+            0x20, 0xfb, // jr nz, -5
+        ]);
+    }
+    code.extend([
+        0x3e, sb, // ld a, sb
+        0xe0, 0x01, // ldh [$01], a
+        0x3e, sc, // ld a, sc
+        0xe0, 0x02, // ldh [$02], a
+        0xf0, 0x02, // ldh a, [$02]
+        0xe6, 0x80, // and $80
+        0x20, 0xfa, // jr nz, -6
+        0xf0, 0x01, // ldh a, [$01]
+        0xe0, 0x80, // ldh [$80], a
+        0x18, 0xfe, // jr -2
+    ]);
+    rom[0x150..0x150 + code.len()].copy_from_slice(&code);
+    rom
+}
+
+/// Constructs an emulator running the provided ROM.
+fn emu(rom: &[u8]) -> GameBoy {
+    let mut emu = GameBoy::new();
+    emu.insert(Cartridge::unchecked(rom).unwrap());
+    emu
+}
+
+/// Reads the data received by an emulator, if any.
+fn recv(emu: &GameBoy) -> Option<u8> {
+    Some(emu.inner().soc.cpu.blk.bus.read(DONE)).filter(|&data| data != 0)
+}
+
+/// Constructs a pair of links connected over loopback.
+fn pair() -> (Link, Link) {
+    let a = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let b = UdpSocket::bind("127.0.0.1:0").unwrap();
+    a.connect(b.local_addr().unwrap()).unwrap();
+    b.connect(a.local_addr().unwrap()).unwrap();
+    (Link::new(a).unwrap(), Link::new(b).unwrap())
+}
+
+/// Emulates a pair of linked emulators until both have received data.
+fn emulate(
+    (mut emu_a, mut link_a): (GameBoy, Link),
+    (mut emu_b, mut link_b): (GameBoy, Link),
+) -> (u8, u8) {
+    for _ in 0..TIMEOUT {
+        if !link_a.sync(&mut emu_a).unwrap() {
+            emu_a.cycle();
+        }
+        if !link_b.sync(&mut emu_b).unwrap() {
+            emu_b.cycle();
+        }
+        if let (Some(a), Some(b)) = (recv(&emu_a), recv(&emu_b)) {
+            return (a, b);
+        }
+    }
+    panic!("timed out awaiting transfer");
+}
+
+#[test]
+fn loopback_works() {
+    let (link_a, link_b) = pair();
+    let master = emu(&rom(0x29, 0x81, false));
+    let slave = emu(&rom(0x55, 0x80, false));
+    assert_eq!(emulate((master, link_a), (slave, link_b)), (0x55, 0x29));
+}
+
+#[test]
+fn loopback_latency_works() {
+    // Slave becomes ready well after the master starts transferring
+    let (link_a, link_b) = pair();
+    let master = emu(&rom(0x29, 0x81, false));
+    let slave = emu(&rom(0x55, 0x80, true));
+    assert_eq!(emulate((master, link_a), (slave, link_b)), (0x55, 0x29));
+}
+
+#[test]
+fn disconnected_works() {
+    // Peer never responds
+    let (mut link, _peer) = pair();
+    let mut emu = emu(&rom(0x29, 0x81, false));
+    for _ in 0..TIMEOUT {
+        if !link.sync(&mut emu).unwrap() {
+            emu.cycle();
+        }
+        if let Some(data) = recv(&emu) {
+            assert_eq!(data, 0xff);
+            return;
+        }
+    }
+    panic!("timed out awaiting transfer");
+}